@@ -23,6 +23,8 @@ mod bybit_routing_tests {
         // Test serialization/deserialization
         let client_message = ClientMessage::Subscribe {
             channels: vec![channel.clone()],
+            stats: false,
+            envelope: false,
         };
 
         let json = serde_json::to_string(&client_message).expect("Failed to serialize");
@@ -32,7 +34,7 @@ mod bybit_routing_tests {
             serde_json::from_str(&json).expect("Failed to deserialize");
 
         match deserialized {
-            ClientMessage::Subscribe { channels } => {
+            ClientMessage::Subscribe { channels, .. } => {
                 assert_eq!(channels.len(), 1);
                 let ch = &channels[0];
                 assert_eq!(ch.exchange.as_str(), "bybit");
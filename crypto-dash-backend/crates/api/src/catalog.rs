@@ -1,13 +1,19 @@
+use crate::http::{get_json, rest_retry_config};
 use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
 use crypto_dash_cache::CacheHandle;
-use crypto_dash_core::model::{ExchangeId, MarketType, SymbolMeta};
+use crypto_dash_core::config::RestUrls;
+use crypto_dash_core::model::{ExchangeId, MarketType, Symbol, SymbolMeta};
 use crypto_dash_core::normalize::precision_from_tick_size;
+use crypto_dash_core::time::{from_millis, now};
 use crypto_dash_exchanges_common::ExchangeAdapter;
+use futures::stream::{self, StreamExt};
 use reqwest::Client;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::future::Future;
 use std::str::FromStr;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -15,11 +21,17 @@ use tracing::{error, info, warn};
 
 pub const ALLOWED_SPOT_QUOTES: &[&str] = &["USDT", "USDC", "TUSD"];
 pub const ALLOWED_PERP_QUOTES: &[&str] = &["USDT", "USDC"];
+pub const ALLOWED_INVERSE_QUOTES: &[&str] = &["USD"];
+pub const ALLOWED_FUTURE_QUOTES: &[&str] = &["USD", "USDT", "USDC"];
+/// Maximum number of exchanges whose symbol metadata is fetched at once
+const CATALOG_LOAD_CONCURRENCY: usize = 4;
 
 pub fn is_quote_allowed(market_type: MarketType, quote: &str) -> bool {
     let allowed = match market_type {
         MarketType::Spot => ALLOWED_SPOT_QUOTES,
         MarketType::Perpetual => ALLOWED_PERP_QUOTES,
+        MarketType::InversePerpetual => ALLOWED_INVERSE_QUOTES,
+        MarketType::Future => ALLOWED_FUTURE_QUOTES,
     };
 
     allowed
@@ -36,6 +48,15 @@ struct BinanceSymbol {
     quote_asset: String,
     base_asset_precision: u32,
     quote_precision: u32,
+    /// `TRADING` for live instruments; delisted ones linger as `BREAK`, `SETTLING` or `CLOSE`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    status: Option<String>,
+    /// Only on USD-M futures: `PERPETUAL` or a delivery contract such as `CURRENT_QUARTER`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    contract_type: Option<String>,
+    /// Only on USD-M futures: settlement time in epoch millis
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    delivery_date: Option<i64>,
     filters: Vec<BinanceFilter>,
 }
 
@@ -63,6 +84,8 @@ struct BybitSymbol {
     quote_coin: String,
     price_filter: Option<BybitPriceFilter>,
     lot_size_filter: Option<BybitLotSizeFilter>,
+    /// Delivery time in milliseconds; "0" or absent for instruments that never expire
+    delivery_time: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -86,30 +109,142 @@ struct BybitResponse {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct BybitResult {
     list: Vec<BybitSymbol>,
+    /// Cursor of the next page; empty or absent on the last one
+    #[serde(default)]
+    next_page_cursor: Option<String>,
+}
+
+/// Run `load` for each exchange with at most `limit` in flight, returning the failures
+async fn load_concurrently<F, Fut>(
+    exchange_names: Vec<String>,
+    limit: usize,
+    load: F,
+) -> Vec<(String, anyhow::Error)>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    stream::iter(exchange_names)
+        .map(|exchange_name| {
+            let load = load(exchange_name.clone());
+            async move { (exchange_name, load.await) }
+        })
+        .buffer_unordered(limit)
+        .filter_map(
+            |(exchange_name, result)| async move { result.err().map(|e| (exchange_name, e)) },
+        )
+        .collect()
+        .await
+}
+
+/// Key of the single-symbol index: exchange, market and the pair with base and quote uppercased
+type SymbolKey = (ExchangeId, MarketType, Symbol);
+
+fn symbol_key(exchange: &str, market_type: MarketType, symbol: &Symbol) -> SymbolKey {
+    let mut symbol = symbol.clone();
+    symbol.base.make_ascii_uppercase();
+    symbol.quote.make_ascii_uppercase();
+    (ExchangeId::from(exchange), market_type, symbol)
+}
+
+/// How fresh one exchange's symbol list is
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogStatus {
+    pub exchange: String,
+    pub symbol_count: usize,
+    /// Last successful fetch from the exchange; `None` while served from cache or fallback
+    pub last_refreshed_at: Option<DateTime<Utc>>,
 }
 
 /// Exchange catalog service for fetching and caching symbol metadata
 pub struct ExchangeCatalog {
     cache: CacheHandle,
     client: Client,
+    base_urls: HashMap<String, String>,
+    /// Roots for perpetual instrument lists, where the exchange serves them separately
+    perp_base_urls: HashMap<String, String>,
     symbol_cache: Arc<RwLock<HashMap<String, Vec<SymbolMeta>>>>,
+    /// The same metadata keyed by instrument, so one symbol is found without a scan
+    symbol_index: Arc<RwLock<HashMap<SymbolKey, SymbolMeta>>>,
+    refreshed_at: Arc<RwLock<HashMap<String, DateTime<Utc>>>>,
 }
 
 impl ExchangeCatalog {
     pub fn new(cache: CacheHandle) -> Self {
+        let defaults = RestUrls::default();
         Self {
             cache,
             client: Client::new(),
+            base_urls: HashMap::from([
+                (ExchangeId::BINANCE.to_string(), defaults.binance_catalog),
+                (ExchangeId::BYBIT.to_string(), defaults.bybit_catalog),
+            ]),
+            perp_base_urls: HashMap::from([(
+                ExchangeId::BINANCE.to_string(),
+                defaults.binance_perp_catalog,
+            )]),
             symbol_cache: Arc::new(RwLock::new(HashMap::new())),
+            symbol_index: Arc::new(RwLock::new(HashMap::new())),
+            refreshed_at: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
+    /// Use a pre-configured HTTP client, e.g. one with custom timeouts or proxies
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Override the REST root an exchange's metadata is fetched from
+    pub fn with_base_url(mut self, exchange: &str, url: impl Into<String>) -> Self {
+        self.base_urls.insert(exchange.to_string(), url.into());
+        self
+    }
+
+    /// Override the REST root an exchange's perpetual instruments are fetched from
+    pub fn with_perp_base_url(mut self, exchange: &str, url: impl Into<String>) -> Self {
+        self.perp_base_urls.insert(exchange.to_string(), url.into());
+        self
+    }
+
+    fn base_url(&self, exchange: &str) -> Result<&str> {
+        self.base_urls
+            .get(exchange)
+            .map(|url| url.trim_end_matches('/'))
+            .ok_or_else(|| anyhow!("No REST URL configured for {}", exchange))
+    }
+
+    fn perp_base_url(&self, exchange: &str) -> Result<&str> {
+        self.perp_base_urls
+            .get(exchange)
+            .map(|url| url.trim_end_matches('/'))
+            .ok_or_else(|| anyhow!("No perpetual REST URL configured for {}", exchange))
+    }
+
     fn filter_symbols(symbols: &mut Vec<SymbolMeta>) {
         symbols.retain(|meta| is_quote_allowed(meta.market_type, &meta.quote));
     }
 
+    /// Replace an exchange's symbol list and its entries in the index
+    async fn store_symbols(&self, exchange_name: &str, symbols: Vec<SymbolMeta>) {
+        let mut cache = self.symbol_cache.write().await;
+        let mut index = self.symbol_index.write().await;
+
+        index.retain(|(exchange, _, _), _| exchange.as_str() != exchange_name);
+        for meta in &symbols {
+            let mut pair = Symbol::new(meta.base.clone(), meta.quote.clone());
+            pair.expiry = meta.expiry;
+            index.insert(
+                symbol_key(exchange_name, meta.market_type, &pair),
+                meta.clone(),
+            );
+        }
+        cache.insert(exchange_name.to_string(), symbols);
+    }
+
     /// Load symbol metadata for all exchanges
     pub async fn load_all(
         &self,
@@ -117,18 +252,23 @@ impl ExchangeCatalog {
     ) -> Result<()> {
         info!("Loading symbol metadata for all exchanges");
 
-        for (exchange_name, _adapter) in exchanges {
-            if let Err(e) = self.load_exchange_symbols(exchange_name).await {
-                error!("Failed to load symbols for {}: {}", exchange_name, e);
-                // Try to load from cache
-                if let Err(cache_err) = self.load_from_cache(exchange_name).await {
-                    warn!(
-                        "Failed to load symbols from cache for {}: {}",
-                        exchange_name, cache_err
-                    );
-                    // Load fallback symbols
-                    self.load_fallback_symbols(exchange_name).await;
-                }
+        let failures = load_concurrently(
+            exchanges.keys().cloned().collect(),
+            CATALOG_LOAD_CONCURRENCY,
+            |exchange_name| async move { self.load_exchange_symbols(&exchange_name).await },
+        )
+        .await;
+
+        for (exchange_name, e) in failures {
+            error!("Failed to load symbols for {}: {}", exchange_name, e);
+            // Try to load from cache
+            if let Err(cache_err) = self.load_from_cache(&exchange_name).await {
+                warn!(
+                    "Failed to load symbols from cache for {}: {}",
+                    exchange_name, cache_err
+                );
+                // Load fallback symbols
+                self.load_fallback_symbols(&exchange_name).await;
             }
         }
 
@@ -140,18 +280,19 @@ impl ExchangeCatalog {
         info!("Loading symbols for exchange: {}", exchange_name);
 
         let mut symbols = match exchange_name {
-            "binance" => self.fetch_binance_symbols().await?,
-            "bybit" => self.fetch_bybit_symbols().await?,
+            ExchangeId::BINANCE => self.fetch_binance_symbols().await?,
+            ExchangeId::BYBIT => self.fetch_bybit_symbols().await?,
             _ => return Err(anyhow!("Unsupported exchange: {}", exchange_name)),
         };
 
         Self::filter_symbols(&mut symbols);
 
         // Store in memory cache
-        {
-            let mut cache = self.symbol_cache.write().await;
-            cache.insert(exchange_name.to_string(), symbols.clone());
-        }
+        self.store_symbols(exchange_name, symbols.clone()).await;
+        self.refreshed_at
+            .write()
+            .await
+            .insert(exchange_name.to_string(), now());
 
         // Store in persistent cache
         let cache_key = format!("exchange_symbols_{}", exchange_name);
@@ -163,25 +304,72 @@ impl ExchangeCatalog {
         Ok(())
     }
 
-    /// Get symbols for specific exchange(s)
-    pub async fn get_symbols(&self, exchange: Option<&str>) -> Vec<SymbolMeta> {
+    /// Get symbols for specific exchange(s), optionally of one market type only
+    pub async fn get_symbols(
+        &self,
+        exchange: Option<&str>,
+        market_type: Option<MarketType>,
+    ) -> Vec<SymbolMeta> {
         let cache = self.symbol_cache.read().await;
 
-        match exchange {
-            Some(exchange_name) => {
-                let mut symbols = cache.get(exchange_name).cloned().unwrap_or_default();
-                Self::filter_symbols(&mut symbols);
-                symbols
-            }
+        let mut symbols = match exchange {
+            Some(exchange_name) => cache.get(exchange_name).cloned().unwrap_or_default(),
             None => {
                 let mut all_symbols = Vec::new();
                 for symbols in cache.values() {
                     all_symbols.extend(symbols.clone());
                 }
-                Self::filter_symbols(&mut all_symbols);
                 all_symbols
             }
+        };
+        Self::filter_symbols(&mut symbols);
+        if let Some(market_type) = market_type {
+            symbols.retain(|meta| meta.market_type == market_type);
         }
+        symbols
+    }
+
+    /// Metadata of one instrument, looked up in the index rather than the exchange's list;
+    /// base and quote match case-insensitively
+    pub async fn get_symbol_meta_one(
+        &self,
+        exchange: &str,
+        market_type: MarketType,
+        symbol: &Symbol,
+    ) -> Option<SymbolMeta> {
+        self.symbol_index
+            .read()
+            .await
+            .get(&symbol_key(exchange, market_type, symbol))
+            .cloned()
+    }
+
+    /// Symbol count and last refresh time per loaded exchange, ordered by exchange
+    pub async fn status(&self) -> Vec<CatalogStatus> {
+        let cache = self.symbol_cache.read().await;
+        let refreshed_at = self.refreshed_at.read().await;
+
+        let mut status: Vec<CatalogStatus> = cache
+            .iter()
+            .map(|(exchange, symbols)| CatalogStatus {
+                exchange: exchange.clone(),
+                symbol_count: symbols.len(),
+                last_refreshed_at: refreshed_at.get(exchange).copied(),
+            })
+            .collect();
+        status.sort_by(|a, b| a.exchange.cmp(&b.exchange));
+        status
+    }
+
+    /// Market type to assume for a symbol when a client names none: perpetual if the
+    /// exchange lists the symbol only as a perpetual, otherwise `None` and spot applies
+    pub async fn implied_market_type(&self, exchange: &str, symbol: &Symbol) -> Option<MarketType> {
+        let pair = Symbol::new(symbol.base.clone(), symbol.quote.clone());
+        let index = self.symbol_index.read().await;
+        let listed = |market_type| index.contains_key(&symbol_key(exchange, market_type, &pair));
+
+        (listed(MarketType::Perpetual) && !listed(MarketType::Spot))
+            .then_some(MarketType::Perpetual)
     }
 
     /// Refresh symbols for a specific exchange
@@ -191,14 +379,68 @@ impl ExchangeCatalog {
     }
 
     async fn fetch_binance_symbols(&self) -> Result<Vec<SymbolMeta>> {
-        let url = "https://api.binance.com/api/v3/exchangeInfo";
-        let response = self.client.get(url).send().await?;
-        let exchange_info: BinanceExchangeInfo = response.json().await?;
+        let url = format!(
+            "{}/api/v3/exchangeInfo",
+            self.base_url(ExchangeId::BINANCE)?
+        );
+        let spot: BinanceExchangeInfo =
+            get_json(&self.client, &url, &[], &rest_retry_config()).await?;
+        let mut symbols = Self::binance_symbol_metas(spot, MarketType::Spot);
 
+        // USD-M futures list their own instruments; spot metadata stays usable without them
+        match self.fetch_binance_perp_info().await {
+            Ok(perp) => symbols.extend(Self::binance_symbol_metas(perp, MarketType::Perpetual)),
+            Err(e) => warn!("Failed to load Binance perpetual instruments: {}", e),
+        }
+
+        Self::filter_symbols(&mut symbols);
+
+        Ok(symbols)
+    }
+
+    async fn fetch_binance_perp_info(&self) -> Result<BinanceExchangeInfo> {
+        let url = format!(
+            "{}/fapi/v1/exchangeInfo",
+            self.perp_base_url(ExchangeId::BINANCE)?
+        );
+        Ok(get_json(&self.client, &url, &[], &rest_retry_config()).await?)
+    }
+
+    /// One entry per live instrument: `market_type` for spot pairs and perpetual contracts,
+    /// `Future` with its delivery date for quarterly contracts. Each market is read from its
+    /// own listing, so a pair traded on only one market gets no entry on the other.
+    fn binance_symbol_metas(
+        exchange_info: BinanceExchangeInfo,
+        market_type: MarketType,
+    ) -> Vec<SymbolMeta> {
         let mut symbols = Vec::new();
-        let exchange_id = ExchangeId::from("binance");
+        let exchange_id = ExchangeId::from(ExchangeId::BINANCE);
 
         for symbol in exchange_info.symbols {
+            if symbol
+                .status
+                .as_deref()
+                .is_some_and(|status| status != "TRADING")
+            {
+                continue;
+            }
+            let (market_type, expiry) = match symbol.contract_type.as_deref() {
+                None | Some("PERPETUAL") => (market_type, None),
+                Some("CURRENT_QUARTER" | "NEXT_QUARTER") => {
+                    let Some(expiry) = symbol
+                        .delivery_date
+                        .and_then(from_millis)
+                        .map(|delivery| delivery.date_naive())
+                    else {
+                        warn!("Binance contract {} has no delivery date", symbol.symbol);
+                        continue;
+                    };
+                    (MarketType::Future, Some(expiry))
+                }
+                // e.g. TRADIFI_PERPETUAL, not streamed by the adapter
+                Some(_) => continue,
+            };
+
             // Clone the symbol for serialization before moving parts
             let symbol_for_info = symbol.clone();
 
@@ -234,25 +476,43 @@ impl ExchangeCatalog {
 
             let price_precision = precision_from_tick_size(&tick_size).unwrap_or(2);
 
-            let spot_meta = SymbolMeta {
+            symbols.push(SymbolMeta {
                 exchange: exchange_id.clone(),
-                market_type: MarketType::Spot,
+                market_type,
                 symbol: symbol.symbol.clone(),
                 base: symbol.base_asset.clone(),
                 quote: symbol.quote_asset.clone(),
                 price_precision,
-                tick_size: tick_size.clone(),
+                tick_size,
                 min_qty,
                 step_size,
-                filters: Some(filters_map.clone()),
+                filters: Some(filters_map),
                 info: serde_json::to_value(&symbol_for_info).unwrap_or(Value::Null),
-            };
+                expiry,
+            });
+        }
 
-            let mut perp_meta = spot_meta.clone();
-            perp_meta.market_type = MarketType::Perpetual;
+        symbols
+    }
+
+    async fn fetch_bybit_symbols(&self) -> Result<Vec<SymbolMeta>> {
+        let spot = self.fetch_bybit_category("spot").await?;
+        let mut symbols = Self::bybit_symbol_metas(spot, &[MarketType::Spot]);
 
-            symbols.push(spot_meta);
-            symbols.push(perp_meta);
+        // USDT/USDC contracts are listed separately, as are coin-margined ones; spot
+        // metadata stays usable without them
+        match self.fetch_bybit_category("linear").await {
+            Ok(linear) => {
+                symbols.extend(Self::bybit_symbol_metas(linear, &[MarketType::Perpetual]))
+            }
+            Err(e) => warn!("Failed to load Bybit linear instruments: {}", e),
+        }
+        match self.fetch_bybit_category("inverse").await {
+            Ok(inverse) => symbols.extend(Self::bybit_symbol_metas(
+                inverse,
+                &[MarketType::InversePerpetual],
+            )),
+            Err(e) => warn!("Failed to load Bybit inverse instruments: {}", e),
         }
 
         Self::filter_symbols(&mut symbols);
@@ -260,15 +520,43 @@ impl ExchangeCatalog {
         Ok(symbols)
     }
 
-    async fn fetch_bybit_symbols(&self) -> Result<Vec<SymbolMeta>> {
-        let url = "https://api.bybit.com/v5/market/instruments-info?category=spot";
-        let response = self.client.get(url).send().await?;
-        let bybit_response: BybitResponse = response.json().await?;
+    /// All instruments of a category, following `nextPageCursor` across pages
+    async fn fetch_bybit_category(&self, category: &str) -> Result<BybitResponse> {
+        let url = format!(
+            "{}/v5/market/instruments-info",
+            self.base_url(ExchangeId::BYBIT)?
+        );
+        let mut list = Vec::new();
+        let mut cursor = String::new();
+        loop {
+            let mut params = vec![("category", category), ("limit", "1000")];
+            if !cursor.is_empty() {
+                params.push(("cursor", cursor.as_str()));
+            }
+            let page: BybitResponse =
+                get_json(&self.client, &url, &params, &rest_retry_config()).await?;
+            list.extend(page.result.list);
+            match page.result.next_page_cursor.filter(|next| !next.is_empty()) {
+                Some(next) if next != cursor => cursor = next,
+                _ => break,
+            }
+        }
 
+        Ok(BybitResponse {
+            result: BybitResult {
+                list,
+                next_page_cursor: None,
+            },
+        })
+    }
+
+    /// One entry per instrument and market type it is listed under; instruments with a
+    /// delivery time are listed as dated futures instead
+    fn bybit_symbol_metas(response: BybitResponse, market_types: &[MarketType]) -> Vec<SymbolMeta> {
         let mut symbols = Vec::new();
-        let exchange_id = ExchangeId::from("bybit");
+        let exchange_id = ExchangeId::from(ExchangeId::BYBIT);
 
-        for symbol in bybit_response.result.list {
+        for symbol in response.result.list {
             // Clone the symbol for serialization before moving parts
             let symbol_for_info = symbol.clone();
 
@@ -306,30 +594,38 @@ impl ExchangeCatalog {
                 }
             }
 
-            let spot_meta = SymbolMeta {
-                exchange: exchange_id.clone(),
-                market_type: MarketType::Spot,
-                symbol: symbol.symbol.clone(),
-                base: symbol.base_coin.clone(),
-                quote: symbol.quote_coin.clone(),
-                price_precision,
-                tick_size: tick_size.clone(),
-                min_qty,
-                step_size,
-                filters: Some(filters_map.clone()),
-                info: serde_json::to_value(&symbol_for_info).unwrap_or(Value::Null),
+            let expiry = symbol
+                .delivery_time
+                .as_deref()
+                .and_then(|ms| ms.parse::<i64>().ok())
+                .filter(|ms| *ms > 0)
+                .and_then(from_millis)
+                .map(|delivery| delivery.date_naive());
+            let listed_as: &[MarketType] = if expiry.is_some() {
+                &[MarketType::Future]
+            } else {
+                market_types
             };
 
-            let mut perp_meta = spot_meta.clone();
-            perp_meta.market_type = MarketType::Perpetual;
-
-            symbols.push(spot_meta);
-            symbols.push(perp_meta);
+            for &market_type in listed_as {
+                symbols.push(SymbolMeta {
+                    exchange: exchange_id.clone(),
+                    market_type,
+                    symbol: symbol.symbol.clone(),
+                    base: symbol.base_coin.clone(),
+                    quote: symbol.quote_coin.clone(),
+                    price_precision,
+                    tick_size: tick_size.clone(),
+                    min_qty,
+                    step_size,
+                    filters: Some(filters_map.clone()),
+                    info: serde_json::to_value(&symbol_for_info).unwrap_or(Value::Null),
+                    expiry,
+                });
+            }
         }
 
-        Self::filter_symbols(&mut symbols);
-
-        Ok(symbols)
+        symbols
     }
 
     async fn load_from_cache(&self, exchange_name: &str) -> Result<()> {
@@ -337,8 +633,7 @@ impl ExchangeCatalog {
         if let Ok(Some(mut symbols)) = self.cache.get::<Vec<SymbolMeta>>(&cache_key).await {
             Self::filter_symbols(&mut symbols);
 
-            let mut cache = self.symbol_cache.write().await;
-            cache.insert(exchange_name.to_string(), symbols);
+            self.store_symbols(exchange_name, symbols).await;
             info!("Loaded symbols for {} from cache", exchange_name);
             Ok(())
         } else {
@@ -364,6 +659,7 @@ impl ExchangeCatalog {
                 step_size: Decimal::from_str("0.001").unwrap(),
                 filters: None,
                 info: Value::Null,
+                expiry: None,
             },
             SymbolMeta {
                 exchange: exchange_id.clone(),
@@ -377,6 +673,7 @@ impl ExchangeCatalog {
                 step_size: Decimal::from_str("0.001").unwrap(),
                 filters: None,
                 info: Value::Null,
+                expiry: None,
             },
         ];
 
@@ -392,8 +689,7 @@ impl ExchangeCatalog {
 
         Self::filter_symbols(&mut fallback_symbols);
 
-        let mut cache = self.symbol_cache.write().await;
-        cache.insert(exchange_name.to_string(), fallback_symbols);
+        self.store_symbols(exchange_name, fallback_symbols).await;
     }
 }
 
@@ -416,7 +712,333 @@ mod tests {
         let catalog = ExchangeCatalog::new(cache_handle);
 
         // Test that empty symbols are returned initially
-        let symbols = catalog.get_symbols(Some("binance")).await;
+        let symbols = catalog.get_symbols(Some("binance"), None).await;
         assert!(symbols.is_empty());
     }
+
+    /// Serve `router` on an ephemeral local port and return its base URL
+    async fn spawn_mock_rest(router: axum::Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_catalog_parses_mock_exchange_info() {
+        use axum::{routing::get, Json};
+
+        let exchange_info = serde_json::json!({
+            "timezone": "UTC",
+            "symbols": [{
+                "symbol": "BTCUSDT",
+                "status": "TRADING",
+                "baseAsset": "BTC",
+                "quoteAsset": "USDT",
+                "baseAssetPrecision": 8,
+                "quotePrecision": 8,
+                "filters": [
+                    {"filterType": "PRICE_FILTER", "tickSize": "0.01"},
+                    {"filterType": "LOT_SIZE", "minQty": "0.00001000", "stepSize": "0.00001000"}
+                ]
+            }, {
+                "symbol": "ETHBTC",
+                "baseAsset": "ETH",
+                "quoteAsset": "BTC",
+                "baseAssetPrecision": 8,
+                "quotePrecision": 8,
+                "filters": []
+            }]
+        });
+        // Futures list their own instruments, with a coarser tick and delivery contracts
+        let futures_info = serde_json::json!({
+            "symbols": [{
+                "symbol": "BTCUSDT",
+                "pair": "BTCUSDT",
+                "contractType": "PERPETUAL",
+                "baseAsset": "BTC",
+                "quoteAsset": "USDT",
+                "baseAssetPrecision": 8,
+                "quotePrecision": 8,
+                "filters": [
+                    {"filterType": "PRICE_FILTER", "tickSize": "0.10"},
+                    {"filterType": "LOT_SIZE", "minQty": "0.001", "stepSize": "0.001"}
+                ]
+            }, {
+                "symbol": "BTCUSDT_250627",
+                "pair": "BTCUSDT",
+                "contractType": "CURRENT_QUARTER",
+                "deliveryDate": 1751011200000i64,
+                "baseAsset": "BTC",
+                "quoteAsset": "USDT",
+                "baseAssetPrecision": 8,
+                "quotePrecision": 8,
+                "filters": []
+            }, {
+                "symbol": "1000PEPEUSDT",
+                "pair": "1000PEPEUSDT",
+                "contractType": "PERPETUAL",
+                "baseAsset": "1000PEPE",
+                "quoteAsset": "USDT",
+                "baseAssetPrecision": 8,
+                "quotePrecision": 8,
+                "filters": []
+            }, {
+                "symbol": "SRMUSDT",
+                "pair": "SRMUSDT",
+                "contractType": "PERPETUAL",
+                "status": "SETTLING",
+                "baseAsset": "SRM",
+                "quoteAsset": "USDT",
+                "baseAssetPrecision": 8,
+                "quotePrecision": 8,
+                "filters": []
+            }]
+        });
+        let router = axum::Router::new()
+            .route(
+                "/api/v3/exchangeInfo",
+                get(move || async move { Json(exchange_info) }),
+            )
+            .route(
+                "/fapi/v1/exchangeInfo",
+                get(move || async move { Json(futures_info) }),
+            );
+        let base_url = spawn_mock_rest(router).await;
+
+        let client = Client::builder()
+            .timeout(std::time::Duration::from_secs(5))
+            .build()
+            .unwrap();
+        let catalog = ExchangeCatalog::new(MemoryCache::new().handle())
+            .with_http_client(client)
+            .with_base_url(ExchangeId::BINANCE, base_url.clone())
+            .with_perp_base_url(ExchangeId::BINANCE, base_url);
+
+        catalog.load_exchange_symbols("binance").await.unwrap();
+
+        // ETHBTC is dropped by the quote filter and SRMUSDT is delisted
+        let symbols = catalog.get_symbols(Some("binance"), None).await;
+        assert_eq!(symbols.len(), 4);
+
+        let spot = catalog
+            .get_symbols(Some("binance"), Some(MarketType::Spot))
+            .await;
+        assert_eq!(spot.len(), 1);
+        assert_eq!(spot[0].symbol, "BTCUSDT");
+        assert_eq!(spot[0].tick_size, "0.01");
+        assert_eq!(spot[0].price_precision, 2);
+        assert_eq!(spot[0].step_size, Decimal::from_str("0.00001").unwrap());
+
+        // Perpetual metadata comes from the futures listing, not a copy of spot
+        let mut perps = catalog
+            .get_symbols(Some("binance"), Some(MarketType::Perpetual))
+            .await;
+        perps.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        let listed: Vec<&str> = perps.iter().map(|meta| meta.symbol.as_str()).collect();
+        assert_eq!(listed, ["1000PEPEUSDT", "BTCUSDT"]);
+        assert_eq!(perps[1].tick_size, "0.10");
+        assert_eq!(perps[1].step_size, Decimal::from_str("0.001").unwrap());
+
+        // The quarterly contract is listed as a dated future, apart from the perpetual
+        let futures = catalog
+            .get_symbols(Some("binance"), Some(MarketType::Future))
+            .await;
+        assert_eq!(futures.len(), 1);
+        assert_eq!(futures[0].symbol, "BTCUSDT_250627");
+        assert_eq!(
+            futures[0].expiry,
+            chrono::NaiveDate::from_ymd_opt(2025, 6, 27)
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "hits the live Binance spot and futures APIs"]
+    async fn test_binance_spot_and_perp_metadata_are_sourced_separately() {
+        let catalog = ExchangeCatalog::new(MemoryCache::new().handle());
+        catalog.load_exchange_symbols("binance").await.unwrap();
+
+        let spot = catalog
+            .get_symbols(Some("binance"), Some(MarketType::Spot))
+            .await;
+        let perps = catalog
+            .get_symbols(Some("binance"), Some(MarketType::Perpetual))
+            .await;
+        let find = |metas: &[SymbolMeta], symbol: &str| {
+            metas.iter().find(|meta| meta.symbol == symbol).cloned()
+        };
+
+        // Spot quotes BTCUSDT in cents, futures in tenths
+        let spot_btc = find(&spot, "BTCUSDT").expect("BTCUSDT spot listing");
+        let perp_btc = find(&perps, "BTCUSDT").expect("BTCUSDT perpetual listing");
+        assert_ne!(spot_btc.tick_size, perp_btc.tick_size);
+        assert_ne!(spot_btc.price_precision, perp_btc.price_precision);
+
+        // Cloning spot listings into perpetuals would give every spot pair a perpetual entry
+        assert!(
+            spot.iter().any(|meta| find(&perps, &meta.symbol).is_none()),
+            "every spot pair has a perpetual entry"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_catalog_loads_bybit_linear_instruments_across_pages() {
+        use axum::{extract::Query, routing::get, Json};
+
+        let instrument = |symbol: &str, base: &str, tick: &str| {
+            serde_json::json!({
+                "symbol": symbol,
+                "baseCoin": base,
+                "quoteCoin": "USDT",
+                "deliveryTime": "0",
+                "priceFilter": {"tickSize": tick}
+            })
+        };
+        let spot = serde_json::json!({"result": {"list": [instrument("BTCUSDT", "BTC", "0.01")]}});
+        let first_page = serde_json::json!({"result": {
+            "list": [instrument("BTCUSDT", "BTC", "0.10")],
+            "nextPageCursor": "page2"
+        }});
+        let second_page = serde_json::json!({"result": {
+            "list": [instrument("SOLUSDT", "SOL", "0.010")],
+            "nextPageCursor": ""
+        }});
+        let router = axum::Router::new().route(
+            "/v5/market/instruments-info",
+            get(
+                move |Query(params): Query<HashMap<String, String>>| async move {
+                    let page = match (params["category"].as_str(), params.get("cursor")) {
+                        ("spot", _) => spot,
+                        ("linear", None) => first_page,
+                        ("linear", Some(_)) => second_page,
+                        _ => serde_json::json!({"result": {"list": []}}),
+                    };
+                    Json(page)
+                },
+            ),
+        );
+        let base_url = spawn_mock_rest(router).await;
+
+        let catalog = ExchangeCatalog::new(MemoryCache::new().handle())
+            .with_base_url(ExchangeId::BYBIT, base_url);
+        catalog.load_exchange_symbols("bybit").await.unwrap();
+
+        let spot = catalog
+            .get_symbols(Some("bybit"), Some(MarketType::Spot))
+            .await;
+        assert_eq!(spot.len(), 1);
+        assert_eq!(spot[0].tick_size, "0.01");
+
+        let mut perps = catalog
+            .get_symbols(Some("bybit"), Some(MarketType::Perpetual))
+            .await;
+        perps.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        let listed: Vec<(&str, &str)> = perps
+            .iter()
+            .map(|meta| (meta.symbol.as_str(), meta.tick_size.as_str()))
+            .collect();
+        assert_eq!(listed, [("BTCUSDT", "0.10"), ("SOLUSDT", "0.010")]);
+    }
+
+    #[test]
+    fn test_bybit_dated_futures_surface_expiry() {
+        let response: BybitResponse = serde_json::from_value(serde_json::json!({
+            "result": {"list": [
+                {
+                    "symbol": "BTCUSD",
+                    "baseCoin": "BTC",
+                    "quoteCoin": "USD",
+                    "deliveryTime": "0",
+                    "priceFilter": {"tickSize": "0.5"}
+                },
+                {
+                    "symbol": "BTCUSDM24",
+                    "baseCoin": "BTC",
+                    "quoteCoin": "USD",
+                    "deliveryTime": "1719561600000",
+                    "priceFilter": {"tickSize": "0.5"}
+                }
+            ]}
+        }))
+        .unwrap();
+
+        let metas = ExchangeCatalog::bybit_symbol_metas(response, &[MarketType::InversePerpetual]);
+        assert_eq!(metas.len(), 2);
+
+        let perpetual = metas.iter().find(|meta| meta.symbol == "BTCUSD").unwrap();
+        assert_eq!(perpetual.market_type, MarketType::InversePerpetual);
+        assert_eq!(perpetual.expiry, None);
+
+        let future = metas
+            .iter()
+            .find(|meta| meta.symbol == "BTCUSDM24")
+            .unwrap();
+        assert_eq!(future.market_type, MarketType::Future);
+        assert_eq!(future.expiry, chrono::NaiveDate::from_ymd_opt(2024, 6, 28));
+    }
+
+    #[tokio::test]
+    async fn test_single_symbol_lookup_uses_the_index() {
+        let catalog = ExchangeCatalog::new(MemoryCache::new().handle());
+        catalog.load_fallback_symbols("binance").await;
+        // With the per-exchange lists gone, only the index can answer
+        catalog.symbol_cache.write().await.clear();
+
+        let eth_perp = catalog
+            .get_symbol_meta_one(
+                "binance",
+                MarketType::Perpetual,
+                &Symbol::new("eth", "usdt"),
+            )
+            .await
+            .expect("ETH-USDT perpetual listing");
+        assert_eq!(eth_perp.symbol, "ETHUSDT");
+        assert_eq!(eth_perp.market_type, MarketType::Perpetual);
+
+        let btc = Symbol::new("BTC", "USDT");
+        assert!(catalog
+            .get_symbol_meta_one("binance", MarketType::Future, &btc)
+            .await
+            .is_none());
+        assert!(catalog
+            .get_symbol_meta_one("bybit", MarketType::Spot, &btc)
+            .await
+            .is_none());
+
+        // Storing an exchange's list again replaces its index entries
+        catalog.store_symbols("binance", Vec::new()).await;
+        assert!(catalog
+            .get_symbol_meta_one("binance", MarketType::Spot, &btc)
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_exchanges_load_concurrently() {
+        use std::time::{Duration, Instant};
+
+        let started = Instant::now();
+        let failures = load_concurrently(
+            vec!["slow".to_string(), "fast".to_string()],
+            CATALOG_LOAD_CONCURRENCY,
+            |exchange_name| async move {
+                if exchange_name == "slow" {
+                    tokio::time::sleep(Duration::from_millis(300)).await;
+                    Err(anyhow!("upstream unavailable"))
+                } else {
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    Ok(())
+                }
+            },
+        )
+        .await;
+        let elapsed = started.elapsed();
+
+        // Sequential loading would take at least 500ms
+        assert!(elapsed < Duration::from_millis(450), "took {:?}", elapsed);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, "slow");
+    }
 }
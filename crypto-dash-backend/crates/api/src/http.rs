@@ -0,0 +1,271 @@
+use crypto_dash_exchanges_common::{exponential_backoff, RetryConfig};
+use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use tracing::warn;
+
+/// Time allowed for one attempt, including reading the body
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Characters of an error response body kept in `HttpError::Status` and `HttpError::Decode`
+const ERROR_BODY_LIMIT: usize = 200;
+
+/// Retries used for exchange REST requests: three attempts, backing off from 200ms
+pub fn rest_retry_config() -> RetryConfig {
+    RetryConfig {
+        max_attempts: 3,
+        base_delay: Duration::from_millis(200),
+        max_delay: Duration::from_secs(2),
+        multiplier: 2.0,
+    }
+}
+
+/// Why a JSON GET failed
+#[derive(Debug)]
+pub enum HttpError {
+    /// No response within `REQUEST_TIMEOUT`
+    Timeout,
+    /// The request could not be sent or the body could not be read
+    Transport(reqwest::Error),
+    /// The server answered with a non-2xx status
+    Status { status: StatusCode, body: String },
+    /// The body was not the expected JSON, e.g. an HTML maintenance page
+    Decode {
+        status: StatusCode,
+        body: String,
+        source: serde_json::Error,
+    },
+}
+
+impl HttpError {
+    /// Failures worth another attempt: timeouts, connection errors, 5xx and 429
+    pub fn is_transient(&self) -> bool {
+        match self {
+            HttpError::Timeout | HttpError::Transport(_) => true,
+            HttpError::Status { status, .. } => {
+                status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            HttpError::Decode { .. } => false,
+        }
+    }
+}
+
+impl std::fmt::Display for HttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HttpError::Timeout => write!(f, "request timed out after {:?}", REQUEST_TIMEOUT),
+            HttpError::Transport(e) => write!(f, "request failed: {}", e),
+            HttpError::Status { status, body } => write!(f, "HTTP {}: {}", status, body),
+            HttpError::Decode {
+                status,
+                body,
+                source,
+            } => write!(
+                f,
+                "invalid JSON response (HTTP {}): {}; body: {:?}",
+                status, source, body
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+impl From<reqwest::Error> for HttpError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            HttpError::Timeout
+        } else {
+            HttpError::Transport(e)
+        }
+    }
+}
+
+/// GET `url` with `query` and parse the JSON body, retrying transient failures with backoff
+pub async fn get_json<T>(
+    client: &Client,
+    url: &str,
+    query: &[(&str, &str)],
+    retry: &RetryConfig,
+) -> Result<T, HttpError>
+where
+    T: DeserializeOwned,
+{
+    let mut attempt = 0;
+    loop {
+        exponential_backoff(attempt, retry).await;
+        attempt += 1;
+
+        match get_json_once(client, url, query).await {
+            Err(e) if e.is_transient() && attempt < retry.max_attempts => {
+                warn!(
+                    "GET {} failed (attempt {}/{}): {}",
+                    url, attempt, retry.max_attempts, e
+                );
+            }
+            result => return result,
+        }
+    }
+}
+
+async fn get_json_once<T>(
+    client: &Client,
+    url: &str,
+    query: &[(&str, &str)],
+) -> Result<T, HttpError>
+where
+    T: DeserializeOwned,
+{
+    let response = client
+        .get(url)
+        .query(query)
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await?;
+
+    let status = response.status();
+    let body = response.text().await?;
+    let snippet = || body.trim().chars().take(ERROR_BODY_LIMIT).collect();
+    if !status.is_success() {
+        return Err(HttpError::Status {
+            status,
+            body: snippet(),
+        });
+    }
+
+    serde_json::from_str(&body).map_err(|source| HttpError::Decode {
+        status,
+        body: snippet(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::StatusCode as AxumStatus, routing::get, Json, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn fast_retry(max_attempts: u32) -> RetryConfig {
+        RetryConfig {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            multiplier: 2.0,
+        }
+    }
+
+    /// Serve a route that fails `failures` times with `status`, then returns `[1, 2, 3]`
+    async fn flaky_server(failures: usize, status: AxumStatus) -> (String, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let router = Router::new().route(
+            "/data",
+            get(move || {
+                let counter = Arc::clone(&counter);
+                async move {
+                    if counter.fetch_add(1, Ordering::SeqCst) < failures {
+                        Err(status)
+                    } else {
+                        Ok(Json(vec![1, 2, 3]))
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/data", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        (url, calls)
+    }
+
+    #[tokio::test]
+    async fn transient_failures_are_retried() {
+        let (url, calls) = flaky_server(2, AxumStatus::SERVICE_UNAVAILABLE).await;
+
+        let data: Vec<i32> = get_json(
+            &Client::new(),
+            &url,
+            &[("symbol", "BTCUSDT")],
+            &fast_retry(3),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(data, vec![1, 2, 3]);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() {
+        let (url, calls) = flaky_server(5, AxumStatus::BAD_REQUEST).await;
+
+        let err = get_json::<Vec<i32>>(&Client::new(), &url, &[], &fast_retry(3))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            HttpError::Status {
+                status: StatusCode::BAD_REQUEST,
+                ..
+            }
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Server errors stop once the attempts run out
+        let (url, calls) = flaky_server(5, AxumStatus::BAD_GATEWAY).await;
+        let err = get_json::<Vec<i32>>(&Client::new(), &url, &[], &fast_retry(2))
+            .await
+            .unwrap_err();
+        assert!(err.is_transient());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn non_json_bodies_report_status_and_snippet() {
+        let maintenance = format!(
+            "<html><body><h1>Service under maintenance</h1>{}</body></html>",
+            "x".repeat(500)
+        );
+        let router = Router::new()
+            .route(
+                "/data",
+                get(move || async move { axum::response::Html(maintenance) }),
+            )
+            .route("/empty", get(|| async { "" }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let err = get_json::<Vec<i32>>(
+            &Client::new(),
+            &format!("{}/data", base),
+            &[],
+            &fast_retry(3),
+        )
+        .await
+        .unwrap_err();
+        let HttpError::Decode { status, body, .. } = &err else {
+            panic!("expected a decode error, got {:?}", err);
+        };
+        assert_eq!(*status, StatusCode::OK);
+        assert_eq!(body.chars().count(), ERROR_BODY_LIMIT);
+        let message = err.to_string();
+        assert!(message.contains("HTTP 200 OK"), "{}", message);
+        assert!(
+            message.contains("<html><body><h1>Service under maintenance"),
+            "{}",
+            message
+        );
+
+        let err = get_json::<Vec<i32>>(
+            &Client::new(),
+            &format!("{}/empty", base),
+            &[],
+            &fast_retry(3),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("body: \"\""), "{}", err);
+    }
+}
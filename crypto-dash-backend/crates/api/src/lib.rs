@@ -1,12 +1,25 @@
 pub mod catalog;
+pub mod http;
+pub mod maintenance;
 pub mod routes;
+pub mod selftest;
 pub mod state;
+pub mod stats;
+pub mod subscriptions;
+pub mod upstream;
+pub mod warmer;
 pub mod ws;
 
 #[cfg(test)]
 mod bybit_test;
 
 pub use catalog::*;
+pub use maintenance::*;
 pub use routes::*;
+pub use selftest::*;
 pub use state::*;
+pub use stats::*;
+pub use subscriptions::*;
+pub use upstream::*;
+pub use warmer::*;
 pub use ws::*;
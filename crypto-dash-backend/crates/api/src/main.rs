@@ -1,6 +1,13 @@
 mod catalog;
+mod http;
+mod maintenance;
 mod routes;
+mod selftest;
 mod state;
+mod stats;
+mod subscriptions;
+mod upstream;
+mod warmer;
 mod ws;
 
 use anyhow::Result;
@@ -8,74 +15,148 @@ use axum::{
     routing::{get, post},
     Router,
 };
-use crypto_dash_binance::BinanceAdapter;
-use crypto_dash_bybit::BybitAdapter;
-use crypto_dash_cache::MemoryCache;
+use crypto_dash_cache::{MemoryCache, RedisCache};
 use crypto_dash_core::config::Config;
-use crypto_dash_exchanges_common::ExchangeAdapter;
-use crypto_dash_stream_hub::StreamHub;
+use crypto_dash_stream_hub::{StreamHub, Topic};
 use dotenvy::dotenv;
 use state::AppState;
-use std::sync::Arc;
 use tower_http::{cors::CorsLayer, services::ServeDir};
 use tracing::info;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Load variables from the local .env before tracing reads RUST_LOG
     let _ = dotenv();
 
-    // Initialize tracing
+    // Load configuration
+    let config = Config::from_env()?;
+
+    // Initialize tracing; JSON output keeps structured fields such as topic exchange/market
+    let fmt_layer = if config.log_json {
+        tracing_subscriber::fmt::layer().json().boxed()
+    } else {
+        tracing_subscriber::fmt::layer().boxed()
+    };
     tracing_subscriber::registry()
         .with(
             tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
                 "crypto_dash=debug,tower_http=debug,axum::rejection=trace".into()
             }),
         )
-        .with(tracing_subscriber::fmt::layer())
+        .with(fmt_layer)
         .init();
 
-    // Load configuration
-    let config = Config::from_env()?;
+    // Topic keys rendered from here on follow the configured scheme
+    Topic::set_key_scheme(config.topic_key_scheme);
+
+    // `exchange-selftest <exchange> [timeout_secs]` checks one adapter end to end instead of serving
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("exchange-selftest") {
+        return exchange_selftest(&config, &args[1..]).await;
+    }
+
+    for spec in &config.invalid_channel_specs {
+        tracing::warn!("Ignoring invalid channel spec {}", spec);
+    }
+
     info!("Starting crypto-dash API server on {}", config.bind_addr);
     info!("Enabled exchanges: {:?}", config.exchanges);
 
     // Initialize core services
-    let stream_hub = StreamHub::new();
+    let stream_hub = StreamHub::with_limits(config.max_hub_topics, config.stream_channel_capacity)
+        .with_skew_warn_threshold(std::time::Duration::from_millis(config.clock_skew_warn_ms));
     let hub_handle = stream_hub.start().await?;
 
-    let cache = MemoryCache::new();
-    let cache_handle = cache.start().await?;
+    let cache_handle = if config.enable_redis {
+        RedisCache::new(&config.redis_url)
+            .with_namespace(&config.cache_namespace)
+            .with_orderbook_depth_cap(config.cache_book_depth)
+            .with_key_scheme(config.topic_key_scheme)
+            .with_market_data_ttl(std::time::Duration::from_secs(
+                config.cache_ticker_max_age_secs,
+            ))
+            .start()
+            .await?
+    } else {
+        MemoryCache::new()
+            .with_namespace(&config.cache_namespace)
+            .with_orderbook_depth_cap(config.cache_book_depth)
+            .with_ticker_max_age(std::time::Duration::from_secs(
+                config.cache_ticker_max_age_secs,
+            ))
+            .start()
+            .await?
+    };
 
     // Create application state
-    let mut app_state = AppState::new(hub_handle.clone(), cache_handle.clone());
+    let http_client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()?;
+    let mut app_state = AppState::new(hub_handle.clone(), cache_handle.clone())
+        .with_http_client(http_client)
+        .with_rest_urls(config.rest_urls.clone())
+        .with_status_coalesce_window(std::time::Duration::from_millis(config.status_coalesce_ms))
+        .with_book_resync(config.book_resync)
+        .with_serialize_buffer_bytes(config.ws_serialize_buffer_bytes)
+        .with_client_message_rate_limit(config.ws_max_messages_per_sec)
+        .with_idle_timeout(std::time::Duration::from_secs(config.ws_idle_timeout_secs))
+        .with_default_channels(config.ws_default_channels.clone())
+        .with_upstream_probe_ttl(std::time::Duration::from_secs(
+            config.upstream_probe_cache_secs,
+        ))
+        .with_maintenance_drain_grace(std::time::Duration::from_secs(
+            config.maintenance_drain_grace_secs,
+        ))
+        .with_exchange_priority(config.exchange_priority());
+    if let Some(token) = &config.admin_token {
+        app_state = app_state.with_admin_token(token);
+    }
+    app_state.set_subscription_limit(config.max_upstream_subscriptions);
 
     // Initialize exchange adapters
-    for exchange_name in &config.exchanges {
-        match exchange_name.as_str() {
-            "binance" => {
-                let adapter = Arc::new(BinanceAdapter::new());
-                adapter
-                    .start(hub_handle.clone(), cache_handle.clone())
-                    .await?;
-                app_state.add_exchange(adapter);
-                info!("Initialized Binance adapter");
-            }
-            "bybit" => {
-                let adapter = Arc::new(BybitAdapter::new());
-                adapter
-                    .start(hub_handle.clone(), cache_handle.clone())
-                    .await?;
-                app_state.add_exchange(adapter);
-                info!("Initialized Bybit adapter");
-            }
-            _ => {
-                tracing::warn!("Unknown exchange: {}", exchange_name);
+    for adapter in state::create_adapters(&config) {
+        adapter
+            .start(hub_handle.clone(), cache_handle.clone())
+            .await?;
+        info!("Initialized {} adapter", adapter.id().as_str());
+        app_state.add_exchange(adapter);
+    }
+
+    // Open exchange connections up front so first subscribes are fast
+    if !config.preconnect_markets.is_empty() {
+        for adapter in app_state.exchanges.values() {
+            let markets: Vec<_> = config
+                .preconnect_markets
+                .iter()
+                .copied()
+                .filter(|market| adapter.supported_markets().contains(market))
+                .collect();
+
+            if let Err(e) = adapter.connect_markets(&markets).await {
+                tracing::warn!(
+                    "Failed to pre-connect {} markets: {}",
+                    adapter.id().as_str(),
+                    e
+                );
             }
         }
     }
 
+    // Keep configured channels cached even while no client watches them
+    if !config.cache_warm_channels.is_empty() {
+        warmer::spawn_cache_warmer(&app_state, &config.cache_warm_channels).await;
+    }
+
+    // Count lifecycle events for /api/stats
+    stats::spawn_lifecycle_counter(&app_state);
+
+    // Periodic stats for sessions that subscribe with `stats: true`
+    stats::spawn_stats_broadcast(
+        app_state.clone(),
+        std::time::Duration::from_secs(config.stats_interval_secs.max(1)),
+    );
+
     // Load symbol metadata for all exchanges
     info!("Loading symbol metadata for all exchanges...");
     if let Err(e) = app_state.load_symbol_metadata().await {
@@ -88,29 +169,150 @@ async fn main() -> Result<()> {
     }
 
     // Build the application router
-    let app = Router::new()
+    let mut router = Router::new()
         // Health endpoints
         .route("/health", get(routes::health))
         .route("/ready", get(routes::ready))
         // API routes
         .route("/api/exchanges", get(routes::list_exchanges))
+        .route("/api/markets", get(routes::markets_overview))
         .route("/api/symbols", get(routes::list_symbols))
+        .route("/api/symbols/:exchange/:symbol", get(routes::get_symbol))
+        .route("/api/ticker", get(routes::get_ticker))
+        .route("/api/tickers", get(routes::list_tickers))
+        .route("/api/funding", get(routes::get_funding))
+        .route("/api/orderbook", get(routes::get_orderbook))
+        .route("/api/movers", get(routes::get_movers))
+        .route("/api/composite", get(routes::get_composite))
+        .route("/api/stats", get(routes::get_stats))
         .route("/api/candles", get(routes::get_candles))
+        .route("/api/candles/batch", post(routes::get_candles_batch))
         .route("/api/symbols/refresh", post(routes::refresh_symbols))
+        .route("/api/catalog/status", get(routes::catalog_status))
         // WebSocket endpoint
-        .route("/ws", get(ws::websocket_handler))
+        .route("/ws", get(ws::websocket_handler));
+
+    // Lifecycle event stream for operators, off unless explicitly enabled
+    if config.enable_admin_events {
+        if config.admin_token.is_some() {
+            info!("Admin event stream enabled at /admin/events");
+            router = router.route(
+                "/admin/events",
+                get(routes::admin_events).route_layer(axum::middleware::from_fn_with_state(
+                    app_state.clone(),
+                    routes::require_admin_token,
+                )),
+            );
+        } else {
+            tracing::warn!(
+                "ENABLE_ADMIN_EVENTS is set without ADMIN_TOKEN; /admin/events stays off"
+            );
+        }
+    }
+    if config.enable_admin_maintenance {
+        if config.admin_token.is_some() {
+            info!("Maintenance switch enabled at /admin/maintenance");
+            router = router.route(
+                "/admin/maintenance",
+                post(routes::admin_maintenance).route_layer(axum::middleware::from_fn_with_state(
+                    app_state.clone(),
+                    routes::require_admin_token,
+                )),
+            );
+        } else {
+            tracing::warn!(
+                "ENABLE_ADMIN_MAINTENANCE is set without ADMIN_TOKEN; /admin/maintenance stays off"
+            );
+        }
+    }
+
+    let app = router
         // Serve static files from the frontend build
         .nest_service("/", ServeDir::new("/usr/local/bin/static"))
         // Add middleware
         .layer(CorsLayer::permissive())
         .layer(tower_http::trace::TraceLayer::new_for_http())
-        .with_state(app_state);
+        .with_state(app_state.clone());
 
     // Start the server
     let listener = tokio::net::TcpListener::bind(&config.bind_addr).await?;
     info!("Server listening on {}", config.bind_addr);
 
-    axum::serve(listener, app).await?;
+    let shutdown_state = app_state.clone();
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            shutdown_signal().await;
+            info!("Closing open WebSocket sessions");
+            shutdown_state.begin_shutdown();
+        })
+        .await?;
+
+    info!("Shutting down exchange adapters");
+    app_state.stop_exchanges().await;
 
     Ok(())
 }
+
+/// Stream one BTC-USDT spot ticker from the exchange; an error exits with status 1
+async fn exchange_selftest(config: &Config, args: &[String]) -> Result<()> {
+    let usage = "usage: exchange-selftest <exchange> [timeout_secs]";
+    let exchange = args
+        .first()
+        .ok_or_else(|| anyhow::anyhow!(usage))?
+        .trim()
+        .to_lowercase();
+    let timeout = match args.get(1) {
+        Some(secs) => std::time::Duration::from_secs(
+            secs.parse()
+                .map_err(|_| anyhow::anyhow!("invalid timeout '{}'; {}", secs, usage))?,
+        ),
+        None => selftest::DEFAULT_SELFTEST_TIMEOUT,
+    };
+    let adapter = state::create_adapter(&exchange, config)
+        .ok_or_else(|| anyhow::anyhow!("Unknown exchange: {}", exchange))?;
+
+    info!("Running {} self-test", exchange);
+    let ticker = selftest::run_selftest(adapter, timeout)
+        .await
+        .map_err(|e| anyhow::anyhow!("{} self-test failed: {:#}", exchange, e))?;
+    info!(
+        "{} self-test passed: {} last {} bid {} ask {}",
+        exchange,
+        ticker.symbol.canonical(),
+        ticker.last,
+        ticker.bid,
+        ticker.ask
+    );
+    Ok(())
+}
+
+/// Resolve on Ctrl-C, or on SIGTERM as sent by container runtimes
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!("Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+    info!("Shutdown signal received");
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Server-wide maintenance switch. While it is on new WebSocket sessions are refused and,
+/// with a drain grace set, open sessions are told to reconnect elsewhere and then closed.
+#[derive(Clone)]
+pub struct Maintenance {
+    enabled: Arc<watch::Sender<bool>>,
+    /// How long draining sessions keep streaming before they are closed; zero leaves
+    /// open sessions alone
+    drain_grace: Duration,
+}
+
+impl Maintenance {
+    pub fn new(drain_grace: Duration) -> Self {
+        let (enabled, _) = watch::channel(false);
+        Self {
+            enabled: Arc::new(enabled),
+            drain_grace,
+        }
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.send_replace(enabled);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        *self.enabled.borrow()
+    }
+
+    pub fn drain_grace(&self) -> Duration {
+        self.drain_grace
+    }
+
+    /// Notified each time maintenance is switched on or off
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.enabled.subscribe()
+    }
+}
@@ -0,0 +1,200 @@
+use crate::state::AppState;
+use axum::{
+    extract::{Query, Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Json, Response,
+    },
+};
+use futures::stream::{self, Stream};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::warn;
+
+/// Route layer for the `/admin` endpoints: passes requests carrying
+/// `Authorization: Bearer <ADMIN_TOKEN>` and rejects the rest, all of them when no token is set
+pub async fn require_admin_token(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let presented = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    match (state.admin_token.as_deref(), presented) {
+        (Some(expected), Some(presented)) if tokens_match(expected, presented) => {
+            Ok(next.run(request).await)
+        }
+        _ => {
+            warn!(
+                "Rejected unauthenticated admin request to {}",
+                request.uri().path()
+            );
+            Err(StatusCode::UNAUTHORIZED)
+        }
+    }
+}
+
+/// Compare without stopping at the first differing byte, so timing does not reveal a prefix
+fn tokens_match(expected: &str, presented: &str) -> bool {
+    expected.len() == presented.len()
+        && expected
+            .bytes()
+            .zip(presented.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// GET /admin/events - Server-sent stream of lifecycle events emitted after connecting
+pub async fn admin_events(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let receiver = state.hub.subscribe_events();
+
+    let events = stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => {
+                    let sse_event = Event::default()
+                        .json_data(&event)
+                        .unwrap_or_else(|_| Event::default().comment("unserializable event"));
+                    return Some((Ok(sse_event), receiver));
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("Admin event stream lagged, skipped {} events", skipped);
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MaintenanceQuery {
+    pub enabled: bool,
+}
+
+/// POST /admin/maintenance?enabled= - Switch maintenance mode: new WebSocket sessions are
+/// refused and, with a drain grace configured, open ones are drained
+pub async fn admin_maintenance(
+    State(state): State<AppState>,
+    Query(params): Query<MaintenanceQuery>,
+) -> Json<Value> {
+    warn!(
+        "Maintenance mode {}",
+        if params.enabled {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+    state.maintenance.set_enabled(params.enabled);
+
+    Json(json!({
+        "maintenance": params.enabled,
+        "drain_grace_secs": state.maintenance.drain_grace().as_secs()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        middleware::from_fn_with_state,
+        routing::{get, post},
+        Router,
+    };
+    use crypto_dash_cache::MemoryCache;
+    use crypto_dash_stream_hub::StreamHub;
+
+    async fn spawn_admin_server(state: AppState) -> String {
+        let app = Router::new()
+            .route(
+                "/admin/events",
+                get(admin_events)
+                    .route_layer(from_fn_with_state(state.clone(), require_admin_token)),
+            )
+            .route(
+                "/admin/maintenance",
+                post(admin_maintenance)
+                    .route_layer(from_fn_with_state(state.clone(), require_admin_token)),
+            )
+            .with_state(state);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        url
+    }
+
+    #[tokio::test]
+    async fn maintenance_switch_requires_the_admin_token() {
+        let state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle())
+            .with_admin_token("s3cret");
+        let url = format!(
+            "{}/admin/maintenance?enabled=true",
+            spawn_admin_server(state.clone()).await
+        );
+        let client = reqwest::Client::new();
+
+        for token in [None, Some("wrong"), Some("s3cre")] {
+            let mut request = client.post(&url);
+            if let Some(token) = token {
+                request = request.bearer_auth(token);
+            }
+            assert_eq!(request.send().await.unwrap().status(), 401);
+        }
+        assert!(!state.maintenance.is_enabled());
+
+        let response = client
+            .post(&url)
+            .bearer_auth("s3cret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert!(state.maintenance.is_enabled());
+    }
+
+    #[tokio::test]
+    async fn admin_endpoints_refuse_everything_without_a_token() {
+        let state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle());
+        let url = spawn_admin_server(state.clone()).await;
+
+        let response = reqwest::Client::new()
+            .post(format!("{}/admin/maintenance?enabled=true", url))
+            .bearer_auth("")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 401);
+        assert!(!state.maintenance.is_enabled());
+    }
+
+    #[tokio::test]
+    async fn event_stream_requires_the_admin_token() {
+        let state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle())
+            .with_admin_token("s3cret");
+        let url = format!("{}/admin/events", spawn_admin_server(state).await);
+        let client = reqwest::Client::new();
+
+        let response = client.get(&url).send().await.unwrap();
+        assert_eq!(response.status(), 401);
+        let response = client.get(&url).bearer_auth("wrong").send().await.unwrap();
+        assert_eq!(response.status(), 401);
+
+        let response = client.get(&url).bearer_auth("s3cret").send().await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers()[reqwest::header::CONTENT_TYPE],
+            "text/event-stream"
+        );
+    }
+}
@@ -1,21 +1,49 @@
+use crate::http::{get_json, rest_retry_config, HttpError};
+use crate::routes::query::optional_market_type;
 use crate::state::AppState;
 use anyhow::{anyhow, Result};
 use axum::{
+    body::{Body, Bytes},
     extract::{Query, State},
-    http::StatusCode,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use chrono::{DateTime, Duration, TimeZone, Utc};
-use crypto_dash_core::model::{Candlestick, MarketType};
+use crypto_dash_core::config::RestUrls;
+use crypto_dash_core::model::{Candlestick, ExchangeId, MarketType};
+use futures::stream::{self, StreamExt};
 use reqwest::Client;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::str::FromStr;
 use tracing::{error, warn};
 
 const DEFAULT_CANDLE_LIMIT: usize = 200;
 const MAX_CANDLE_LIMIT: usize = 1000;
-const CACHE_TTL_SECONDS: i64 = 30;
+/// Per-interval defaults used when the client omits `limit`; others use DEFAULT_CANDLE_LIMIT
+const INTERVAL_DEFAULT_LIMITS: &[(&str, usize)] = &[
+    ("1m", 500),
+    ("5m", 400),
+    ("15m", 300),
+    ("1d", 180),
+    ("1w", 104),
+    ("1M", 36),
+];
+/// Cached candles live for this fraction of one bar, within the bounds below
+const CACHE_TTL_BAR_DIVISOR: i64 = 12;
+const MIN_CACHE_TTL_SECONDS: i64 = 5;
+const MAX_CACHE_TTL_SECONDS: i64 = 900;
+/// Kline intervals each exchange accepts, in `CandleInterval` notation
+const BINANCE_INTERVALS: &[&str] = &[
+    "1m", "3m", "5m", "15m", "30m", "1h", "2h", "4h", "6h", "8h", "12h", "1d", "3d", "1w", "1M",
+];
+const BYBIT_INTERVALS: &[&str] = &[
+    "1m", "3m", "5m", "15m", "30m", "1h", "2h", "4h", "6h", "12h", "1d", "1w", "1M",
+];
+const MAX_BATCH_REQUESTS: usize = 20;
+const BATCH_CONCURRENCY: usize = 4;
 
 #[derive(Debug, Deserialize)]
 pub struct CandlesQuery {
@@ -23,7 +51,40 @@ pub struct CandlesQuery {
     pub symbol: String,
     pub interval: String,
     pub limit: Option<usize>,
+    #[serde(default, deserialize_with = "optional_market_type")]
     pub market_type: Option<MarketType>,
+    /// Response encoding; ignored by the batch endpoint
+    #[serde(default)]
+    pub format: CandlesFormat,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CandlesFormat {
+    /// One JSON object with the query echoed and a `candles` array
+    #[default]
+    Json,
+    /// Candles only, streamed one JSON object per line
+    Ndjson,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CandlesBatchRequest {
+    pub requests: Vec<CandlesQuery>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CandlesBatchResponse {
+    pub results: BTreeMap<String, CandlesBatchResult>,
+}
+
+/// Outcome of a single batch item; exactly one of `data` or `error` is set
+#[derive(Debug, Serialize)]
+pub struct CandlesBatchResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<CandlesResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -46,42 +107,113 @@ struct CachedCandles {
 pub async fn get_candles(
     State(state): State<AppState>,
     Query(params): Query<CandlesQuery>,
-) -> Result<Json<CandlesResponse>, StatusCode> {
-    let exchange = params.exchange.trim().to_lowercase();
-    if exchange.is_empty() {
+) -> Result<Response, StatusCode> {
+    let format = params.format;
+    let response = load_candles(&state, params)
+        .await
+        .map_err(|(status, _)| status)?;
+
+    Ok(match format {
+        CandlesFormat::Json => Json(response).into_response(),
+        CandlesFormat::Ndjson => ndjson_response(response.candles),
+    })
+}
+
+/// Stream candles as newline-delimited JSON so clients can render before the body ends
+fn ndjson_response(candles: Vec<Candlestick>) -> Response {
+    let lines = stream::iter(candles).map(|candle| {
+        serde_json::to_vec(&candle).map(|mut line| {
+            line.push(b'\n');
+            Bytes::from(line)
+        })
+    });
+
+    (
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(lines),
+    )
+        .into_response()
+}
+
+/// POST /api/candles/batch - Candles for several symbols, fetched concurrently
+pub async fn get_candles_batch(
+    State(state): State<AppState>,
+    Json(request): Json<CandlesBatchRequest>,
+) -> Result<Json<CandlesBatchResponse>, StatusCode> {
+    if request.requests.is_empty() || request.requests.len() > MAX_BATCH_REQUESTS {
         return Err(StatusCode::BAD_REQUEST);
     }
 
-    let market_type = params.market_type.unwrap_or(MarketType::Spot);
+    let results = stream::iter(request.requests)
+        .map(|params| {
+            let state = state.clone();
+            async move {
+                let key = batch_key(&params);
+                let result = match load_candles(&state, params).await {
+                    Ok(response) => CandlesBatchResult {
+                        data: Some(response),
+                        error: None,
+                    },
+                    Err((_, message)) => CandlesBatchResult {
+                        data: None,
+                        error: Some(message),
+                    },
+                };
+                (key, result)
+            }
+        })
+        .buffer_unordered(BATCH_CONCURRENCY)
+        .collect::<BTreeMap<_, _>>()
+        .await;
 
-    let limit = params.limit.unwrap_or(DEFAULT_CANDLE_LIMIT);
-    if limit == 0 || limit > MAX_CANDLE_LIMIT {
-        return Err(StatusCode::BAD_REQUEST);
+    Ok(Json(CandlesBatchResponse { results }))
+}
+
+/// Validate a candle query and serve it from cache or the exchange REST API
+async fn load_candles(
+    state: &AppState,
+    params: CandlesQuery,
+) -> Result<CandlesResponse, (StatusCode, String)> {
+    let exchange = params.exchange.trim().to_lowercase();
+    if exchange.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "Missing exchange".to_string()));
     }
 
+    let market_type = params.market_type.unwrap_or(MarketType::Spot);
+
     let interval = match CandleInterval::parse(params.interval.trim()) {
         Some(value) => value,
-        None => return Err(StatusCode::BAD_REQUEST),
+        None => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Invalid interval: {}", params.interval),
+            ))
+        }
     };
 
+    if let Err(message) = interval.validate_for(&exchange) {
+        return Err((StatusCode::BAD_REQUEST, message));
+    }
+
+    let limit = params.limit.unwrap_or_else(|| interval.default_limit());
+    if limit == 0 || limit > MAX_CANDLE_LIMIT {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Limit must be between 1 and {}", MAX_CANDLE_LIMIT),
+        ));
+    }
+
     let normalized_symbol = normalize_symbol(&params.symbol);
     if normalized_symbol.is_empty() {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err((StatusCode::BAD_REQUEST, "Missing symbol".to_string()));
     }
 
-    let cache_key = format!(
-        "candles:{}:{}:{}:{}:{}",
-        exchange,
-        market_label(market_type),
-        normalized_symbol,
-        interval.cache_key_fragment(),
-        limit
-    );
+    let cache_key = candles_cache_key(&exchange, market_type, &normalized_symbol, &interval, limit);
 
     let cache = state.cache.clone();
     if let Ok(Some(cached)) = cache.get::<CachedCandles>(&cache_key).await {
-        if !is_stale(&cached) {
-            return Ok(Json(CandlesResponse {
+        if !is_stale(&cached, &interval) {
+            return Ok(CandlesResponse {
                 exchange: exchange.clone(),
                 symbol: normalized_symbol,
                 market_type,
@@ -89,13 +221,14 @@ pub async fn get_candles(
                 limit,
                 candles: cached.candles,
                 cached: true,
-            }));
+            });
         }
     }
 
     let client = state.http_client.clone();
     let candles = match fetch_exchange_candles(
         &client,
+        &state.rest_urls,
         &exchange,
         &normalized_symbol,
         &interval,
@@ -112,7 +245,7 @@ pub async fn get_candles(
                 interval = %params.interval,
                 "Failed to fetch candles: {err:?}"
             );
-            return Err(StatusCode::BAD_GATEWAY);
+            return Err((fetch_error_status(&err), err.to_string()));
         }
     };
 
@@ -121,7 +254,9 @@ pub async fn get_candles(
         candles: candles.clone(),
     };
 
-    if let Err(err) = cache.set(&cache_key, &cached_payload).await {
+    // Entries expire with the interval's TTL so old keys do not accumulate in the cache
+    let ttl = interval.cache_ttl().to_std().unwrap_or_default();
+    if let Err(err) = cache.set_with_ttl(&cache_key, &cached_payload, ttl).await {
         warn!(
             exchange = %exchange,
             symbol = %normalized_symbol,
@@ -130,7 +265,7 @@ pub async fn get_candles(
         );
     }
 
-    Ok(Json(CandlesResponse {
+    Ok(CandlesResponse {
         exchange,
         symbol: normalized_symbol,
         market_type,
@@ -138,7 +273,40 @@ pub async fn get_candles(
         limit,
         candles,
         cached: false,
-    }))
+    })
+}
+
+fn candles_cache_key(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    interval: &CandleInterval,
+    limit: usize,
+) -> String {
+    format!(
+        "candles:{}:{}:{}:{}:{}",
+        exchange,
+        market_label(market_type),
+        symbol,
+        interval.cache_key_fragment(),
+        limit
+    )
+}
+
+/// Key identifying a batch item in the response, e.g. `binance:spot:BTCUSDT:1m`, with
+/// `:{limit}` appended when the item sets one so items differing only in limit stay apart
+fn batch_key(params: &CandlesQuery) -> String {
+    let key = format!(
+        "{}:{}:{}:{}",
+        params.exchange.trim().to_lowercase(),
+        market_label(params.market_type.unwrap_or(MarketType::Spot)),
+        normalize_symbol(&params.symbol),
+        params.interval.trim()
+    );
+    match params.limit {
+        Some(limit) => format!("{key}:{limit}"),
+        None => key,
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -184,8 +352,60 @@ impl CandleInterval {
         }
     }
 
+    /// Reject intervals the exchange does not serve; unknown exchanges are left to the fetcher
+    fn validate_for(&self, exchange: &str) -> Result<(), String> {
+        let supported = match exchange {
+            ExchangeId::BINANCE => BINANCE_INTERVALS,
+            ExchangeId::BYBIT => BYBIT_INTERVALS,
+            _ => return Ok(()),
+        };
+
+        let fragment = self.cache_key_fragment();
+        if supported.contains(&fragment.as_str()) {
+            Ok(())
+        } else {
+            Err(format!(
+                "Interval {} is not supported by {}; valid intervals: {}",
+                fragment,
+                exchange,
+                supported.join(", ")
+            ))
+        }
+    }
+
+    /// Length of one bar; months count as 30 days
+    fn bar_seconds(&self) -> i64 {
+        let (magnitude, unit_seconds) = match self {
+            Self::Minutes(v) => (*v, 60),
+            Self::Hours(v) => (*v, 3_600),
+            Self::Days(v) => (*v, 86_400),
+            Self::Weeks(v) => (*v, 7 * 86_400),
+            Self::Months(v) => (*v, 30 * 86_400),
+        };
+        i64::from(magnitude).saturating_mul(unit_seconds)
+    }
+
+    /// How long fetched candles are served from cache: short bars stay fresh, long ones
+    /// change too little to refetch often
+    fn cache_ttl(&self) -> Duration {
+        let seconds = (self.bar_seconds() / CACHE_TTL_BAR_DIVISOR)
+            .clamp(MIN_CACHE_TTL_SECONDS, MAX_CACHE_TTL_SECONDS);
+        Duration::seconds(seconds)
+    }
+
+    fn default_limit(&self) -> usize {
+        let fragment = self.cache_key_fragment();
+        INTERVAL_DEFAULT_LIMITS
+            .iter()
+            .find(|(interval, _)| *interval == fragment)
+            .map(|(_, limit)| *limit)
+            .unwrap_or(DEFAULT_CANDLE_LIMIT)
+            .min(MAX_CANDLE_LIMIT)
+    }
+
     fn to_binance_interval(&self) -> String {
-        self.cache_key_fragment().to_lowercase()
+        // Binance is case-sensitive: `1M` is one month, `1m` one minute
+        self.cache_key_fragment()
     }
 
     fn to_bybit_interval(&self) -> String {
@@ -219,6 +439,7 @@ impl CandleInterval {
 
 async fn fetch_exchange_candles(
     client: &Client,
+    rest_urls: &RestUrls,
     exchange: &str,
     symbol: &str,
     interval: &CandleInterval,
@@ -226,36 +447,53 @@ async fn fetch_exchange_candles(
     market_type: MarketType,
 ) -> Result<Vec<Candlestick>> {
     match exchange {
-        "binance" => fetch_binance_candles(client, symbol, interval, limit, market_type).await,
-        "bybit" => fetch_bybit_candles(client, symbol, interval, limit, market_type).await,
+        ExchangeId::BINANCE => {
+            fetch_binance_candles(client, rest_urls, symbol, interval, limit, market_type).await
+        }
+        ExchangeId::BYBIT => {
+            fetch_bybit_candles(client, rest_urls, symbol, interval, limit, market_type).await
+        }
         _ => Err(anyhow!("Unsupported exchange: {exchange}")),
     }
 }
 
 async fn fetch_binance_candles(
     client: &Client,
+    rest_urls: &RestUrls,
     symbol: &str,
     interval: &CandleInterval,
     limit: usize,
     market_type: MarketType,
 ) -> Result<Vec<Candlestick>> {
-    let base_url = match market_type {
-        MarketType::Spot => "https://api.binance.com/api/v3/klines",
-        MarketType::Perpetual => "https://fapi.binance.com/fapi/v1/klines",
+    let url = match market_type {
+        MarketType::Spot => format!(
+            "{}/api/v3/klines",
+            rest_urls.binance_spot_candles.trim_end_matches('/')
+        ),
+        MarketType::Perpetual => format!(
+            "{}/fapi/v1/klines",
+            rest_urls.binance_perp_candles.trim_end_matches('/')
+        ),
+        MarketType::InversePerpetual | MarketType::Future => {
+            return Err(RejectedCandleRequest(format!(
+                "Binance {} candles are not supported",
+                market_label(market_type)
+            ))
+            .into())
+        }
     };
 
-    let response = client
-        .get(base_url)
-        .query(&[
+    let raw: Vec<Vec<serde_json::Value>> = get_json(
+        client,
+        &url,
+        &[
             ("symbol", symbol),
             ("interval", &interval.to_binance_interval()),
             ("limit", &limit.to_string()),
-        ])
-        .send()
-        .await?
-        .error_for_status()?;
-
-    let raw: Vec<Vec<serde_json::Value>> = response.json().await?;
+        ],
+        &rest_retry_config(),
+    )
+    .await?;
 
     raw.into_iter()
         .map(|entry| parse_binance_entry(&entry))
@@ -294,38 +532,74 @@ fn parse_binance_entry(entry: &[serde_json::Value]) -> Result<Candlestick> {
 
 async fn fetch_bybit_candles(
     client: &Client,
+    rest_urls: &RestUrls,
     symbol: &str,
     interval: &CandleInterval,
     limit: usize,
     market_type: MarketType,
 ) -> Result<Vec<Candlestick>> {
-    let url = "https://api.bybit.com/v5/market/kline";
+    let url = format!(
+        "{}/v5/market/kline",
+        rest_urls.bybit_candles.trim_end_matches('/')
+    );
 
     let category = match market_type {
         MarketType::Spot => "spot",
         MarketType::Perpetual => "linear",
+        MarketType::InversePerpetual => "inverse",
+        // Inverse futures are listed like BTCUSDH25, linear ones like BTC-27JUN25
+        MarketType::Future if !symbol.contains('-') => "inverse",
+        MarketType::Future => "linear",
     };
 
-    let response = client
-        .get(url)
-        .query(&[
+    let payload: BybitKlineResponse = get_json(
+        client,
+        &url,
+        &[
             ("category", category),
             ("symbol", symbol),
             ("interval", &interval.to_bybit_interval()),
             ("limit", &limit.to_string()),
-        ])
-        .send()
-        .await?
-        .error_for_status()?;
-
-    let payload: BybitKlineResponse = response.json().await?;
-
-    if payload.ret_code != 0 {
-        return Err(anyhow!(
-            "Bybit returned error {}: {}",
-            payload.ret_code,
-            payload.ret_msg
-        ));
+        ],
+        &rest_retry_config(),
+    )
+    .await?;
+    bybit_candles_from_response(payload)
+}
+
+/// Bybit reports bad parameters (including unknown symbols) with this retCode
+const BYBIT_PARAMS_ERROR: i32 = 10001;
+
+/// The exchange rejected the request itself, e.g. an unknown symbol
+#[derive(Debug)]
+struct RejectedCandleRequest(String);
+
+impl std::fmt::Display for RejectedCandleRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for RejectedCandleRequest {}
+
+/// An empty list means the symbol is valid but has no candles in range
+fn bybit_candles_from_response(payload: BybitKlineResponse) -> Result<Vec<Candlestick>> {
+    match payload.ret_code {
+        0 => {}
+        BYBIT_PARAMS_ERROR => {
+            return Err(RejectedCandleRequest(format!(
+                "Bybit rejected the request: {}",
+                payload.ret_msg
+            ))
+            .into())
+        }
+        code => {
+            return Err(anyhow!(
+                "Bybit returned error {}: {}",
+                code,
+                payload.ret_msg
+            ))
+        }
     }
 
     let result = payload
@@ -339,6 +613,17 @@ async fn fetch_bybit_candles(
         .collect()
 }
 
+/// Status returned to the client when fetching candles from the exchange fails
+fn fetch_error_status(err: &anyhow::Error) -> StatusCode {
+    if err.is::<RejectedCandleRequest>() {
+        StatusCode::BAD_REQUEST
+    } else if matches!(err.downcast_ref::<HttpError>(), Some(HttpError::Timeout)) {
+        StatusCode::GATEWAY_TIMEOUT
+    } else {
+        StatusCode::BAD_GATEWAY
+    }
+}
+
 fn parse_bybit_entry(entry: &[String]) -> Result<Candlestick> {
     if entry.len() < 6 {
         return Err(anyhow!("Unexpected Bybit kline payload length"));
@@ -378,6 +663,8 @@ struct BybitKlineResponse {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct BybitKlineResult {
+    // Error responses carry an empty `result` object
+    #[serde(default)]
     list: Vec<Vec<String>>,
 }
 
@@ -388,7 +675,7 @@ fn parse_decimal(value: &serde_json::Value) -> Result<Decimal> {
     Decimal::from_str(text).map_err(|err| anyhow!("Failed to parse decimal: {err}"))
 }
 
-fn normalize_symbol(symbol: &str) -> String {
+pub(crate) fn normalize_symbol(symbol: &str) -> String {
     symbol
         .chars()
         .filter(|c| !c.is_ascii_whitespace() && *c != '-')
@@ -400,22 +687,396 @@ fn market_label(market_type: MarketType) -> &'static str {
     match market_type {
         MarketType::Spot => "spot",
         MarketType::Perpetual => "perpetual",
+        MarketType::InversePerpetual => "inverse_perpetual",
+        MarketType::Future => "future",
     }
 }
 
-fn is_stale(cached: &CachedCandles) -> bool {
-    Utc::now().signed_duration_since(cached.fetched_at) > Duration::seconds(CACHE_TTL_SECONDS)
+fn is_stale(cached: &CachedCandles, interval: &CandleInterval) -> bool {
+    Utc::now().signed_duration_since(cached.fetched_at) > interval.cache_ttl()
 }
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn market_type_query_param_accepts_aliases() {
+        let market_type = |query: &str| {
+            let uri: axum::http::Uri = format!(
+                "/api/candles?exchange=binance&symbol=BTCUSDT&interval=1m{}",
+                query
+            )
+            .parse()
+            .unwrap();
+            Query::<CandlesQuery>::try_from_uri(&uri).map(|Query(params)| params.market_type)
+        };
+
+        assert_eq!(
+            market_type("&market_type=perp").unwrap(),
+            Some(MarketType::Perpetual)
+        );
+        assert_eq!(
+            market_type("&market_type=perpetual").unwrap(),
+            Some(MarketType::Perpetual)
+        );
+        assert_eq!(
+            market_type("&market_type=spot").unwrap(),
+            Some(MarketType::Spot)
+        );
+        assert_eq!(market_type("").unwrap(), None);
+
+        let err = market_type("&market_type=margin").unwrap_err();
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+        assert!(err.body_text().contains("unknown market type 'margin'"));
+    }
+
+    #[test]
+    fn default_limit_depends_on_interval() {
+        let minute = CandleInterval::parse("1m").unwrap();
+        let day = CandleInterval::parse("1d").unwrap();
+        let hour = CandleInterval::parse("1h").unwrap();
+
+        assert_ne!(minute.default_limit(), day.default_limit());
+        assert_eq!(hour.default_limit(), DEFAULT_CANDLE_LIMIT);
+        assert!(minute.default_limit() <= MAX_CANDLE_LIMIT);
+    }
+
+    #[test]
+    fn cache_ttl_grows_with_interval_within_bounds() {
+        let ttls: Vec<Duration> = ["1m", "5m", "15m", "1h", "4h", "1d", "1w", "1M"]
+            .iter()
+            .map(|interval| CandleInterval::parse(interval).unwrap().cache_ttl())
+            .collect();
+
+        assert!(ttls.windows(2).all(|pair| pair[0] <= pair[1]), "{:?}", ttls);
+        assert_eq!(ttls[0], Duration::seconds(MIN_CACHE_TTL_SECONDS));
+        assert_eq!(ttls[7], Duration::seconds(MAX_CACHE_TTL_SECONDS));
+
+        let ttl = |interval: &str| CandleInterval::parse(interval).unwrap().cache_ttl();
+        assert!(ttl("1m") < ttl("1h"));
+        assert!(ttl("1h") < ttl("1d"));
+        assert!(ttl("1d") <= ttl("1w"));
+        assert!(ttl("1w") <= ttl("1M"));
+    }
+
+    #[test]
+    fn staleness_follows_interval_ttl() {
+        let cached = CachedCandles {
+            fetched_at: Utc::now() - Duration::seconds(60),
+            candles: Vec::new(),
+        };
+
+        assert!(is_stale(&cached, &CandleInterval::Minutes(1)));
+        assert!(!is_stale(&cached, &CandleInterval::Days(1)));
+    }
+
+    #[tokio::test]
+    async fn batch_reports_failures_per_item() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        let state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle());
+
+        // Prime the cache so the first item is served without a network call
+        let interval = CandleInterval::Minutes(1);
+        let key = candles_cache_key("binance", MarketType::Spot, "BTCUSDT", &interval, 5);
+        let cached = CachedCandles {
+            fetched_at: Utc::now(),
+            candles: vec![Candlestick {
+                timestamp: Utc::now(),
+                open: Decimal::ONE,
+                high: Decimal::ONE,
+                low: Decimal::ONE,
+                close: Decimal::ONE,
+                volume: Decimal::ONE,
+            }],
+        };
+        state.cache.set(&key, &cached).await.unwrap();
+
+        let query = |exchange: &str| CandlesQuery {
+            exchange: exchange.to_string(),
+            symbol: "BTCUSDT".to_string(),
+            interval: "1m".to_string(),
+            limit: Some(5),
+            market_type: None,
+            format: CandlesFormat::Json,
+        };
+        let request = CandlesBatchRequest {
+            requests: vec![query("binance"), query("kraken")],
+        };
+
+        let Json(response) = get_candles_batch(State(state), Json(request))
+            .await
+            .unwrap();
+
+        let binance = &response.results["binance:spot:BTCUSDT:1m:5"];
+        assert!(binance.error.is_none());
+        assert!(binance.data.as_ref().unwrap().cached);
+
+        let kraken = &response.results["kraken:spot:BTCUSDT:1m:5"];
+        assert!(kraken.data.is_none());
+        assert!(kraken
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("Unsupported exchange"));
+    }
+
+    #[tokio::test]
+    async fn batch_items_differing_only_in_limit_are_kept_apart() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        let state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle());
+        let interval = CandleInterval::Minutes(1);
+        let candle = Candlestick {
+            timestamp: Utc::now(),
+            open: Decimal::ONE,
+            high: Decimal::ONE,
+            low: Decimal::ONE,
+            close: Decimal::ONE,
+            volume: Decimal::ONE,
+        };
+        for limit in [2, 3] {
+            let key = candles_cache_key("binance", MarketType::Spot, "BTCUSDT", &interval, limit);
+            let cached = CachedCandles {
+                fetched_at: Utc::now(),
+                candles: vec![candle.clone(); limit],
+            };
+            state.cache.set(&key, &cached).await.unwrap();
+        }
+
+        let query = |limit: usize| CandlesQuery {
+            exchange: "binance".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            interval: "1m".to_string(),
+            limit: Some(limit),
+            market_type: None,
+            format: CandlesFormat::Json,
+        };
+        let request = CandlesBatchRequest {
+            requests: vec![query(2), query(3)],
+        };
+
+        let Json(response) = get_candles_batch(State(state), Json(request))
+            .await
+            .unwrap();
+
+        assert_eq!(response.results.len(), 2);
+        for limit in [2, 3] {
+            let result = &response.results[&format!("binance:spot:BTCUSDT:1m:{limit}")];
+            assert_eq!(result.data.as_ref().unwrap().candles.len(), limit);
+        }
+    }
+
+    #[tokio::test]
+    async fn ndjson_format_streams_one_candle_per_line() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        let state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle());
+        let interval = CandleInterval::Hours(1);
+        let key = candles_cache_key("bybit", MarketType::Spot, "ETHUSDT", &interval, 3);
+        let candle = |close: i64| Candlestick {
+            timestamp: Utc::now(),
+            open: Decimal::ONE,
+            high: Decimal::new(close, 0),
+            low: Decimal::ONE,
+            close: Decimal::new(close, 0),
+            volume: Decimal::ONE,
+        };
+        let cached = CachedCandles {
+            fetched_at: Utc::now(),
+            candles: vec![candle(2), candle(3), candle(4)],
+        };
+        state.cache.set(&key, &cached).await.unwrap();
+
+        let query = CandlesQuery {
+            exchange: "bybit".to_string(),
+            symbol: "ETH-USDT".to_string(),
+            interval: "1h".to_string(),
+            limit: Some(3),
+            market_type: None,
+            format: CandlesFormat::Ndjson,
+        };
+        let response = get_candles(State(state), Query(query)).await.unwrap();
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/x-ndjson"
+        );
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let candles: Vec<Candlestick> = std::str::from_utf8(&body)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(candles.len(), 3);
+        assert_eq!(candles[2].close, Decimal::new(4, 0));
+        assert!(body.ends_with(b"\n"));
+    }
+
+    #[tokio::test]
+    async fn candles_use_injected_client_and_endpoints() {
+        use axum::{extract::Query, routing::get};
+        use std::collections::HashMap;
+
+        let router = axum::Router::new()
+            .route(
+                "/fapi/v1/klines",
+                get(|Query(query): Query<HashMap<String, String>>| async move {
+                    assert_eq!(query["symbol"], "BTCUSDT");
+                    Json(serde_json::json!([[
+                        1700000000000i64,
+                        "100.0",
+                        "110.0",
+                        "90.0",
+                        "105.0",
+                        "12.5",
+                        1700000059999i64
+                    ]]))
+                }),
+            )
+            .route(
+                "/v5/market/kline",
+                get(|| async {
+                    Json(serde_json::json!({
+                        "retCode": 0,
+                        "retMsg": "OK",
+                        "result": {"list": [["1700000000000", "1", "2", "0.5", "1.5", "7"]]}
+                    }))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+
+        let client = Client::builder()
+            .timeout(std::time::Duration::from_secs(5))
+            .build()
+            .unwrap();
+        let rest_urls = RestUrls {
+            binance_spot_candles: base_url.clone(),
+            binance_perp_candles: base_url.clone(),
+            bybit_candles: base_url,
+            ..RestUrls::default()
+        };
+        let interval = CandleInterval::Minutes(1);
+
+        let binance = fetch_exchange_candles(
+            &client,
+            &rest_urls,
+            "binance",
+            "BTCUSDT",
+            &interval,
+            1,
+            MarketType::Perpetual,
+        )
+        .await
+        .unwrap();
+        assert_eq!(binance.len(), 1);
+        assert_eq!(binance[0].close, Decimal::from(105));
+
+        let bybit = fetch_exchange_candles(
+            &client,
+            &rest_urls,
+            "bybit",
+            "BTCUSDT",
+            &interval,
+            1,
+            MarketType::Spot,
+        )
+        .await
+        .unwrap();
+        assert_eq!(bybit[0].volume, Decimal::from(7));
+    }
+
+    #[test]
+    fn intervals_are_validated_per_exchange() {
+        let seven_minutes = CandleInterval::parse("7m").unwrap();
+        for exchange in [ExchangeId::BINANCE, ExchangeId::BYBIT] {
+            let err = seven_minutes.validate_for(exchange).unwrap_err();
+            assert!(err.contains("valid intervals"), "{}", err);
+            assert!(err.contains("15m"), "{}", err);
+
+            for valid in ["5m", "15m"] {
+                let interval = CandleInterval::parse(valid).unwrap();
+                assert!(interval.validate_for(exchange).is_ok());
+            }
+        }
+
+        // Exchange-specific values
+        let eight_hours = CandleInterval::parse("8h").unwrap();
+        assert!(eight_hours.validate_for(ExchangeId::BINANCE).is_ok());
+        assert!(eight_hours.validate_for(ExchangeId::BYBIT).is_err());
+        assert_eq!(
+            CandleInterval::parse("1M").unwrap().to_binance_interval(),
+            "1M"
+        );
+    }
+
+    #[tokio::test]
+    async fn unsupported_interval_is_rejected_before_fetching() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        // Unroutable REST roots: reaching the network would fail with 502, not 400
+        let rest_urls = RestUrls {
+            binance_spot_candles: "http://127.0.0.1:1".to_string(),
+            ..RestUrls::default()
+        };
+        let state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle())
+            .with_rest_urls(rest_urls);
+        let query = CandlesQuery {
+            exchange: "binance".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            interval: "7m".to_string(),
+            limit: None,
+            market_type: None,
+            format: CandlesFormat::Json,
+        };
+
+        let (status, message) = load_candles(&state, query).await.unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(message.contains("valid intervals"));
+    }
+
+    #[test]
+    fn bybit_empty_kline_list_is_not_an_error() {
+        let payload: BybitKlineResponse = serde_json::from_str(
+            r#"{"retCode":0,"retMsg":"OK","result":{"symbol":"BTCUSDT","category":"spot","list":[]}}"#,
+        )
+        .unwrap();
+
+        assert!(bybit_candles_from_response(payload).unwrap().is_empty());
+    }
+
+    #[test]
+    fn bybit_invalid_symbol_is_a_client_error() {
+        let payload: BybitKlineResponse = serde_json::from_str(
+            r#"{"retCode":10001,"retMsg":"Not supported symbols","result":{},"time":1700000000000}"#,
+        )
+        .unwrap();
+
+        let err = bybit_candles_from_response(payload).unwrap_err();
+        assert_eq!(fetch_error_status(&err), StatusCode::BAD_REQUEST);
+        assert!(err.to_string().contains("Not supported symbols"));
+
+        let upstream = anyhow!("Bybit returned error 10006: Too many visits");
+        assert_eq!(fetch_error_status(&upstream), StatusCode::BAD_GATEWAY);
+    }
+
     #[tokio::test]
     async fn fetch_binance_candles_returns_data() {
         let client = Client::new();
         let interval = CandleInterval::Minutes(1);
         let result = fetch_exchange_candles(
             &client,
+            &RestUrls::default(),
             "binance",
             "BTCUSDT",
             &interval,
@@ -432,10 +1093,17 @@ mod tests {
     async fn fetch_bybit_candles_returns_data() {
         let client = Client::new();
         let interval = CandleInterval::Minutes(1);
-        let result =
-            fetch_exchange_candles(&client, "bybit", "BTCUSDT", &interval, 5, MarketType::Spot)
-                .await
-                .expect("failed to fetch bybit candles");
+        let result = fetch_exchange_candles(
+            &client,
+            &RestUrls::default(),
+            "bybit",
+            "BTCUSDT",
+            &interval,
+            5,
+            MarketType::Spot,
+        )
+        .await
+        .expect("failed to fetch bybit candles");
 
         assert!(!result.is_empty());
     }
@@ -0,0 +1,112 @@
+use crate::routes::candles::normalize_symbol;
+use crate::routes::query::optional_market_type;
+use crate::routes::ticker::split_symbol;
+use crate::state::AppState;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use crypto_dash_core::composite::CompositeTicker;
+use crypto_dash_core::model::{MarketType, Ticker};
+use crypto_dash_core::time::now;
+
+/// Tickers older than this only count when no exchange has a fresher one
+const COMPOSITE_STALE_AFTER_SECS: i64 = 30;
+
+#[derive(Debug, serde::Deserialize)]
+pub struct CompositeQuery {
+    pub symbol: String,
+    #[serde(default, deserialize_with = "optional_market_type")]
+    pub market_type: Option<MarketType>,
+}
+
+/// GET /api/composite - Best bid and ask for one instrument across the cached exchanges
+pub async fn get_composite(
+    State(state): State<AppState>,
+    Query(params): Query<CompositeQuery>,
+) -> Result<Json<CompositeTicker>, StatusCode> {
+    let market_type = params.market_type.unwrap_or_default();
+    let symbol = split_symbol(&normalize_symbol(&params.symbol), market_type)
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
+    let tickers: Vec<Ticker> = state
+        .cache
+        .get_all_tickers()
+        .await
+        .into_iter()
+        .filter(|ticker| ticker.market_type == market_type && ticker.symbol == symbol)
+        .collect();
+
+    CompositeTicker::build(
+        &tickers,
+        &state.exchange_priority,
+        chrono::Duration::seconds(COMPOSITE_STALE_AFTER_SECS),
+        now(),
+    )
+    .map(Json)
+    .ok_or(StatusCode::NOT_FOUND)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_dash_cache::MemoryCache;
+    use crypto_dash_core::composite::ExchangePriority;
+    use crypto_dash_core::model::{ExchangeId, Symbol};
+    use crypto_dash_stream_hub::StreamHub;
+    use rust_decimal::Decimal;
+
+    fn ticker(exchange: &str, bid: i64, ask: i64) -> Ticker {
+        Ticker {
+            timestamp: now(),
+            exchange: ExchangeId::from(exchange),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            bid: Decimal::from(bid),
+            ask: Decimal::from(ask),
+            last: Decimal::from(bid),
+            bid_size: Decimal::ONE,
+            ask_size: Decimal::ONE,
+            quote_volume_24h: None,
+            change_pct_24h: None,
+            tick_direction: None,
+            high_24h: None,
+            low_24h: None,
+            volume_24h: None,
+        }
+    }
+
+    fn query(symbol: &str) -> Query<CompositeQuery> {
+        Query(CompositeQuery {
+            symbol: symbol.to_string(),
+            market_type: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn composite_uses_configured_exchange_priority() {
+        let cache = MemoryCache::new().handle();
+        cache.set_ticker(ticker("binance", 100, 103)).await;
+        cache.set_ticker(ticker("bybit", 100, 102)).await;
+        let state = AppState::new(StreamHub::new().handle(), cache).with_exchange_priority(
+            ExchangePriority::new(["bybit", "binance"].map(ExchangeId::from)),
+        );
+
+        let Json(composite) = get_composite(State(state.clone()), query("btc-usdt"))
+            .await
+            .unwrap();
+        assert_eq!(composite.bid_exchange.as_str(), "bybit");
+        assert_eq!(composite.ask_exchange.as_str(), "bybit");
+        assert_eq!(composite.ask, Decimal::from(102));
+
+        let err = get_composite(State(state.clone()), query("ETHUSDT"))
+            .await
+            .unwrap_err();
+        assert_eq!(err, StatusCode::NOT_FOUND);
+        let err = get_composite(State(state), query("USDT"))
+            .await
+            .unwrap_err();
+        assert_eq!(err, StatusCode::BAD_REQUEST);
+    }
+}
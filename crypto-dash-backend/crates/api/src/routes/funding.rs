@@ -0,0 +1,97 @@
+use crate::routes::candles::normalize_symbol;
+use crate::routes::query::optional_market_type;
+use crate::routes::ticker::split_symbol;
+use crate::state::AppState;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use crypto_dash_core::model::{ExchangeId, FundingRate, MarketType};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct FundingQuery {
+    pub exchange: String,
+    pub symbol: String,
+    #[serde(default, deserialize_with = "optional_market_type")]
+    pub market_type: Option<MarketType>,
+}
+
+/// GET /api/funding - Latest cached funding rate for one perpetual, defaulting to linear
+pub async fn get_funding(
+    State(state): State<AppState>,
+    Query(params): Query<FundingQuery>,
+) -> Result<Json<FundingRate>, StatusCode> {
+    let market_type = params.market_type.unwrap_or(MarketType::Perpetual);
+    // Only perpetuals pay funding
+    if !matches!(
+        market_type,
+        MarketType::Perpetual | MarketType::InversePerpetual
+    ) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let exchange = ExchangeId::from(params.exchange.trim().to_lowercase().as_str());
+    let symbol = split_symbol(&normalize_symbol(&params.symbol), market_type)
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
+    state
+        .cache
+        .get_funding_rate(&exchange, market_type, &symbol)
+        .await
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_dash_cache::MemoryCache;
+    use crypto_dash_core::model::Symbol;
+    use crypto_dash_core::time::now;
+    use crypto_dash_stream_hub::StreamHub;
+    use rust_decimal::Decimal;
+
+    fn query(symbol: &str, market_type: Option<MarketType>) -> Query<FundingQuery> {
+        Query(FundingQuery {
+            exchange: "Bybit".to_string(),
+            symbol: symbol.to_string(),
+            market_type,
+        })
+    }
+
+    #[tokio::test]
+    async fn returns_cached_funding_for_perpetuals_only() {
+        let cache = MemoryCache::new().handle();
+        let state = AppState::new(StreamHub::new().handle(), cache.clone());
+
+        cache
+            .set_funding_rate(FundingRate {
+                timestamp: now(),
+                exchange: ExchangeId::from("bybit"),
+                market_type: MarketType::Perpetual,
+                symbol: Symbol::new("BTC", "USDT"),
+                rate: Decimal::new(1, 4),
+                next_funding_time: now(),
+                mark_price: None,
+                index_price: None,
+            })
+            .await;
+
+        let Json(funding) = get_funding(State(state.clone()), query("btc-usdt", None))
+            .await
+            .unwrap();
+        assert_eq!(funding.symbol, Symbol::new("BTC", "USDT"));
+        assert_eq!(funding.rate, Decimal::new(1, 4));
+
+        let err = get_funding(State(state.clone()), query("ETHUSDT", None))
+            .await
+            .unwrap_err();
+        assert_eq!(err, StatusCode::NOT_FOUND);
+
+        let err = get_funding(State(state), query("BTCUSDT", Some(MarketType::Spot)))
+            .await
+            .unwrap_err();
+        assert_eq!(err, StatusCode::BAD_REQUEST);
+    }
+}
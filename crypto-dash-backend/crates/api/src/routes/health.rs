@@ -1,4 +1,10 @@
-use axum::{http::StatusCode, response::Json};
+use crate::state::AppState;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde::Deserialize;
 use serde_json::{json, Value};
 
 /// GET /api/health - Health check endpoint
@@ -10,10 +16,25 @@ pub async fn health() -> Result<Json<Value>, StatusCode> {
     })))
 }
 
+#[derive(Debug, Default, Deserialize)]
+pub struct ReadyQuery {
+    /// Also ping each exchange's REST API
+    #[serde(default)]
+    pub upstream: bool,
+}
+
 /// GET /api/ready - Readiness check endpoint
-pub async fn ready() -> Result<Json<Value>, StatusCode> {
+pub async fn ready(
+    State(state): State<AppState>,
+    Query(params): Query<ReadyQuery>,
+) -> Result<Json<Value>, StatusCode> {
+    // Load balancers stop routing here while clients move to other instances
+    if state.maintenance.is_enabled() {
+        return Err(StatusCode::SERVICE_UNAVAILABLE);
+    }
+
     // In a real implementation, check if services are ready
-    Ok(Json(json!({
+    let mut body = json!({
         "status": "ready",
         "service": "crypto-dash-api",
         "timestamp": chrono::Utc::now().to_rfc3339(),
@@ -22,5 +43,15 @@ pub async fn ready() -> Result<Json<Value>, StatusCode> {
             "cache": "ok",
             "exchanges": "ok"
         }
-    })))
+    });
+
+    if params.upstream {
+        let upstream = state.upstream_probe.check(&state).await;
+        if upstream.values().any(|exchange| !exchange.reachable) {
+            body["status"] = json!("degraded");
+        }
+        body["upstream"] = json!(upstream);
+    }
+
+    Ok(Json(body))
 }
@@ -0,0 +1,131 @@
+use crate::state::AppState;
+use axum::{extract::State, http::StatusCode, response::Json};
+use crypto_dash_core::model::{MarketType, Ticker};
+use serde::Serialize;
+
+const MAX_TICKERS_PER_MARKET: usize = 5;
+
+#[derive(Debug, Serialize)]
+pub struct MarketsOverviewResponse {
+    pub markets: Vec<MarketOverviewDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MarketOverviewDto {
+    pub exchange: String,
+    pub market_type: MarketType,
+    pub connected: bool,
+    pub active_topics: usize,
+    pub tickers: Vec<Ticker>,
+}
+
+/// GET /api/markets - Connection status, topic counts and recent tickers per exchange market
+pub async fn markets_overview(
+    State(state): State<AppState>,
+) -> Result<Json<MarketsOverviewResponse>, StatusCode> {
+    let all_tickers = state.cache.get_all_tickers().await;
+
+    let mut exchange_ids: Vec<&String> = state.exchanges.keys().collect();
+    exchange_ids.sort();
+
+    let mut markets = Vec::new();
+    for exchange_id in exchange_ids {
+        let adapter = &state.exchanges[exchange_id];
+        let exchange = adapter.id();
+
+        for market_type in adapter.supported_markets() {
+            let mut tickers: Vec<Ticker> = all_tickers
+                .iter()
+                .filter(|ticker| ticker.exchange == exchange && ticker.market_type == market_type)
+                .cloned()
+                .collect();
+            // Most recently updated first
+            tickers.sort_by_key(|ticker| std::cmp::Reverse(ticker.timestamp));
+            tickers.truncate(MAX_TICKERS_PER_MARKET);
+
+            markets.push(MarketOverviewDto {
+                exchange: exchange_id.clone(),
+                market_type,
+                connected: adapter.is_market_connected(market_type).await,
+                active_topics: state.hub.topic_count_for(&exchange, market_type),
+                tickers,
+            });
+        }
+    }
+
+    Ok(Json(MarketsOverviewResponse { markets }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_dash_binance::BinanceAdapter;
+    use crypto_dash_bybit::BybitAdapter;
+    use crypto_dash_cache::MemoryCache;
+    use crypto_dash_core::model::{ExchangeId, Symbol};
+    use crypto_dash_core::time::now;
+    use crypto_dash_stream_hub::{StreamHub, Topic};
+    use rust_decimal::Decimal;
+    use std::sync::Arc;
+
+    fn ticker(exchange: &str, base: &str) -> Ticker {
+        Ticker {
+            timestamp: now(),
+            exchange: ExchangeId::from(exchange),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new(base, "USDT"),
+            bid: Decimal::new(100, 0),
+            ask: Decimal::new(101, 0),
+            last: Decimal::new(100, 0),
+            bid_size: Decimal::ONE,
+            ask_size: Decimal::ONE,
+            quote_volume_24h: None,
+            change_pct_24h: None,
+            tick_direction: None,
+            high_24h: None,
+            low_24h: None,
+            volume_24h: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn overview_includes_all_exchanges() {
+        let hub = StreamHub::new().handle();
+        let cache = MemoryCache::new().handle();
+        let mut state = AppState::new(hub.clone(), cache.clone());
+        state.add_exchange(Arc::new(BinanceAdapter::new()));
+        state.add_exchange(Arc::new(BybitAdapter::new()));
+
+        for base in ["BTC", "ETH", "SOL", "ADA", "XRP", "DOT", "LINK"] {
+            cache.set_ticker(ticker("binance", base)).await;
+        }
+        let _subscriber = hub
+            .subscribe(&Topic::ticker(
+                ExchangeId::from("binance"),
+                MarketType::Spot,
+                Symbol::new("BTC", "USDT"),
+            ))
+            .await
+            .unwrap();
+
+        let Json(overview) = markets_overview(State(state)).await.unwrap();
+
+        let binance_spot = overview
+            .markets
+            .iter()
+            .find(|m| m.exchange == "binance" && m.market_type == MarketType::Spot)
+            .expect("binance spot market missing");
+        assert!(!binance_spot.connected);
+        assert_eq!(binance_spot.active_topics, 1);
+        assert_eq!(binance_spot.tickers.len(), MAX_TICKERS_PER_MARKET);
+
+        let bybit_markets: Vec<_> = overview
+            .markets
+            .iter()
+            .filter(|m| m.exchange == "bybit")
+            .collect();
+        assert_eq!(bybit_markets.len(), 3);
+        assert!(bybit_markets.iter().all(|m| !m.connected));
+        assert!(bybit_markets.iter().all(|m| m.tickers.is_empty()));
+    }
+}
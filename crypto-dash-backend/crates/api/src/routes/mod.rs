@@ -1,9 +1,26 @@
+pub mod admin;
 pub mod candles;
+pub mod composite;
 pub mod exchanges;
+pub mod funding;
 pub mod health;
+pub mod markets;
+pub mod orderbook;
+pub mod query;
+pub mod stats;
 pub mod symbols;
+pub mod ticker;
+pub mod tickers;
 
+pub use admin::*;
 pub use candles::*;
+pub use composite::*;
 pub use exchanges::*;
+pub use funding::*;
 pub use health::*;
+pub use markets::*;
+pub use orderbook::*;
+pub use stats::*;
 pub use symbols::*;
+pub use ticker::*;
+pub use tickers::*;
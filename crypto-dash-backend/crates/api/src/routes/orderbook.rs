@@ -0,0 +1,124 @@
+use crate::routes::candles::normalize_symbol;
+use crate::routes::query::optional_market_type;
+use crate::routes::ticker::split_symbol;
+use crate::state::AppState;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use crypto_dash_core::model::{ExchangeId, MarketType, OrderBookSnapshot};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct OrderBookQuery {
+    pub exchange: String,
+    pub symbol: String,
+    #[serde(default, deserialize_with = "optional_market_type")]
+    pub market_type: Option<MarketType>,
+    /// Levels per side to return; all cached levels when omitted
+    pub depth: Option<usize>,
+}
+
+/// GET /api/orderbook - Latest cached order book for one instrument, defaulting to spot
+pub async fn get_orderbook(
+    State(state): State<AppState>,
+    Query(params): Query<OrderBookQuery>,
+) -> Result<Json<OrderBookSnapshot>, StatusCode> {
+    let market_type = params.market_type.unwrap_or_default();
+    let exchange = ExchangeId::from(params.exchange.trim().to_lowercase().as_str());
+    let symbol = split_symbol(&normalize_symbol(&params.symbol), market_type)
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    if params.depth == Some(0) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let mut book = state
+        .cache
+        .get_orderbook(&exchange, market_type, &symbol)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+    if let Some(depth) = params.depth {
+        if book.bids.len() > depth || book.asks.len() > depth {
+            book.bids.truncate(depth);
+            book.asks.truncate(depth);
+            // The exchange checksum covers the full book, not the trimmed copy
+            book.checksum = None;
+        }
+    }
+
+    Ok(Json(book))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_dash_cache::MemoryCache;
+    use crypto_dash_core::model::{PriceLevel, Symbol};
+    use crypto_dash_core::time::now;
+    use crypto_dash_stream_hub::StreamHub;
+    use rust_decimal::Decimal;
+
+    fn query(symbol: &str, depth: Option<usize>) -> Query<OrderBookQuery> {
+        Query(OrderBookQuery {
+            exchange: "Bybit".to_string(),
+            symbol: symbol.to_string(),
+            market_type: Some(MarketType::Perpetual),
+            depth,
+        })
+    }
+
+    #[tokio::test]
+    async fn returns_cached_book_trimmed_to_depth() {
+        let cache = MemoryCache::new().handle();
+        let state = AppState::new(StreamHub::new().handle(), cache.clone());
+
+        let level = |price: i64| PriceLevel {
+            price: Decimal::new(price, 0),
+            quantity: Decimal::ONE,
+        };
+        cache
+            .set_orderbook(OrderBookSnapshot {
+                timestamp: now(),
+                exchange: ExchangeId::from("bybit"),
+                market_type: MarketType::Perpetual,
+                symbol: Symbol::new("BTC", "USDT"),
+                bids: vec![level(99), level(98), level(97)],
+                asks: vec![level(101), level(102), level(103)],
+                checksum: Some("42".to_string()),
+            })
+            .await;
+
+        let Json(full) = get_orderbook(State(state.clone()), query("btc-usdt", None))
+            .await
+            .unwrap();
+        assert_eq!(full.bids.len(), 3);
+        assert_eq!(full.checksum.as_deref(), Some("42"));
+
+        let Json(top) = get_orderbook(State(state.clone()), query("BTCUSDT", Some(2)))
+            .await
+            .unwrap();
+        let prices = |levels: &[PriceLevel]| -> Vec<Decimal> {
+            levels.iter().map(|level| level.price).collect()
+        };
+        assert_eq!(
+            prices(&top.bids),
+            [Decimal::new(99, 0), Decimal::new(98, 0)]
+        );
+        assert_eq!(
+            prices(&top.asks),
+            [Decimal::new(101, 0), Decimal::new(102, 0)]
+        );
+        assert_eq!(top.checksum, None);
+
+        let err = get_orderbook(State(state.clone()), query("ETHUSDT", None))
+            .await
+            .unwrap_err();
+        assert_eq!(err, StatusCode::NOT_FOUND);
+
+        let err = get_orderbook(State(state), query("BTCUSDT", Some(0)))
+            .await
+            .unwrap_err();
+        assert_eq!(err, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,13 @@
+use crypto_dash_core::model::MarketType;
+use serde::Deserialize;
+
+/// Query param deserializer for `market_type` that accepts `MarketType::from_str`
+/// aliases such as `perp`; use with `#[serde(default, deserialize_with = ...)]`
+pub(crate) fn optional_market_type<'de, D>(deserializer: D) -> Result<Option<MarketType>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|value| value.parse().map_err(serde::de::Error::custom))
+        .transpose()
+}
@@ -0,0 +1,358 @@
+use crate::state::AppState;
+use crate::stats::LifecycleStats;
+use axum::{extract::State, http::StatusCode, response::Json};
+use crypto_dash_core::model::{AdapterMetrics, ExchangeId, MarketType};
+use crypto_dash_core::time::now;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct TopicStats {
+    pub topic: String,
+    pub subscribers: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExchangeClockSkew {
+    pub exchange: ExchangeId,
+    /// Rolling mean of server time minus exchange event time, latency included
+    pub skew_ms: f64,
+    pub samples: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExchangeMessageStats {
+    pub exchange: ExchangeId,
+    #[serde(flatten)]
+    pub metrics: AdapterMetrics,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TickerAge {
+    pub exchange: ExchangeId,
+    pub market_type: MarketType,
+    pub symbol: String,
+    /// Milliseconds since the exchange timestamp of the newest cached ticker
+    pub last_update_age_ms: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HubStatsResponse {
+    pub total_topics: usize,
+    pub global_subscribers: usize,
+    pub cached_tickers: usize,
+    pub cached_orderbooks: usize,
+    /// Per-topic receiver counts, most watched first
+    pub topics: Vec<TopicStats>,
+    /// Per-exchange clock skew, for exchanges that have sent timestamped data
+    pub clock_skew: Vec<ExchangeClockSkew>,
+    /// Per-exchange upstream message counters; parse errors rising alongside received
+    /// messages usually means the exchange changed its message format
+    pub exchange_messages: Vec<ExchangeMessageStats>,
+    /// Age of every cached ticker, oldest first; one that keeps growing has gone quiet
+    pub ticker_ages: Vec<TickerAge>,
+    /// Client session, subscription and upstream connection events since startup
+    pub lifecycle: LifecycleStats,
+}
+
+/// GET /api/stats - Hub topics and subscribers plus cache sizes, for monitoring
+pub async fn get_stats(
+    State(state): State<AppState>,
+) -> Result<Json<HubStatsResponse>, StatusCode> {
+    let cache = state.cache.stats().await;
+    let topics: Vec<TopicStats> = state
+        .hub
+        .active_topics()
+        .into_iter()
+        .map(|(topic, subscribers)| TopicStats {
+            topic: topic.to_string(),
+            subscribers,
+        })
+        .collect();
+    let clock_skew = state
+        .hub
+        .clock_skew()
+        .into_iter()
+        .map(|(exchange, skew)| ExchangeClockSkew {
+            exchange,
+            skew_ms: skew.skew_ms,
+            samples: skew.samples,
+        })
+        .collect();
+
+    let mut exchange_messages = Vec::new();
+    for adapter in state.exchanges.values() {
+        exchange_messages.push(ExchangeMessageStats {
+            exchange: adapter.id(),
+            metrics: adapter.metrics().await,
+        });
+    }
+    exchange_messages.sort_by(|a, b| a.exchange.as_str().cmp(b.exchange.as_str()));
+
+    let checked_at = now();
+    let mut ticker_ages: Vec<TickerAge> = state
+        .cache
+        .get_all_tickers()
+        .await
+        .into_iter()
+        .map(|ticker| TickerAge {
+            last_update_age_ms: (checked_at - ticker.timestamp).num_milliseconds(),
+            exchange: ticker.exchange,
+            market_type: ticker.market_type,
+            symbol: ticker.symbol.canonical(),
+        })
+        .collect();
+    ticker_ages.sort_by_key(|age| std::cmp::Reverse(age.last_update_age_ms));
+
+    Ok(Json(HubStatsResponse {
+        total_topics: topics.len(),
+        global_subscribers: state.hub.global_subscriber_count(),
+        cached_tickers: cache.ticker_count,
+        cached_orderbooks: cache.orderbook_count,
+        topics,
+        clock_skew,
+        exchange_messages,
+        ticker_ages,
+        lifecycle: state.lifecycle.snapshot(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_dash_cache::MemoryCache;
+    use crypto_dash_core::model::{MarketType, Symbol};
+    use crypto_dash_stream_hub::{StreamHub, Topic};
+
+    #[tokio::test]
+    async fn stats_list_watched_topics() {
+        let state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle());
+        let topic = Topic::ticker(
+            ExchangeId::from("bybit"),
+            MarketType::Perpetual,
+            Symbol::new("SOL", "USDT"),
+        );
+        let _subscriber = state.hub.subscribe(&topic).await.unwrap();
+        let _session = state.hub.subscribe_all().await;
+
+        let Json(stats) = get_stats(State(state)).await.unwrap();
+
+        assert_eq!(stats.total_topics, 1);
+        assert_eq!(stats.global_subscribers, 1);
+        assert_eq!(stats.cached_tickers, 0);
+        assert_eq!(stats.topics[0].topic, topic.to_string());
+        assert_eq!(stats.topics[0].subscribers, 1);
+        assert!(stats.clock_skew.is_empty());
+        assert!(stats.ticker_ages.is_empty());
+    }
+
+    #[tokio::test]
+    async fn stats_count_lifecycle_events() {
+        use crate::stats::spawn_lifecycle_counter;
+        use crypto_dash_core::model::{Channel, ChannelType, LifecycleEvent};
+
+        let state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle());
+        let counter = spawn_lifecycle_counter(&state);
+        let channel = Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: ExchangeId::from("binance"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        for event in [
+            LifecycleEvent::ClientConnected {
+                session_id: "a".to_string(),
+            },
+            LifecycleEvent::Subscribed {
+                session_id: "a".to_string(),
+                channel: channel.clone(),
+            },
+            LifecycleEvent::Subscribed {
+                session_id: "a".to_string(),
+                channel,
+            },
+            LifecycleEvent::UpstreamDisconnected {
+                exchange: ExchangeId::from("binance"),
+                market_type: MarketType::Spot,
+                reason: "closed".to_string(),
+            },
+        ] {
+            state.hub.emit_event(event);
+        }
+
+        let expected = LifecycleStats {
+            client_connects: 1,
+            subscribes: 2,
+            upstream_disconnects: 1,
+            ..LifecycleStats::default()
+        };
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while state.lifecycle.snapshot() != expected {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("events were not counted");
+
+        let Json(stats) = get_stats(State(state)).await.unwrap();
+        assert_eq!(stats.lifecycle, expected);
+        counter.abort();
+    }
+
+    #[tokio::test]
+    async fn stats_report_ticker_ages_oldest_first() {
+        use crypto_dash_core::model::Ticker;
+        use crypto_dash_core::time::now;
+        use rust_decimal::Decimal;
+
+        let state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle());
+        for (base, age_secs) in [("BTC", 1), ("ETH", 30)] {
+            let ticker = Ticker {
+                timestamp: now() - chrono::Duration::seconds(age_secs),
+                exchange: ExchangeId::from("binance"),
+                market_type: MarketType::Spot,
+                symbol: Symbol::new(base, "USDT"),
+                bid: Decimal::ONE,
+                ask: Decimal::ONE,
+                last: Decimal::ONE,
+                bid_size: Decimal::ONE,
+                ask_size: Decimal::ONE,
+                quote_volume_24h: None,
+                change_pct_24h: None,
+                tick_direction: None,
+                high_24h: None,
+                low_24h: None,
+                volume_24h: None,
+            };
+            state.cache.set_ticker(ticker).await;
+        }
+
+        let Json(stats) = get_stats(State(state)).await.unwrap();
+
+        let ages: Vec<(&str, i64)> = stats
+            .ticker_ages
+            .iter()
+            .map(|age| (age.symbol.as_str(), age.last_update_age_ms / 1000))
+            .collect();
+        assert_eq!(ages, [("ETH-USDT", 30), ("BTC-USDT", 1)]);
+    }
+
+    #[tokio::test]
+    async fn stats_report_exchange_clock_skew() {
+        use crypto_dash_core::model::{StreamMessage, Ticker};
+        use crypto_dash_core::time::now;
+        use rust_decimal::Decimal;
+
+        let state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle());
+        let topic = Topic::ticker(
+            ExchangeId::from("binance"),
+            MarketType::Spot,
+            Symbol::new("BTC", "USDT"),
+        );
+
+        // The exchange stamps every ticker two seconds behind our clock
+        for _ in 0..5 {
+            let ticker = Ticker {
+                timestamp: now() - chrono::Duration::seconds(2),
+                exchange: ExchangeId::from("binance"),
+                market_type: MarketType::Spot,
+                symbol: Symbol::new("BTC", "USDT"),
+                bid: Decimal::ONE,
+                ask: Decimal::ONE,
+                last: Decimal::ONE,
+                bid_size: Decimal::ONE,
+                ask_size: Decimal::ONE,
+                quote_volume_24h: None,
+                change_pct_24h: None,
+                tick_direction: None,
+                high_24h: None,
+                low_24h: None,
+                volume_24h: None,
+            };
+            state
+                .hub
+                .publish(&topic, StreamMessage::Ticker(ticker))
+                .await;
+        }
+
+        let Json(stats) = get_stats(State(state)).await.unwrap();
+
+        assert_eq!(stats.clock_skew.len(), 1);
+        let skew = &stats.clock_skew[0];
+        assert_eq!(skew.exchange, ExchangeId::from("binance"));
+        assert_eq!(skew.samples, 5);
+        assert!(
+            (2000.0..2100.0).contains(&skew.skew_ms),
+            "skew was {}ms",
+            skew.skew_ms
+        );
+    }
+
+    #[tokio::test]
+    async fn stats_count_exchange_messages() {
+        use crypto_dash_binance::BinanceAdapter;
+        use crypto_dash_core::model::{Channel, ChannelType};
+        use crypto_dash_exchanges_common::ExchangeAdapter;
+        use futures::{SinkExt, StreamExt};
+        use std::sync::Arc;
+        use std::time::Duration;
+        use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+        // After the subscribe ack: an undecodable frame, a ticker with an unparsable price,
+        // then a valid ticker
+        let upstream = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let upstream_url = format!("ws://{}", upstream.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = upstream.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while let Some(Ok(WsMessage::Text(text))) = ws.next().await {
+                let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                let ack = format!(r#"{{"result":null,"id":{}}}"#, request["id"]);
+                for frame in [
+                    ack.as_str(),
+                    "not json",
+                    r#"{"stream":"btcusdt@ticker","data":{"s":"BTCUSDT","c":"abc","b":"1","a":"2","E":1234567890}}"#,
+                    r#"{"stream":"btcusdt@ticker","data":{"s":"BTCUSDT","c":"50000.00","b":"49999.00","a":"50002.00","E":1234567890}}"#,
+                ] {
+                    ws.send(WsMessage::Text(frame.to_string())).await.unwrap();
+                }
+            }
+        });
+
+        let hub = StreamHub::new().handle();
+        let cache = MemoryCache::new().handle();
+        // Keeps the adapter from dropping the connection for lack of subscribers
+        let _session = hub.subscribe_all().await;
+        let adapter = Arc::new(BinanceAdapter::new().with_ws_url(MarketType::Spot, upstream_url));
+        adapter.start(hub.clone(), cache.clone()).await.unwrap();
+        let mut state = AppState::new(hub, cache);
+        state.add_exchange(adapter.clone());
+
+        let channel = Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: ExchangeId::from("binance"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        adapter.subscribe(&[channel]).await.unwrap();
+
+        let messages = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let Json(stats) = get_stats(State(state.clone())).await.unwrap();
+                let messages = &stats.exchange_messages[0];
+                if messages.metrics.messages_published == 1 {
+                    break messages.metrics;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("ticker was never published");
+
+        assert_eq!(messages.messages_received, 4);
+        assert_eq!(messages.parse_errors, 2);
+
+        adapter.stop().await.unwrap();
+    }
+}
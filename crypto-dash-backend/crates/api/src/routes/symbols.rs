@@ -1,25 +1,80 @@
-use crate::catalog::{is_quote_allowed, ALLOWED_PERP_QUOTES, ALLOWED_SPOT_QUOTES};
+use crate::catalog::{
+    is_quote_allowed, CatalogStatus, ALLOWED_FUTURE_QUOTES, ALLOWED_INVERSE_QUOTES,
+    ALLOWED_PERP_QUOTES, ALLOWED_SPOT_QUOTES,
+};
+use crate::routes::candles::normalize_symbol;
+use crate::routes::query::optional_market_type;
+use crate::routes::ticker::split_symbol;
 use crate::state::AppState;
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::Json,
 };
-use crypto_dash_core::model::MarketType;
+use chrono::NaiveDate;
+use crypto_dash_core::model::{ExchangeId, MarketType, Symbol, SymbolMeta};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct SymbolsQuery {
     exchange: Option<String>,
+    #[serde(default, deserialize_with = "optional_market_type")]
+    market_type: Option<MarketType>,
+    /// Case-insensitive substring of the base, quote or display name
+    query: Option<String>,
+    /// Symbols per exchange in the page; all when omitted
+    limit: Option<usize>,
+    offset: Option<usize>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct SymbolResponse {
     pub exchange: String,
+    /// Symbols matching `query` before `offset`/`limit` were applied
+    pub total: usize,
     pub symbols: Vec<SymbolMetaDto>,
 }
 
+impl SymbolResponse {
+    /// The page of `symbols` selected by the query's market type, search and paging params,
+    /// sorted so pages are stable between requests
+    fn page(exchange: String, mut symbols: Vec<SymbolMetaDto>, params: &SymbolsQuery) -> Self {
+        if let Some(market_type) = params.market_type {
+            symbols.retain(|dto| dto.market_type == market_type);
+        }
+        if let Some(query) = params
+            .query
+            .as_deref()
+            .map(|query| query.trim().to_lowercase())
+            .filter(|query| !query.is_empty())
+        {
+            symbols.retain(|dto| {
+                [&dto.base, &dto.quote, &dto.display_name]
+                    .iter()
+                    .any(|field| field.to_lowercase().contains(&query))
+            });
+        }
+        symbols.sort_by(|a, b| {
+            a.symbol
+                .cmp(&b.symbol)
+                .then_with(|| a.market_type.as_str().cmp(b.market_type.as_str()))
+        });
+
+        let total = symbols.len();
+        let symbols = symbols
+            .into_iter()
+            .skip(params.offset.unwrap_or(0))
+            .take(params.limit.unwrap_or(usize::MAX))
+            .collect();
+        Self {
+            exchange,
+            total,
+            symbols,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct SymbolMetaDto {
     pub symbol: String,
@@ -31,6 +86,38 @@ pub struct SymbolMetaDto {
     pub tick_size: String,
     pub min_qty: rust_decimal::Decimal,
     pub step_size: rust_decimal::Decimal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiry: Option<NaiveDate>,
+}
+
+impl SymbolMetaDto {
+    fn from_meta(meta: SymbolMeta) -> Self {
+        let mut pair = Symbol::new(meta.base.clone(), meta.quote.clone());
+        pair.expiry = meta.expiry;
+        let display_name = match meta.expiry {
+            Some(expiry) => format!("{} / {} {}", meta.base, meta.quote, expiry),
+            None => format!("{} / {}", meta.base, meta.quote),
+        };
+
+        Self {
+            symbol: pair.canonical(),
+            base: meta.base,
+            quote: meta.quote,
+            market_type: meta.market_type,
+            display_name,
+            price_precision: meta.price_precision,
+            tick_size: meta.tick_size,
+            min_qty: meta.min_qty,
+            step_size: meta.step_size,
+            expiry: meta.expiry,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SymbolQuery {
+    #[serde(default, deserialize_with = "optional_market_type")]
+    market_type: Option<MarketType>,
 }
 
 // Legacy SymbolInfo for backwards compatibility
@@ -46,6 +133,8 @@ pub struct SymbolInfo {
 pub struct AllowedQuotesDto {
     pub spot: Vec<String>,
     pub perpetual: Vec<String>,
+    pub inverse_perpetual: Vec<String>,
+    pub future: Vec<String>,
 }
 
 impl AllowedQuotesDto {
@@ -53,6 +142,14 @@ impl AllowedQuotesDto {
         Self {
             spot: ALLOWED_SPOT_QUOTES.iter().map(|s| s.to_string()).collect(),
             perpetual: ALLOWED_PERP_QUOTES.iter().map(|s| s.to_string()).collect(),
+            inverse_perpetual: ALLOWED_INVERSE_QUOTES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            future: ALLOWED_FUTURE_QUOTES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
         }
     }
 }
@@ -69,7 +166,9 @@ pub async fn list_symbols(
     State(state): State<AppState>,
 ) -> Result<Json<SymbolsResponseDto>, StatusCode> {
     // Try to get symbols from the catalog first
-    let symbol_metas = state.get_symbol_meta(params.exchange.as_deref()).await;
+    let symbol_metas = state
+        .get_symbol_meta(params.exchange.as_deref(), params.market_type)
+        .await;
 
     if !symbol_metas.is_empty() {
         // Group symbols by exchange
@@ -80,31 +179,17 @@ pub async fn list_symbols(
                 continue;
             }
 
-            let display_name = format!("{} / {}", meta.base, meta.quote);
-            let symbol_key = format!("{}-{}", meta.base, meta.quote);
-
-            let dto = SymbolMetaDto {
-                symbol: symbol_key,
-                base: meta.base,
-                quote: meta.quote,
-                market_type: meta.market_type,
-                display_name,
-                price_precision: meta.price_precision,
-                tick_size: meta.tick_size,
-                min_qty: meta.min_qty,
-                step_size: meta.step_size,
-            };
-
             response_map
                 .entry(meta.exchange.as_str().to_string())
                 .or_insert_with(Vec::new)
-                .push(dto);
+                .push(SymbolMetaDto::from_meta(meta));
         }
 
-        let response: Vec<SymbolResponse> = response_map
+        let mut response: Vec<SymbolResponse> = response_map
             .into_iter()
-            .map(|(exchange, symbols)| SymbolResponse { exchange, symbols })
+            .map(|(exchange, symbols)| SymbolResponse::page(exchange, symbols, &params))
             .collect();
+        response.sort_by(|a, b| a.exchange.cmp(&b.exchange));
 
         return Ok(Json(SymbolsResponseDto {
             allowed_quotes: AllowedQuotesDto::new(),
@@ -118,7 +203,7 @@ pub async fn list_symbols(
 
     let mut response = Vec::new();
 
-    if let Some(exchange_filter) = params.exchange {
+    if let Some(exchange_filter) = params.exchange.clone() {
         // Return symbols for specific exchange
         if let Some(symbols) = popular_symbols.get(&exchange_filter) {
             let mut symbol_dtos: Vec<SymbolMetaDto> = Vec::new();
@@ -133,6 +218,7 @@ pub async fn list_symbols(
                     tick_size: "0.01".to_string(),
                     min_qty: rust_decimal::Decimal::new(1, 3), // 0.001
                     step_size: rust_decimal::Decimal::new(1, 3), // 0.001
+                    expiry: None,
                 };
 
                 if is_quote_allowed(MarketType::Spot, &spot.quote) {
@@ -145,10 +231,7 @@ pub async fn list_symbols(
                 }
             }
 
-            response.push(SymbolResponse {
-                exchange: exchange_filter,
-                symbols: symbol_dtos,
-            });
+            response.push(SymbolResponse::page(exchange_filter, symbol_dtos, &params));
         }
     } else {
         // Return symbols for all available exchanges
@@ -166,6 +249,7 @@ pub async fn list_symbols(
                         tick_size: "0.01".to_string(),
                         min_qty: rust_decimal::Decimal::new(1, 3), // 0.001
                         step_size: rust_decimal::Decimal::new(1, 3), // 0.001
+                        expiry: None,
                     };
 
                     if is_quote_allowed(MarketType::Spot, &spot.quote) {
@@ -178,10 +262,11 @@ pub async fn list_symbols(
                     }
                 }
 
-                response.push(SymbolResponse {
-                    exchange: exchange.id.as_str().to_string(),
-                    symbols: symbol_dtos,
-                });
+                response.push(SymbolResponse::page(
+                    exchange.id.as_str().to_string(),
+                    symbol_dtos,
+                    &params,
+                ));
             }
         }
     }
@@ -192,6 +277,24 @@ pub async fn list_symbols(
     }))
 }
 
+/// GET /api/symbols/:exchange/:symbol - Metadata of one catalog instrument, defaulting to spot
+pub async fn get_symbol(
+    Path((exchange, symbol)): Path<(String, String)>,
+    Query(params): Query<SymbolQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<SymbolMetaDto>, StatusCode> {
+    let market_type = params.market_type.unwrap_or_default();
+    let symbol =
+        split_symbol(&normalize_symbol(&symbol), market_type).ok_or(StatusCode::BAD_REQUEST)?;
+
+    state
+        .symbol_catalog
+        .get_symbol_meta_one(&exchange.trim().to_lowercase(), market_type, &symbol)
+        .await
+        .map(|meta| Json(SymbolMetaDto::from_meta(meta)))
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
 /// POST /api/symbols/refresh - Refresh symbol metadata for an exchange
 pub async fn refresh_symbols(
     Query(params): Query<SymbolsQuery>,
@@ -229,6 +332,18 @@ pub async fn refresh_symbols(
     }
 }
 
+#[derive(Debug, Serialize)]
+pub struct CatalogStatusResponse {
+    pub exchanges: Vec<CatalogStatus>,
+}
+
+/// GET /api/catalog/status - Symbol count and last refresh time per exchange
+pub async fn catalog_status(State(state): State<AppState>) -> Json<CatalogStatusResponse> {
+    Json(CatalogStatusResponse {
+        exchanges: state.symbol_catalog.status().await,
+    })
+}
+
 fn get_popular_symbols() -> HashMap<String, Vec<SymbolInfo>> {
     let mut symbols = HashMap::new();
 
@@ -273,8 +388,154 @@ fn get_popular_symbols() -> HashMap<String, Vec<SymbolInfo>> {
         .collect();
 
     // Both Binance and Bybit support these pairs
-    symbols.insert("binance".to_string(), symbol_infos.clone());
-    symbols.insert("bybit".to_string(), symbol_infos);
+    symbols.insert(ExchangeId::BINANCE.to_string(), symbol_infos.clone());
+    symbols.insert(ExchangeId::BYBIT.to_string(), symbol_infos);
 
     symbols
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_dash_cache::MemoryCache;
+    use crypto_dash_core::config::RestUrls;
+    use crypto_dash_core::time::now;
+    use crypto_dash_stream_hub::StreamHub;
+
+    #[tokio::test]
+    async fn catalog_status_reports_refreshed_exchanges() {
+        use axum::routing::get;
+
+        let exchange_info = serde_json::json!({
+            "symbols": [
+                {"symbol": "BTCUSDT", "baseAsset": "BTC", "quoteAsset": "USDT",
+                 "baseAssetPrecision": 8, "quotePrecision": 8, "filters": []},
+                {"symbol": "ETHUSDT", "baseAsset": "ETH", "quoteAsset": "USDT",
+                 "baseAssetPrecision": 8, "quotePrecision": 8, "filters": []}
+            ]
+        });
+        let router = axum::Router::new().route(
+            "/api/v3/exchangeInfo",
+            get(move || async move { Json(exchange_info) }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let rest_urls = RestUrls {
+            binance_catalog: base_url.clone(),
+            binance_perp_catalog: base_url,
+            ..RestUrls::default()
+        };
+        let state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle())
+            .with_rest_urls(rest_urls);
+
+        let Json(before) = catalog_status(State(state.clone())).await;
+        assert!(before.exchanges.is_empty());
+
+        let started = now();
+        state.refresh_exchange_symbols("binance").await.unwrap();
+        let Json(status) = catalog_status(State(state)).await;
+
+        // The mock serves no futures listing, so only the spot instruments are counted
+        assert_eq!(status.exchanges.len(), 1);
+        let binance = &status.exchanges[0];
+        assert_eq!(binance.exchange, "binance");
+        assert_eq!(binance.symbol_count, 2);
+        let refreshed_at = binance
+            .last_refreshed_at
+            .expect("refresh time not recorded");
+        assert!(refreshed_at >= started && refreshed_at <= now());
+    }
+
+    #[tokio::test]
+    async fn single_symbol_is_looked_up_by_market() {
+        let rest_urls = RestUrls {
+            bybit_catalog: "http://127.0.0.1:1".to_string(),
+            ..RestUrls::default()
+        };
+        let mut state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle())
+            .with_rest_urls(rest_urls);
+        state.add_exchange(
+            crate::state::create_adapter("bybit", &crypto_dash_core::config::Config::default())
+                .unwrap(),
+        );
+        // Bybit is unreachable and nothing is cached, so the fallback listings are loaded
+        state.load_symbol_metadata().await.unwrap();
+
+        let get = |exchange: &str, symbol: &str, market_type: Option<MarketType>| {
+            let path = Path((exchange.to_string(), symbol.to_string()));
+            get_symbol(
+                path,
+                Query(SymbolQuery { market_type }),
+                State(state.clone()),
+            )
+        };
+
+        let Json(perp) = get("Bybit", "eth-usdt", Some(MarketType::Perpetual))
+            .await
+            .unwrap();
+        assert_eq!(perp.symbol, "ETH-USDT");
+        assert_eq!(perp.market_type, MarketType::Perpetual);
+        let Json(spot) = get("bybit", "BTCUSDT", None).await.unwrap();
+        assert_eq!(spot.market_type, MarketType::Spot);
+
+        assert_eq!(
+            get("bybit", "SOLUSDT", None).await.unwrap_err(),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            get("bybit", "BTC", None).await.unwrap_err(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[tokio::test]
+    async fn symbols_can_be_searched_and_paged() {
+        let state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle());
+        let list = |query: &str, limit: Option<usize>, offset: Option<usize>| {
+            let state = state.clone();
+            let params = SymbolsQuery {
+                exchange: Some("binance".to_string()),
+                market_type: None,
+                query: Some(query.to_string()),
+                limit,
+                offset,
+            };
+            async move {
+                let Json(response) = list_symbols(Query(params), State(state)).await.unwrap();
+                let binance = response.exchanges.into_iter().next().unwrap();
+                let symbols: Vec<_> = binance
+                    .symbols
+                    .iter()
+                    .map(|dto| format!("{}:{}", dto.symbol, dto.market_type))
+                    .collect();
+                (binance.total, symbols)
+            }
+        };
+
+        // Display names match too: "Bitcoin Cash / USDT"
+        let (total, symbols) = list("  BITCOIN ", None, None).await;
+        assert_eq!(total, 4);
+        assert_eq!(
+            symbols,
+            [
+                "BCH-USDT:perpetual",
+                "BCH-USDT:spot",
+                "BTC-USDT:perpetual",
+                "BTC-USDT:spot"
+            ]
+        );
+
+        let (total, page) = list("bitcoin", Some(2), Some(1)).await;
+        assert_eq!(total, 4);
+        assert_eq!(page, ["BCH-USDT:spot", "BTC-USDT:perpetual"]);
+
+        let (total, page) = list("bitcoin", Some(2), Some(10)).await;
+        assert_eq!(total, 4);
+        assert!(page.is_empty());
+
+        let (total, _) = list("", None, None).await;
+        assert!(total > 4);
+    }
+}
@@ -0,0 +1,261 @@
+use crate::catalog::is_quote_allowed;
+use crate::routes::candles::normalize_symbol;
+use crate::routes::query::optional_market_type;
+use crate::state::AppState;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use crypto_dash_core::model::{ExchangeId, MarketType, OrderBookSnapshot, Symbol, Ticker};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Quote assets recognised when splitting a concatenated pair; longer suffixes first
+const QUOTE_SUFFIXES: &[&str] = &["USDT", "USDC", "TUSD", "USD"];
+/// `include` value that adds the cached order book's best levels
+const INCLUDE_BOOK_TOP: &str = "book_top";
+
+#[derive(Debug, Deserialize)]
+pub struct TickerQuery {
+    pub exchange: String,
+    pub symbol: String,
+    #[serde(default, deserialize_with = "optional_market_type")]
+    pub market_type: Option<MarketType>,
+    /// Comma-separated extras, currently only `book_top`
+    pub include: Option<String>,
+}
+
+/// Best bid and ask levels of the cached order book
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BookTop {
+    pub bid: Decimal,
+    pub bid_size: Decimal,
+    pub ask: Decimal,
+    pub ask_size: Decimal,
+}
+
+impl BookTop {
+    /// None unless the book has levels on both sides
+    fn from_orderbook(book: &OrderBookSnapshot) -> Option<Self> {
+        let bid = book.bids.first()?;
+        let ask = book.asks.first()?;
+        Some(Self {
+            bid: bid.price,
+            bid_size: bid.quantity,
+            ask: ask.price,
+            ask_size: ask.quantity,
+        })
+    }
+}
+
+/// The cached ticker, plus the book top when requested and a book is cached
+#[derive(Debug, Serialize)]
+pub struct TickerResponse {
+    #[serde(flatten)]
+    pub ticker: Ticker,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub book_top: Option<BookTop>,
+}
+
+/// GET /api/ticker - Latest cached ticker for one instrument, defaulting to spot
+pub async fn get_ticker(
+    State(state): State<AppState>,
+    Query(params): Query<TickerQuery>,
+) -> Result<Json<TickerResponse>, StatusCode> {
+    let market_type = params.market_type.unwrap_or_default();
+    let exchange = ExchangeId::from(params.exchange.trim().to_lowercase().as_str());
+    let symbol = split_symbol(&normalize_symbol(&params.symbol), market_type)
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    let mut include_book_top = false;
+    for include in params.include.iter().flat_map(|include| include.split(',')) {
+        match include.trim() {
+            "" => {}
+            INCLUDE_BOOK_TOP => include_book_top = true,
+            _ => return Err(StatusCode::BAD_REQUEST),
+        }
+    }
+
+    let ticker = state
+        .cache
+        .get_ticker(&exchange, market_type, &symbol)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let book_top = if include_book_top {
+        state
+            .cache
+            .get_orderbook(&exchange, market_type, &symbol)
+            .await
+            .and_then(|book| BookTop::from_orderbook(&book))
+    } else {
+        None
+    };
+
+    Ok(Json(TickerResponse { ticker, book_top }))
+}
+
+/// Split a normalized pair such as `BTCUSDT` on a quote the market allows
+pub(crate) fn split_symbol(normalized: &str, market_type: MarketType) -> Option<Symbol> {
+    QUOTE_SUFFIXES
+        .iter()
+        .filter(|quote| is_quote_allowed(market_type, quote))
+        .find_map(|quote| {
+            normalized
+                .strip_suffix(quote)
+                .filter(|base| !base.is_empty())
+                .map(|base| Symbol::new(base, *quote))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_dash_cache::MemoryCache;
+    use crypto_dash_core::time::now;
+    use crypto_dash_stream_hub::StreamHub;
+    use rust_decimal::Decimal;
+
+    fn query(symbol: &str, market_type: Option<MarketType>) -> Query<TickerQuery> {
+        Query(TickerQuery {
+            exchange: "Binance".to_string(),
+            symbol: symbol.to_string(),
+            market_type,
+            include: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn returns_cached_ticker_or_not_found() {
+        let cache = MemoryCache::new().handle();
+        let state = AppState::new(StreamHub::new().handle(), cache.clone());
+
+        cache
+            .set_ticker(Ticker {
+                timestamp: now(),
+                exchange: ExchangeId::from("binance"),
+                market_type: MarketType::Perpetual,
+                symbol: Symbol::new("BTC", "USDT"),
+                bid: Decimal::new(100, 0),
+                ask: Decimal::new(101, 0),
+                last: Decimal::new(100, 0),
+                bid_size: Decimal::ONE,
+                ask_size: Decimal::ONE,
+                quote_volume_24h: None,
+                change_pct_24h: None,
+                tick_direction: None,
+                high_24h: None,
+                low_24h: None,
+                volume_24h: None,
+            })
+            .await;
+
+        let Json(TickerResponse { ticker, book_top }) = get_ticker(
+            State(state.clone()),
+            query("btc-usdt", Some(MarketType::Perpetual)),
+        )
+        .await
+        .unwrap();
+        assert!(book_top.is_none());
+        assert_eq!(ticker.symbol, Symbol::new("BTC", "USDT"));
+        assert_eq!(ticker.last, Decimal::new(100, 0));
+
+        // Only the perpetual is cached, so the default spot lookup misses
+        let err = get_ticker(State(state.clone()), query("BTCUSDT", None))
+            .await
+            .unwrap_err();
+        assert_eq!(err, StatusCode::NOT_FOUND);
+
+        let err = get_ticker(State(state), query("USDT", None))
+            .await
+            .unwrap_err();
+        assert_eq!(err, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn include_book_top_adds_best_levels() {
+        use crypto_dash_core::model::PriceLevel;
+
+        let cache = MemoryCache::new().handle();
+        let state = AppState::new(StreamHub::new().handle(), cache.clone());
+        let symbol = Symbol::new("ETH", "USDT");
+        cache
+            .set_ticker(Ticker {
+                timestamp: now(),
+                exchange: ExchangeId::from("binance"),
+                market_type: MarketType::Spot,
+                symbol: symbol.clone(),
+                bid: Decimal::new(2000, 0),
+                ask: Decimal::new(2001, 0),
+                last: Decimal::new(2000, 0),
+                bid_size: Decimal::ONE,
+                ask_size: Decimal::ONE,
+                quote_volume_24h: None,
+                change_pct_24h: None,
+                tick_direction: None,
+                high_24h: None,
+                low_24h: None,
+                volume_24h: None,
+            })
+            .await;
+        cache
+            .set_orderbook(OrderBookSnapshot {
+                timestamp: now(),
+                exchange: ExchangeId::from("binance"),
+                market_type: MarketType::Spot,
+                symbol,
+                bids: vec![
+                    PriceLevel::new(Decimal::new(19995, 1), Decimal::new(3, 0)),
+                    PriceLevel::new(Decimal::new(1999, 0), Decimal::new(7, 0)),
+                ],
+                asks: vec![
+                    PriceLevel::new(Decimal::new(20005, 1), Decimal::new(2, 0)),
+                    PriceLevel::new(Decimal::new(2001, 0), Decimal::new(5, 0)),
+                ],
+                checksum: None,
+            })
+            .await;
+
+        let mut params = query("ETHUSDT", None);
+        params.include = Some("book_top".to_string());
+        let Json(response) = get_ticker(State(state.clone()), params).await.unwrap();
+        assert_eq!(
+            response.book_top,
+            Some(BookTop {
+                bid: Decimal::new(19995, 1),
+                bid_size: Decimal::new(3, 0),
+                ask: Decimal::new(20005, 1),
+                ask_size: Decimal::new(2, 0),
+            })
+        );
+
+        // The book top sits alongside the ticker fields
+        let body = serde_json::to_value(&response).unwrap();
+        assert_eq!(body["last"], serde_json::json!("2000"));
+        assert_eq!(body["book_top"]["ask"], serde_json::json!("2000.5"));
+
+        // Without the param the response is the bare ticker
+        let Json(response) = get_ticker(State(state.clone()), query("ETHUSDT", None))
+            .await
+            .unwrap();
+        let body = serde_json::to_value(&response).unwrap();
+        assert!(body.get("book_top").is_none());
+
+        let mut params = query("ETHUSDT", None);
+        params.include = Some("depth".to_string());
+        let err = get_ticker(State(state), params).await.unwrap_err();
+        assert_eq!(err, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn splits_on_quotes_allowed_for_market() {
+        assert_eq!(
+            split_symbol("ETHUSD", MarketType::InversePerpetual),
+            Some(Symbol::new("ETH", "USD"))
+        );
+        assert_eq!(split_symbol("ETHUSD", MarketType::Spot), None);
+        assert_eq!(
+            split_symbol("SOLTUSD", MarketType::Spot),
+            Some(Symbol::new("SOL", "TUSD"))
+        );
+    }
+}
@@ -0,0 +1,290 @@
+use crate::routes::query::optional_market_type;
+use crate::state::AppState;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use crypto_dash_core::model::{MarketType, Ticker};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+
+const MAX_TICKERS: usize = 500;
+const DEFAULT_MOVERS_LIMIT: usize = 5;
+const MAX_MOVERS_LIMIT: usize = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct TickersQuery {
+    pub exchange: Option<String>,
+    #[serde(default, deserialize_with = "optional_market_type")]
+    pub market_type: Option<MarketType>,
+    /// Defaults to and is capped at 500
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TickersResponse {
+    pub tickers: Vec<Ticker>,
+    /// Matching tickers before `limit` was applied
+    pub total: usize,
+}
+
+/// GET /api/tickers - All cached tickers, highest 24h quote volume first
+pub async fn list_tickers(
+    State(state): State<AppState>,
+    Query(params): Query<TickersQuery>,
+) -> Result<Json<TickersResponse>, StatusCode> {
+    let limit = params.limit.unwrap_or(MAX_TICKERS);
+    if limit == 0 || limit > MAX_TICKERS {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let exchange = params
+        .exchange
+        .as_deref()
+        .map(|exchange| exchange.trim().to_lowercase())
+        .filter(|exchange| !exchange.is_empty());
+
+    let mut tickers: Vec<Ticker> = state
+        .cache
+        .get_all_tickers()
+        .await
+        .into_iter()
+        .filter(|ticker| {
+            exchange
+                .as_deref()
+                .is_none_or(|exchange| ticker.exchange.as_str() == exchange)
+        })
+        .filter(|ticker| {
+            params
+                .market_type
+                .is_none_or(|market_type| ticker.market_type == market_type)
+        })
+        .collect();
+
+    // Tickers without volume sort last; symbol keeps the order stable
+    tickers.sort_by(|a, b| {
+        Reverse(a.quote_volume_24h)
+            .cmp(&Reverse(b.quote_volume_24h))
+            .then_with(|| a.exchange.as_str().cmp(b.exchange.as_str()))
+            .then_with(|| a.symbol.base.cmp(&b.symbol.base))
+            .then_with(|| a.symbol.quote.cmp(&b.symbol.quote))
+    });
+
+    let total = tickers.len();
+    tickers.truncate(limit);
+
+    Ok(Json(TickersResponse { tickers, total }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MoversQuery {
+    pub exchange: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MoversResponse {
+    pub gainers: Vec<Ticker>,
+    pub losers: Vec<Ticker>,
+}
+
+/// GET /api/movers - Top gainers and losers by 24h change from cached tickers
+pub async fn get_movers(
+    State(state): State<AppState>,
+    Query(params): Query<MoversQuery>,
+) -> Result<Json<MoversResponse>, StatusCode> {
+    let limit = params.limit.unwrap_or(DEFAULT_MOVERS_LIMIT);
+    if limit == 0 || limit > MAX_MOVERS_LIMIT {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let exchange = params
+        .exchange
+        .as_deref()
+        .map(|exchange| exchange.trim().to_lowercase())
+        .filter(|exchange| !exchange.is_empty());
+
+    let mut changed: Vec<Ticker> = state
+        .cache
+        .get_all_tickers()
+        .await
+        .into_iter()
+        .filter(|ticker| {
+            exchange
+                .as_deref()
+                .is_none_or(|exchange| ticker.exchange.as_str() == exchange)
+        })
+        .filter(|ticker| ticker.change_pct_24h.is_some())
+        .collect();
+
+    // Biggest gain first; ties broken by symbol for a stable order
+    changed.sort_by(|a, b| {
+        b.change_pct_24h
+            .cmp(&a.change_pct_24h)
+            .then_with(|| a.symbol.base.cmp(&b.symbol.base))
+            .then_with(|| a.symbol.quote.cmp(&b.symbol.quote))
+    });
+
+    let gainers = changed
+        .iter()
+        .filter(|ticker| ticker.change_pct_24h.is_some_and(|pct| pct > Decimal::ZERO))
+        .take(limit)
+        .cloned()
+        .collect();
+    let losers = changed
+        .iter()
+        .rev()
+        .filter(|ticker| ticker.change_pct_24h.is_some_and(|pct| pct < Decimal::ZERO))
+        .take(limit)
+        .cloned()
+        .collect();
+
+    Ok(Json(MoversResponse { gainers, losers }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_dash_cache::MemoryCache;
+    use crypto_dash_core::model::{ExchangeId, Symbol};
+    use crypto_dash_core::time::now;
+    use crypto_dash_stream_hub::StreamHub;
+
+    fn ticker(exchange: &str, base: &str, volume: Option<i64>) -> Ticker {
+        Ticker {
+            timestamp: now(),
+            exchange: ExchangeId::from(exchange),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new(base, "USDT"),
+            bid: Decimal::new(100, 0),
+            ask: Decimal::new(101, 0),
+            last: Decimal::new(100, 0),
+            bid_size: Decimal::ONE,
+            ask_size: Decimal::ONE,
+            quote_volume_24h: volume.map(Decimal::from),
+            change_pct_24h: None,
+            tick_direction: None,
+            high_24h: None,
+            low_24h: None,
+            volume_24h: None,
+        }
+    }
+
+    fn mover(exchange: &str, base: &str, change_pct: &str) -> Ticker {
+        let mut ticker = ticker(exchange, base, None);
+        ticker.change_pct_24h = Some(change_pct.parse().unwrap());
+        ticker
+    }
+
+    #[tokio::test]
+    async fn movers_ranked_and_capped() {
+        let cache = MemoryCache::new().handle();
+        let state = AppState::new(StreamHub::new().handle(), cache.clone());
+
+        for (base, change) in [
+            ("BTC", "2.5"),
+            ("ETH", "7.1"),
+            ("SOL", "-3.2"),
+            ("ADA", "-9.8"),
+            ("XRP", "0.4"),
+            ("DOT", "-0.1"),
+            ("LINK", "0"),
+        ] {
+            cache.set_ticker(mover("binance", base, change)).await;
+        }
+        cache.set_ticker(mover("bybit", "DOGE", "40")).await;
+        cache.set_ticker(ticker("binance", "TRX", None)).await;
+
+        let query = MoversQuery {
+            exchange: Some("binance".to_string()),
+            limit: Some(2),
+        };
+        let Json(movers) = get_movers(State(state.clone()), Query(query))
+            .await
+            .unwrap();
+
+        let bases = |tickers: &[Ticker]| -> Vec<String> {
+            tickers
+                .iter()
+                .map(|ticker| ticker.symbol.base.clone())
+                .collect()
+        };
+        assert_eq!(bases(&movers.gainers), ["ETH", "BTC"]);
+        assert_eq!(bases(&movers.losers), ["ADA", "SOL"]);
+
+        // Without a cap, flat tickers are neither gainers nor losers
+        let query = MoversQuery {
+            exchange: Some("binance".to_string()),
+            limit: Some(10),
+        };
+        let Json(movers) = get_movers(State(state.clone()), Query(query))
+            .await
+            .unwrap();
+        assert_eq!(bases(&movers.gainers), ["ETH", "BTC", "XRP"]);
+        assert_eq!(bases(&movers.losers), ["ADA", "SOL", "DOT"]);
+
+        let query = MoversQuery {
+            exchange: None,
+            limit: Some(0),
+        };
+        let err = get_movers(State(state), Query(query)).await.unwrap_err();
+        assert_eq!(err, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn tickers_filtered_by_exchange_and_sorted_by_volume() {
+        let cache = MemoryCache::new().handle();
+        let state = AppState::new(StreamHub::new().handle(), cache.clone());
+
+        cache.set_ticker(ticker("binance", "BTC", Some(500))).await;
+        cache.set_ticker(ticker("binance", "ETH", Some(900))).await;
+        cache.set_ticker(ticker("binance", "ADA", None)).await;
+        cache.set_ticker(ticker("bybit", "SOL", Some(10_000))).await;
+        let mut perp = ticker("binance", "XRP", Some(1));
+        perp.market_type = MarketType::Perpetual;
+        cache.set_ticker(perp).await;
+
+        let query = TickersQuery {
+            exchange: Some("Binance".to_string()),
+            market_type: Some(MarketType::Spot),
+            limit: None,
+        };
+        let Json(response) = list_tickers(State(state.clone()), Query(query))
+            .await
+            .unwrap();
+
+        let bases: Vec<_> = response
+            .tickers
+            .iter()
+            .map(|ticker| ticker.symbol.base.as_str())
+            .collect();
+        assert_eq!(bases, ["ETH", "BTC", "ADA"]);
+        assert_eq!(response.total, 3);
+
+        let all = TickersQuery {
+            exchange: None,
+            market_type: None,
+            limit: Some(2),
+        };
+        let Json(response) = list_tickers(State(state.clone()), Query(all))
+            .await
+            .unwrap();
+        assert_eq!(response.total, 5);
+        assert_eq!(response.tickers.len(), 2);
+        assert_eq!(response.tickers[0].exchange.as_str(), "bybit");
+
+        for limit in [0, MAX_TICKERS + 1] {
+            let query = TickersQuery {
+                exchange: None,
+                market_type: None,
+                limit: Some(limit),
+            };
+            let err = list_tickers(State(state.clone()), Query(query))
+                .await
+                .unwrap_err();
+            assert_eq!(err, StatusCode::BAD_REQUEST);
+        }
+    }
+}
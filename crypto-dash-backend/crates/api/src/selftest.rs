@@ -0,0 +1,138 @@
+use anyhow::{anyhow, bail, Result};
+use crypto_dash_cache::MemoryCache;
+use crypto_dash_core::model::{Channel, ChannelType, MarketType, StreamMessage, Symbol, Ticker};
+use crypto_dash_exchanges_common::ExchangeAdapter;
+use crypto_dash_stream_hub::{StreamHub, Topic};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+
+/// How long `exchange-selftest` waits for a normalized ticker unless told otherwise
+pub const DEFAULT_SELFTEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Connect `adapter` to its exchange, subscribe the BTC-USDT spot ticker and wait for
+/// one message to come through parsed and normalized.
+///
+/// Runs on its own hub and cache, so it exercises the adapter end to end without a server.
+/// The adapter is stopped before returning.
+pub async fn run_selftest(adapter: Arc<dyn ExchangeAdapter>, timeout: Duration) -> Result<Ticker> {
+    let hub = StreamHub::new().handle();
+    adapter
+        .start(hub.clone(), MemoryCache::new().handle())
+        .await?;
+
+    let channel = Channel {
+        channel_type: ChannelType::Ticker,
+        exchange: adapter.id(),
+        market_type: MarketType::Spot,
+        symbol: Symbol::new("BTC", "USDT"),
+        depth: None,
+    };
+    // Subscribed before the adapter so the first ticker is neither missed nor seen as unwatched
+    let mut handle = hub.subscribe(&Topic::from_channel(&channel)).await?;
+
+    let result = async {
+        adapter.subscribe(std::slice::from_ref(&channel)).await?;
+
+        tokio::time::timeout(timeout, async {
+            loop {
+                match handle.recv().await {
+                    Ok(StreamMessage::Ticker(ticker)) => return Ok(ticker),
+                    Ok(StreamMessage::Error { message, .. }) => bail!("{}", message),
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => bail!("stream hub closed"),
+                }
+            }
+        })
+        .await
+        .map_err(|_| anyhow!("no {} ticker within {:?}", channel.instrument(), timeout))?
+    }
+    .await;
+
+    if let Err(e) = adapter.stop().await {
+        tracing::warn!("Failed to stop {} adapter: {}", adapter.id().as_str(), e);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_dash_binance::BinanceAdapter;
+    use futures::{SinkExt, StreamExt};
+    use rust_decimal::Decimal;
+    use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+    /// Binance-style upstream that acks the subscribe, then sends `frames`
+    async fn spawn_upstream(frames: &'static [&'static str]) -> String {
+        let upstream = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", upstream.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = upstream.accept().await {
+                tokio::spawn(async move {
+                    let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                    while let Some(Ok(WsMessage::Text(text))) = ws.next().await {
+                        let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                        let ack = format!(r#"{{"result":null,"id":{}}}"#, request["id"]);
+                        ws.send(WsMessage::Text(ack)).await.unwrap();
+                        for frame in frames {
+                            ws.send(WsMessage::Text(frame.to_string())).await.unwrap();
+                        }
+                    }
+                });
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn selftest_passes_on_a_valid_ticker() {
+        let url = spawn_upstream(&[
+            "not json",
+            r#"{"stream":"btcusdt@ticker","data":{"s":"BTCUSDT","c":"50000.00","b":"49999.00","a":"50002.00","E":1234567890}}"#,
+        ])
+        .await;
+        let adapter = Arc::new(BinanceAdapter::new().with_ws_url(MarketType::Spot, url));
+
+        let ticker = run_selftest(adapter.clone(), Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        assert_eq!(ticker.symbol, Symbol::new("BTC", "USDT"));
+        assert_eq!(ticker.last, Decimal::new(5000000, 2));
+        assert!(!adapter.is_market_connected(MarketType::Spot).await);
+    }
+
+    #[tokio::test]
+    async fn configured_ws_url_replaces_the_production_endpoint() {
+        use crypto_dash_core::config::{Config, WsUrls};
+
+        let url = spawn_upstream(&[
+            r#"{"stream":"btcusdt@ticker","data":{"s":"BTCUSDT","c":"50000.00","b":"49999.00","a":"50002.00","E":1234567890}}"#,
+        ])
+        .await;
+        let config = Config {
+            ws_urls: WsUrls {
+                binance_spot: Some(url),
+                ..WsUrls::default()
+            },
+            ..Config::default()
+        };
+        let adapter = crate::state::create_adapter("binance", &config).unwrap();
+
+        let ticker = run_selftest(adapter, Duration::from_secs(5)).await.unwrap();
+        assert_eq!(ticker.symbol, Symbol::new("BTC", "USDT"));
+    }
+
+    #[tokio::test]
+    async fn selftest_fails_without_a_ticker() {
+        let url = spawn_upstream(&["not json"]).await;
+        let adapter = Arc::new(BinanceAdapter::new().with_ws_url(MarketType::Spot, url));
+
+        let err = run_selftest(adapter, Duration::from_millis(200))
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("no BTC-USDT"), "{}", err);
+    }
+}
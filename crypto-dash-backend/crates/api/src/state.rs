@@ -1,22 +1,152 @@
 use crate::catalog::ExchangeCatalog;
+use crate::maintenance::Maintenance;
+use crate::stats::LifecycleCounters;
+use crate::subscriptions::SubscriptionRegistry;
+use crate::upstream::UpstreamProbe;
+use crypto_dash_binance::BinanceAdapter;
+use crypto_dash_bybit::BybitAdapter;
 use crypto_dash_cache::CacheHandle;
-use crypto_dash_core::model::{ExchangeInfo, SymbolMeta};
+use crypto_dash_core::composite::ExchangePriority;
+use crypto_dash_core::config::{Config, RestUrls};
+use crypto_dash_core::model::{
+    Channel, CircuitState, ExchangeId, ExchangeInfo, MarketStatus, MarketType, SymbolMeta,
+};
+use crypto_dash_core::normalize::SymbolMapper;
 use crypto_dash_exchanges_common::ExchangeAdapter;
+use crypto_dash_okx::OkxAdapter;
 use crypto_dash_stream_hub::HubHandle;
 use reqwest::Client;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Create the adapter registered for an exchange id, with its timeouts and pacing from config
+pub fn create_adapter(exchange: &str, config: &Config) -> Option<Arc<dyn ExchangeAdapter>> {
+    let subscribe_timeout = Duration::from_secs(config.subscribe_timeout_secs);
+    let ws_urls = &config.ws_urls;
+    match exchange {
+        ExchangeId::BINANCE => {
+            let mut adapter = BinanceAdapter::new()
+                .with_subscribe_timeout(subscribe_timeout)
+                .with_subscribe_pacing(Duration::from_millis(config.binance_subscribe_pacing_ms))
+                .with_combined_streams(config.binance_combined_streams)
+                .with_orderbook_deltas(config.binance_orderbook_deltas)
+                .with_rest_url(MarketType::Spot, config.rest_urls.binance_catalog.clone())
+                .with_rest_url(
+                    MarketType::Perpetual,
+                    config.rest_urls.binance_perp_catalog.clone(),
+                )
+                .with_symbol_mapper(symbol_mapper(config));
+            for (market_type, url) in [
+                (MarketType::Spot, &ws_urls.binance_spot),
+                (MarketType::Perpetual, &ws_urls.binance_perp),
+            ] {
+                if let Some(url) = url {
+                    adapter = adapter.with_ws_url(market_type, url.clone());
+                }
+            }
+            Some(Arc::new(adapter))
+        }
+        ExchangeId::BYBIT => {
+            let mut adapter = BybitAdapter::new()
+                .with_subscribe_timeout(subscribe_timeout)
+                .with_subscribe_pacing(Duration::from_millis(config.bybit_subscribe_pacing_ms))
+                .with_symbol_mapper(symbol_mapper(config));
+            for (market_type, url) in [
+                (MarketType::Spot, &ws_urls.bybit_spot),
+                (MarketType::Perpetual, &ws_urls.bybit_linear),
+                (MarketType::InversePerpetual, &ws_urls.bybit_inverse),
+            ] {
+                if let Some(url) = url {
+                    adapter = adapter.with_ws_url(market_type, url.clone());
+                }
+            }
+            Some(Arc::new(adapter))
+        }
+        ExchangeId::OKX => {
+            let mut adapter = OkxAdapter::new().with_subscribe_timeout(subscribe_timeout);
+            if let Some(url) = &ws_urls.okx_public {
+                adapter = adapter.with_ws_url(url.clone());
+            }
+            Some(Arc::new(adapter))
+        }
+        _ => None,
+    }
+}
+
+/// Built-in symbol mappings merged with the configured override file, if any
+fn symbol_mapper(config: &Config) -> SymbolMapper {
+    let mut mapper = SymbolMapper::default();
+    if let Some(path) = &config.symbol_overrides_path {
+        match mapper.load_overrides(path) {
+            Ok(applied) => tracing::info!("Loaded {} symbol overrides from {}", applied, path),
+            Err(e) => tracing::warn!("Ignoring symbol overrides from {}: {:#}", path, e),
+        }
+    }
+    mapper
+}
+
+/// Create adapters for the configured exchanges, skipping duplicate and unknown entries
+pub fn create_adapters(config: &Config) -> Vec<Arc<dyn ExchangeAdapter>> {
+    let mut seen = HashSet::new();
+    let mut adapters = Vec::new();
+
+    for exchange in &config.exchanges {
+        let exchange = exchange.trim().to_lowercase();
+        if !seen.insert(exchange.clone()) {
+            tracing::warn!(
+                "Exchange '{}' listed more than once; ignoring duplicate",
+                exchange
+            );
+            continue;
+        }
+
+        match create_adapter(&exchange, config) {
+            Some(adapter) => adapters.push(adapter),
+            None => tracing::warn!("Unknown exchange: {}", exchange),
+        }
+    }
+
+    adapters
+}
 
 /// Shared application state
 #[derive(Clone)]
 pub struct AppState {
     #[allow(dead_code)]
     pub hub: HubHandle,
-    #[allow(dead_code)]
+    /// Shared by routes for both generic entries (`get`/`set`) and typed market data
     pub cache: CacheHandle,
     pub exchanges: HashMap<String, Arc<dyn ExchangeAdapter>>,
     pub symbol_catalog: Arc<ExchangeCatalog>,
     pub http_client: Client,
+    pub rest_urls: RestUrls,
+    pub subscriptions: Arc<SubscriptionRegistry>,
+    /// Window within which a session gets at most one Info/Error message per exchange
+    pub status_coalesce_window: Duration,
+    /// Guarantee sessions a snapshot before the order book deltas that build on it
+    pub book_resync: bool,
+    /// Cap on the buffer each session pre-sizes from its previous message; 0 disables sizing
+    pub serialize_buffer_bytes: usize,
+    /// Client messages a session may send per second before it is closed; 0 disables
+    pub client_message_rate_limit: u32,
+    /// Close sessions that send nothing for this long; zero disables
+    pub idle_timeout: Duration,
+    /// Channels each new session is subscribed to unless it opts out on connect
+    pub default_channels: Vec<Channel>,
+    /// Cached exchange pings behind `/ready?upstream=true`
+    pub upstream_probe: UpstreamProbe,
+    /// Refuses new sessions and drains open ones while the server is under maintenance
+    pub maintenance: Maintenance,
+    /// Preference order for `/api/composite` ties and stale fallbacks
+    pub exchange_priority: ExchangePriority,
+    /// Lifecycle event totals, filled in by `spawn_lifecycle_counter`
+    pub lifecycle: Arc<LifecycleCounters>,
+    /// Bearer token the `/admin` endpoints require; without one they refuse every request
+    pub admin_token: Option<Arc<str>>,
+    /// Flipped once graceful shutdown starts so open sessions close with 1001
+    shutdown: Arc<watch::Sender<bool>>,
 }
 
 impl AppState {
@@ -28,9 +158,130 @@ impl AppState {
             exchanges: HashMap::new(),
             symbol_catalog,
             http_client: Client::new(),
+            rest_urls: RestUrls::default(),
+            subscriptions: Arc::new(SubscriptionRegistry::new(
+                Config::default().max_upstream_subscriptions,
+            )),
+            status_coalesce_window: Duration::from_millis(Config::default().status_coalesce_ms),
+            book_resync: Config::default().book_resync,
+            serialize_buffer_bytes: Config::default().ws_serialize_buffer_bytes,
+            client_message_rate_limit: Config::default().ws_max_messages_per_sec,
+            idle_timeout: Duration::from_secs(Config::default().ws_idle_timeout_secs),
+            default_channels: Vec::new(),
+            upstream_probe: UpstreamProbe::new(Duration::from_secs(
+                Config::default().upstream_probe_cache_secs,
+            )),
+            maintenance: Maintenance::new(Duration::from_secs(
+                Config::default().maintenance_drain_grace_secs,
+            )),
+            exchange_priority: Config::default().exchange_priority(),
+            lifecycle: Arc::new(LifecycleCounters::default()),
+            admin_token: None,
+            shutdown: Arc::new(watch::channel(false).0),
         }
     }
 
+    /// Share one pre-configured HTTP client between catalog and candle requests
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.http_client = client;
+        self.symbol_catalog = Arc::new(self.build_catalog());
+        self
+    }
+
+    /// Point catalog and candle requests at the given REST roots
+    pub fn with_rest_urls(mut self, rest_urls: RestUrls) -> Self {
+        self.rest_urls = rest_urls;
+        self.symbol_catalog = Arc::new(self.build_catalog());
+        self
+    }
+
+    fn build_catalog(&self) -> ExchangeCatalog {
+        ExchangeCatalog::new(self.cache.clone())
+            .with_http_client(self.http_client.clone())
+            .with_base_url(ExchangeId::BINANCE, self.rest_urls.binance_catalog.clone())
+            .with_perp_base_url(
+                ExchangeId::BINANCE,
+                self.rest_urls.binance_perp_catalog.clone(),
+            )
+            .with_base_url(ExchangeId::BYBIT, self.rest_urls.bybit_catalog.clone())
+    }
+
+    /// Coalesce per-session status messages over the given window; zero disables it
+    pub fn with_status_coalesce_window(mut self, window: Duration) -> Self {
+        self.status_coalesce_window = window;
+        self
+    }
+
+    /// Enable or disable snapshot-before-delta ordering for order book topics
+    pub fn with_book_resync(mut self, enabled: bool) -> Self {
+        self.book_resync = enabled;
+        self
+    }
+
+    /// Cap the buffer each session pre-sizes for outbound messages
+    pub fn with_serialize_buffer_bytes(mut self, bytes: usize) -> Self {
+        self.serialize_buffer_bytes = bytes;
+        self
+    }
+
+    /// Close sessions that send more than `limit` messages in a second; zero disables
+    pub fn with_client_message_rate_limit(mut self, limit: u32) -> Self {
+        self.client_message_rate_limit = limit;
+        self
+    }
+
+    /// Close sessions silent for longer than `timeout`; zero disables
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    /// Subscribe every new session to `channels` unless it connects with `?defaults=false`
+    pub fn with_default_channels(mut self, channels: Vec<Channel>) -> Self {
+        self.default_channels = channels;
+        self
+    }
+
+    /// Reuse exchange ping results for `ttl` between upstream readiness probes
+    pub fn with_upstream_probe_ttl(mut self, ttl: Duration) -> Self {
+        self.upstream_probe = UpstreamProbe::new(ttl);
+        self
+    }
+
+    /// Close open sessions `grace` after maintenance starts, telling them to reconnect
+    /// elsewhere first; zero leaves them open
+    pub fn with_maintenance_drain_grace(mut self, grace: Duration) -> Self {
+        self.maintenance = Maintenance::new(grace);
+        self
+    }
+
+    /// Prefer exchanges in this order when composite prices tie or every ticker is stale
+    pub fn with_exchange_priority(mut self, priority: ExchangePriority) -> Self {
+        self.exchange_priority = priority;
+        self
+    }
+
+    /// Require `Authorization: Bearer <token>` on the `/admin` endpoints
+    pub fn with_admin_token(mut self, token: impl Into<String>) -> Self {
+        self.admin_token = Some(Arc::from(token.into()));
+        self
+    }
+
+    /// Close every open WebSocket session with 1001 so a graceful shutdown can finish
+    pub fn begin_shutdown(&self) {
+        self.shutdown.send_replace(true);
+    }
+
+    /// Changes to `true` once shutdown has begun
+    pub fn subscribe_shutdown(&self) -> watch::Receiver<bool> {
+        self.shutdown.subscribe()
+    }
+
+    /// Replace the upstream subscription registry with one using the given limit
+    pub fn set_subscription_limit(&mut self, limit: usize) {
+        self.subscriptions = Arc::new(SubscriptionRegistry::new(limit));
+    }
+
     pub fn add_exchange(&mut self, adapter: Arc<dyn ExchangeAdapter>) {
         let id = adapter.id().as_str().to_string();
         tracing::info!(
@@ -45,10 +296,24 @@ impl AppState {
         let mut exchanges = Vec::new();
 
         for (id, adapter) in &self.exchanges {
+            let mut markets = Vec::new();
+            for market_type in adapter.supported_markets() {
+                markets.push(MarketStatus {
+                    market_type,
+                    connected: adapter.is_market_connected(market_type).await,
+                    stats: adapter.connection_stats(market_type).await,
+                });
+            }
+
+            let degraded = markets
+                .iter()
+                .any(|market| market.stats.circuit != CircuitState::Closed);
             let info = ExchangeInfo {
                 id: adapter.id(),
                 name: id.clone(),
-                status: if adapter.is_connected().await {
+                status: if degraded {
+                    crypto_dash_core::model::ExchangeStatus::Degraded
+                } else if adapter.is_connected().await {
                     crypto_dash_core::model::ExchangeStatus::Online
                 } else {
                     crypto_dash_core::model::ExchangeStatus::Offline
@@ -56,6 +321,7 @@ impl AppState {
                 rate_limits: HashMap::new(),
                 ws_url: "".to_string(),
                 rest_url: "".to_string(),
+                markets,
             };
             exchanges.push(info);
         }
@@ -64,8 +330,12 @@ impl AppState {
     }
 
     /// Get symbol metadata from the catalog
-    pub async fn get_symbol_meta(&self, exchange: Option<&str>) -> Vec<SymbolMeta> {
-        self.symbol_catalog.get_symbols(exchange).await
+    pub async fn get_symbol_meta(
+        &self,
+        exchange: Option<&str>,
+        market_type: Option<MarketType>,
+    ) -> Vec<SymbolMeta> {
+        self.symbol_catalog.get_symbols(exchange, market_type).await
     }
 
     /// Load all symbol metadata
@@ -77,4 +347,169 @@ impl AppState {
     pub async fn refresh_exchange_symbols(&self, exchange: &str) -> anyhow::Result<()> {
         self.symbol_catalog.refresh_exchange(exchange).await
     }
+
+    /// Stop every adapter so exchange WebSockets are closed rather than reset on exit
+    pub async fn stop_exchanges(&self) {
+        for (id, adapter) in &self.exchanges {
+            match adapter.stop().await {
+                Ok(()) => tracing::info!("Stopped {} adapter", id),
+                Err(e) => tracing::warn!("Failed to stop {} adapter: {}", id, e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_adapter_covers_known_exchanges() {
+        for id in ExchangeId::KNOWN {
+            let adapter =
+                create_adapter(id, &Config::default()).expect("no adapter for registered exchange");
+            assert_eq!(adapter.id(), ExchangeId::from(*id));
+        }
+
+        assert!(create_adapter("binanace", &Config::default()).is_none());
+    }
+
+    #[tokio::test]
+    async fn stop_exchanges_closes_upstream_connections() {
+        use crypto_dash_binance::BinanceAdapter;
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_core::model::MarketType;
+        use crypto_dash_stream_hub::StreamHub;
+
+        // Upstream that reports when the adapter's connection ends
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (closed_tx, closed_rx) = tokio::sync::oneshot::channel();
+        tokio::spawn(async move {
+            use futures::StreamExt;
+
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let mut frames = Vec::new();
+            while let Some(Ok(frame)) = ws.next().await {
+                frames.push(frame);
+            }
+            closed_tx.send(frames).unwrap();
+        });
+
+        let hub = StreamHub::new().handle();
+        let cache = MemoryCache::new().handle();
+        let adapter = Arc::new(BinanceAdapter::new().with_ws_url(MarketType::Spot, url));
+        adapter.start(hub.clone(), cache.clone()).await.unwrap();
+        adapter.connect_markets(&[MarketType::Spot]).await.unwrap();
+        let mut state = AppState::new(hub, cache);
+        state.add_exchange(adapter.clone());
+
+        state.stop_exchanges().await;
+
+        assert!(!adapter.is_connected().await);
+        let frames = tokio::time::timeout(Duration::from_secs(5), closed_rx)
+            .await
+            .expect("upstream connection left open")
+            .unwrap();
+        assert!(frames.iter().any(|frame| frame.is_close()));
+    }
+
+    #[test]
+    fn duplicated_exchanges_create_one_adapter() {
+        let config = Config {
+            exchanges: ["binance", "bybit", "Binance ", "kraken", "binance"]
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            ..Config::default()
+        };
+
+        let ids: Vec<_> = create_adapters(&config)
+            .iter()
+            .map(|adapter| adapter.id())
+            .collect();
+        assert_eq!(
+            ids,
+            [ExchangeId::from("binance"), ExchangeId::from("bybit")]
+        );
+    }
+
+    #[tokio::test]
+    async fn rest_urls_route_catalog_and_candles_to_configured_host() {
+        use crate::routes::{get_candles, CandlesFormat, CandlesQuery};
+        use axum::extract::{Query, State};
+        use axum::{routing::get, Json};
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_core::model::MarketType;
+        use crypto_dash_stream_hub::StreamHub;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let hits = Arc::new(AtomicUsize::new(0));
+        let instruments_hits = hits.clone();
+        let kline_hits = hits.clone();
+        let router = axum::Router::new()
+            .route(
+                "/v5/market/instruments-info",
+                get(move || async move {
+                    instruments_hits.fetch_add(1, Ordering::SeqCst);
+                    Json(serde_json::json!({
+                        "result": {"list": [{
+                            "symbol": "ETHUSDT",
+                            "baseCoin": "ETH",
+                            "quoteCoin": "USDT",
+                            "priceFilter": {"tickSize": "0.01"},
+                            "lotSizeFilter": {"minOrderQty": "0.001", "qtyStep": "0.001"}
+                        }]}
+                    }))
+                }),
+            )
+            .route(
+                "/v5/market/kline",
+                get(move || async move {
+                    kline_hits.fetch_add(1, Ordering::SeqCst);
+                    Json(serde_json::json!({
+                        "retCode": 0,
+                        "retMsg": "OK",
+                        "result": {"list": [["1700000000000", "1", "2", "0.5", "1.5", "7"]]}
+                    }))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+
+        let rest_urls = RestUrls {
+            bybit_catalog: base_url.clone(),
+            bybit_candles: base_url,
+            ..RestUrls::default()
+        };
+        let state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle())
+            .with_rest_urls(rest_urls);
+
+        state.refresh_exchange_symbols("bybit").await.unwrap();
+        let symbols = state.get_symbol_meta(Some("bybit"), None).await;
+        assert!(symbols.iter().any(|meta| meta.symbol == "ETHUSDT"));
+
+        let query = CandlesQuery {
+            exchange: "bybit".to_string(),
+            symbol: "ETHUSDT".to_string(),
+            interval: "1m".to_string(),
+            limit: Some(1),
+            market_type: Some(MarketType::Spot),
+            format: CandlesFormat::Json,
+        };
+        let response = get_candles(State(state), Query(query)).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(response["candles"].as_array().unwrap().len(), 1);
+        assert_eq!(response["cached"], false);
+
+        // Spot, linear and inverse instrument lists, then the kline request
+        assert_eq!(hits.load(Ordering::SeqCst), 4);
+    }
 }
@@ -0,0 +1,112 @@
+use crate::state::AppState;
+use crypto_dash_core::model::{LifecycleEvent, StreamMessage, StreamStats};
+use crypto_dash_core::time::now;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+/// Running totals of hub lifecycle events, reported by `/api/stats`
+#[derive(Debug, Default)]
+pub struct LifecycleCounters {
+    client_connects: AtomicU64,
+    client_disconnects: AtomicU64,
+    subscribes: AtomicU64,
+    unsubscribes: AtomicU64,
+    upstream_connects: AtomicU64,
+    upstream_disconnects: AtomicU64,
+}
+
+/// Lifecycle event totals since startup
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LifecycleStats {
+    pub client_connects: u64,
+    pub client_disconnects: u64,
+    pub subscribes: u64,
+    pub unsubscribes: u64,
+    pub upstream_connects: u64,
+    pub upstream_disconnects: u64,
+}
+
+impl LifecycleCounters {
+    pub fn record(&self, event: &LifecycleEvent) {
+        let counter = match event {
+            LifecycleEvent::ClientConnected { .. } => &self.client_connects,
+            LifecycleEvent::ClientDisconnected { .. } => &self.client_disconnects,
+            LifecycleEvent::Subscribed { .. } => &self.subscribes,
+            LifecycleEvent::Unsubscribed { .. } => &self.unsubscribes,
+            LifecycleEvent::UpstreamConnected { .. } => &self.upstream_connects,
+            LifecycleEvent::UpstreamDisconnected { .. } => &self.upstream_disconnects,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> LifecycleStats {
+        LifecycleStats {
+            client_connects: self.client_connects.load(Ordering::Relaxed),
+            client_disconnects: self.client_disconnects.load(Ordering::Relaxed),
+            subscribes: self.subscribes.load(Ordering::Relaxed),
+            unsubscribes: self.unsubscribes.load(Ordering::Relaxed),
+            upstream_connects: self.upstream_connects.load(Ordering::Relaxed),
+            upstream_disconnects: self.upstream_disconnects.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Count hub lifecycle events into `state.lifecycle` until the hub is dropped
+pub fn spawn_lifecycle_counter(state: &AppState) -> JoinHandle<()> {
+    let mut events = state.hub.subscribe_events();
+    let counters = state.lifecycle.clone();
+    tokio::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(event) => counters.record(&event),
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("Lifecycle counters missed {} events", skipped)
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+/// Snapshot of connection and throughput stats
+pub async fn collect_stats(state: &AppState, messages_per_second: f64) -> StreamStats {
+    let mut connected_exchanges = Vec::new();
+    for adapter in state.exchanges.values() {
+        if adapter.is_connected().await {
+            connected_exchanges.push(adapter.id());
+        }
+    }
+    connected_exchanges.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
+    StreamStats {
+        timestamp: now(),
+        connected_exchanges,
+        active_topics: state.hub.topic_count(),
+        messages_per_second,
+    }
+}
+
+/// Broadcast a `stats` message every `interval` to sessions that opted in
+pub fn spawn_stats_broadcast(state: AppState, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        // The first tick completes immediately; skip it so the first rate covers a full interval
+        ticker.tick().await;
+        let mut last_published = state.hub.published_count();
+
+        loop {
+            ticker.tick().await;
+
+            let published = state.hub.published_count();
+            let rate = published.saturating_sub(last_published) as f64 / interval.as_secs_f64();
+            last_published = published;
+
+            let stats = collect_stats(&state, rate).await;
+            state.hub.broadcast(StreamMessage::Stats(stats));
+        }
+    })
+}
@@ -0,0 +1,107 @@
+use anyhow::{anyhow, Result};
+use crypto_dash_core::model::Channel;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Reference-counted registry of upstream exchange subscriptions shared by all clients
+pub struct SubscriptionRegistry {
+    limit: usize,
+    channels: Mutex<HashMap<Channel, usize>>,
+}
+
+impl SubscriptionRegistry {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            channels: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Add a client reference to a channel.
+    ///
+    /// Returns true if this is the first reference. Fails when the channel is not yet
+    /// subscribed upstream and the distinct subscription limit has been reached.
+    pub fn acquire(&self, channel: &Channel) -> Result<bool> {
+        let mut channels = self.channels.lock().unwrap();
+
+        if let Some(count) = channels.get_mut(channel) {
+            *count += 1;
+            return Ok(false);
+        }
+
+        if channels.len() >= self.limit {
+            return Err(anyhow!(
+                "Subscription limit reached ({} distinct upstream subscriptions)",
+                self.limit
+            ));
+        }
+
+        channels.insert(channel.clone(), 1);
+        Ok(true)
+    }
+
+    /// Drop a client reference to a channel, returning true if it was the last one
+    pub fn release(&self, channel: &Channel) -> bool {
+        let mut channels = self.channels.lock().unwrap();
+
+        match channels.get_mut(channel) {
+            Some(count) if *count > 1 => {
+                *count -= 1;
+                false
+            }
+            Some(_) => {
+                channels.remove(channel);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Number of distinct upstream subscriptions
+    pub fn active_count(&self) -> usize {
+        self.channels.lock().unwrap().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_dash_core::model::{ChannelType, ExchangeId, MarketType, Symbol};
+
+    fn ticker_channel(base: &str) -> Channel {
+        Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: ExchangeId::from("binance"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new(base, "USDT"),
+            depth: None,
+        }
+    }
+
+    #[test]
+    fn rejects_new_channels_beyond_limit() {
+        let registry = SubscriptionRegistry::new(2);
+
+        assert!(registry.acquire(&ticker_channel("BTC")).unwrap());
+        assert!(registry.acquire(&ticker_channel("ETH")).unwrap());
+        assert!(registry.acquire(&ticker_channel("SOL")).is_err());
+
+        // Existing channels still accept additional subscribers
+        assert!(!registry.acquire(&ticker_channel("BTC")).unwrap());
+        assert_eq!(registry.active_count(), 2);
+    }
+
+    #[test]
+    fn release_frees_slot_after_last_reference() {
+        let registry = SubscriptionRegistry::new(1);
+        let btc = ticker_channel("BTC");
+
+        registry.acquire(&btc).unwrap();
+        registry.acquire(&btc).unwrap();
+
+        assert!(!registry.release(&btc));
+        assert!(registry.release(&btc));
+        assert_eq!(registry.active_count(), 0);
+        assert!(registry.acquire(&ticker_channel("ETH")).unwrap());
+    }
+}
@@ -0,0 +1,153 @@
+use crate::state::AppState;
+use crypto_dash_core::model::ExchangeId;
+use reqwest::Client;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// How long a ping may take before the exchange counts as unreachable
+const PING_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UpstreamReachability {
+    pub reachable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+type ProbeResults = BTreeMap<String, UpstreamReachability>;
+
+/// Pings each exchange's REST API, reusing the last results for `ttl` so frequent
+/// readiness probes don't turn into a request per probe against the exchanges.
+#[derive(Clone)]
+pub struct UpstreamProbe {
+    ttl: Duration,
+    last: Arc<Mutex<Option<(Instant, ProbeResults)>>>,
+}
+
+impl UpstreamProbe {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            last: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Reachability of every enabled exchange that has a ping endpoint, keyed by exchange id
+    pub async fn check(&self, state: &AppState) -> ProbeResults {
+        // Held across the pings so concurrent probes share one round instead of each pinging
+        let mut last = self.last.lock().await;
+        if let Some((checked_at, results)) = &*last {
+            if checked_at.elapsed() < self.ttl {
+                return results.clone();
+            }
+        }
+
+        let pings = state.exchanges.keys().filter_map(|exchange_id| {
+            let url = ping_url(state, exchange_id)?;
+            let client = &state.http_client;
+            Some(async move { (exchange_id.clone(), ping(client, &url).await) })
+        });
+        let results: ProbeResults = futures::future::join_all(pings).await.into_iter().collect();
+
+        *last = Some((Instant::now(), results.clone()));
+        results
+    }
+}
+
+/// Cheapest public endpoint of each exchange; exchanges without one are not probed
+fn ping_url(state: &AppState, exchange_id: &str) -> Option<String> {
+    match exchange_id {
+        ExchangeId::BINANCE => Some(format!("{}/api/v3/ping", state.rest_urls.binance_catalog)),
+        ExchangeId::BYBIT => Some(format!("{}/v5/market/time", state.rest_urls.bybit_catalog)),
+        _ => None,
+    }
+}
+
+async fn ping(client: &Client, url: &str) -> UpstreamReachability {
+    let started = Instant::now();
+    let result = client
+        .get(url)
+        .timeout(PING_TIMEOUT)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+
+    match result {
+        Ok(_) => UpstreamReachability {
+            reachable: true,
+            latency_ms: Some(started.elapsed().as_millis() as u64),
+            error: None,
+        },
+        Err(e) => UpstreamReachability {
+            reachable: false,
+            latency_ms: None,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Json};
+    use crypto_dash_binance::BinanceAdapter;
+    use crypto_dash_bybit::BybitAdapter;
+    use crypto_dash_cache::MemoryCache;
+    use crypto_dash_core::config::RestUrls;
+    use crypto_dash_stream_hub::StreamHub;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn reports_reachable_and_unreachable_exchanges() {
+        let pings = Arc::new(AtomicUsize::new(0));
+        let ping_hits = pings.clone();
+        let router = axum::Router::new().route(
+            "/api/v3/ping",
+            get(move || async move {
+                ping_hits.fetch_add(1, Ordering::SeqCst);
+                Json(serde_json::json!({}))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let binance_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+
+        // Nothing listens on a port whose listener was dropped
+        let closed = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let bybit_url = format!("http://{}", closed.local_addr().unwrap());
+        drop(closed);
+
+        let rest_urls = RestUrls {
+            binance_catalog: binance_url,
+            bybit_catalog: bybit_url,
+            ..RestUrls::default()
+        };
+        let mut state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle())
+            .with_rest_urls(rest_urls);
+        state.add_exchange(Arc::new(BinanceAdapter::new()));
+        state.add_exchange(Arc::new(BybitAdapter::new()));
+
+        let probe = UpstreamProbe::new(Duration::from_secs(60));
+        let results = probe.check(&state).await;
+        assert!(results["binance"].reachable);
+        assert!(results["binance"].latency_ms.is_some());
+        assert!(!results["bybit"].reachable);
+        assert!(results["bybit"].error.is_some());
+
+        // Cached results are served without pinging again
+        assert_eq!(probe.check(&state).await, results);
+        assert_eq!(pings.load(Ordering::SeqCst), 1);
+
+        let uncached = UpstreamProbe::new(Duration::ZERO);
+        uncached.check(&state).await;
+        uncached.check(&state).await;
+        assert_eq!(pings.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,169 @@
+use crate::state::AppState;
+use crypto_dash_core::model::{Channel, ChannelType};
+use crypto_dash_exchanges_common::resolve_depth;
+use crypto_dash_stream_hub::Topic;
+use std::collections::HashMap;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// Subscribe `channels` upstream with no client attached so their data stays cached.
+///
+/// Each channel holds a subscription registry reference and a hub subscriber that is
+/// drained in the background, so neither client unsubscribes nor the adapters'
+/// no-subscriber disconnect tear the feed down. Returns the drain tasks.
+pub async fn spawn_cache_warmer(state: &AppState, channels: &[Channel]) -> Vec<JoinHandle<()>> {
+    let mut tasks = Vec::new();
+    let mut exchanges_channels: HashMap<String, Vec<Channel>> = HashMap::new();
+
+    for channel in channels {
+        let exchange_id = channel.exchange.as_str().to_string();
+        let Some(adapter) = state.exchanges.get(&exchange_id) else {
+            warn!(
+                "Not warming {}: exchange {} is not enabled",
+                channel.instrument(),
+                exchange_id
+            );
+            continue;
+        };
+
+        let mut channel = channel.clone();
+        if channel.channel_type == ChannelType::OrderBook {
+            match resolve_depth(channel.depth, adapter.supported_depths()) {
+                Ok(depth) => channel.depth = depth,
+                Err(e) => {
+                    warn!(
+                        "Not warming {} on {}: {}",
+                        channel.instrument(),
+                        exchange_id,
+                        e
+                    );
+                    continue;
+                }
+            }
+        }
+
+        if let Err(e) = state.subscriptions.acquire(&channel) {
+            warn!(
+                "Not warming {} on {}: {}",
+                channel.instrument(),
+                exchange_id,
+                e
+            );
+            continue;
+        }
+
+        let mut handle = match state.hub.subscribe(&Topic::from_channel(&channel)).await {
+            Ok(handle) => handle,
+            Err(e) => {
+                warn!(
+                    "Not warming {} on {}: {}",
+                    channel.instrument(),
+                    exchange_id,
+                    e
+                );
+                state.subscriptions.release(&channel);
+                continue;
+            }
+        };
+        tasks.push(tokio::spawn(async move {
+            // Only the cache needs the data; lagging behind is harmless
+            while let Ok(_) | Err(RecvError::Lagged(_)) = handle.recv().await {}
+        }));
+
+        exchanges_channels
+            .entry(exchange_id)
+            .or_default()
+            .push(channel);
+    }
+
+    for (exchange_id, channels) in exchanges_channels {
+        let adapter = &state.exchanges[&exchange_id];
+        match adapter.subscribe(&channels).await {
+            Ok(()) => info!("Warming {} {} channels", channels.len(), exchange_id),
+            Err(e) => warn!("Failed to warm {} channels: {}", exchange_id, e),
+        }
+    }
+
+    tasks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_dash_binance::BinanceAdapter;
+    use crypto_dash_cache::MemoryCache;
+    use crypto_dash_core::model::{ExchangeId, MarketType, Symbol};
+    use crypto_dash_exchanges_common::ExchangeAdapter;
+    use crypto_dash_stream_hub::StreamHub;
+    use futures::{SinkExt, StreamExt};
+    use rust_decimal::Decimal;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+    #[tokio::test]
+    async fn warmed_channels_stay_connected_without_clients() {
+        // Upstream acknowledges the subscribe, then streams two tickers
+        let upstream = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let upstream_url = format!("ws://{}", upstream.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = upstream.accept().await {
+                tokio::spawn(async move {
+                    let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                    while let Some(Ok(WsMessage::Text(text))) = ws.next().await {
+                        let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                        let ack = format!(r#"{{"result":null,"id":{}}}"#, request["id"]);
+                        ws.send(WsMessage::Text(ack)).await.unwrap();
+                        for last in ["50000.00", "50001.00"] {
+                            let frame = format!(
+                                r#"{{"stream":"btcusdt@ticker","data":{{"s":"BTCUSDT","c":"{}","b":"49999.00","a":"50002.00","E":1234567890}}}}"#,
+                                last
+                            );
+                            ws.send(WsMessage::Text(frame)).await.unwrap();
+                        }
+                    }
+                });
+            }
+        });
+
+        let hub = StreamHub::new().handle();
+        let cache = MemoryCache::new().handle();
+        let adapter = Arc::new(BinanceAdapter::new().with_ws_url(MarketType::Spot, upstream_url));
+        adapter.start(hub.clone(), cache.clone()).await.unwrap();
+        let mut state = AppState::new(hub.clone(), cache.clone());
+        state.add_exchange(adapter.clone());
+
+        let channel = Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: ExchangeId::from("binance"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        let tasks = spawn_cache_warmer(&state, std::slice::from_ref(&channel)).await;
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(state.subscriptions.active_count(), 1);
+
+        // The second ticker is handled only after the first one's disconnect check
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let ticker = cache
+                    .get_ticker(&channel.exchange, MarketType::Spot, &channel.symbol)
+                    .await;
+                if ticker.is_some_and(|t| t.last == Decimal::new(5000100, 2)) {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("warmed ticker never reached the cache");
+
+        assert_eq!(hub.global_subscriber_count(), 0);
+        assert_eq!(hub.subscriber_count(&Topic::from_channel(&channel)), 1);
+        assert!(adapter.is_market_connected(MarketType::Spot).await);
+
+        adapter.stop().await.unwrap();
+    }
+}
@@ -0,0 +1,111 @@
+use axum::extract::ws::{CloseFrame, Message};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Why the server ended a session; each maps to a close code clients can act on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseReason {
+    /// The server is shutting down and will not send further data
+    Shutdown,
+    /// The server entered maintenance and the drain grace period ran out
+    Drained,
+    /// The client sent messages faster than the per-session limit
+    RateLimited,
+    /// No frames arrived from the client within the idle timeout
+    Idle,
+}
+
+impl CloseReason {
+    pub fn code(self) -> u16 {
+        match self {
+            // 1001 Going Away
+            CloseReason::Shutdown | CloseReason::Drained => 1001,
+            // 1008 Policy Violation
+            CloseReason::RateLimited => 1008,
+            // Application-defined range starts at 4000
+            CloseReason::Idle => 4000,
+        }
+    }
+
+    pub fn reason(self) -> &'static str {
+        match self {
+            CloseReason::Shutdown => "server shutting down",
+            CloseReason::Drained => "server draining",
+            CloseReason::RateLimited => "rate limit exceeded",
+            CloseReason::Idle => "idle timeout",
+        }
+    }
+
+    /// Close frame announcing this reason
+    pub fn frame(self) -> Message {
+        Message::Close(Some(CloseFrame {
+            code: self.code(),
+            reason: self.reason().into(),
+        }))
+    }
+}
+
+/// Counts client messages per one-second window; a limit of zero disables it
+pub struct MessageRateLimiter {
+    limit: u32,
+    window_start: Instant,
+    count: u32,
+}
+
+impl MessageRateLimiter {
+    pub fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            window_start: Instant::now(),
+            count: 0,
+        }
+    }
+
+    /// Record one message, returning false once the current window's limit is exceeded
+    pub fn allow(&mut self, now: Instant) -> bool {
+        if self.limit == 0 {
+            return true;
+        }
+        if now.duration_since(self.window_start) >= Duration::from_secs(1) {
+            self.window_start = now;
+            self.count = 0;
+        }
+        self.count += 1;
+        self.count <= self.limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn close_reasons_have_distinct_codes() {
+        let reasons = [
+            CloseReason::Shutdown,
+            CloseReason::RateLimited,
+            CloseReason::Idle,
+        ];
+        let codes: Vec<u16> = reasons.iter().map(|reason| reason.code()).collect();
+        assert_eq!(codes, [1001, 1008, 4000]);
+
+        let Message::Close(Some(frame)) = CloseReason::Shutdown.frame() else {
+            panic!("expected a close frame");
+        };
+        assert_eq!(frame.code, 1001);
+        assert_eq!(frame.reason, "server shutting down");
+    }
+
+    #[test]
+    fn rate_limiter_resets_each_second() {
+        let mut limiter = MessageRateLimiter::new(2);
+        let start = Instant::now();
+        assert!(limiter.allow(start));
+        assert!(limiter.allow(start));
+        assert!(!limiter.allow(start));
+        assert!(limiter.allow(start + Duration::from_secs(1)));
+
+        let mut unlimited = MessageRateLimiter::new(0);
+        assert!((0..1_000).all(|_| unlimited.allow(start)));
+    }
+}
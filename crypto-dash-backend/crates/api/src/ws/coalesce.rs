@@ -0,0 +1,180 @@
+use crypto_dash_core::model::{ExchangeId, StreamMessage};
+use crypto_dash_stream_hub::Topic;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Status messages (`Info`/`Error`) are coalesced per exchange; market data is not
+pub fn is_status_message(message: &StreamMessage) -> bool {
+    matches!(
+        message,
+        StreamMessage::Info { .. } | StreamMessage::Error { .. }
+    )
+}
+
+/// Per-session limiter that sends at most one status message per exchange per window,
+/// keeping only the latest one that arrived while the window was closed
+pub struct StatusCoalescer {
+    window: Duration,
+    last_sent: HashMap<ExchangeId, Instant>,
+    pending: HashMap<ExchangeId, (Topic, StreamMessage)>,
+}
+
+impl StatusCoalescer {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            last_sent: HashMap::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Returns the message if it may be sent now, otherwise holds it as the exchange's latest
+    pub fn offer(
+        &mut self,
+        topic: Topic,
+        message: StreamMessage,
+        now: Instant,
+    ) -> Option<(Topic, StreamMessage)> {
+        if self.window.is_zero() || !is_status_message(&message) {
+            return Some((topic, message));
+        }
+
+        let exchange = topic.exchange.clone();
+        match self.last_sent.get(&exchange) {
+            Some(sent_at) if now.duration_since(*sent_at) < self.window => {
+                self.pending.insert(exchange, (topic, message));
+                None
+            }
+            _ => {
+                self.last_sent.insert(exchange, now);
+                Some((topic, message))
+            }
+        }
+    }
+
+    /// Held messages whose exchange window has elapsed
+    pub fn take_due(&mut self, now: Instant) -> Vec<(Topic, StreamMessage)> {
+        let due: Vec<ExchangeId> = self
+            .pending
+            .keys()
+            .filter(|exchange| {
+                self.last_sent
+                    .get(*exchange)
+                    .is_none_or(|sent_at| now.duration_since(*sent_at) >= self.window)
+            })
+            .cloned()
+            .collect();
+
+        due.into_iter()
+            .filter_map(|exchange| {
+                self.last_sent.insert(exchange.clone(), now);
+                self.pending.remove(&exchange)
+            })
+            .collect()
+    }
+
+    /// When the earliest held message becomes due, if any are held
+    pub fn next_due(&self) -> Option<Instant> {
+        self.pending
+            .keys()
+            .filter_map(|exchange| self.last_sent.get(exchange))
+            .map(|sent_at| *sent_at + self.window)
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_dash_core::model::{MarketType, Symbol, Ticker};
+    use crypto_dash_core::time::now;
+    use rust_decimal::Decimal;
+
+    fn topic(exchange: &str) -> Topic {
+        Topic::ticker(
+            ExchangeId::from(exchange),
+            MarketType::Spot,
+            Symbol::new("BTC", "USDT"),
+        )
+    }
+
+    fn info(message: &str) -> StreamMessage {
+        StreamMessage::Info {
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn rapid_status_changes_are_coalesced() {
+        let window = Duration::from_millis(100);
+        let mut coalescer = StatusCoalescer::new(window);
+        let start = Instant::now();
+
+        let mut delivered = Vec::new();
+        for i in 0..20 {
+            let at = start + Duration::from_millis(i * 2);
+            let status = info(&format!("status {}", i));
+            delivered.extend(coalescer.offer(topic("binance"), status, at));
+            delivered.extend(coalescer.take_due(at));
+        }
+        assert_eq!(delivered.len(), 1);
+
+        // Nothing is released before the window closes, then only the latest
+        let due = coalescer.next_due().unwrap();
+        assert_eq!(due, start + window);
+        assert!(coalescer
+            .take_due(due - Duration::from_millis(1))
+            .is_empty());
+        delivered.extend(coalescer.take_due(due));
+        assert!(coalescer.next_due().is_none());
+
+        let messages: Vec<_> = delivered
+            .iter()
+            .map(|(_, message)| match message {
+                StreamMessage::Info { message } => message.as_str(),
+                other => panic!("unexpected message {:?}", other),
+            })
+            .collect();
+        assert_eq!(messages, ["status 0", "status 19"]);
+    }
+
+    #[test]
+    fn exchanges_and_market_data_are_not_coalesced_together() {
+        let mut coalescer = StatusCoalescer::new(Duration::from_secs(1));
+        let at = Instant::now();
+
+        assert!(coalescer.offer(topic("binance"), info("a"), at).is_some());
+        assert!(coalescer.offer(topic("bybit"), info("b"), at).is_some());
+        assert!(coalescer.offer(topic("binance"), info("c"), at).is_none());
+
+        let ticker = StreamMessage::Ticker(Ticker {
+            timestamp: now(),
+            exchange: ExchangeId::from("binance"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            bid: Decimal::ONE,
+            ask: Decimal::ONE,
+            last: Decimal::ONE,
+            bid_size: Decimal::ONE,
+            ask_size: Decimal::ONE,
+            quote_volume_24h: None,
+            change_pct_24h: None,
+            tick_direction: None,
+            high_24h: None,
+            low_24h: None,
+            volume_24h: None,
+        });
+        for _ in 0..20 {
+            assert!(coalescer
+                .offer(topic("binance"), ticker.clone(), at)
+                .is_some());
+        }
+
+        // A zero window disables coalescing
+        let mut passthrough = StatusCoalescer::new(Duration::ZERO);
+        for _ in 0..5 {
+            assert!(passthrough.offer(topic("binance"), info("x"), at).is_some());
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crypto_dash_core::model::{StreamEnvelope, StreamMessage};
+use crypto_dash_core::time::{now, to_millis};
+use crypto_dash_stream_hub::Topic;
+use serde::Serialize;
+
+/// Per-session serializer for outbound stream messages.
+///
+/// Each message is written straight into a buffer sized from the previous one and that
+/// buffer becomes the outgoing text, so it is neither regrown from a small capacity nor
+/// copied on the way to the socket. The size hint is capped at `max_hint` bytes so one
+/// deep order book does not inflate every later allocation; a `max_hint` of zero falls
+/// back to `serde_json::to_string`.
+pub struct MessageEncoder {
+    size_hint: usize,
+    max_hint: usize,
+}
+
+impl MessageEncoder {
+    pub fn new(max_hint: usize) -> Self {
+        Self {
+            size_hint: 0,
+            max_hint,
+        }
+    }
+
+    /// Serialize a hub message for a session, wrapped in a `StreamEnvelope` if requested
+    pub fn encode(
+        &mut self,
+        topic: &Topic,
+        stream_msg: StreamMessage,
+        envelope: bool,
+    ) -> serde_json::Result<String> {
+        if envelope {
+            self.serialize(&StreamEnvelope {
+                server_ts: to_millis(now()),
+                topic: topic.to_string(),
+                data: stream_msg,
+            })
+        } else {
+            self.serialize(&stream_msg)
+        }
+    }
+
+    fn serialize<T: Serialize>(&mut self, value: &T) -> serde_json::Result<String> {
+        if self.max_hint == 0 {
+            return serde_json::to_string(value);
+        }
+
+        let mut buffer = Vec::with_capacity(self.size_hint);
+        serde_json::to_writer(&mut buffer, value)?;
+        self.size_hint = buffer.len().min(self.max_hint);
+        String::from_utf8(buffer).map_err(serde::ser::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_dash_core::model::{
+        ErrorCode, ExchangeId, MarketType, OrderBookSnapshot, PriceLevel, Symbol, Ticker,
+    };
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn buffered_output_matches_to_string() {
+        let symbol = Symbol::new("BTC", "USDT");
+        let topic = Topic::ticker(
+            ExchangeId::from("binance"),
+            MarketType::Spot,
+            symbol.clone(),
+        );
+        let levels = |start: i64| {
+            (0..200)
+                .map(|i| PriceLevel::new(Decimal::new(start + i, 2), Decimal::new(i + 1, 3)))
+                .collect::<Vec<_>>()
+        };
+        let messages = vec![
+            StreamMessage::Ticker(Ticker {
+                timestamp: now(),
+                exchange: ExchangeId::from("binance"),
+                market_type: MarketType::Spot,
+                symbol: symbol.clone(),
+                bid: Decimal::new(4325050, 2),
+                ask: Decimal::new(4325175, 2),
+                last: Decimal::new(4325100, 2),
+                bid_size: Decimal::ONE,
+                ask_size: Decimal::ONE,
+                quote_volume_24h: Some(Decimal::new(123456789, 1)),
+                change_pct_24h: None,
+                tick_direction: None,
+                high_24h: None,
+                low_24h: None,
+                volume_24h: None,
+            }),
+            // Larger than the retained capacity
+            StreamMessage::OrderBookSnapshot(OrderBookSnapshot {
+                timestamp: now(),
+                exchange: ExchangeId::from("binance"),
+                market_type: MarketType::Spot,
+                symbol,
+                bids: levels(4_000_000),
+                asks: levels(4_100_000),
+                checksum: None,
+            }),
+            StreamMessage::Info {
+                message: "caf\u{e9} \"quoted\"".to_string(),
+            },
+            StreamMessage::Error {
+                message: "timed out".to_string(),
+                code: Some(ErrorCode::SubscribeTimeout),
+            },
+        ];
+
+        let mut encoder = MessageEncoder::new(1024);
+        for message in messages {
+            let expected = serde_json::to_string(&message).unwrap();
+            assert_eq!(
+                encoder.encode(&topic, message.clone(), false).unwrap(),
+                expected
+            );
+            assert_eq!(encoder.size_hint, expected.len().min(1024));
+
+            let envelope = StreamEnvelope {
+                server_ts: 1_704_067_200_123,
+                topic: topic.to_string(),
+                data: message,
+            };
+            assert_eq!(
+                encoder.serialize(&envelope).unwrap(),
+                serde_json::to_string(&envelope).unwrap()
+            );
+        }
+    }
+}
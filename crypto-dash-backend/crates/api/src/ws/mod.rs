@@ -1,3 +1,8 @@
+pub mod close;
+pub mod coalesce;
+pub mod encode;
+pub mod ordering;
 pub mod server;
+pub mod session;
 
 pub use server::websocket_handler;
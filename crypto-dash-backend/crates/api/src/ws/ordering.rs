@@ -0,0 +1,273 @@
+use chrono::{DateTime, Utc};
+use crypto_dash_cache::CacheHandle;
+use crypto_dash_core::model::StreamMessage;
+use crypto_dash_stream_hub::Topic;
+use std::collections::HashMap;
+use tracing::debug;
+
+/// Per-session guard so a client never receives an order book delta before the snapshot
+/// it applies to. Deltas for a topic without a delivered snapshot (never sent, or lost to
+/// backpressure) trigger a resend of the cached snapshot first.
+pub struct BookSequencer {
+    enabled: bool,
+    /// Timestamp of the snapshot each topic's deltas currently build on
+    baselines: HashMap<Topic, DateTime<Utc>>,
+}
+
+impl BookSequencer {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            baselines: HashMap::new(),
+        }
+    }
+
+    /// Hub messages were dropped for this session, so any snapshot may be among them
+    pub fn messages_lost(&mut self) {
+        self.baselines.clear();
+    }
+
+    /// Messages to forward for a received hub message, in order
+    pub async fn admit(
+        &mut self,
+        topic: Topic,
+        message: StreamMessage,
+        cache: &CacheHandle,
+    ) -> Vec<(Topic, StreamMessage)> {
+        if !self.enabled {
+            return vec![(topic, message)];
+        }
+
+        match message {
+            StreamMessage::OrderBookSnapshot(snapshot) => {
+                self.baselines.insert(topic.clone(), snapshot.timestamp);
+                vec![(topic, StreamMessage::OrderBookSnapshot(snapshot))]
+            }
+            StreamMessage::OrderBookDelta(delta) => {
+                let mut ready = Vec::new();
+                if !self.baselines.contains_key(&topic) {
+                    let Some(snapshot) = cache
+                        .get_orderbook(&delta.exchange, delta.market_type, &delta.symbol)
+                        .await
+                    else {
+                        debug!("Dropping delta for {} until a snapshot is available", topic);
+                        return ready;
+                    };
+                    debug!("Resending snapshot for {} before its next delta", topic);
+                    self.baselines.insert(topic.clone(), snapshot.timestamp);
+                    ready.push((topic.clone(), StreamMessage::OrderBookSnapshot(snapshot)));
+                }
+
+                // Deltas already reflected in the snapshot the client holds are skipped
+                if self.baselines[&topic] < delta.timestamp {
+                    ready.push((topic, StreamMessage::OrderBookDelta(delta)));
+                }
+                ready
+            }
+            StreamMessage::OrderBookReset { .. } => {
+                // Deltas wait for the snapshot that follows the reset
+                self.baselines.remove(&topic);
+                vec![(topic, message)]
+            }
+            other => vec![(topic, other)],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crypto_dash_cache::MemoryCache;
+    use crypto_dash_core::model::{
+        ExchangeId, MarketType, OrderBookDelta, OrderBookSnapshot, PriceLevel, Symbol, Ticker,
+    };
+    use crypto_dash_core::time::now;
+    use crypto_dash_stream_hub::StreamHub;
+    use rust_decimal::Decimal;
+    use tokio::sync::broadcast::error::TryRecvError;
+
+    fn snapshot(timestamp: DateTime<Utc>, price: i64) -> OrderBookSnapshot {
+        OrderBookSnapshot {
+            timestamp,
+            exchange: ExchangeId::from("binance"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            bids: vec![PriceLevel::new(Decimal::new(price, 0), Decimal::ONE)],
+            asks: vec![PriceLevel::new(Decimal::new(price + 1, 0), Decimal::ONE)],
+            checksum: None,
+        }
+    }
+
+    fn delta(timestamp: DateTime<Utc>) -> OrderBookDelta {
+        OrderBookDelta {
+            timestamp,
+            exchange: ExchangeId::from("binance"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            bids_upserts: vec![PriceLevel::new(Decimal::new(99, 0), Decimal::TWO)],
+            asks_upserts: Vec::new(),
+            deletes: None,
+        }
+    }
+
+    fn ticker() -> Ticker {
+        Ticker {
+            timestamp: now(),
+            exchange: ExchangeId::from("binance"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("ETH", "USDT"),
+            bid: Decimal::ONE,
+            ask: Decimal::ONE,
+            last: Decimal::ONE,
+            bid_size: Decimal::ONE,
+            ask_size: Decimal::ONE,
+            quote_volume_24h: None,
+            change_pct_24h: None,
+            tick_direction: None,
+            high_24h: None,
+            low_24h: None,
+            volume_24h: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn dropped_snapshot_is_resent_before_next_delta() {
+        let hub = StreamHub::new().handle();
+        let cache = MemoryCache::new().handle();
+        let mut receiver = hub.subscribe_all().await;
+        let book_topic = Topic::orderbook(
+            ExchangeId::from("binance"),
+            MarketType::Spot,
+            Symbol::new("BTC", "USDT"),
+        );
+        let ticker_topic = Topic::ticker(
+            ExchangeId::from("binance"),
+            MarketType::Spot,
+            Symbol::new("ETH", "USDT"),
+        );
+
+        let start = now();
+        let first = snapshot(start, 100);
+        cache.set_orderbook(first.clone()).await;
+        hub.publish(&book_topic, StreamMessage::OrderBookSnapshot(first))
+            .await;
+
+        // Overflow the session's hub buffer so the snapshot is dropped
+        for _ in 0..1_100 {
+            hub.publish(&ticker_topic, StreamMessage::Ticker(ticker()))
+                .await;
+        }
+
+        let resync = snapshot(start + Duration::seconds(2), 200);
+        cache.set_orderbook(resync.clone()).await;
+        let stale = delta(start + Duration::seconds(1));
+        let fresh = delta(start + Duration::seconds(3));
+        hub.publish(&book_topic, StreamMessage::OrderBookDelta(stale))
+            .await;
+        hub.publish(&book_topic, StreamMessage::OrderBookDelta(fresh.clone()))
+            .await;
+
+        let mut sequencer = BookSequencer::new(true);
+        let mut forwarded = Vec::new();
+        let mut lagged = false;
+        loop {
+            match receiver.receiver.try_recv() {
+                Ok((topic, message)) => {
+                    forwarded.extend(sequencer.admit(topic, message, &cache).await);
+                }
+                Err(TryRecvError::Lagged(_)) => {
+                    lagged = true;
+                    sequencer.messages_lost();
+                }
+                Err(_) => break,
+            }
+        }
+        assert!(lagged, "hub buffer was not overflowed");
+
+        let book: Vec<_> = forwarded
+            .into_iter()
+            .filter(|(topic, _)| *topic == book_topic)
+            .map(|(_, message)| message)
+            .collect();
+        assert_eq!(book.len(), 2);
+        match &book[0] {
+            StreamMessage::OrderBookSnapshot(snapshot) => {
+                assert_eq!(snapshot.timestamp, resync.timestamp)
+            }
+            other => panic!("expected a resync snapshot first, got {:?}", other),
+        }
+        match &book[1] {
+            StreamMessage::OrderBookDelta(delta) => assert_eq!(delta.timestamp, fresh.timestamp),
+            other => panic!("expected the fresh delta, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn deltas_without_a_snapshot_are_held_back() {
+        let cache = MemoryCache::new().handle();
+        let topic = Topic::orderbook(
+            ExchangeId::from("binance"),
+            MarketType::Spot,
+            Symbol::new("BTC", "USDT"),
+        );
+
+        let mut sequencer = BookSequencer::new(true);
+        let message = StreamMessage::OrderBookDelta(delta(now()));
+        assert!(sequencer
+            .admit(topic.clone(), message.clone(), &cache)
+            .await
+            .is_empty());
+
+        // Disabled sequencing forwards deltas untouched
+        let mut passthrough = BookSequencer::new(false);
+        assert_eq!(passthrough.admit(topic, message, &cache).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn reset_holds_deltas_until_the_next_snapshot() {
+        let cache = MemoryCache::new().handle();
+        let topic = Topic::orderbook(
+            ExchangeId::from("binance"),
+            MarketType::Spot,
+            Symbol::new("BTC", "USDT"),
+        );
+        let mut sequencer = BookSequencer::new(true);
+        let start = now();
+        sequencer
+            .admit(
+                topic.clone(),
+                StreamMessage::OrderBookSnapshot(snapshot(start, 100)),
+                &cache,
+            )
+            .await;
+
+        let reset = StreamMessage::OrderBookReset {
+            exchange: ExchangeId::from("binance"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+        };
+        let forwarded = sequencer.admit(topic.clone(), reset, &cache).await;
+        assert!(matches!(
+            forwarded[..],
+            [(_, StreamMessage::OrderBookReset { .. })]
+        ));
+
+        // The pre-reset book no longer counts as a baseline
+        let early = StreamMessage::OrderBookDelta(delta(start + Duration::seconds(1)));
+        assert!(sequencer
+            .admit(topic.clone(), early, &cache)
+            .await
+            .is_empty());
+
+        sequencer
+            .admit(
+                topic.clone(),
+                StreamMessage::OrderBookSnapshot(snapshot(start + Duration::seconds(2), 200)),
+                &cache,
+            )
+            .await;
+        let next = StreamMessage::OrderBookDelta(delta(start + Duration::seconds(3)));
+        assert_eq!(sequencer.admit(topic, next, &cache).await.len(), 1);
+    }
+}
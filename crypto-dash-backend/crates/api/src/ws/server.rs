@@ -1,28 +1,70 @@
 use crate::state::AppState;
+use crate::ws::close::{CloseReason, MessageRateLimiter};
+use crate::ws::coalesce::StatusCoalescer;
+use crate::ws::encode::MessageEncoder;
+use crate::ws::ordering::BookSequencer;
+use crate::ws::session::SessionChannels;
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
-        State,
+        Query, State,
     },
-    response::Response,
+    http::StatusCode,
+    response::{IntoResponse, Response},
 };
-use crypto_dash_core::model::{ClientMessage, StreamMessage};
+use crypto_dash_core::model::{
+    Channel, ChannelType, ClientMessage, ErrorCode, LifecycleEvent, StreamMessage,
+};
+use crypto_dash_exchanges_common::resolve_depth;
+use crypto_dash_stream_hub::Topic;
 use futures::{sink::SinkExt, stream::StreamExt};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+/// Exchanges one subscribe request contacts at the same time
+const MAX_CONCURRENT_SUBSCRIBES: usize = 4;
+
+/// Query parameters accepted on the WebSocket upgrade request
+#[derive(Debug, Deserialize)]
+pub struct ConnectParams {
+    /// Subscribe to the server's default channels on connect; `?defaults=false` opts out
+    #[serde(default = "default_true")]
+    pub defaults: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
 /// WebSocket upgrade handler
-pub async fn websocket_handler(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
+pub async fn websocket_handler(
+    ws: WebSocketUpgrade,
+    Query(params): Query<ConnectParams>,
+    State(state): State<AppState>,
+) -> Response {
     info!("WebSocket upgrade request received");
-    ws.on_upgrade(|socket| handle_socket(socket, state))
+    if state.maintenance.is_enabled() {
+        warn!("Refusing WebSocket upgrade during maintenance");
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    }
+    ws.on_upgrade(move |socket| handle_socket(socket, state, params.defaults))
 }
 
 /// Handle individual WebSocket connection
-async fn handle_socket(socket: WebSocket, state: AppState) {
+async fn handle_socket(socket: WebSocket, state: AppState, subscribe_defaults: bool) {
     let session_id = Uuid::new_v4();
     info!("New WebSocket connection: {}", session_id);
+    state.hub.emit_event(LifecycleEvent::ClientConnected {
+        session_id: session_id.to_string(),
+    });
 
     let (sender, mut receiver) = socket.split();
     let sender = Arc::new(Mutex::new(sender));
@@ -42,50 +84,204 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
 
     // Create a subscriber for stream hub messages
     let mut stream_receiver = state.hub.subscribe_all().await;
+    // Channels this session holds a reference to in the subscription registry; the
+    // forward task drops messages on topics the session has not subscribed to
+    let session_channels = Arc::new(SessionChannels::new());
+    // Set once the session subscribes with `envelope: true`
+    let envelope = Arc::new(AtomicBool::new(false));
 
     // Spawn a task to forward stream hub messages to the WebSocket
     let ws_sender = Arc::clone(&sender);
+    let forward_envelope = Arc::clone(&envelope);
+    let forward_channels = Arc::clone(&session_channels);
+    let status_window = state.status_coalesce_window;
+    let book_resync = state.book_resync;
+    let cache = state.cache.clone();
+    let serialize_buffer_bytes = state.serialize_buffer_bytes;
     let forward_task = tokio::spawn(async move {
-        loop {
-            match stream_receiver.recv().await {
-                Ok((topic, stream_msg)) => {
-                    debug!("Forwarding stream message for topic: {:?}", topic);
-                    if let Ok(msg_text) = serde_json::to_string(&stream_msg) {
-                        let mut sender_guard = ws_sender.lock().await;
-                        if sender_guard.send(Message::Text(msg_text)).await.is_err() {
-                            debug!("Failed to forward stream message - client disconnected");
-                            break;
+        // Info/Error bursts (e.g. reconnect storms) are coalesced; market data is not
+        let mut coalescer = StatusCoalescer::new(status_window);
+        // Order book deltas only follow a snapshot this session has received
+        let mut sequencer = BookSequencer::new(book_resync);
+        let mut encoder = MessageEncoder::new(serialize_buffer_bytes);
+        'forward: loop {
+            let due = coalescer.next_due();
+            let flush_at = due.unwrap_or_else(Instant::now);
+            let ready = tokio::select! {
+                received = stream_receiver.recv() => match received {
+                    Ok((topic, _)) if !forward_channels.wants(&topic) => Vec::new(),
+                    Ok((topic, stream_msg)) => {
+                        let now = Instant::now();
+                        sequencer
+                            .admit(topic, stream_msg, &cache)
+                            .await
+                            .into_iter()
+                            .filter_map(|(topic, msg)| coalescer.offer(topic, msg, now))
+                            .collect()
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("Session {} lagged, dropped {} messages", session_id, skipped);
+                        sequencer.messages_lost();
+                        // The connection is fine; tell the client about the gap and keep going
+                        let notice = StreamMessage::Info {
+                            message: format!(
+                                "Skipped {} messages because this session fell behind",
+                                skipped
+                            ),
+                        };
+                        if let Ok(msg_text) = serde_json::to_string(&notice) {
+                            let mut sender_guard = ws_sender.lock().await;
+                            if sender_guard.send(Message::Text(msg_text)).await.is_err() {
+                                debug!("Failed to report lag - client disconnected");
+                                break 'forward;
+                            }
                         }
+                        Vec::new()
                     }
+                    Err(RecvError::Closed) => {
+                        error!("Stream hub closed, ending session {}", session_id);
+                        let _ = ws_sender.lock().await.send(CloseReason::Shutdown.frame()).await;
+                        break;
+                    }
+                },
+                _ = tokio::time::sleep_until(flush_at), if due.is_some() => {
+                    coalescer.take_due(Instant::now())
                 }
-                Err(e) => {
-                    error!("Error receiving from stream hub: {}", e);
-                    break;
+            };
+
+            for (topic, stream_msg) in ready {
+                debug!("Forwarding stream message for topic: {:?}", topic);
+                let wrap = forward_envelope.load(Ordering::Relaxed);
+                if let Ok(msg_text) = encoder.encode(&topic, stream_msg, wrap) {
+                    let mut sender_guard = ws_sender.lock().await;
+                    if sender_guard.send(Message::Text(msg_text)).await.is_err() {
+                        debug!("Failed to forward stream message - client disconnected");
+                        break 'forward;
+                    }
                 }
             }
         }
     });
 
-    // Handle incoming messages
-    while let Some(msg) = receiver.next().await {
+    // Forwards periodic stats once the session opts in
+    let mut stats_task: Option<JoinHandle<()>> = None;
+
+    // Part of the welcome flow: default channels are confirmed before any client message
+    if subscribe_defaults && !state.default_channels.is_empty() {
+        let defaults = ClientMessage::Subscribe {
+            channels: state.default_channels.clone(),
+            stats: false,
+            envelope: false,
+        };
+        if let Err(e) = handle_client_message(
+            defaults,
+            &state,
+            &sender,
+            &session_channels,
+            &mut stats_task,
+            &envelope,
+            session_id,
+        )
+        .await
+        {
+            error!(
+                "Error subscribing {} to default channels: {}",
+                session_id, e
+            );
+        }
+    }
+
+    let mut rate_limiter = MessageRateLimiter::new(state.client_message_rate_limit);
+    let mut maintenance = state.maintenance.subscribe();
+    let mut shutdown = state.subscribe_shutdown();
+    // Sessions upgraded after shutdown began close on the first pass
+    shutdown.mark_changed();
+    let drain_grace = state.maintenance.drain_grace();
+    // Set once maintenance starts draining this session
+    let mut drain_at: Option<Instant> = None;
+
+    // Handle incoming messages until the client leaves or the server closes the session
+    let close_reason = loop {
+        let received = async {
+            if state.idle_timeout.is_zero() {
+                Ok(receiver.next().await)
+            } else {
+                tokio::time::timeout(state.idle_timeout, receiver.next()).await
+            }
+        };
+        let next = tokio::select! {
+            received = received => match received {
+                Ok(next) => next,
+                Err(_) => break Some(CloseReason::Idle),
+            },
+            Ok(()) = maintenance.changed(), if drain_at.is_none() && !drain_grace.is_zero() => {
+                if !*maintenance.borrow_and_update() {
+                    continue;
+                }
+                info!("Draining session {} for maintenance", session_id);
+                drain_at = Some(Instant::now() + drain_grace);
+                let notice = StreamMessage::Info {
+                    message: format!(
+                        "server draining, reconnect in {}s",
+                        drain_grace.as_millis().div_ceil(1000)
+                    ),
+                };
+                if let Ok(msg_text) = serde_json::to_string(&notice) {
+                    if sender.lock().await.send(Message::Text(msg_text)).await.is_err() {
+                        break None;
+                    }
+                }
+                continue;
+            }
+            _ = tokio::time::sleep_until(drain_at.unwrap_or_else(Instant::now)), if drain_at.is_some() => {
+                break Some(CloseReason::Drained);
+            }
+            Ok(()) = shutdown.changed() => {
+                if *shutdown.borrow_and_update() {
+                    break Some(CloseReason::Shutdown);
+                }
+                continue;
+            }
+        };
+        let Some(msg) = next else {
+            break None;
+        };
+        if matches!(msg, Ok(Message::Text(_)) | Ok(Message::Binary(_)))
+            && !rate_limiter.allow(Instant::now())
+        {
+            break Some(CloseReason::RateLimited);
+        }
+
         match msg {
             Ok(Message::Text(text)) => {
                 debug!("Received text message from {}: {}", session_id, text);
 
-                match serde_json::from_str::<ClientMessage>(&text) {
-                    Ok(client_msg) => {
+                match parse_client_message(&text) {
+                    Ok(mut client_msg) => {
+                        apply_implied_market_types(&text, &mut client_msg, &state).await;
                         debug!("Successfully parsed client message: {:?}", client_msg);
-                        if let Err(e) = handle_client_message(client_msg, &state, &sender).await {
+                        if let Err(e) = handle_client_message(
+                            client_msg,
+                            &state,
+                            &sender,
+                            &session_channels,
+                            &mut stats_task,
+                            &envelope,
+                            session_id,
+                        )
+                        .await
+                        {
                             error!("Error handling client message: {}", e);
                         }
                     }
-                    Err(e) => {
+                    Err(message) => {
                         warn!(
-                            "Invalid message format from {}: {} - Raw: {}",
-                            session_id, e, text
+                            "Rejected message from {}: {} - Raw: {}",
+                            session_id, message, text
                         );
                         let error_msg = StreamMessage::Error {
-                            message: format!("Invalid message format: {}", e),
+                            message,
+                            code: None,
                         };
 
                         if let Ok(msg_text) = serde_json::to_string(&error_msg) {
@@ -97,13 +293,13 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
             }
             Ok(Message::Close(_)) => {
                 info!("WebSocket connection closed: {}", session_id);
-                break;
+                break None;
             }
             Ok(Message::Ping(ping)) => {
                 debug!("Received ping from {}", session_id);
                 let mut sender_guard = sender.lock().await;
                 if sender_guard.send(Message::Pong(ping)).await.is_err() {
-                    break;
+                    break None;
                 }
             }
             Ok(Message::Pong(_)) => {
@@ -114,26 +310,165 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
             }
             Err(e) => {
                 error!("WebSocket error for {}: {}", session_id, e);
-                break;
+                break None;
             }
         }
-    }
+    };
 
-    // Cancel the forwarding task when WebSocket disconnects
+    // Cancel the forwarding task when WebSocket disconnects, before any close frame so
+    // nothing is sent after it
     forward_task.abort();
+    if let Some(stats_task) = stats_task {
+        stats_task.abort();
+    }
+
+    if let Some(reason) = close_reason {
+        warn!(
+            "Closing session {} ({}): {}",
+            session_id,
+            reason.code(),
+            reason.reason()
+        );
+        let _ = sender.lock().await.send(reason.frame()).await;
+    }
+    release_session_channels(&state, session_channels.take()).await;
+    state.hub.emit_event(LifecycleEvent::ClientDisconnected {
+        session_id: session_id.to_string(),
+    });
     info!("WebSocket connection ended: {}", session_id);
 }
 
+/// Forward hub broadcasts (periodic stats) to a session
+fn spawn_stats_forwarder(
+    state: &AppState,
+    sender: &Arc<Mutex<futures::stream::SplitSink<WebSocket, Message>>>,
+) -> JoinHandle<()> {
+    let mut stats_receiver = state.hub.subscribe_broadcasts();
+    let ws_sender = Arc::clone(sender);
+
+    tokio::spawn(async move {
+        loop {
+            match stats_receiver.recv().await {
+                Ok(stats_msg) => {
+                    if let Ok(msg_text) = serde_json::to_string(&stats_msg) {
+                        let mut sender_guard = ws_sender.lock().await;
+                        if sender_guard.send(Message::Text(msg_text)).await.is_err() {
+                            debug!("Failed to forward stats - client disconnected");
+                            break;
+                        }
+                    }
+                }
+                Err(e) => {
+                    error!("Error receiving stats broadcast: {}", e);
+                    break;
+                }
+            }
+        }
+    })
+}
+
+/// Parse a client message, describing why it was rejected on failure
+fn parse_client_message(text: &str) -> Result<ClientMessage, String> {
+    match serde_json::from_str::<ClientMessage>(text) {
+        Ok(ClientMessage::Unknown) => {
+            let op = serde_json::from_str::<serde_json::Value>(text)
+                .ok()
+                .and_then(|value| value.get("op").map(|op| op.to_string()))
+                .unwrap_or_default();
+            Err(format!("Unsupported op: {}", op.trim_matches('"')))
+        }
+        Ok(message) => Ok(message),
+        Err(e) => Err(format!("Invalid message format: {}", e)),
+    }
+}
+
+/// Move channels sent without `market_type` to perpetual when the catalog lists their
+/// symbol only as a perpetual; older clients omit the field, which otherwise means spot
+async fn apply_implied_market_types(text: &str, message: &mut ClientMessage, state: &AppState) {
+    let channels = match message {
+        ClientMessage::Subscribe { channels, .. } | ClientMessage::Unsubscribe { channels } => {
+            channels
+        }
+        _ => return,
+    };
+    let Ok(raw) = serde_json::from_str::<serde_json::Value>(text) else {
+        return;
+    };
+    let Some(raw_channels) = raw.get("channels").and_then(|value| value.as_array()) else {
+        return;
+    };
+
+    for (channel, raw_channel) in channels.iter_mut().zip(raw_channels) {
+        if raw_channel.get("market_type").is_some() {
+            continue;
+        }
+        if let Some(market_type) = state
+            .symbol_catalog
+            .implied_market_type(channel.exchange.as_str(), &channel.symbol)
+            .await
+        {
+            warn!(
+                "Channel for {} on {} has no market_type and is not listed on spot; using {:?}",
+                channel.symbol.canonical(),
+                channel.exchange.as_str(),
+                market_type
+            );
+            channel.market_type = market_type;
+        }
+    }
+}
+
+/// Drop a closed session's channel references and unsubscribe channels nobody else uses
+async fn release_session_channels(state: &AppState, session_channels: HashSet<Channel>) {
+    let mut unused: HashMap<String, Vec<Channel>> = HashMap::new();
+    for channel in session_channels {
+        if state.subscriptions.release(&channel) {
+            unused
+                .entry(channel.exchange.as_str().to_string())
+                .or_default()
+                .push(channel);
+        }
+    }
+
+    for (exchange_id, exchange_channels) in unused {
+        if let Some(adapter) = state.exchanges.get(&exchange_id) {
+            if let Err(e) = adapter.unsubscribe(&exchange_channels).await {
+                error!(
+                    "Failed to release {} channels on {}: {}",
+                    exchange_channels.len(),
+                    exchange_id,
+                    e
+                );
+            }
+        }
+    }
+}
+
 /// Handle client messages
 async fn handle_client_message(
     message: ClientMessage,
     state: &AppState,
     sender: &Arc<Mutex<futures::stream::SplitSink<WebSocket, Message>>>,
+    session_channels: &SessionChannels,
+    stats_task: &mut Option<JoinHandle<()>>,
+    envelope: &AtomicBool,
+    session_id: Uuid,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     match message {
-        ClientMessage::Subscribe { channels } => {
+        ClientMessage::Subscribe {
+            channels,
+            stats,
+            envelope: wrap,
+        } => {
             debug!("Subscribe request for {} channels", channels.len());
 
+            if wrap {
+                envelope.store(true, Ordering::Relaxed);
+            }
+            if stats && stats_task.is_none() {
+                *stats_task = Some(spawn_stats_forwarder(state, sender));
+            }
+
             // Debug: Log the available exchanges
             debug!(
                 "Available exchanges: {:?}",
@@ -148,61 +483,184 @@ async fn handle_client_message(
                     "Processing channel for exchange: '{}' (channel: {:?})",
                     exchange_id, channel
                 );
+
+                let mut channel = channel.clone();
+                if channel.channel_type == ChannelType::OrderBook {
+                    if let Some(adapter) = state.exchanges.get(&exchange_id) {
+                        match resolve_depth(channel.depth, adapter.supported_depths()) {
+                            Ok(depth) => channel.depth = depth,
+                            Err(e) => {
+                                warn!("Rejected order book channel on {}: {}", exchange_id, e);
+                                let error_msg = StreamMessage::Error {
+                                    message: format!(
+                                        "{} {}: {}",
+                                        exchange_id,
+                                        channel.instrument(),
+                                        e
+                                    ),
+                                    code: None,
+                                };
+                                let msg_text = serde_json::to_string(&error_msg)?;
+                                let mut sender_guard = sender.lock().await;
+                                sender_guard.send(Message::Text(msg_text)).await?;
+                                continue;
+                            }
+                        }
+                    }
+                }
+
+                if state.exchanges.contains_key(&exchange_id)
+                    && !session_channels.contains(&channel)
+                {
+                    // Open the topic first so the hub's topic limit is enforced before
+                    // anything is asked of the exchange
+                    let subscription = match state
+                        .hub
+                        .subscribe(&Topic::from_channel(&channel))
+                        .await
+                    {
+                        Ok(subscription) => subscription,
+                        Err(e) => {
+                            warn!(
+                                "Rejected channel {} on {}: {}",
+                                channel.instrument(),
+                                exchange_id,
+                                e
+                            );
+                            let error_msg = StreamMessage::Error {
+                                message: format!("{} {}: {}", exchange_id, channel.instrument(), e),
+                                code: Some(ErrorCode::TopicLimit),
+                            };
+                            let msg_text = serde_json::to_string(&error_msg)?;
+                            let mut sender_guard = sender.lock().await;
+                            sender_guard.send(Message::Text(msg_text)).await?;
+                            continue;
+                        }
+                    };
+                    if let Err(e) = state.subscriptions.acquire(&channel) {
+                        warn!(
+                            "Rejected channel {} on {}: {}",
+                            channel.instrument(),
+                            exchange_id,
+                            e
+                        );
+                        let error_msg = StreamMessage::Error {
+                            message: format!("{} {}: {}", exchange_id, channel.instrument(), e),
+                            code: None,
+                        };
+                        let msg_text = serde_json::to_string(&error_msg)?;
+                        let mut sender_guard = sender.lock().await;
+                        sender_guard.send(Message::Text(msg_text)).await?;
+                        continue;
+                    }
+                    session_channels.insert(channel.clone(), subscription);
+                    state.hub.emit_event(LifecycleEvent::Subscribed {
+                        session_id: session_id.to_string(),
+                        channel: channel.clone(),
+                    });
+                }
+
                 exchanges_channels
                     .entry(exchange_id)
                     .or_insert_with(Vec::new)
-                    .push(channel.clone());
+                    .push(channel);
             }
 
             let num_exchanges = exchanges_channels.len();
             debug!(
-                "Grouped into {} exchanges: {:?}",
+                "Grouped into {} exchanges: {:?} ({} distinct upstream subscriptions active)",
                 num_exchanges,
-                exchanges_channels.keys().collect::<Vec<_>>()
+                exchanges_channels.keys().collect::<Vec<_>>(),
+                state.subscriptions.active_count()
             );
 
-            // Subscribe to each exchange
-            for (exchange_id, exchange_channels) in &exchanges_channels {
+            // Subscribe on each exchange concurrently so a slow one does not hold up the rest
+            let mut subscribes = Vec::new();
+            let mut failed_exchanges = Vec::new();
+            for (exchange_id, exchange_channels) in exchanges_channels {
                 debug!("Looking up exchange adapter for: '{}'", exchange_id);
-                if let Some(adapter) = state.exchanges.get(exchange_id) {
-                    debug!(
-                        "Found adapter for '{}', subscribing to {} channels",
-                        exchange_id,
-                        exchange_channels.len()
-                    );
-                    match adapter.subscribe(exchange_channels).await {
-                        Ok(()) => {
-                            info!(
-                                "Successfully subscribed to {} channels on {}",
-                                exchange_channels.len(),
-                                exchange_id
-                            );
-                        }
-                        Err(e) => {
-                            error!(
-                                "Failed to subscribe to {} channels on {}: {}",
-                                exchange_channels.len(),
-                                exchange_id,
-                                e
-                            );
-                        }
-                    }
-                } else {
+                let Some(adapter) = state.exchanges.get(&exchange_id).cloned() else {
                     warn!(
                         "Unknown exchange: '{}' (available: {:?})",
                         exchange_id,
                         state.exchanges.keys().collect::<Vec<_>>()
                     );
+                    failed_exchanges.push(exchange_id);
+                    continue;
+                };
+                debug!(
+                    "Found adapter for '{}', subscribing to {} channels",
+                    exchange_id,
+                    exchange_channels.len()
+                );
+                subscribes.push(async move {
+                    let result = adapter.subscribe(&exchange_channels).await;
+                    (exchange_id, adapter, exchange_channels, result)
+                });
+            }
+            let results: Vec<_> = futures::stream::iter(subscribes)
+                .buffer_unordered(MAX_CONCURRENT_SUBSCRIBES)
+                .collect()
+                .await;
+
+            let mut subscribed_channels = 0;
+            let mut subscribed_exchanges = 0;
+            for (exchange_id, adapter, exchange_channels, result) in results {
+                let count = exchange_channels.len();
+                match result {
+                    Ok(()) => {
+                        subscribed_channels += count;
+                        subscribed_exchanges += 1;
+                        info!(
+                            "Successfully subscribed to {} channels on {}",
+                            count, exchange_id
+                        );
+                        // Tell the client the granularity each book actually streams at
+                        for channel in exchange_channels
+                            .iter()
+                            .filter(|channel| channel.channel_type == ChannelType::OrderBook)
+                        {
+                            let (depth, update_speed) =
+                                adapter.orderbook_stream(channel.market_type, channel.depth);
+                            let ack = StreamMessage::OrderBookSubscribed {
+                                exchange: channel.exchange.clone(),
+                                market_type: channel.market_type,
+                                symbol: channel.symbol.clone(),
+                                depth,
+                                update_speed,
+                            };
+                            let msg_text = serde_json::to_string(&ack)?;
+                            let mut sender_guard = sender.lock().await;
+                            sender_guard.send(Message::Text(msg_text)).await?;
+                        }
+                    }
+                    Err(e) => {
+                        error!(
+                            "Failed to subscribe to {} channels on {}: {}",
+                            count, exchange_id, e
+                        );
+                        // e.g. a reconnect budget running out; let the client retry later
+                        let error_msg = StreamMessage::Error {
+                            message: format!("{}: {}", exchange_id, e),
+                            code: None,
+                        };
+                        let msg_text = serde_json::to_string(&error_msg)?;
+                        let mut sender_guard = sender.lock().await;
+                        sender_guard.send(Message::Text(msg_text)).await?;
+                        failed_exchanges.push(exchange_id);
+                    }
                 }
             }
 
-            let response = StreamMessage::Info {
-                message: format!(
-                    "Subscribed to {} channels across {} exchanges",
-                    channels.len(),
-                    num_exchanges
-                ),
-            };
+            let mut message = format!(
+                "Subscribed to {} channels across {} exchanges",
+                subscribed_channels, subscribed_exchanges
+            );
+            if !failed_exchanges.is_empty() {
+                failed_exchanges.sort();
+                message.push_str(&format!("; failed on {}", failed_exchanges.join(", ")));
+            }
+            let response = StreamMessage::Info { message };
 
             let msg_text = serde_json::to_string(&response)?;
             let mut sender_guard = sender.lock().await;
@@ -211,14 +669,36 @@ async fn handle_client_message(
         ClientMessage::Unsubscribe { channels } => {
             debug!("Unsubscribe request for {} channels", channels.len());
 
-            // Group channels by exchange
+            // Group channels no other session still references by exchange
             let mut exchanges_channels = std::collections::HashMap::new();
             for channel in &channels {
+                // Match the depth the channel was registered with on subscribe
+                let mut channel = channel.clone();
+                if channel.channel_type == ChannelType::OrderBook {
+                    if let Some(adapter) = state.exchanges.get(channel.exchange.as_str()) {
+                        if let Ok(depth) = resolve_depth(channel.depth, adapter.supported_depths())
+                        {
+                            channel.depth = depth;
+                        }
+                    }
+                }
+
+                if !session_channels.remove(&channel) {
+                    continue;
+                }
+                state.hub.emit_event(LifecycleEvent::Unsubscribed {
+                    session_id: session_id.to_string(),
+                    channel: channel.clone(),
+                });
+                if !state.subscriptions.release(&channel) {
+                    continue;
+                }
+
                 let exchange_id = channel.exchange.as_str().to_string();
                 exchanges_channels
                     .entry(exchange_id)
                     .or_insert_with(Vec::new)
-                    .push(channel.clone());
+                    .push(channel);
             }
 
             // Unsubscribe from each exchange
@@ -261,7 +741,926 @@ async fn handle_client_message(
             let mut sender_guard = sender.lock().await;
             sender_guard.send(Message::Text(msg_text)).await?;
         }
+        ClientMessage::Unknown => {
+            // Rejected in parse_client_message before reaching the handler
+            warn!("Ignoring unsupported client op");
+        }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+    type Client = tokio_tungstenite::WebSocketStream<
+        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+    >;
+
+    /// Serves `/ws` for `state` on a free local port and returns the endpoint url
+    async fn spawn_ws_server(state: AppState) -> String {
+        let app = axum::Router::new()
+            .route("/ws", axum::routing::get(websocket_handler))
+            .with_state(state);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/ws", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        url
+    }
+
+    /// Next text frame from the session, decoded
+    async fn next_message(client: &mut Client) -> StreamMessage {
+        loop {
+            let frame = tokio::time::timeout(Duration::from_secs(5), client.next())
+                .await
+                .expect("session went quiet")
+                .unwrap()
+                .unwrap();
+            if let WsMessage::Text(text) = frame {
+                return serde_json::from_str(&text).unwrap();
+            }
+        }
+    }
+
+    /// Messages received before the next info message starting with `prefix`
+    async fn until_info(client: &mut Client, prefix: &str) -> Vec<StreamMessage> {
+        let mut received = Vec::new();
+        loop {
+            match next_message(client).await {
+                StreamMessage::Info { message } if message.starts_with(prefix) => return received,
+                message => received.push(message),
+            }
+        }
+    }
+
+    /// Code and reason of the close frame that ends the session
+    async fn close_frame(client: &mut Client) -> (u16, String) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while let Some(Ok(message)) = client.next().await {
+                if let WsMessage::Close(frame) = message {
+                    let frame = frame.expect("close frame without a code");
+                    return (u16::from(frame.code), frame.reason.into_owned());
+                }
+            }
+            panic!("connection ended without a close frame");
+        })
+        .await
+        .expect("server did not close the session")
+    }
+
+    #[test]
+    fn unknown_op_error_names_the_op() {
+        let err = parse_client_message(r#"{"op":"foo"}"#).unwrap_err();
+        assert_eq!(err, "Unsupported op: foo");
+    }
+
+    #[test]
+    fn malformed_message_reports_parse_error() {
+        let err = parse_client_message(r#"{"op":"subscribe"}"#).unwrap_err();
+        assert!(err.starts_with("Invalid message format"));
+
+        assert!(matches!(
+            parse_client_message(r#"{"op":"ping"}"#),
+            Ok(ClientMessage::Ping)
+        ));
+    }
+
+    #[tokio::test]
+    async fn perp_only_symbol_without_market_type_resolves_to_perpetual() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_core::config::RestUrls;
+        use crypto_dash_core::model::{ExchangeId, MarketType, Symbol, SymbolMeta};
+        use crypto_dash_stream_hub::StreamHub;
+
+        let listing = |base: &str, market_type| SymbolMeta {
+            exchange: ExchangeId::from("bybit"),
+            market_type,
+            symbol: format!("{}USDT", base),
+            base: base.to_string(),
+            quote: "USDT".to_string(),
+            price_precision: 4,
+            tick_size: "0.0001".to_string(),
+            min_qty: 1.into(),
+            step_size: 1.into(),
+            filters: None,
+            info: serde_json::Value::Null,
+            expiry: None,
+        };
+        // The catalog fetch fails, so symbols load from the persistent cache
+        let cache = MemoryCache::new().handle();
+        let listings = vec![
+            listing("1000PEPE", MarketType::Perpetual),
+            listing("BTC", MarketType::Spot),
+            listing("BTC", MarketType::Perpetual),
+        ];
+        cache
+            .set("exchange_symbols_bybit", &listings)
+            .await
+            .unwrap();
+        let rest_urls = RestUrls {
+            bybit_catalog: "http://127.0.0.1:1".to_string(),
+            ..RestUrls::default()
+        };
+        let mut state = AppState::new(StreamHub::new().handle(), cache).with_rest_urls(rest_urls);
+        state.add_exchange(
+            crate::state::create_adapter("bybit", &crypto_dash_core::config::Config::default())
+                .unwrap(),
+        );
+        state.load_symbol_metadata().await.unwrap();
+
+        let text = r#"{"op":"subscribe","channels":[
+            {"channel_type":"ticker","exchange":"bybit","symbol":{"base":"1000PEPE","quote":"USDT"}},
+            {"channel_type":"ticker","exchange":"bybit","symbol":{"base":"BTC","quote":"USDT"}},
+            {"channel_type":"ticker","exchange":"bybit","market_type":"spot",
+             "symbol":{"base":"1000PEPE","quote":"USDT"}}
+        ]}"#;
+        let mut message = parse_client_message(text).unwrap();
+        apply_implied_market_types(text, &mut message, &state).await;
+
+        let ClientMessage::Subscribe { channels, .. } = message else {
+            panic!("expected a subscribe message");
+        };
+        let market_types: Vec<_> = channels.iter().map(|channel| channel.market_type).collect();
+        assert_eq!(
+            market_types,
+            [MarketType::Perpetual, MarketType::Spot, MarketType::Spot]
+        );
+        assert_eq!(channels[0].symbol, Symbol::new("1000PEPE", "USDT"));
+    }
+
+    #[test]
+    fn envelope_wraps_messages_only_when_requested() {
+        use crypto_dash_core::model::{ExchangeId, MarketType, StreamEnvelope, Symbol};
+        use crypto_dash_core::time::{now, to_millis};
+        use crypto_dash_stream_hub::Topic;
+
+        let topic = Topic::ticker(
+            ExchangeId::from("binance"),
+            MarketType::Spot,
+            Symbol::new("BTC", "USDT"),
+        );
+        let message = StreamMessage::Info {
+            message: "hello".to_string(),
+        };
+
+        let mut encoder = MessageEncoder::new(0);
+        let raw = encoder.encode(&topic, message.clone(), false).unwrap();
+        assert_eq!(raw, serde_json::to_string(&message).unwrap());
+        let raw: serde_json::Value = serde_json::from_str(&raw).unwrap();
+        assert_eq!(raw["type"], "info");
+        assert!(raw.get("server_ts").is_none());
+
+        let before = to_millis(now());
+        let wrapped = encoder.encode(&topic, message, true).unwrap();
+        let envelope: StreamEnvelope = serde_json::from_str(&wrapped).unwrap();
+        assert!(envelope.server_ts >= before);
+        assert!(envelope.server_ts - before < 1_000);
+        assert_eq!(envelope.topic, topic.to_string());
+        assert!(matches!(envelope.data, StreamMessage::Info { .. }));
+
+        let subscribe: ClientMessage =
+            serde_json::from_str(r#"{"op":"subscribe","channels":[],"envelope":true}"#).unwrap();
+        assert!(matches!(
+            subscribe,
+            ClientMessage::Subscribe { envelope: true, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn stats_flag_enables_periodic_stats() {
+        use crate::stats::spawn_stats_broadcast;
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        let state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle());
+        let interval = Duration::from_millis(100);
+        let stats_broadcast = spawn_stats_broadcast(state.clone(), interval);
+
+        let url = spawn_ws_server(state).await;
+
+        let (mut client, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        client
+            .send(WsMessage::Text(
+                r#"{"op":"subscribe","channels":[],"stats":true}"#.to_string(),
+            ))
+            .await
+            .unwrap();
+
+        let stats = tokio::time::timeout(interval * 5, async {
+            while let Some(Ok(WsMessage::Text(text))) = client.next().await {
+                if let Ok(StreamMessage::Stats(stats)) = serde_json::from_str(&text) {
+                    return stats;
+                }
+            }
+            panic!("connection closed before stats arrived");
+        })
+        .await
+        .expect("no stats message within the interval");
+
+        assert!(stats.connected_exchanges.is_empty());
+        stats_broadcast.abort();
+    }
+
+    #[tokio::test]
+    async fn server_closes_sessions_with_reason_codes() {
+        use crate::ws::close::CloseReason;
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        async fn flooded_close(state: AppState, flood: usize) -> (u16, String) {
+            let url = spawn_ws_server(state).await;
+
+            let (mut client, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+            for _ in 0..flood {
+                client
+                    .send(WsMessage::Text(r#"{"op":"ping"}"#.to_string()))
+                    .await
+                    .unwrap();
+            }
+            close_frame(&mut client).await
+        }
+
+        let state = || AppState::new(StreamHub::new().handle(), MemoryCache::new().handle());
+
+        let idle = state().with_idle_timeout(Duration::from_millis(100));
+        assert_eq!(
+            flooded_close(idle, 0).await,
+            (
+                CloseReason::Idle.code(),
+                CloseReason::Idle.reason().to_string()
+            )
+        );
+
+        let limited = state().with_client_message_rate_limit(3);
+        assert_eq!(
+            flooded_close(limited, 10).await,
+            (
+                CloseReason::RateLimited.code(),
+                CloseReason::RateLimited.reason().to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn maintenance_drains_open_sessions() {
+        use crate::ws::close::CloseReason;
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        let grace = Duration::from_millis(300);
+        let state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle())
+            .with_maintenance_drain_grace(grace);
+        let url = spawn_ws_server(state.clone()).await;
+
+        let (mut client, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        let Some(Ok(WsMessage::Text(welcome))) = client.next().await else {
+            panic!("expected a welcome message");
+        };
+        assert!(welcome.contains("Connected to crypto-dash API"));
+
+        state.maintenance.set_enabled(true);
+        let started = Instant::now();
+
+        match next_message(&mut client).await {
+            StreamMessage::Info { message } => {
+                assert_eq!(message, "server draining, reconnect in 1s")
+            }
+            other => panic!("expected an info message, got {:?}", other),
+        }
+        assert_eq!(
+            close_frame(&mut client).await,
+            (1001, CloseReason::Drained.reason().to_string())
+        );
+        assert!(
+            started.elapsed() >= grace,
+            "closed after {:?}",
+            started.elapsed()
+        );
+
+        // New sessions are refused for as long as maintenance lasts
+        assert!(tokio_tungstenite::connect_async(&url).await.is_err());
+        state.maintenance.set_enabled(false);
+        assert!(tokio_tungstenite::connect_async(&url).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn graceful_shutdown_closes_open_sessions() {
+        use crate::ws::close::CloseReason;
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        let state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle());
+        let app = axum::Router::new()
+            .route("/ws", axum::routing::get(websocket_handler))
+            .with_state(state.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/ws", listener.local_addr().unwrap());
+        let (trigger, triggered) = tokio::sync::oneshot::channel::<()>();
+        let shutdown_state = state.clone();
+        let server = tokio::spawn(async move {
+            axum::serve(listener, app)
+                .with_graceful_shutdown(async move {
+                    let _ = triggered.await;
+                    shutdown_state.begin_shutdown();
+                })
+                .await
+                .unwrap()
+        });
+
+        let (mut client, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        let Some(Ok(WsMessage::Text(welcome))) = client.next().await else {
+            panic!("expected a welcome message");
+        };
+        assert!(welcome.contains("Connected to crypto-dash API"));
+
+        trigger.send(()).unwrap();
+        assert_eq!(
+            close_frame(&mut client).await,
+            (1001, CloseReason::Shutdown.reason().to_string())
+        );
+
+        // With the session gone the server finishes shutting down
+        drop(client);
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .expect("server kept waiting on the session")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn subscribing_emits_lifecycle_events() {
+        use crypto_dash_bybit::BybitAdapter;
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_core::model::{Channel, ChannelType, ExchangeId, MarketType, Symbol};
+        use crypto_dash_stream_hub::StreamHub;
+        use std::sync::Arc;
+
+        let mut state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle());
+        // Unreachable upstream so the subscription fails fast without real network access
+        state.add_exchange(Arc::new(
+            BybitAdapter::new().with_ws_url(MarketType::Spot, "ws://127.0.0.1:1"),
+        ));
+        let mut events = state.hub.subscribe_events();
+
+        let url = spawn_ws_server(state).await;
+
+        let (mut client, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        let channel = Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: ExchangeId::from("bybit"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        let subscribe = ClientMessage::Subscribe {
+            channels: vec![channel.clone()],
+            stats: false,
+            envelope: false,
+        };
+        client
+            .send(WsMessage::Text(serde_json::to_string(&subscribe).unwrap()))
+            .await
+            .unwrap();
+
+        let received = tokio::time::timeout(Duration::from_secs(5), async {
+            let mut received = Vec::new();
+            while let Ok(event) = events.recv().await {
+                let done = matches!(event, LifecycleEvent::Subscribed { .. });
+                received.push(event);
+                if done {
+                    return received;
+                }
+            }
+            panic!("event bus closed before the subscription event");
+        })
+        .await
+        .expect("no subscription event emitted");
+
+        let session_id = match &received[0] {
+            LifecycleEvent::ClientConnected { session_id } => session_id.clone(),
+            other => panic!("expected a connect event first, got {:?}", other),
+        };
+        assert_eq!(
+            received.last(),
+            Some(&LifecycleEvent::Subscribed {
+                session_id,
+                channel,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn new_sessions_receive_default_channel_data() {
+        use crypto_dash_bybit::BybitAdapter;
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_core::model::{Channel, ChannelType, ExchangeId, MarketType, Symbol};
+        use crypto_dash_exchanges_common::ExchangeAdapter;
+        use crypto_dash_stream_hub::StreamHub;
+        use std::sync::Arc;
+
+        // Upstream answers every subscribe with a BTCUSDT ticker
+        let upstream = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let upstream_url = format!("ws://{}", upstream.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = upstream.accept().await {
+                tokio::spawn(async move {
+                    let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                    while let Some(Ok(WsMessage::Text(_))) = ws.next().await {
+                        let ticker = r#"{"topic":"tickers.BTCUSDT","type":"snapshot","ts":1744168585009,"data":{"symbol":"BTCUSDT","lastPrice":"65000"}}"#;
+                        ws.send(WsMessage::Text(ticker.to_string())).await.unwrap();
+                    }
+                });
+            }
+        });
+
+        let btc = Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: ExchangeId::from("bybit"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        let hub = StreamHub::new().handle();
+        let cache = MemoryCache::new().handle();
+        let adapter = Arc::new(BybitAdapter::new().with_ws_url(MarketType::Spot, upstream_url));
+        adapter.start(hub.clone(), cache.clone()).await.unwrap();
+        let mut state = AppState::new(hub, cache).with_default_channels(vec![btc]);
+        state.add_exchange(adapter);
+
+        let url = spawn_ws_server(state).await;
+
+        // Opting out skips the default subscribe, so a ping is answered right after the welcome
+        let (mut opted_out, _) =
+            tokio_tungstenite::connect_async(format!("{}?defaults=false", url))
+                .await
+                .unwrap();
+        assert!(matches!(
+            next_message(&mut opted_out).await,
+            StreamMessage::Info { .. }
+        ));
+        opted_out
+            .send(WsMessage::Text(r#"{"op":"ping"}"#.to_string()))
+            .await
+            .unwrap();
+        match next_message(&mut opted_out).await {
+            StreamMessage::Info { message } => assert_eq!(message, "Pong"),
+            other => panic!("expected pong, got {:?}", other),
+        }
+
+        // Without sending anything, a default session is subscribed and gets BTC data
+        let (mut client, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        match next_message(&mut client).await {
+            StreamMessage::Info { message } => assert!(message.starts_with("Connected")),
+            other => panic!("expected welcome, got {:?}", other),
+        }
+        match next_message(&mut client).await {
+            StreamMessage::Info { message } => {
+                assert_eq!(message, "Subscribed to 1 channels across 1 exchanges")
+            }
+            other => panic!("expected subscribe confirmation, got {:?}", other),
+        }
+        let ticker = loop {
+            if let StreamMessage::Ticker(ticker) = next_message(&mut client).await {
+                break ticker;
+            }
+        };
+        assert_eq!(ticker.symbol, Symbol::new("BTC", "USDT"));
+        assert_eq!(ticker.exchange, ExchangeId::from("bybit"));
+    }
+
+    #[tokio::test]
+    async fn sessions_only_receive_subscribed_topics() {
+        use crypto_dash_bybit::BybitAdapter;
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_core::model::{ExchangeId, MarketType, Symbol, Ticker};
+        use crypto_dash_core::time::now;
+        use crypto_dash_stream_hub::{StreamHub, Topic};
+        use rust_decimal::Decimal;
+        use std::sync::Arc;
+
+        let hub = StreamHub::new().handle();
+        let mut state = AppState::new(hub.clone(), MemoryCache::new().handle());
+        // Unreachable upstream; tickers are published straight to the hub instead
+        state.add_exchange(Arc::new(
+            BybitAdapter::new().with_ws_url(MarketType::Spot, "ws://127.0.0.1:1"),
+        ));
+
+        let url = spawn_ws_server(state).await;
+
+        let (mut client, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+
+        let publish = |base: &str| {
+            let symbol = Symbol::new(base, "USDT");
+            let topic = Topic::ticker(ExchangeId::from("bybit"), MarketType::Spot, symbol.clone());
+            let ticker = Ticker {
+                timestamp: now(),
+                exchange: ExchangeId::from("bybit"),
+                market_type: MarketType::Spot,
+                symbol,
+                bid: Decimal::ONE,
+                ask: Decimal::ONE,
+                last: Decimal::ONE,
+                bid_size: Decimal::ONE,
+                ask_size: Decimal::ONE,
+                quote_volume_24h: None,
+                change_pct_24h: None,
+                tick_direction: None,
+                high_24h: None,
+                low_24h: None,
+                volume_24h: None,
+            };
+            let hub = hub.clone();
+            async move { hub.publish(&topic, StreamMessage::Ticker(ticker)).await }
+        };
+        let tickers = |messages: Vec<StreamMessage>| -> Vec<Symbol> {
+            messages
+                .into_iter()
+                .filter_map(|message| match message {
+                    StreamMessage::Ticker(ticker) => Some(ticker.symbol),
+                    _ => None,
+                })
+                .collect()
+        };
+        let channel = r#"{"channel_type":"ticker","exchange":"bybit","market_type":"spot","symbol":{"base":"BTC","quote":"USDT"}}"#;
+
+        until_info(&mut client, "Connected").await;
+        client
+            .send(WsMessage::Text(format!(
+                r#"{{"op":"subscribe","channels":[{}]}}"#,
+                channel
+            )))
+            .await
+            .unwrap();
+        until_info(&mut client, "Subscribed").await;
+
+        publish("ETH").await;
+        publish("BTC").await;
+        client
+            .send(WsMessage::Text(r#"{"op":"ping"}"#.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(
+            tickers(until_info(&mut client, "Pong").await),
+            [Symbol::new("BTC", "USDT")]
+        );
+
+        client
+            .send(WsMessage::Text(format!(
+                r#"{{"op":"unsubscribe","channels":[{}]}}"#,
+                channel
+            )))
+            .await
+            .unwrap();
+        until_info(&mut client, "Unsubscribed").await;
+
+        publish("BTC").await;
+        client
+            .send(WsMessage::Text(r#"{"op":"ping"}"#.to_string()))
+            .await
+            .unwrap();
+        assert!(tickers(until_info(&mut client, "Pong").await).is_empty());
+    }
+
+    #[tokio::test]
+    async fn lagging_sessions_are_told_and_keep_receiving() {
+        use crypto_dash_bybit::BybitAdapter;
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_core::model::{ExchangeId, MarketType, Symbol, Ticker};
+        use crypto_dash_core::time::now;
+        use crypto_dash_stream_hub::{StreamHub, Topic, DEFAULT_MAX_TOPICS};
+        use rust_decimal::Decimal;
+        use std::sync::Arc;
+
+        // A small buffer so a burst published without yielding overruns the session
+        let hub = StreamHub::with_limits(DEFAULT_MAX_TOPICS, 4).handle();
+        let mut state = AppState::new(hub.clone(), MemoryCache::new().handle());
+        state.add_exchange(Arc::new(
+            BybitAdapter::new().with_ws_url(MarketType::Spot, "ws://127.0.0.1:1"),
+        ));
+
+        let url = spawn_ws_server(state).await;
+
+        let (mut client, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+
+        let topic = Topic::ticker(
+            ExchangeId::from("bybit"),
+            MarketType::Spot,
+            Symbol::new("BTC", "USDT"),
+        );
+        let ticker = |last: u32| {
+            StreamMessage::Ticker(Ticker {
+                timestamp: now(),
+                exchange: ExchangeId::from("bybit"),
+                market_type: MarketType::Spot,
+                symbol: Symbol::new("BTC", "USDT"),
+                bid: Decimal::ONE,
+                ask: Decimal::ONE,
+                last: Decimal::from(last),
+                bid_size: Decimal::ONE,
+                ask_size: Decimal::ONE,
+                quote_volume_24h: None,
+                change_pct_24h: None,
+                tick_direction: None,
+                high_24h: None,
+                low_24h: None,
+                volume_24h: None,
+            })
+        };
+
+        assert!(matches!(
+            next_message(&mut client).await,
+            StreamMessage::Info { .. }
+        ));
+        let channel = r#"{"channel_type":"ticker","exchange":"bybit","market_type":"spot","symbol":{"base":"BTC","quote":"USDT"}}"#;
+        client
+            .send(WsMessage::Text(format!(
+                r#"{{"op":"subscribe","channels":[{}]}}"#,
+                channel
+            )))
+            .await
+            .unwrap();
+        until_info(&mut client, "Subscribed").await;
+
+        for last in 1..=20 {
+            hub.publish(&topic, ticker(last)).await;
+        }
+
+        match next_message(&mut client).await {
+            StreamMessage::Info { message } => {
+                assert_eq!(
+                    message,
+                    "Skipped 16 messages because this session fell behind"
+                );
+            }
+            other => panic!("expected a lag notice, got {:?}", other),
+        }
+        let mut received = Vec::new();
+        while received.len() < 4 {
+            if let StreamMessage::Ticker(ticker) = next_message(&mut client).await {
+                received.push(ticker.last);
+            }
+        }
+        assert_eq!(received, (17..=20).map(Decimal::from).collect::<Vec<_>>());
+
+        // The feed carries on after the gap
+        hub.publish(&topic, ticker(21)).await;
+        loop {
+            if let StreamMessage::Ticker(ticker) = next_message(&mut client).await {
+                assert_eq!(ticker.last, Decimal::from(21));
+                break;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn exchanges_are_subscribed_concurrently() {
+        use async_trait::async_trait;
+        use crypto_dash_cache::{CacheHandle, MemoryCache};
+        use crypto_dash_core::model::{AdapterMetrics, ConnectionStats, ExchangeId, MarketType};
+        use crypto_dash_exchanges_common::ExchangeAdapter;
+        use crypto_dash_stream_hub::{HubHandle, StreamHub};
+        use std::sync::Arc;
+
+        /// Adapter whose subscribe only completes once every barrier party is subscribing,
+        /// or fails outright without a barrier
+        struct BarrierAdapter {
+            id: &'static str,
+            barrier: Option<Arc<tokio::sync::Barrier>>,
+        }
+
+        #[async_trait]
+        impl ExchangeAdapter for BarrierAdapter {
+            fn id(&self) -> ExchangeId {
+                ExchangeId::from(self.id)
+            }
+            fn supported_markets(&self) -> Vec<MarketType> {
+                vec![MarketType::Spot]
+            }
+            fn supported_depths(&self) -> &'static [u16] {
+                &[50]
+            }
+            fn orderbook_stream(&self, _market: MarketType, _depth: Option<u16>) -> (u16, u32) {
+                (50, 100)
+            }
+            async fn start(&self, _hub: HubHandle, _cache: CacheHandle) -> anyhow::Result<()> {
+                Ok(())
+            }
+            async fn connect_markets(&self, _markets: &[MarketType]) -> anyhow::Result<()> {
+                Ok(())
+            }
+            async fn subscribe(&self, _channels: &[Channel]) -> anyhow::Result<()> {
+                let barrier = self
+                    .barrier
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("market unavailable"))?;
+                // Subscribed one after another, the first would wait here forever
+                tokio::time::timeout(Duration::from_secs(5), barrier.wait())
+                    .await
+                    .map_err(|_| anyhow::anyhow!("subscribed alone"))?;
+                Ok(())
+            }
+            async fn unsubscribe(&self, _channels: &[Channel]) -> anyhow::Result<()> {
+                Ok(())
+            }
+            async fn is_connected(&self) -> bool {
+                true
+            }
+            async fn is_market_connected(&self, _market_type: MarketType) -> bool {
+                true
+            }
+            async fn connection_stats(&self, _market_type: MarketType) -> ConnectionStats {
+                ConnectionStats::default()
+            }
+
+            async fn metrics(&self) -> AdapterMetrics {
+                AdapterMetrics::default()
+            }
+            async fn stop(&self) -> anyhow::Result<()> {
+                Ok(())
+            }
+        }
+
+        let barrier = Arc::new(tokio::sync::Barrier::new(2));
+        let mut state = AppState::new(StreamHub::new().handle(), MemoryCache::new().handle());
+        for (id, barrier) in [
+            ("binance", Some(barrier.clone())),
+            ("bybit", Some(barrier)),
+            ("okx", None),
+        ] {
+            state.add_exchange(Arc::new(BarrierAdapter { id, barrier }));
+        }
+
+        let url = spawn_ws_server(state).await;
+
+        let (mut client, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+
+        client
+            .send(WsMessage::Text(
+                r#"{"op":"subscribe","channels":[
+                    {"channel_type":"ticker","exchange":"binance","market_type":"spot","symbol":{"base":"BTC","quote":"USDT"}},
+                    {"channel_type":"ticker","exchange":"bybit","market_type":"spot","symbol":{"base":"BTC","quote":"USDT"}},
+                    {"channel_type":"ticker","exchange":"okx","market_type":"spot","symbol":{"base":"BTC","quote":"USDT"}}
+                ]}"#
+                .to_string(),
+            ))
+            .await
+            .unwrap();
+        let ack = loop {
+            match next_message(&mut client).await {
+                StreamMessage::Info { message } if message.starts_with("Subscribed") => {
+                    break message
+                }
+                _ => continue,
+            }
+        };
+
+        // Both barrier parties got through together; the failing exchange is named apart
+        assert_eq!(
+            ack,
+            "Subscribed to 2 channels across 2 exchanges; failed on okx"
+        );
+    }
+
+    #[tokio::test]
+    async fn order_book_subscribe_acks_the_clamped_depth() {
+        use crypto_dash_binance::BinanceAdapter;
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_core::model::{ExchangeId, MarketType, Symbol};
+        use crypto_dash_exchanges_common::ExchangeAdapter;
+        use crypto_dash_stream_hub::StreamHub;
+        use std::sync::Arc;
+
+        // Upstream acknowledges every SUBSCRIBE request
+        let upstream = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let upstream_url = format!("ws://{}", upstream.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = upstream.accept().await {
+                tokio::spawn(async move {
+                    let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                    while let Some(Ok(WsMessage::Text(text))) = ws.next().await {
+                        let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                        let ack = format!(r#"{{"result":null,"id":{}}}"#, request["id"]);
+                        ws.send(WsMessage::Text(ack)).await.unwrap();
+                    }
+                });
+            }
+        });
+
+        let hub = StreamHub::new().handle();
+        let cache = MemoryCache::new().handle();
+        let adapter = Arc::new(BinanceAdapter::new().with_ws_url(MarketType::Spot, upstream_url));
+        adapter.start(hub.clone(), cache.clone()).await.unwrap();
+        let mut state = AppState::new(hub, cache);
+        state.add_exchange(adapter);
+
+        let url = spawn_ws_server(state).await;
+
+        let (mut client, _) = tokio_tungstenite::connect_async(format!("{}?defaults=false", url))
+            .await
+            .unwrap();
+        assert!(matches!(
+            next_message(&mut client).await,
+            StreamMessage::Info { .. }
+        ));
+        client
+            .send(WsMessage::Text(
+                r#"{"op":"subscribe","channels":[{"channel_type":"order_book","exchange":"binance","market_type":"spot","symbol":{"base":"BTC","quote":"USDT"},"depth":200}]}"#
+                    .to_string(),
+            ))
+            .await
+            .unwrap();
+
+        // Binance streams at most 20 levels, so the requested 200 is clamped
+        match next_message(&mut client).await {
+            StreamMessage::OrderBookSubscribed {
+                exchange,
+                market_type,
+                symbol,
+                depth,
+                update_speed,
+            } => {
+                assert_eq!(exchange, ExchangeId::from("binance"));
+                assert_eq!(market_type, MarketType::Spot);
+                assert_eq!(symbol, Symbol::new("BTC", "USDT"));
+                assert_eq!(depth, 20);
+                assert_eq!(update_speed, 1000);
+            }
+            other => panic!("expected an order book ack, got {:?}", other),
+        }
+        match next_message(&mut client).await {
+            StreamMessage::Info { message } => {
+                assert_eq!(message, "Subscribed to 1 channels across 1 exchanges")
+            }
+            other => panic!("expected subscribe confirmation, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn subscribes_past_the_topic_limit_are_refused() {
+        use crypto_dash_bybit::BybitAdapter;
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_core::model::{ErrorCode, MarketType};
+        use crypto_dash_stream_hub::StreamHub;
+        use std::sync::Arc;
+
+        let mut state = AppState::new(
+            StreamHub::with_max_topics(1).handle(),
+            MemoryCache::new().handle(),
+        );
+        // Unreachable upstream; only the hub's topic accounting matters here
+        state.add_exchange(Arc::new(
+            BybitAdapter::new().with_ws_url(MarketType::Spot, "ws://127.0.0.1:1"),
+        ));
+        let url = spawn_ws_server(state.clone()).await;
+        let (mut client, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        until_info(&mut client, "Connected").await;
+
+        let ticker = |op: &str, base: &str| {
+            WsMessage::Text(format!(
+                r#"{{"op":"{}","channels":[{{"channel_type":"ticker","exchange":"bybit","market_type":"spot","symbol":{{"base":"{}","quote":"USDT"}}}}]}}"#,
+                op, base
+            ))
+        };
+        let topic_limit_errors = |messages: Vec<StreamMessage>| {
+            messages
+                .into_iter()
+                .filter(|message| {
+                    matches!(
+                        message,
+                        StreamMessage::Error {
+                            code: Some(ErrorCode::TopicLimit),
+                            ..
+                        }
+                    )
+                })
+                .count()
+        };
+
+        client.send(ticker("subscribe", "BTC")).await.unwrap();
+        assert_eq!(
+            topic_limit_errors(until_info(&mut client, "Subscribed").await),
+            0
+        );
+        assert_eq!(state.hub.topic_count(), 1);
+
+        // A second distinct topic is refused without reaching the exchange
+        client.send(ticker("subscribe", "ETH")).await.unwrap();
+        assert_eq!(
+            topic_limit_errors(until_info(&mut client, "Subscribed").await),
+            1
+        );
+        assert_eq!(state.subscriptions.active_count(), 1);
+
+        // Unsubscribing releases the session's topic for the next one
+        client.send(ticker("unsubscribe", "BTC")).await.unwrap();
+        until_info(&mut client, "Unsubscribed").await;
+        client.send(ticker("subscribe", "ETH")).await.unwrap();
+        assert_eq!(
+            topic_limit_errors(until_info(&mut client, "Subscribed").await),
+            0
+        );
+    }
+}
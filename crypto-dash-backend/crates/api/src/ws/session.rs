@@ -0,0 +1,131 @@
+use crypto_dash_core::model::Channel;
+use crypto_dash_stream_hub::{SubscriberHandle, Topic};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+/// Channels a session has subscribed to, shared with its forward task so only
+/// messages on those topics are sent to the client
+#[derive(Debug, Default)]
+pub struct SessionChannels {
+    inner: Mutex<SessionChannelsInner>,
+}
+
+#[derive(Debug, Default)]
+struct SessionChannelsInner {
+    channels: HashSet<Channel>,
+    // Order book channels with different depths share a topic
+    topics: HashMap<Topic, SessionTopic>,
+}
+
+/// A topic the session holds, kept open on the hub so it counts toward the topic limit
+#[derive(Debug)]
+struct SessionTopic {
+    channels: usize,
+    _subscription: SubscriberHandle,
+}
+
+impl SessionChannels {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn contains(&self, channel: &Channel) -> bool {
+        self.inner.lock().unwrap().channels.contains(channel)
+    }
+
+    /// Returns false if the session already held the channel. `subscription` holds the
+    /// channel's topic open on the hub and is dropped if the session already holds the topic.
+    pub fn insert(&self, channel: Channel, subscription: SubscriberHandle) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let topic = Topic::from_channel(&channel);
+        if !inner.channels.insert(channel) {
+            return false;
+        }
+        inner
+            .topics
+            .entry(topic)
+            .or_insert(SessionTopic {
+                channels: 0,
+                _subscription: subscription,
+            })
+            .channels += 1;
+        true
+    }
+
+    /// Returns false if the session did not hold the channel
+    pub fn remove(&self, channel: &Channel) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        if !inner.channels.remove(channel) {
+            return false;
+        }
+        let topic = Topic::from_channel(channel);
+        if let Some(held) = inner.topics.get_mut(&topic) {
+            held.channels -= 1;
+            if held.channels == 0 {
+                inner.topics.remove(&topic);
+            }
+        }
+        true
+    }
+
+    /// Whether messages on `topic` should be forwarded to the session
+    pub fn wants(&self, topic: &Topic) -> bool {
+        self.inner.lock().unwrap().topics.contains_key(topic)
+    }
+
+    /// Empty the set, returning the channels it held
+    pub fn take(&self) -> HashSet<Channel> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.topics.clear();
+        std::mem::take(&mut inner.channels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_dash_core::model::{ChannelType, ExchangeId, MarketType, Symbol};
+    use crypto_dash_stream_hub::StreamHub;
+
+    fn book(depth: u16) -> Channel {
+        Channel {
+            channel_type: ChannelType::OrderBook,
+            exchange: ExchangeId::from("bybit"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: Some(depth),
+        }
+    }
+
+    #[tokio::test]
+    async fn topic_stays_wanted_until_every_channel_on_it_is_removed() {
+        let hub = StreamHub::new().handle();
+        let session = SessionChannels::new();
+        let topic = Topic::from_channel(&book(50));
+        let insert = |depth| {
+            let hub = hub.clone();
+            let session = &session;
+            async move {
+                let channel = book(depth);
+                let subscription = hub.subscribe(&Topic::from_channel(&channel)).await.unwrap();
+                session.insert(channel, subscription)
+            }
+        };
+        assert!(!session.wants(&topic));
+
+        assert!(insert(50).await);
+        assert!(insert(200).await);
+        assert!(!insert(50).await);
+        assert!(session.wants(&topic));
+        // Only the first channel's subscription is kept for the shared topic
+        assert_eq!(hub.subscriber_count(&topic), 1);
+
+        assert!(session.remove(&book(50)));
+        assert!(!session.remove(&book(50)));
+        assert!(session.wants(&topic));
+
+        assert!(session.remove(&book(200)));
+        assert!(!session.wants(&topic));
+        assert_eq!(hub.subscriber_count(&topic), 0);
+    }
+}
@@ -0,0 +1,101 @@
+//! Locks the cache surface routes rely on: `AppState::cache` serves both generic
+//! key/value entries (e.g. cached candles) and typed market data (tickers, books, stats).
+
+use chrono::Utc;
+use crypto_dash_api::AppState;
+use crypto_dash_cache::{CacheHandle, MemoryCache};
+use crypto_dash_core::model::{
+    ExchangeId, MarketType, OrderBookSnapshot, PriceLevel, Symbol, Ticker,
+};
+use crypto_dash_stream_hub::StreamHub;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct CachedEntry {
+    fetched_at: i64,
+    values: Vec<Decimal>,
+}
+
+fn state() -> AppState {
+    AppState::new(StreamHub::new().handle(), MemoryCache::new().handle())
+}
+
+#[tokio::test]
+async fn app_state_cache_supports_generic_and_typed_ops() {
+    let state = state();
+    let cache: &CacheHandle = &state.cache;
+
+    let entry = CachedEntry {
+        fetched_at: 1_700_000_000_000,
+        values: vec![Decimal::ONE, Decimal::new(15, 1)],
+    };
+    cache.set("candles:test", &entry).await.unwrap();
+    assert_eq!(
+        cache.get::<CachedEntry>("candles:test").await.unwrap(),
+        Some(entry)
+    );
+    assert!(cache.get::<CachedEntry>("missing").await.unwrap().is_none());
+
+    let exchange = ExchangeId::from("binance");
+    let symbol = Symbol::new("BTC", "USDT");
+    let ticker = Ticker {
+        timestamp: Utc::now(),
+        exchange: exchange.clone(),
+        market_type: MarketType::Spot,
+        symbol: symbol.clone(),
+        bid: Decimal::new(100, 0),
+        ask: Decimal::new(101, 0),
+        last: Decimal::new(100, 0),
+        bid_size: Decimal::ONE,
+        ask_size: Decimal::ONE,
+        quote_volume_24h: None,
+        change_pct_24h: None,
+        tick_direction: None,
+        high_24h: None,
+        low_24h: None,
+        volume_24h: None,
+    };
+    cache.set_ticker(ticker).await;
+    let cached = cache
+        .get_ticker(&exchange, MarketType::Spot, &symbol)
+        .await
+        .expect("ticker not cached");
+    assert_eq!(cached.ask, Decimal::new(101, 0));
+    assert!(cache
+        .get_ticker(&exchange, MarketType::Perpetual, &symbol)
+        .await
+        .is_none());
+
+    cache
+        .set_orderbook(OrderBookSnapshot {
+            timestamp: Utc::now(),
+            exchange: exchange.clone(),
+            market_type: MarketType::Spot,
+            symbol: symbol.clone(),
+            bids: vec![PriceLevel::new(Decimal::new(100, 0), Decimal::ONE)],
+            asks: vec![PriceLevel::new(Decimal::new(101, 0), Decimal::ONE)],
+            checksum: None,
+        })
+        .await;
+    assert!(cache
+        .get_orderbook(&exchange, MarketType::Spot, &symbol)
+        .await
+        .is_some());
+
+    let stats = cache.stats().await;
+    assert_eq!(stats.ticker_count, 1);
+    assert_eq!(stats.orderbook_count, 1);
+}
+
+#[tokio::test]
+async fn cloned_state_shares_one_cache() {
+    let state = state();
+    let route_state = state.clone();
+
+    state.cache.set("shared", &42u32).await.unwrap();
+    assert_eq!(
+        route_state.cache.get::<u32>("shared").await.unwrap(),
+        Some(42)
+    );
+}
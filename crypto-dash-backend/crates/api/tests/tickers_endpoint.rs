@@ -0,0 +1,89 @@
+//! Serves `GET /api/tickers` over HTTP from a seeded cache, covering the query
+//! string parsing the handler-level unit tests bypass.
+
+use axum::{routing::get, Router};
+use crypto_dash_api::{routes::list_tickers, AppState};
+use crypto_dash_cache::MemoryCache;
+use crypto_dash_core::model::{ExchangeId, MarketType, Symbol, Ticker};
+use crypto_dash_core::time::now;
+use crypto_dash_stream_hub::StreamHub;
+use rust_decimal::Decimal;
+use serde_json::Value;
+
+#[tokio::test]
+async fn tickers_endpoint_returns_cached_tickers() {
+    let cache = MemoryCache::new().handle();
+    for (exchange, base, volume) in [
+        ("binance", "BTC", 900),
+        ("binance", "ETH", 500),
+        ("bybit", "SOL", 100),
+    ] {
+        cache
+            .set_ticker(Ticker {
+                timestamp: now(),
+                exchange: ExchangeId::from(exchange),
+                market_type: MarketType::Spot,
+                symbol: Symbol::new(base, "USDT"),
+                bid: Decimal::new(100, 0),
+                ask: Decimal::new(101, 0),
+                last: Decimal::new(100, 0),
+                bid_size: Decimal::ONE,
+                ask_size: Decimal::ONE,
+                quote_volume_24h: Some(Decimal::from(volume)),
+                change_pct_24h: None,
+                tick_direction: None,
+                high_24h: None,
+                low_24h: None,
+                volume_24h: None,
+            })
+            .await;
+    }
+
+    let state = AppState::new(StreamHub::new().handle(), cache);
+    let router = Router::new()
+        .route("/api/tickers", get(list_tickers))
+        .with_state(state);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    let client = reqwest::Client::new();
+
+    let body: Value = client
+        .get(format!("http://{}/api/tickers", addr))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["total"], 3);
+    let bases: Vec<&str> = body["tickers"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|ticker| ticker["symbol"]["base"].as_str().unwrap())
+        .collect();
+    assert_eq!(bases, ["BTC", "ETH", "SOL"]);
+
+    let body: Value = client
+        .get(format!(
+            "http://{}/api/tickers?exchange=binance&market_type=spot&limit=1",
+            addr
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["total"], 2);
+    assert_eq!(body["tickers"].as_array().unwrap().len(), 1);
+    assert_eq!(body["tickers"][0]["symbol"]["base"], "BTC");
+
+    let response = client
+        .get(format!("http://{}/api/tickers?limit=0", addr))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+}
@@ -1,3 +1,5 @@
 pub mod mem;
+pub mod redis_cache;
 
 pub use mem::{CacheHandle, MemoryCache};
+pub use redis_cache::RedisCache;
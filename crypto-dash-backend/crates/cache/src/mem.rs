@@ -1,8 +1,43 @@
-use crypto_dash_core::model::{ExchangeId, MarketType, OrderBookSnapshot, Symbol, Ticker};
+use crate::redis_cache::RedisStore;
+use chrono::{DateTime, Utc};
+use crypto_dash_core::model::{
+    ExchangeId, FundingRate, InstrumentId, MarketType, OrderBookSnapshot, Symbol, Ticker,
+    TopicKeyScheme,
+};
+use crypto_dash_core::time::now;
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use tracing::debug;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+pub(crate) const TICKER_KEY_PREFIX: &str = "ticker";
+pub(crate) const ORDERBOOK_KEY_PREFIX: &str = "orderbook";
+pub(crate) const FUNDING_KEY_PREFIX: &str = "funding";
+/// How often the memory cache purges expired entries
+const DEFAULT_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// `{prefix}:{exchange}:{market}:{symbol}`, the layout shared by stream topic keys
+fn storage_key(
+    prefix: &str,
+    exchange: &ExchangeId,
+    market_type: MarketType,
+    symbol: &Symbol,
+) -> String {
+    let market_segment = match market_type {
+        MarketType::Spot => "spot",
+        MarketType::Perpetual => "perpetual",
+        MarketType::InversePerpetual => "inverse_perpetual",
+        MarketType::Future => "future",
+    };
+    format!(
+        "{}:{}:{}:{}",
+        prefix,
+        exchange.as_str(),
+        market_segment,
+        symbol.canonical()
+    )
+}
 
 /// Cache key for ticker data
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -20,6 +55,16 @@ impl TickerKey {
             symbol,
         }
     }
+
+    /// Key under which an external store keeps the ticker, e.g. `ticker:binance:spot:BTC-USDT`
+    pub fn storage_key(&self) -> String {
+        storage_key(
+            TICKER_KEY_PREFIX,
+            &self.exchange,
+            self.market_type,
+            &self.symbol,
+        )
+    }
 }
 
 /// Cache key for order book data
@@ -38,23 +83,101 @@ impl OrderBookKey {
             symbol,
         }
     }
+
+    /// Key under which an external store keeps the book, e.g. `orderbook:bybit:spot:BTC-USDT`
+    pub fn storage_key(&self) -> String {
+        self.storage_key_with_scheme(TopicKeyScheme::V1, None)
+    }
+
+    /// Storage key in the given layout; under v2 the cached depth is appended,
+    /// e.g. `orderbook:bybit:spot:BTC-USDT:50`
+    pub fn storage_key_with_scheme(&self, scheme: TopicKeyScheme, depth: Option<usize>) -> String {
+        let key = storage_key(
+            ORDERBOOK_KEY_PREFIX,
+            &self.exchange,
+            self.market_type,
+            &self.symbol,
+        );
+        match (scheme, depth) {
+            (TopicKeyScheme::V2, Some(depth)) => format!("{}:{}", key, depth),
+            _ => key,
+        }
+    }
 }
 
-/// Handle to interact with the cache
+/// Where a handle keeps its data
+#[derive(Clone)]
+pub(crate) enum Backend {
+    Memory(Arc<MemoryCacheInner>),
+    Redis(RedisStore),
+}
+
+/// Handle to interact with the cache; the same surface whichever backend is active
 #[derive(Clone)]
 pub struct CacheHandle {
-    inner: Arc<MemoryCacheInner>,
+    pub(crate) backend: Backend,
+    pub(crate) namespace: Arc<str>,
+    pub(crate) orderbook_depth_cap: Option<usize>,
+    pub(crate) key_scheme: TopicKeyScheme,
 }
 
 impl CacheHandle {
+    /// Key as stored, prefixed with the namespace when one is configured
+    pub fn namespaced_key(&self, key: &str) -> String {
+        if self.namespace.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}:{}", self.namespace, key)
+        }
+    }
+
+    /// Namespaced store key for a book in the configured key scheme
+    fn orderbook_storage_key(&self, key: &OrderBookKey) -> String {
+        self.namespaced_key(&key.storage_key_with_scheme(self.key_scheme, self.orderbook_depth_cap))
+    }
+
     /// Store arbitrary data in the cache
     pub async fn set<T>(&self, key: &str, value: &T) -> anyhow::Result<()>
+    where
+        T: Serialize,
+    {
+        self.store(key, value, None).await
+    }
+
+    /// Store arbitrary data that `get` treats as absent once `ttl` has passed
+    pub async fn set_with_ttl<T>(&self, key: &str, value: &T, ttl: Duration) -> anyhow::Result<()>
+    where
+        T: Serialize,
+    {
+        self.store(key, value, Some(ttl)).await
+    }
+
+    async fn store<T>(&self, key: &str, value: &T, ttl: Option<Duration>) -> anyhow::Result<()>
     where
         T: Serialize,
     {
         let serialized = serde_json::to_string(value)?;
-        self.inner.generic_data.insert(key.to_string(), serialized);
+        let key = self.namespaced_key(key);
         debug!("Cached data for key: {}", key);
+        match &self.backend {
+            Backend::Memory(inner) => {
+                let expires_at = match ttl {
+                    Some(ttl) => Some(now() + chrono::Duration::from_std(ttl)?),
+                    None => None,
+                };
+                inner.generic_data.insert(
+                    key,
+                    CachedValue {
+                        value: serialized,
+                        expires_at,
+                    },
+                );
+            }
+            Backend::Redis(store) => match ttl {
+                Some(ttl) => store.set_with_ttl(&key, serialized, ttl).await?,
+                None => store.set(&key, serialized).await?,
+            },
+        }
         Ok(())
     }
 
@@ -63,11 +186,14 @@ impl CacheHandle {
     where
         T: for<'de> Deserialize<'de>,
     {
-        if let Some(entry) = self.inner.generic_data.get(key) {
-            let value: T = serde_json::from_str(&entry)?;
-            Ok(Some(value))
-        } else {
-            Ok(None)
+        let key = self.namespaced_key(key);
+        let serialized = match &self.backend {
+            Backend::Memory(inner) => inner.get_generic(&key, now()),
+            Backend::Redis(store) => store.get(&key).await?,
+        };
+        match serialized {
+            Some(serialized) => Ok(Some(serde_json::from_str(&serialized)?)),
+            None => Ok(None),
         }
     }
 
@@ -81,9 +207,19 @@ impl CacheHandle {
         debug!(
             "Cached ticker for {}/{}",
             ticker.exchange.as_str(),
-            ticker.symbol.canonical()
+            InstrumentId::new(ticker.symbol.clone(), ticker.market_type)
         );
-        self.inner.tickers.insert(key, ticker);
+        match &self.backend {
+            Backend::Memory(inner) => {
+                inner.tickers.insert(key, ticker);
+            }
+            Backend::Redis(store) => {
+                let key = self.namespaced_key(&key.storage_key());
+                if let Err(e) = store.set_market_json(&key, &ticker).await {
+                    warn!("Failed to cache ticker {}: {}", key, e);
+                }
+            }
+        }
     }
 
     /// Get a ticker from the cache
@@ -94,14 +230,85 @@ impl CacheHandle {
         symbol: &Symbol,
     ) -> Option<Ticker> {
         let key = TickerKey::new(exchange.clone(), market_type, symbol.clone());
-        self.inner
-            .tickers
-            .get(&key)
-            .map(|entry| entry.value().clone())
+        match &self.backend {
+            Backend::Memory(inner) => inner.tickers.get(&key).map(|entry| entry.value().clone()),
+            Backend::Redis(store) => {
+                let key = self.namespaced_key(&key.storage_key());
+                store.get_json(&key).await.unwrap_or_else(|e| {
+                    warn!("Failed to read cached ticker {}: {}", key, e);
+                    None
+                })
+            }
+        }
     }
 
-    /// Store an order book snapshot in the cache
-    pub async fn set_orderbook(&self, orderbook: OrderBookSnapshot) {
+    /// Store the latest funding rate of a perpetual market
+    pub async fn set_funding_rate(&self, funding: FundingRate) {
+        let key = TickerKey::new(
+            funding.exchange.clone(),
+            funding.market_type,
+            funding.symbol.clone(),
+        );
+        match &self.backend {
+            Backend::Memory(inner) => {
+                inner.funding_rates.insert(key, funding);
+            }
+            Backend::Redis(store) => {
+                let key = self.namespaced_key(&storage_key(
+                    FUNDING_KEY_PREFIX,
+                    &key.exchange,
+                    key.market_type,
+                    &key.symbol,
+                ));
+                if let Err(e) = store.set_market_json(&key, &funding).await {
+                    warn!("Failed to cache funding rate {}: {}", key, e);
+                }
+            }
+        }
+    }
+
+    /// Get the latest funding rate of a perpetual market from the cache
+    pub async fn get_funding_rate(
+        &self,
+        exchange: &ExchangeId,
+        market_type: MarketType,
+        symbol: &Symbol,
+    ) -> Option<FundingRate> {
+        match &self.backend {
+            Backend::Memory(inner) => inner
+                .funding_rates
+                .get(&TickerKey::new(
+                    exchange.clone(),
+                    market_type,
+                    symbol.clone(),
+                ))
+                .map(|entry| entry.value().clone()),
+            Backend::Redis(store) => {
+                let key = self.namespaced_key(&storage_key(
+                    FUNDING_KEY_PREFIX,
+                    exchange,
+                    market_type,
+                    symbol,
+                ));
+                store.get_json(&key).await.unwrap_or_else(|e| {
+                    warn!("Failed to read cached funding rate {}: {}", key, e);
+                    None
+                })
+            }
+        }
+    }
+
+    /// Store an order book snapshot in the cache, trimmed to the configured depth cap
+    pub async fn set_orderbook(&self, mut orderbook: OrderBookSnapshot) {
+        if let Some(cap) = self.orderbook_depth_cap {
+            if orderbook.bids.len() > cap || orderbook.asks.len() > cap {
+                orderbook.bids.truncate(cap);
+                orderbook.asks.truncate(cap);
+                // The exchange checksum covers the full book, not the trimmed copy
+                orderbook.checksum = None;
+            }
+        }
+
         let key = OrderBookKey::new(
             orderbook.exchange.clone(),
             orderbook.market_type,
@@ -110,9 +317,19 @@ impl CacheHandle {
         debug!(
             "Cached orderbook for {}/{}",
             orderbook.exchange.as_str(),
-            orderbook.symbol.canonical()
+            InstrumentId::new(orderbook.symbol.clone(), orderbook.market_type)
         );
-        self.inner.orderbooks.insert(key, orderbook);
+        match &self.backend {
+            Backend::Memory(inner) => {
+                inner.orderbooks.insert(key, orderbook);
+            }
+            Backend::Redis(store) => {
+                let key = self.orderbook_storage_key(&key);
+                if let Err(e) = store.set_market_json(&key, &orderbook).await {
+                    warn!("Failed to cache orderbook {}: {}", key, e);
+                }
+            }
+        }
     }
 
     /// Get an order book snapshot from the cache
@@ -123,44 +340,124 @@ impl CacheHandle {
         symbol: &Symbol,
     ) -> Option<OrderBookSnapshot> {
         let key = OrderBookKey::new(exchange.clone(), market_type, symbol.clone());
-        self.inner
-            .orderbooks
-            .get(&key)
-            .map(|entry| entry.value().clone())
+        match &self.backend {
+            Backend::Memory(inner) => inner
+                .orderbooks
+                .get(&key)
+                .map(|entry| entry.value().clone()),
+            Backend::Redis(store) => {
+                let key = self.orderbook_storage_key(&key);
+                store.get_json(&key).await.unwrap_or_else(|e| {
+                    warn!("Failed to read cached orderbook {}: {}", key, e);
+                    None
+                })
+            }
+        }
+    }
+
+    /// Drop a cached order book so it is not served until a fresh snapshot arrives
+    pub async fn remove_orderbook(
+        &self,
+        exchange: &ExchangeId,
+        market_type: MarketType,
+        symbol: &Symbol,
+    ) {
+        let key = OrderBookKey::new(exchange.clone(), market_type, symbol.clone());
+        match &self.backend {
+            Backend::Memory(inner) => {
+                inner.orderbooks.remove(&key);
+            }
+            Backend::Redis(store) => {
+                let key = self.orderbook_storage_key(&key);
+                if let Err(e) = store.delete(&key).await {
+                    warn!("Failed to drop cached orderbook {}: {}", key, e);
+                }
+            }
+        }
     }
 
     /// Get all cached tickers
     pub async fn get_all_tickers(&self) -> Vec<Ticker> {
-        self.inner
-            .tickers
-            .iter()
-            .map(|entry| entry.value().clone())
-            .collect()
+        match &self.backend {
+            Backend::Memory(inner) => inner
+                .tickers
+                .iter()
+                .map(|entry| entry.value().clone())
+                .collect(),
+            Backend::Redis(store) => self.redis_values(store, TICKER_KEY_PREFIX).await,
+        }
     }
 
     /// Get all cached order books
     pub async fn get_all_orderbooks(&self) -> Vec<OrderBookSnapshot> {
-        self.inner
-            .orderbooks
-            .iter()
-            .map(|entry| entry.value().clone())
-            .collect()
+        match &self.backend {
+            Backend::Memory(inner) => inner
+                .orderbooks
+                .iter()
+                .map(|entry| entry.value().clone())
+                .collect(),
+            Backend::Redis(store) => self.redis_values(store, ORDERBOOK_KEY_PREFIX).await,
+        }
     }
 
     /// Clear all cached data
     pub async fn clear(&self) {
-        self.inner.tickers.clear();
-        self.inner.orderbooks.clear();
+        match &self.backend {
+            Backend::Memory(inner) => {
+                inner.tickers.clear();
+                inner.orderbooks.clear();
+                inner.funding_rates.clear();
+            }
+            Backend::Redis(store) => {
+                for prefix in [TICKER_KEY_PREFIX, ORDERBOOK_KEY_PREFIX, FUNDING_KEY_PREFIX] {
+                    let pattern = self.redis_pattern(prefix);
+                    if let Err(e) = store.delete_matching(&pattern).await {
+                        warn!("Failed to clear cached {} data: {}", prefix, e);
+                    }
+                }
+            }
+        }
         debug!("Cleared all cache data");
     }
 
     /// Get cache statistics
     pub async fn stats(&self) -> CacheStats {
-        CacheStats {
-            ticker_count: self.inner.tickers.len(),
-            orderbook_count: self.inner.orderbooks.len(),
+        match &self.backend {
+            Backend::Memory(inner) => CacheStats {
+                ticker_count: inner.tickers.len(),
+                orderbook_count: inner.orderbooks.len(),
+            },
+            Backend::Redis(store) => {
+                let count = |prefix: &'static str| async move {
+                    let pattern = self.redis_pattern(prefix);
+                    store.count_matching(&pattern).await.unwrap_or_else(|e| {
+                        warn!("Failed to count cached {} data: {}", prefix, e);
+                        0
+                    })
+                };
+                CacheStats {
+                    ticker_count: count(TICKER_KEY_PREFIX).await,
+                    orderbook_count: count(ORDERBOOK_KEY_PREFIX).await,
+                }
+            }
         }
     }
+
+    /// Glob matching every Redis key of one data type in this handle's namespace
+    fn redis_pattern(&self, prefix: &str) -> String {
+        self.namespaced_key(&format!("{}:*", prefix))
+    }
+
+    async fn redis_values<T>(&self, store: &RedisStore, prefix: &str) -> Vec<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let pattern = self.redis_pattern(prefix);
+        store.get_all_json(&pattern).await.unwrap_or_else(|e| {
+            warn!("Failed to read cached {} data: {}", prefix, e);
+            Vec::new()
+        })
+    }
 }
 
 /// Cache statistics
@@ -170,10 +467,24 @@ pub struct CacheStats {
     pub orderbook_count: usize,
 }
 
-struct MemoryCacheInner {
+/// JSON serialized data stored with `set`/`set_with_ttl`
+struct CachedValue {
+    value: String,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+impl CachedValue {
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+pub(crate) struct MemoryCacheInner {
     tickers: DashMap<TickerKey, Ticker>,
     orderbooks: DashMap<OrderBookKey, OrderBookSnapshot>,
-    generic_data: DashMap<String, String>, // JSON serialized data
+    // Funding rates share the ticker key: one entry per market
+    funding_rates: DashMap<TickerKey, FundingRate>,
+    generic_data: DashMap<String, CachedValue>,
 }
 
 impl MemoryCacheInner {
@@ -181,14 +492,71 @@ impl MemoryCacheInner {
         Self {
             tickers: DashMap::new(),
             orderbooks: DashMap::new(),
+            funding_rates: DashMap::new(),
             generic_data: DashMap::new(),
         }
     }
+
+    /// Value under `key` unless it has expired, in which case it is removed
+    fn get_generic(&self, key: &str, now: DateTime<Utc>) -> Option<String> {
+        if let Some(entry) = self.generic_data.get(key) {
+            if !entry.is_expired(now) {
+                return Some(entry.value.clone());
+            }
+        }
+        self.generic_data
+            .remove_if(key, |_, entry| entry.is_expired(now));
+        None
+    }
+
+    /// Drop expired generic entries and tickers last updated more than `ticker_max_age` ago
+    fn purge_expired(&self, now: DateTime<Utc>, ticker_max_age: Option<Duration>) {
+        let generic_before = self.generic_data.len();
+        self.generic_data.retain(|_, entry| !entry.is_expired(now));
+
+        let tickers_before = self.tickers.len();
+        if let Some(cutoff) = ticker_max_age
+            .and_then(|max_age| chrono::Duration::from_std(max_age).ok())
+            .map(|max_age| now - max_age)
+        {
+            self.tickers.retain(|_, ticker| ticker.timestamp >= cutoff);
+        }
+
+        debug!(
+            "Cache sweep removed {} expired entries and {} stale tickers",
+            generic_before - self.generic_data.len(),
+            tickers_before - self.tickers.len()
+        );
+    }
+}
+
+/// Purge expired entries every `interval` until the cache is dropped
+fn spawn_sweep(
+    inner: Weak<MemoryCacheInner>,
+    interval: Duration,
+    ticker_max_age: Option<Duration>,
+) {
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(interval);
+        // The first tick completes immediately
+        ticks.tick().await;
+        loop {
+            ticks.tick().await;
+            let Some(inner) = inner.upgrade() else {
+                break;
+            };
+            inner.purge_expired(now(), ticker_max_age);
+        }
+    });
 }
 
 /// In-memory cache for market data
 pub struct MemoryCache {
     inner: Arc<MemoryCacheInner>,
+    namespace: Arc<str>,
+    orderbook_depth_cap: Option<usize>,
+    sweep_interval: Duration,
+    ticker_max_age: Option<Duration>,
 }
 
 impl MemoryCache {
@@ -196,18 +564,55 @@ impl MemoryCache {
     pub fn new() -> Self {
         Self {
             inner: Arc::new(MemoryCacheInner::new()),
+            namespace: Arc::from(""),
+            orderbook_depth_cap: None,
+            sweep_interval: DEFAULT_SWEEP_INTERVAL,
+            ticker_max_age: None,
         }
     }
 
+    /// Prefix every `set`/`get` key, e.g. to share one store across environments
+    pub fn with_namespace(mut self, namespace: impl AsRef<str>) -> Self {
+        self.namespace = Arc::from(namespace.as_ref().trim().trim_end_matches(':'));
+        self
+    }
+
+    /// Keep at most `levels` per side of each cached order book; 0 keeps full books.
+    /// Forwarded books are unaffected.
+    pub fn with_orderbook_depth_cap(mut self, levels: usize) -> Self {
+        self.orderbook_depth_cap = (levels > 0).then_some(levels);
+        self
+    }
+
+    /// How often the sweep started by `start` purges expired entries
+    pub fn with_sweep_interval(mut self, interval: Duration) -> Self {
+        self.sweep_interval = interval;
+        self
+    }
+
+    /// Have the sweep drop tickers not updated within `max_age`; zero keeps them
+    pub fn with_ticker_max_age(mut self, max_age: Duration) -> Self {
+        self.ticker_max_age = (!max_age.is_zero()).then_some(max_age);
+        self
+    }
+
     /// Get a handle to interact with the cache
     pub fn handle(&self) -> CacheHandle {
         CacheHandle {
-            inner: Arc::clone(&self.inner),
+            backend: Backend::Memory(Arc::clone(&self.inner)),
+            namespace: Arc::clone(&self.namespace),
+            orderbook_depth_cap: self.orderbook_depth_cap,
+            key_scheme: TopicKeyScheme::V1,
         }
     }
 
-    /// Start the cache (currently just returns the handle)
+    /// Start the background sweep of expired entries and return a handle
     pub async fn start(self) -> anyhow::Result<CacheHandle> {
+        spawn_sweep(
+            Arc::downgrade(&self.inner),
+            self.sweep_interval,
+            self.ticker_max_age,
+        );
         debug!("Memory cache started");
         Ok(self.handle())
     }
@@ -240,6 +645,12 @@ mod tests {
             last: Decimal::new(50000, 0),
             bid_size: Decimal::new(1, 0),
             ask_size: Decimal::new(1, 0),
+            quote_volume_24h: None,
+            change_pct_24h: None,
+            tick_direction: None,
+            high_24h: None,
+            low_24h: None,
+            volume_24h: None,
         };
 
         handle.set_ticker(ticker.clone()).await;
@@ -273,6 +684,12 @@ mod tests {
             last: Decimal::new(50000, 0),
             bid_size: Decimal::new(1, 0),
             ask_size: Decimal::new(1, 0),
+            quote_volume_24h: None,
+            change_pct_24h: None,
+            tick_direction: None,
+            high_24h: None,
+            low_24h: None,
+            volume_24h: None,
         };
 
         handle.set_ticker(ticker).await;
@@ -280,4 +697,189 @@ mod tests {
         let stats = handle.stats().await;
         assert_eq!(stats.ticker_count, 1);
     }
+
+    #[tokio::test]
+    async fn test_namespace_prefixes_keys() {
+        let handle = MemoryCache::new().with_namespace("staging").handle();
+        assert_eq!(
+            handle.namespaced_key("exchange_symbols_binance"),
+            "staging:exchange_symbols_binance"
+        );
+
+        handle.set("candles:binance", &vec![1, 2, 3]).await.unwrap();
+        let Backend::Memory(inner) = &handle.backend else {
+            unreachable!("memory cache handle");
+        };
+        assert!(inner.generic_data.contains_key("staging:candles:binance"));
+        assert!(!inner.generic_data.contains_key("candles:binance"));
+
+        let cached: Option<Vec<i32>> = handle.get("candles:binance").await.unwrap();
+        assert_eq!(cached, Some(vec![1, 2, 3]));
+
+        // Another namespace over the same store does not see the entry
+        let other = CacheHandle {
+            backend: handle.backend.clone(),
+            namespace: Arc::from("prod"),
+            orderbook_depth_cap: None,
+            key_scheme: TopicKeyScheme::V1,
+        };
+        let missing: Option<Vec<i32>> = other.get("candles:binance").await.unwrap();
+        assert!(missing.is_none());
+
+        // The default namespace keeps keys unchanged
+        let plain = MemoryCache::new().handle();
+        assert_eq!(plain.namespaced_key("candles:binance"), "candles:binance");
+    }
+
+    #[tokio::test]
+    async fn test_entries_expire_after_ttl() {
+        let handle = MemoryCache::new().handle();
+        handle
+            .set_with_ttl("candles:binance", &vec![1, 2, 3], Duration::from_millis(50))
+            .await
+            .unwrap();
+        handle.set("symbols", &vec![4]).await.unwrap();
+
+        let cached: Option<Vec<i32>> = handle.get("candles:binance").await.unwrap();
+        assert_eq!(cached, Some(vec![1, 2, 3]));
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let expired: Option<Vec<i32>> = handle.get("candles:binance").await.unwrap();
+        assert!(expired.is_none());
+        let Backend::Memory(inner) = &handle.backend else {
+            unreachable!("memory cache handle");
+        };
+        // Reading an expired entry removes it; entries without a TTL stay
+        assert!(!inner.generic_data.contains_key("candles:binance"));
+        let kept: Option<Vec<i32>> = handle.get("symbols").await.unwrap();
+        assert_eq!(kept, Some(vec![4]));
+    }
+
+    #[tokio::test]
+    async fn test_sweep_purges_expired_entries_and_stale_tickers() {
+        let ticker = |base: &str, age_secs: i64| Ticker {
+            timestamp: now() - chrono::Duration::seconds(age_secs),
+            exchange: ExchangeId::from("binance"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new(base, "USDT"),
+            bid: Decimal::ONE,
+            ask: Decimal::ONE,
+            last: Decimal::ONE,
+            bid_size: Decimal::ONE,
+            ask_size: Decimal::ONE,
+            quote_volume_24h: None,
+            change_pct_24h: None,
+            tick_direction: None,
+            high_24h: None,
+            low_24h: None,
+            volume_24h: None,
+        };
+
+        let handle = MemoryCache::new()
+            .with_sweep_interval(Duration::from_millis(20))
+            .with_ticker_max_age(Duration::from_secs(60))
+            .start()
+            .await
+            .unwrap();
+        handle
+            .set_with_ttl("short", &1, Duration::from_millis(10))
+            .await
+            .unwrap();
+        handle.set("long", &2).await.unwrap();
+        handle.set_ticker(ticker("BTC", 0)).await;
+        handle.set_ticker(ticker("ETH", 600)).await;
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let Backend::Memory(inner) = &handle.backend else {
+            unreachable!("memory cache handle");
+        };
+        assert!(!inner.generic_data.contains_key("short"));
+        assert!(inner.generic_data.contains_key("long"));
+        let symbols: Vec<Symbol> = handle
+            .get_all_tickers()
+            .await
+            .into_iter()
+            .map(|ticker| ticker.symbol)
+            .collect();
+        assert_eq!(symbols, vec![Symbol::new("BTC", "USDT")]);
+    }
+
+    #[test]
+    fn test_storage_keys() {
+        let symbol = Symbol::new("BTC", "USDT");
+        let ticker = TickerKey::new(
+            ExchangeId::from("binance"),
+            MarketType::Spot,
+            symbol.clone(),
+        );
+        assert_eq!(ticker.storage_key(), "ticker:binance:spot:BTC-USDT");
+
+        let book = OrderBookKey::new(
+            ExchangeId::from("bybit"),
+            MarketType::InversePerpetual,
+            Symbol::new("BTC", "USD"),
+        );
+        assert_eq!(
+            book.storage_key(),
+            "orderbook:bybit:inverse_perpetual:BTC-USD"
+        );
+        assert_eq!(
+            book.storage_key_with_scheme(TopicKeyScheme::V2, Some(50)),
+            "orderbook:bybit:inverse_perpetual:BTC-USD:50"
+        );
+        assert_eq!(
+            book.storage_key_with_scheme(TopicKeyScheme::V2, None),
+            book.storage_key()
+        );
+
+        let handle = MemoryCache::new().with_namespace("staging").handle();
+        assert_eq!(handle.redis_pattern(TICKER_KEY_PREFIX), "staging:ticker:*");
+    }
+
+    #[tokio::test]
+    async fn test_orderbook_depth_cap() {
+        use crypto_dash_core::model::PriceLevel;
+
+        let levels = |start: i64, step: i64| -> Vec<PriceLevel> {
+            (0..200)
+                .map(|i| PriceLevel {
+                    price: Decimal::new(start + step * i, 0),
+                    quantity: Decimal::ONE,
+                })
+                .collect()
+        };
+        let book = OrderBookSnapshot {
+            timestamp: now(),
+            exchange: ExchangeId::from("bybit"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            bids: levels(50_000, -1),
+            asks: levels(50_001, 1),
+            checksum: Some("123".to_string()),
+        };
+
+        let handle = MemoryCache::new().with_orderbook_depth_cap(50).handle();
+        handle.set_orderbook(book.clone()).await;
+
+        let cached = handle
+            .get_orderbook(&book.exchange, MarketType::Spot, &book.symbol)
+            .await
+            .unwrap();
+        assert_eq!(cached.bids.len(), 50);
+        assert_eq!(cached.asks.len(), 50);
+        // Best levels are kept
+        assert_eq!(cached.bids[0].price, Decimal::new(50_000, 0));
+        assert_eq!(cached.asks[49].price, Decimal::new(50_050, 0));
+        assert!(cached.checksum.is_none());
+
+        // No cap keeps the full book
+        let full = MemoryCache::new().handle();
+        full.set_orderbook(book.clone()).await;
+        let cached = full
+            .get_orderbook(&book.exchange, MarketType::Spot, &book.symbol)
+            .await
+            .unwrap();
+        assert_eq!(cached.bids.len(), 200);
+    }
 }
@@ -0,0 +1,167 @@
+use crate::model::{ExchangeId, MarketType, Symbol, Ticker};
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Exchange preference for composite tie-breaks and stale fallbacks; unlisted exchanges rank last
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExchangePriority(Vec<ExchangeId>);
+
+impl ExchangePriority {
+    pub fn new(order: impl IntoIterator<Item = ExchangeId>) -> Self {
+        Self(order.into_iter().collect())
+    }
+
+    /// Position in the preference order; lower is preferred
+    pub fn rank(&self, exchange: &ExchangeId) -> usize {
+        self.0
+            .iter()
+            .position(|preferred| preferred == exchange)
+            .unwrap_or(self.0.len())
+    }
+}
+
+impl Default for ExchangePriority {
+    fn default() -> Self {
+        Self::new(ExchangeId::KNOWN.iter().map(|id| ExchangeId::from(*id)))
+    }
+}
+
+/// Best bid and ask for one instrument across exchanges
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompositeTicker {
+    pub timestamp: DateTime<Utc>,
+    pub market_type: MarketType,
+    pub symbol: Symbol,
+    pub bid: Decimal,
+    pub bid_size: Decimal,
+    pub bid_exchange: ExchangeId,
+    pub ask: Decimal,
+    pub ask_size: Decimal,
+    pub ask_exchange: ExchangeId,
+}
+
+impl CompositeTicker {
+    /// Combine one instrument's tickers. Tickers older than `stale_after` are ignored while any
+    /// exchange is fresh; otherwise the highest-priority exchange alone is used.
+    pub fn build(
+        tickers: &[Ticker],
+        priority: &ExchangePriority,
+        stale_after: Duration,
+        now: DateTime<Utc>,
+    ) -> Option<Self> {
+        let fresh: Vec<&Ticker> = tickers
+            .iter()
+            .filter(|ticker| now.signed_duration_since(ticker.timestamp) <= stale_after)
+            .collect();
+        let candidates = if fresh.is_empty() {
+            tickers
+                .iter()
+                .min_by_key(|ticker| priority.rank(&ticker.exchange))
+                .into_iter()
+                .collect()
+        } else {
+            fresh
+        };
+
+        // Equal prices go to the preferred exchange
+        let best_bid = candidates
+            .iter()
+            .filter(|ticker| ticker.bid > Decimal::ZERO)
+            .min_by(|a, b| {
+                b.bid
+                    .cmp(&a.bid)
+                    .then_with(|| priority.rank(&a.exchange).cmp(&priority.rank(&b.exchange)))
+            })?;
+        let best_ask = candidates
+            .iter()
+            .filter(|ticker| ticker.ask > Decimal::ZERO)
+            .min_by(|a, b| {
+                a.ask
+                    .cmp(&b.ask)
+                    .then_with(|| priority.rank(&a.exchange).cmp(&priority.rank(&b.exchange)))
+            })?;
+
+        Some(Self {
+            timestamp: best_bid.timestamp.max(best_ask.timestamp),
+            market_type: best_bid.market_type,
+            symbol: best_bid.symbol.clone(),
+            bid: best_bid.bid,
+            bid_size: best_bid.bid_size,
+            bid_exchange: best_bid.exchange.clone(),
+            ask: best_ask.ask,
+            ask_size: best_ask.ask_size,
+            ask_exchange: best_ask.exchange.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticker(exchange: &str, bid: i64, ask: i64, age_secs: i64, now: DateTime<Utc>) -> Ticker {
+        Ticker {
+            timestamp: now - Duration::seconds(age_secs),
+            exchange: ExchangeId::from(exchange),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            bid: Decimal::from(bid),
+            ask: Decimal::from(ask),
+            last: Decimal::from(bid),
+            bid_size: Decimal::ONE,
+            ask_size: Decimal::ONE,
+            quote_volume_24h: None,
+            change_pct_24h: None,
+            tick_direction: None,
+            high_24h: None,
+            low_24h: None,
+            volume_24h: None,
+        }
+    }
+
+    #[test]
+    fn equal_bids_resolve_to_configured_priority() {
+        let now = Utc::now();
+        let tickers = [
+            ticker("bybit", 100, 102, 0, now),
+            ticker("binance", 100, 102, 0, now),
+        ];
+        let stale_after = Duration::seconds(30);
+
+        let binance_first = ExchangePriority::new(["binance", "bybit"].map(ExchangeId::from));
+        let composite = CompositeTicker::build(&tickers, &binance_first, stale_after, now).unwrap();
+        assert_eq!(composite.bid_exchange.as_str(), "binance");
+        assert_eq!(composite.ask_exchange.as_str(), "binance");
+
+        let bybit_first = ExchangePriority::new(["bybit", "binance"].map(ExchangeId::from));
+        let composite = CompositeTicker::build(&tickers, &bybit_first, stale_after, now).unwrap();
+        assert_eq!(composite.bid_exchange.as_str(), "bybit");
+    }
+
+    #[test]
+    fn stale_exchanges_are_skipped_until_all_are_stale() {
+        let now = Utc::now();
+        let priority = ExchangePriority::default();
+        let stale_after = Duration::seconds(30);
+
+        // The stale Binance bid is better but ignored while Bybit is fresh
+        let tickers = [
+            ticker("binance", 105, 106, 60, now),
+            ticker("bybit", 100, 101, 1, now),
+        ];
+        let composite = CompositeTicker::build(&tickers, &priority, stale_after, now).unwrap();
+        assert_eq!(composite.bid_exchange.as_str(), "bybit");
+        assert_eq!(composite.bid, Decimal::from(100));
+
+        let tickers = [
+            ticker("bybit", 100, 101, 90, now),
+            ticker("binance", 99, 100, 60, now),
+        ];
+        let composite = CompositeTicker::build(&tickers, &priority, stale_after, now).unwrap();
+        assert_eq!(composite.bid_exchange.as_str(), "binance");
+        assert_eq!(composite.ask_exchange.as_str(), "binance");
+
+        assert!(CompositeTicker::build(&[], &priority, stale_after, now).is_none());
+    }
+}
@@ -1,42 +1,387 @@
+use crate::composite::ExchangePriority;
+use crate::model::{Channel, ChannelType, ExchangeId, MarketType, Symbol, TopicKeyScheme};
 use serde::{Deserialize, Serialize};
 use std::env;
 
+/// Exchanges enabled, in priority order, when `EXCHANGES`/`EXCHANGE_PRIORITY` are unset.
+/// OKX has no symbol catalog or candles yet, so it is opt-in.
+const DEFAULT_EXCHANGES: &[&str] = &[ExchangeId::BINANCE, ExchangeId::BYBIT];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub bind_addr: String,
     pub exchanges: Vec<String>,
+    /// Preferred exchanges for composite ticker ties and stale fallbacks (`EXCHANGE_PRIORITY`)
+    pub exchange_priority: Vec<String>,
     pub enable_redis: bool,
     pub redis_url: String,
+    /// Prefix for cache keys so environments can share one store (`CACHE_NAMESPACE`)
+    pub cache_namespace: String,
     pub book_depth_default: u16,
+    /// Levels per side kept for cached order books, independent of forwarded depth; 0 keeps all
+    pub cache_book_depth: usize,
+    /// Seconds without an update before the cache drops a ticker (with Redis, also order books
+    /// and funding rates); 0 keeps them
+    pub cache_ticker_max_age_secs: u64,
+    /// Layout of topic and cache keys, `v1` or `v2` with the book depth (`TOPIC_KEY_SCHEME`)
+    pub topic_key_scheme: TopicKeyScheme,
     pub log_level: String,
+    /// Emit JSON log lines instead of the compact text format (`LOG_FORMAT=json`)
+    pub log_json: bool,
     pub enable_real_connections: bool,
+    pub max_upstream_subscriptions: usize,
+    pub preconnect_markets: Vec<MarketType>,
+    /// Channels every new WebSocket session is subscribed to unless it connects with
+    /// `?defaults=false` (`WS_DEFAULT_CHANNELS`)
+    pub ws_default_channels: Vec<Channel>,
+    /// Channels kept subscribed upstream with no client attached so their data stays
+    /// cached (`CACHE_WARM_CHANNELS`)
+    pub cache_warm_channels: Vec<Channel>,
+    /// Channel list entries that did not parse, as `VAR: entry`; the server logs each
+    /// once tracing is up
+    pub invalid_channel_specs: Vec<String>,
+    pub stats_interval_secs: u64,
+    /// Per-session window for coalescing Info/Error messages per exchange; 0 disables
+    pub status_coalesce_ms: u64,
+    /// Resend a cached snapshot before any order book delta a session lacks the base for
+    pub book_resync: bool,
+    /// Seconds an upstream subscribe may go unconfirmed before clients get an error; 0 disables
+    pub subscribe_timeout_secs: u64,
+    /// Minimum milliseconds between subscribe frames on one Binance connection
+    pub binance_subscribe_pacing_ms: u64,
+    /// Name a Binance market's known streams in its connection URL instead of subscribing
+    /// them one request at a time after connecting (`BINANCE_COMBINED_STREAMS`)
+    pub binance_combined_streams: bool,
+    /// Stream Binance order books as deltas kept in sync from the diff depth stream and REST
+    /// snapshots instead of partial book snapshots (`BINANCE_ORDERBOOK_DELTAS`)
+    pub binance_orderbook_deltas: bool,
+    /// Minimum milliseconds between subscribe frames on one Bybit connection
+    pub bybit_subscribe_pacing_ms: u64,
+    /// Distinct topics the stream hub creates before refusing new ones
+    pub max_hub_topics: usize,
+    /// Messages each stream hub channel buffers before slow receivers lag and drop some
+    pub stream_channel_capacity: usize,
+    /// Milliseconds of exchange clock skew before a warning is logged; 0 disables the warning
+    pub clock_skew_warn_ms: u64,
+    /// Largest buffer a WebSocket session pre-sizes from its previous message; 0 disables sizing
+    pub ws_serialize_buffer_bytes: usize,
+    /// Messages a client may send per second before the server closes it with 1008; 0 disables
+    pub ws_max_messages_per_sec: u32,
+    /// Seconds without client frames before the server closes the session with 4000; 0 disables
+    pub ws_idle_timeout_secs: u64,
+    /// Serve lifecycle events as server-sent events on `/admin/events` (`ENABLE_ADMIN_EVENTS`)
+    pub enable_admin_events: bool,
+    /// Serve `POST /admin/maintenance` to switch maintenance mode (`ENABLE_ADMIN_MAINTENANCE`)
+    pub enable_admin_maintenance: bool,
+    /// Bearer token every `/admin` endpoint requires (`ADMIN_TOKEN`); unset keeps them off
+    pub admin_token: Option<String>,
+    /// Seconds open sessions keep streaming after maintenance starts before they are closed
+    /// with 1001; 0 leaves them open (`MAINTENANCE_DRAIN_GRACE_SECS`)
+    pub maintenance_drain_grace_secs: u64,
+    /// Seconds `/ready?upstream=true` reuses its last exchange ping results (`UPSTREAM_PROBE_CACHE_SECS`)
+    pub upstream_probe_cache_secs: u64,
+    /// JSON file of symbol mappings merged over the built-in ones (`SYMBOL_OVERRIDES_PATH`)
+    pub symbol_overrides_path: Option<String>,
+    pub rest_urls: RestUrls,
+    pub ws_urls: WsUrls,
+}
+
+/// REST roots used for exchange metadata and candles, per exchange and purpose
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestUrls {
+    pub binance_catalog: String,
+    /// USD-M futures root the Binance perpetual instrument list is fetched from
+    pub binance_perp_catalog: String,
+    pub binance_spot_candles: String,
+    pub binance_perp_candles: String,
+    pub bybit_catalog: String,
+    pub bybit_candles: String,
+}
+
+impl RestUrls {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str, default: String| {
+            env::var(name)
+                .ok()
+                .map(|url| url.trim().trim_end_matches('/').to_string())
+                .filter(|url| !url.is_empty())
+                .unwrap_or(default)
+        };
+
+        Self {
+            binance_catalog: var("BINANCE_CATALOG_URL", defaults.binance_catalog),
+            binance_perp_catalog: var("BINANCE_PERP_CATALOG_URL", defaults.binance_perp_catalog),
+            binance_spot_candles: var("BINANCE_SPOT_CANDLES_URL", defaults.binance_spot_candles),
+            binance_perp_candles: var("BINANCE_PERP_CANDLES_URL", defaults.binance_perp_candles),
+            bybit_catalog: var("BYBIT_CATALOG_URL", defaults.bybit_catalog),
+            bybit_candles: var("BYBIT_CANDLES_URL", defaults.bybit_candles),
+        }
+    }
+}
+
+/// Exchange WebSocket endpoints overriding the adapters' production ones, e.g. to stream
+/// from a testnet or a local mock; `None` keeps the adapter's default
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WsUrls {
+    pub binance_spot: Option<String>,
+    pub binance_perp: Option<String>,
+    pub bybit_spot: Option<String>,
+    /// USDT/USDC-margined contracts
+    pub bybit_linear: Option<String>,
+    /// Coin-margined contracts
+    pub bybit_inverse: Option<String>,
+    pub okx_public: Option<String>,
+}
+
+impl WsUrls {
+    pub fn from_env() -> Self {
+        let var = |name: &str| {
+            env::var(name)
+                .ok()
+                .map(|url| url.trim().to_string())
+                .filter(|url| !url.is_empty())
+        };
+
+        Self {
+            binance_spot: var("BINANCE_SPOT_WS_URL"),
+            binance_perp: var("BINANCE_PERP_WS_URL"),
+            bybit_spot: var("BYBIT_SPOT_WS_URL"),
+            bybit_linear: var("BYBIT_LINEAR_WS_URL"),
+            bybit_inverse: var("BYBIT_INVERSE_WS_URL"),
+            okx_public: var("OKX_WS_URL"),
+        }
+    }
+}
+
+impl Default for RestUrls {
+    fn default() -> Self {
+        Self {
+            binance_catalog: "https://api.binance.com".to_string(),
+            binance_perp_catalog: "https://fapi.binance.com".to_string(),
+            binance_spot_candles: "https://api.binance.com".to_string(),
+            binance_perp_candles: "https://fapi.binance.com".to_string(),
+            bybit_catalog: "https://api.bybit.com".to_string(),
+            bybit_candles: "https://api.bybit.com".to_string(),
+        }
+    }
 }
 
 impl Config {
     pub fn from_env() -> anyhow::Result<Self> {
+        let mut invalid_channel_specs = Vec::new();
+        let ws_default_channels =
+            channel_specs_from_env("WS_DEFAULT_CHANNELS", &mut invalid_channel_specs);
+        let cache_warm_channels =
+            channel_specs_from_env("CACHE_WARM_CHANNELS", &mut invalid_channel_specs);
+
         Ok(Config {
             bind_addr: env::var("BIND_ADDR").unwrap_or_else(|_| "0.0.0.0:8080".to_string()),
             exchanges: env::var("EXCHANGES")
-                .unwrap_or_else(|_| "binance,bybit".to_string())
+                .unwrap_or_else(|_| DEFAULT_EXCHANGES.join(","))
                 .split(',')
                 .map(|s| s.trim().to_string())
                 .collect(),
+            exchange_priority: env::var("EXCHANGE_PRIORITY")
+                .unwrap_or_else(|_| DEFAULT_EXCHANGES.join(","))
+                .split(',')
+                .map(|s| s.trim().to_lowercase())
+                .filter(|s| !s.is_empty())
+                .collect(),
             enable_redis: env::var("ENABLE_REDIS")
                 .unwrap_or_else(|_| "false".to_string())
                 .parse()
                 .unwrap_or(false),
             redis_url: env::var("REDIS_URL")
                 .unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string()),
+            cache_namespace: env::var("CACHE_NAMESPACE").unwrap_or_default(),
             book_depth_default: env::var("BOOK_DEPTH_DEFAULT")
                 .unwrap_or_else(|_| "50".to_string())
                 .parse()
                 .unwrap_or(50),
+            cache_book_depth: env::var("CACHE_BOOK_DEPTH")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .unwrap_or(0),
+            cache_ticker_max_age_secs: env::var("CACHE_TICKER_MAX_AGE_SECS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .unwrap_or(0),
+            topic_key_scheme: env::var("TOPIC_KEY_SCHEME")
+                .ok()
+                .and_then(|scheme| TopicKeyScheme::parse(&scheme))
+                .unwrap_or_default(),
             log_level: env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string()),
+            log_json: env::var("LOG_FORMAT")
+                .map(|format| format.trim().eq_ignore_ascii_case("json"))
+                .unwrap_or(false),
             enable_real_connections: env::var("ENABLE_REAL_CONNECTIONS")
                 .unwrap_or_else(|_| "true".to_string())
                 .parse()
                 .unwrap_or(true),
+            max_upstream_subscriptions: env::var("MAX_UPSTREAM_SUBSCRIPTIONS")
+                .unwrap_or_else(|_| "500".to_string())
+                .parse()
+                .unwrap_or(500),
+            preconnect_markets: env::var("PRECONNECT_MARKETS")
+                .unwrap_or_default()
+                .split(',')
+                .filter_map(|s| match s.trim() {
+                    "spot" => Some(MarketType::Spot),
+                    "perpetual" => Some(MarketType::Perpetual),
+                    "inverse_perpetual" => Some(MarketType::InversePerpetual),
+                    _ => None,
+                })
+                .collect(),
+            ws_default_channels,
+            cache_warm_channels,
+            invalid_channel_specs,
+            stats_interval_secs: env::var("STATS_INTERVAL_SECS")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .unwrap_or(5),
+            status_coalesce_ms: env::var("STATUS_COALESCE_MS")
+                .unwrap_or_else(|_| "1000".to_string())
+                .parse()
+                .unwrap_or(1000),
+            book_resync: env::var("BOOK_RESYNC")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true),
+            subscribe_timeout_secs: env::var("SUBSCRIBE_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .unwrap_or(10),
+            binance_subscribe_pacing_ms: env::var("BINANCE_SUBSCRIBE_PACING_MS")
+                .unwrap_or_else(|_| "200".to_string())
+                .parse()
+                .unwrap_or(200),
+            binance_combined_streams: env::var("BINANCE_COMBINED_STREAMS")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            binance_orderbook_deltas: env::var("BINANCE_ORDERBOOK_DELTAS")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            bybit_subscribe_pacing_ms: env::var("BYBIT_SUBSCRIBE_PACING_MS")
+                .unwrap_or_else(|_| "100".to_string())
+                .parse()
+                .unwrap_or(100),
+            max_hub_topics: env::var("MAX_HUB_TOPICS")
+                .unwrap_or_else(|_| "10000".to_string())
+                .parse()
+                .unwrap_or(10000),
+            stream_channel_capacity: env::var("STREAM_CHANNEL_CAPACITY")
+                .unwrap_or_else(|_| "1000".to_string())
+                .parse()
+                .unwrap_or(1000),
+            clock_skew_warn_ms: env::var("CLOCK_SKEW_WARN_MS")
+                .unwrap_or_else(|_| "1000".to_string())
+                .parse()
+                .unwrap_or(1000),
+            ws_serialize_buffer_bytes: env::var("WS_SERIALIZE_BUFFER_BYTES")
+                .unwrap_or_else(|_| "65536".to_string())
+                .parse()
+                .unwrap_or(65536),
+            ws_max_messages_per_sec: env::var("WS_MAX_MESSAGES_PER_SEC")
+                .unwrap_or_else(|_| "50".to_string())
+                .parse()
+                .unwrap_or(50),
+            ws_idle_timeout_secs: env::var("WS_IDLE_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .unwrap_or(0),
+            enable_admin_events: env::var("ENABLE_ADMIN_EVENTS")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            enable_admin_maintenance: env::var("ENABLE_ADMIN_MAINTENANCE")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            admin_token: env::var("ADMIN_TOKEN")
+                .ok()
+                .map(|token| token.trim().to_string())
+                .filter(|token| !token.is_empty()),
+            maintenance_drain_grace_secs: env::var("MAINTENANCE_DRAIN_GRACE_SECS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .unwrap_or(0),
+            upstream_probe_cache_secs: env::var("UPSTREAM_PROBE_CACHE_SECS")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .unwrap_or(5),
+            symbol_overrides_path: env::var("SYMBOL_OVERRIDES_PATH")
+                .ok()
+                .map(|path| path.trim().to_string())
+                .filter(|path| !path.is_empty()),
+            rest_urls: RestUrls::from_env(),
+            ws_urls: WsUrls::from_env(),
+        })
+    }
+}
+
+/// Parse a comma-separated channel list from `var`, recording entries that do not parse
+fn channel_specs_from_env(var: &str, invalid: &mut Vec<String>) -> Vec<Channel> {
+    parse_channel_specs(var, &env::var(var).unwrap_or_default(), invalid)
+}
+
+fn parse_channel_specs(var: &str, list: &str, invalid: &mut Vec<String>) -> Vec<Channel> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|spec| !spec.is_empty())
+        .filter_map(|spec| {
+            let channel = parse_channel_spec(spec);
+            if channel.is_none() {
+                invalid.push(format!("{}: {}", var, spec));
+            }
+            channel
         })
+        .collect()
+}
+
+/// Parse a channel written like a topic key, `<channel>:<exchange>:<market>:<BASE>-<QUOTE>`,
+/// e.g. `ticker:binance:spot:BTC-USDT`
+fn parse_channel_spec(spec: &str) -> Option<Channel> {
+    let mut parts = spec.trim().split(':');
+    let channel_type = match parts.next()? {
+        "ticker" => ChannelType::Ticker,
+        "orderbook" => ChannelType::OrderBook,
+        "funding" => ChannelType::Funding,
+        "trades" => ChannelType::Trades,
+        _ => return None,
+    };
+    let exchange = ExchangeId::from(parts.next()?.to_lowercase().as_str());
+    let market_type = match parts.next()? {
+        "spot" => MarketType::Spot,
+        "perpetual" => MarketType::Perpetual,
+        "inverse_perpetual" => MarketType::InversePerpetual,
+        "future" => MarketType::Future,
+        _ => return None,
+    };
+    let (base, quote) = parts.next()?.split_once('-')?;
+    if parts.next().is_some() || base.is_empty() || quote.is_empty() {
+        return None;
+    }
+
+    Some(Channel {
+        channel_type,
+        exchange,
+        market_type,
+        symbol: Symbol::new(base.to_uppercase(), quote.to_uppercase()),
+        depth: None,
+    })
+}
+
+impl Config {
+    /// Composite ticker preference order built from `exchange_priority`
+    pub fn exchange_priority(&self) -> ExchangePriority {
+        ExchangePriority::new(
+            self.exchange_priority
+                .iter()
+                .map(|name| ExchangeId::from(name.as_str())),
+        )
     }
 }
 
@@ -44,12 +389,103 @@ impl Default for Config {
     fn default() -> Self {
         Self {
             bind_addr: "0.0.0.0:8080".to_string(),
-            exchanges: vec!["binance".to_string(), "bybit".to_string()],
+            exchanges: DEFAULT_EXCHANGES.iter().map(|id| id.to_string()).collect(),
+            exchange_priority: DEFAULT_EXCHANGES.iter().map(|id| id.to_string()).collect(),
             enable_redis: false,
             redis_url: "redis://127.0.0.1:6379".to_string(),
+            cache_namespace: String::new(),
             book_depth_default: 50,
+            cache_book_depth: 0,
+            cache_ticker_max_age_secs: 0,
+            topic_key_scheme: TopicKeyScheme::V1,
             log_level: "info".to_string(),
+            log_json: false,
             enable_real_connections: true,
+            max_upstream_subscriptions: 500,
+            preconnect_markets: Vec::new(),
+            ws_default_channels: Vec::new(),
+            cache_warm_channels: Vec::new(),
+            invalid_channel_specs: Vec::new(),
+            stats_interval_secs: 5,
+            status_coalesce_ms: 1000,
+            book_resync: true,
+            subscribe_timeout_secs: 10,
+            binance_subscribe_pacing_ms: 200,
+            binance_combined_streams: false,
+            binance_orderbook_deltas: false,
+            bybit_subscribe_pacing_ms: 100,
+            max_hub_topics: 10000,
+            stream_channel_capacity: 1000,
+            clock_skew_warn_ms: 1000,
+            ws_serialize_buffer_bytes: 65536,
+            ws_max_messages_per_sec: 50,
+            ws_idle_timeout_secs: 0,
+            enable_admin_events: false,
+            enable_admin_maintenance: false,
+            admin_token: None,
+            maintenance_drain_grace_secs: 0,
+            upstream_probe_cache_secs: 5,
+            symbol_overrides_path: None,
+            rest_urls: RestUrls::default(),
+            ws_urls: WsUrls::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_exchanges_leave_out_okx() {
+        let config = Config::default();
+        assert_eq!(config.exchanges, ["binance", "bybit"]);
+        assert_eq!(config.exchange_priority, config.exchanges);
+    }
+
+    #[test]
+    fn channel_specs_use_topic_key_order() {
+        assert_eq!(
+            parse_channel_spec(" ticker:binance:spot:btc-usdt"),
+            Some(Channel {
+                channel_type: ChannelType::Ticker,
+                exchange: ExchangeId::from("binance"),
+                market_type: MarketType::Spot,
+                symbol: Symbol::new("BTC", "USDT"),
+                depth: None,
+            })
+        );
+        assert_eq!(
+            parse_channel_spec("orderbook:bybit:perpetual:ETH-USDT").map(|c| c.channel_type),
+            Some(ChannelType::OrderBook)
+        );
+
+        for invalid in [
+            "",
+            "ticker:binance:spot",
+            "quotes:binance:spot:BTC-USDT",
+            "ticker:binance:spot:BTCUSDT",
+        ] {
+            assert_eq!(parse_channel_spec(invalid), None, "{}", invalid);
         }
+        assert_eq!(
+            parse_channel_spec("ticker:binance:future:BTC-USDT").map(|c| c.market_type),
+            Some(MarketType::Future)
+        );
+
+        let mut invalid = Vec::new();
+        let channels = parse_channel_specs(
+            "WS_DEFAULT_CHANNELS",
+            "ticker:binance:spot:BTC-USDT, ticker:binance:spot:BTCUSDT,,quotes:bybit:spot:ETH-USDT",
+            &mut invalid,
+        );
+        assert_eq!(channels.len(), 1);
+        assert_eq!(
+            invalid,
+            [
+                "WS_DEFAULT_CHANNELS: ticker:binance:spot:BTCUSDT",
+                "WS_DEFAULT_CHANNELS: quotes:bybit:spot:ETH-USDT",
+            ]
+        );
     }
 }
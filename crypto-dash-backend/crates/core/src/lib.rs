@@ -1,9 +1,11 @@
+pub mod composite;
 pub mod config;
 pub mod model;
 pub mod normalize;
 pub mod time;
 
 pub mod prelude {
+    pub use crate::composite::*;
     pub use crate::config::*;
     pub use crate::model::*;
     pub use crate::normalize::*;
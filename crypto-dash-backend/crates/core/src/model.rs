@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -8,9 +8,21 @@ use std::collections::HashMap;
 pub struct ExchangeId(pub String);
 
 impl ExchangeId {
+    pub const BINANCE: &'static str = "binance";
+    pub const BYBIT: &'static str = "bybit";
+    pub const OKX: &'static str = "okx";
+
+    /// Exchange ids that have an adapter implementation
+    pub const KNOWN: &'static [&'static str] = &[Self::BINANCE, Self::BYBIT, Self::OKX];
+
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Check whether this id belongs to a registered exchange
+    pub fn is_known(&self) -> bool {
+        Self::KNOWN.contains(&self.as_str())
+    }
 }
 
 impl From<&str> for ExchangeId {
@@ -24,6 +36,9 @@ impl From<&str> for ExchangeId {
 pub struct Symbol {
     pub base: String,
     pub quote: String,
+    /// Delivery date of a dated future; absent for spot and perpetual instruments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiry: Option<NaiveDate>,
 }
 
 impl Symbol {
@@ -31,11 +46,55 @@ impl Symbol {
         Self {
             base: base.into(),
             quote: quote.into(),
+            expiry: None,
+        }
+    }
+
+    /// Same pair delivering on the given date
+    pub fn with_expiry(mut self, expiry: NaiveDate) -> Self {
+        self.expiry = Some(expiry);
+        self
+    }
+
+    /// `BASE-QUOTE`, or `BASE-QUOTE-YYYYMMDD` for dated futures
+    pub fn canonical(&self) -> String {
+        match self.expiry {
+            Some(expiry) => format!("{}-{}-{}", self.base, self.quote, expiry.format("%Y%m%d")),
+            None => format!("{}-{}", self.base, self.quote),
+        }
+    }
+}
+
+/// Symbol qualified by its market, so spot and perpetual instruments stay distinct in logs
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct InstrumentId {
+    pub symbol: Symbol,
+    pub market_type: MarketType,
+}
+
+impl InstrumentId {
+    pub fn new(symbol: Symbol, market_type: MarketType) -> Self {
+        Self {
+            symbol,
+            market_type,
         }
     }
 
+    /// `BASE-QUOTE:spot`, `BASE-QUOTE:perp`, `BASE-QUOTE:inverse` or `BASE-QUOTE-YYYYMMDD:future`
     pub fn canonical(&self) -> String {
-        format!("{}-{}", self.base, self.quote)
+        let market = match self.market_type {
+            MarketType::Spot => "spot",
+            MarketType::Perpetual => "perp",
+            MarketType::InversePerpetual => "inverse",
+            MarketType::Future => "future",
+        };
+        format!("{}:{}", self.symbol.canonical(), market)
+    }
+}
+
+impl std::fmt::Display for InstrumentId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.canonical())
     }
 }
 
@@ -45,6 +104,10 @@ impl Symbol {
 pub enum MarketType {
     Spot,
     Perpetual,
+    /// Coin-margined perpetual quoted in USD, e.g. Bybit's `BTCUSD`
+    InversePerpetual,
+    /// Dated future; the delivery date is carried by `Symbol::expiry`
+    Future,
 }
 
 impl Default for MarketType {
@@ -53,6 +116,88 @@ impl Default for MarketType {
     }
 }
 
+impl MarketType {
+    /// Name used in JSON, topic keys and query params, e.g. `inverse_perpetual`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarketType::Spot => "spot",
+            MarketType::Perpetual => "perpetual",
+            MarketType::InversePerpetual => "inverse_perpetual",
+            MarketType::Future => "future",
+        }
+    }
+}
+
+impl std::fmt::Display for MarketType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A market type name that `MarketType::from_str` does not recognise
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownMarketType(pub String);
+
+impl std::fmt::Display for UnknownMarketType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown market type '{}', expected spot, perp, perpetual, inverse_perpetual or future",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnknownMarketType {}
+
+/// Accepts the serialized names in any case, plus the `perp` and `inverse_perp` shorthands
+impl std::str::FromStr for MarketType {
+    type Err = UnknownMarketType;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "spot" => Ok(MarketType::Spot),
+            "perp" | "perpetual" => Ok(MarketType::Perpetual),
+            "inverse_perp" | "inverse_perpetual" => Ok(MarketType::InversePerpetual),
+            "future" => Ok(MarketType::Future),
+            _ => Err(UnknownMarketType(value.to_string())),
+        }
+    }
+}
+
+/// Aggressor side of a trade or liquidation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+impl Side {
+    /// Parse an exchange's side field.
+    ///
+    /// Accepts `"Buy"`/`"Sell"` and `"b"`/`"s"` in any case. For Binance, `"true"`/`"false"`
+    /// is read as the trade's `m` (buyer is maker) flag.
+    pub fn from_exchange(exchange: &str, raw: &str) -> Option<Self> {
+        match (exchange, raw.trim().to_ascii_lowercase().as_str()) {
+            (ExchangeId::BINANCE, "true") => Some(Self::from_buyer_maker(true)),
+            (ExchangeId::BINANCE, "false") => Some(Self::from_buyer_maker(false)),
+            (_, "buy" | "b") => Some(Self::Buy),
+            (_, "sell" | "s") => Some(Self::Sell),
+            _ => None,
+        }
+    }
+
+    /// Aggressor side from Binance's `m` flag: a maker buyer means the taker sold
+    pub fn from_buyer_maker(is_buyer_maker: bool) -> Self {
+        if is_buyer_maker {
+            Self::Sell
+        } else {
+            Self::Buy
+        }
+    }
+}
+
 /// Exchange-specific symbol information (legacy)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SymbolInfo {
@@ -79,6 +224,9 @@ pub struct SymbolMeta {
     pub step_size: Decimal,
     pub filters: Option<HashMap<String, String>>,
     pub info: serde_json::Value,
+    /// Delivery date for dated futures
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiry: Option<NaiveDate>,
 }
 
 /// Price level in order book
@@ -107,6 +255,116 @@ pub struct Ticker {
     pub last: Decimal,
     pub bid_size: Decimal,
     pub ask_size: Decimal,
+    /// 24h traded value in the quote asset, when the feed provides it
+    #[serde(default)]
+    pub quote_volume_24h: Option<Decimal>,
+    /// 24h price change in percent, e.g. `2.5` for +2.5%
+    #[serde(default)]
+    pub change_pct_24h: Option<Decimal>,
+    /// Whether the last price rose or fell against the previous tick
+    #[serde(default)]
+    pub tick_direction: Option<TickDirection>,
+    /// Highest traded price over the last 24h
+    #[serde(default)]
+    pub high_24h: Option<Decimal>,
+    /// Lowest traded price over the last 24h
+    #[serde(default)]
+    pub low_24h: Option<Decimal>,
+    /// 24h traded amount in the base asset
+    #[serde(default)]
+    pub volume_24h: Option<Decimal>,
+}
+
+impl Ticker {
+    /// Midpoint of bid and ask, or None if the sum overflows
+    pub fn mid(&self) -> Option<Decimal> {
+        self.bid.checked_add(self.ask)?.checked_div(Decimal::TWO)
+    }
+
+    /// Ask minus bid, or None on overflow
+    pub fn spread(&self) -> Option<Decimal> {
+        self.ask.checked_sub(self.bid)
+    }
+
+    /// Spread relative to the mid in basis points, or None on overflow or a zero mid
+    pub fn spread_bps(&self) -> Option<Decimal> {
+        self.spread()?
+            .checked_mul(Decimal::from(10_000))?
+            .checked_div(self.mid()?)
+    }
+}
+
+/// Direction of the last price move, e.g. to color a price green or red
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TickDirection {
+    Up,
+    Down,
+    /// Unchanged, or no earlier price to compare with
+    Neutral,
+}
+
+impl TickDirection {
+    /// Compare a new last price with the previous one, if any
+    pub fn between(previous: Option<Decimal>, last: Decimal) -> Self {
+        match previous {
+            Some(previous) if last > previous => TickDirection::Up,
+            Some(previous) if last < previous => TickDirection::Down,
+            _ => TickDirection::Neutral,
+        }
+    }
+}
+
+/// Funding rate and mark price of a perpetual market
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FundingRate {
+    pub timestamp: DateTime<Utc>,
+    pub exchange: ExchangeId,
+    #[serde(default)]
+    pub market_type: MarketType,
+    pub symbol: Symbol,
+    /// Rate for the current funding interval, e.g. `0.0001` for 0.01%
+    pub rate: Decimal,
+    pub next_funding_time: DateTime<Utc>,
+    /// Price used for funding and liquidations, when the feed includes it
+    #[serde(default)]
+    pub mark_price: Option<Decimal>,
+    #[serde(default)]
+    pub index_price: Option<Decimal>,
+}
+
+impl FundingRate {
+    /// Time left until the next funding event, or None if it has already passed
+    pub fn countdown(&self, now: DateTime<Utc>) -> Option<std::time::Duration> {
+        crate::time::funding_countdown(self.next_funding_time, now)
+    }
+}
+
+/// Public trade print
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trade {
+    pub timestamp: DateTime<Utc>,
+    pub exchange: ExchangeId,
+    #[serde(default)]
+    pub market_type: MarketType,
+    pub symbol: Symbol,
+    pub price: Decimal,
+    pub quantity: Decimal,
+    pub side: Side,
+    pub trade_id: Option<String>,
+}
+
+/// Forced liquidation order on a derivatives market
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Liquidation {
+    pub timestamp: DateTime<Utc>,
+    pub exchange: ExchangeId,
+    #[serde(default)]
+    pub market_type: MarketType,
+    pub symbol: Symbol,
+    pub price: Decimal,
+    pub quantity: Decimal,
+    pub side: Side,
 }
 
 /// Candlestick data point
@@ -152,6 +410,10 @@ pub struct OrderBookDelta {
 pub enum ChannelType {
     Ticker,
     OrderBook,
+    /// Funding rate and mark price; perpetual markets only
+    Funding,
+    /// Public trade prints
+    Trades,
 }
 
 /// Subscription channel specification
@@ -165,6 +427,35 @@ pub struct Channel {
     pub depth: Option<u16>, // for order book channels
 }
 
+impl Channel {
+    /// Market-qualified instrument this channel streams
+    pub fn instrument(&self) -> InstrumentId {
+        InstrumentId::new(self.symbol.clone(), self.market_type)
+    }
+}
+
+/// Layout of topic and cache keys; new fields only appear under a newer scheme so
+/// existing stored keys stay valid until an operator opts in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TopicKeyScheme {
+    /// `{channel}:{exchange}:{market}:{symbol}`
+    #[default]
+    V1,
+    /// v1 followed by `:{depth}` when the key has a book depth
+    V2,
+}
+
+impl TopicKeyScheme {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "v1" => Some(Self::V1),
+            "v2" => Some(Self::V2),
+            _ => None,
+        }
+    }
+}
+
 /// WebSocket message types sent to clients
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "payload")]
@@ -173,8 +464,108 @@ pub enum StreamMessage {
     Ticker(Ticker),
     OrderBookSnapshot(OrderBookSnapshot),
     OrderBookDelta(OrderBookDelta),
-    Info { message: String },
-    Error { message: String },
+    FundingRate(FundingRate),
+    Trade(Trade),
+    /// The upstream feed reconnected: discard the local book, a fresh snapshot follows
+    OrderBookReset {
+        exchange: ExchangeId,
+        market_type: MarketType,
+        symbol: Symbol,
+    },
+    /// Subscribe ack for an order book channel with the granularity the exchange streams
+    OrderBookSubscribed {
+        exchange: ExchangeId,
+        market_type: MarketType,
+        symbol: Symbol,
+        /// Levels per side after clamping to a depth the exchange supports
+        depth: u16,
+        /// Milliseconds between exchange updates
+        update_speed: u32,
+    },
+    Info {
+        message: String,
+    },
+    Error {
+        message: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        code: Option<ErrorCode>,
+    },
+    Stats(StreamStats),
+}
+
+impl StreamMessage {
+    /// Channel type a market data message belongs to; `None` for status and control messages
+    pub fn channel_type(&self) -> Option<ChannelType> {
+        match self {
+            StreamMessage::Ticker(_) => Some(ChannelType::Ticker),
+            StreamMessage::OrderBookSnapshot(_)
+            | StreamMessage::OrderBookDelta(_)
+            | StreamMessage::OrderBookReset { .. }
+            | StreamMessage::OrderBookSubscribed { .. } => Some(ChannelType::OrderBook),
+            StreamMessage::FundingRate(_) => Some(ChannelType::Funding),
+            StreamMessage::Trade(_) => Some(ChannelType::Trades),
+            StreamMessage::Info { .. } | StreamMessage::Error { .. } | StreamMessage::Stats(_) => {
+                None
+            }
+        }
+    }
+}
+
+/// Machine-readable reason attached to some `Error` messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// The exchange neither acknowledged a subscribe nor sent data for it in time
+    SubscribeTimeout,
+    /// Subscribing would create a topic past the hub's `MAX_HUB_TOPICS` limit
+    TopicLimit,
+}
+
+/// Periodic server statistics sent to sessions that opt in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamStats {
+    pub timestamp: DateTime<Utc>,
+    pub connected_exchanges: Vec<ExchangeId>,
+    pub active_topics: usize,
+    pub messages_per_second: f64,
+}
+
+/// Stream message wrapped with forwarding metadata for sessions that opt in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamEnvelope {
+    /// Unix milliseconds at which the server forwarded the message
+    pub server_ts: i64,
+    pub topic: String,
+    pub data: StreamMessage,
+}
+
+/// System lifecycle event for observability: client sessions, subscriptions and upstream links
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum LifecycleEvent {
+    ClientConnected {
+        session_id: String,
+    },
+    ClientDisconnected {
+        session_id: String,
+    },
+    Subscribed {
+        session_id: String,
+        channel: Channel,
+    },
+    Unsubscribed {
+        session_id: String,
+        channel: Channel,
+    },
+    UpstreamConnected {
+        exchange: ExchangeId,
+        market_type: MarketType,
+    },
+    UpstreamDisconnected {
+        exchange: ExchangeId,
+        market_type: MarketType,
+        reason: String,
+    },
 }
 
 /// WebSocket operations from clients
@@ -182,9 +573,22 @@ pub enum StreamMessage {
 #[serde(tag = "op")]
 #[serde(rename_all = "snake_case")]
 pub enum ClientMessage {
-    Subscribe { channels: Vec<Channel> },
-    Unsubscribe { channels: Vec<Channel> },
+    Subscribe {
+        channels: Vec<Channel>,
+        /// Also receive periodic `stats` messages on this session
+        #[serde(default)]
+        stats: bool,
+        /// Wrap stream messages in a `StreamEnvelope` on this session
+        #[serde(default)]
+        envelope: bool,
+    },
+    Unsubscribe {
+        channels: Vec<Channel>,
+    },
     Ping,
+    /// Any op this server does not recognise
+    #[serde(other)]
+    Unknown,
 }
 
 /// Exchange metadata
@@ -196,6 +600,52 @@ pub struct ExchangeInfo {
     pub rate_limits: HashMap<String, u32>,
     pub ws_url: String,
     pub rest_url: String,
+    #[serde(default)]
+    pub markets: Vec<MarketStatus>,
+}
+
+/// Per-market connection status for an exchange
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketStatus {
+    pub market_type: MarketType,
+    pub connected: bool,
+    #[serde(flatten)]
+    pub stats: ConnectionStats,
+}
+
+/// Connection attempt history for an exchange market
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConnectionStats {
+    pub last_connect_error: Option<String>,
+    /// Failed connection attempts since the last successful connect
+    pub reconnect_attempts: u32,
+    pub last_connected_at: Option<DateTime<Utc>>,
+    /// Connect circuit breaker state; anything but `closed` means connects fail fast
+    #[serde(default)]
+    pub circuit: CircuitState,
+}
+
+/// Upstream message counts of an exchange adapter since it was created.
+///
+/// Counters only grow; rates come from the difference between two snapshots.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdapterMetrics {
+    /// Text frames read from the exchange's WebSockets
+    pub messages_received: u64,
+    /// Market data messages published to the hub
+    pub messages_published: u64,
+    /// Frames that did not decode, or decoded but could not be normalized
+    pub parse_errors: u64,
+}
+
+/// State of a market's connect circuit breaker
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    #[default]
+    Closed,
+    Open,
+    HalfOpen,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -204,6 +654,8 @@ pub enum ExchangeStatus {
     Online,
     Offline,
     Maintenance,
+    /// A market's connect circuit breaker is open or half-open
+    Degraded,
 }
 
 #[cfg(test)]
@@ -216,6 +668,111 @@ mod tests {
         assert_eq!(symbol.canonical(), "BTC-USDT");
     }
 
+    #[test]
+    fn test_market_type_from_str() {
+        assert_eq!("perp".parse(), Ok(MarketType::Perpetual));
+        assert_eq!(" Perpetual ".parse(), Ok(MarketType::Perpetual));
+        assert_eq!("spot".parse(), Ok(MarketType::Spot));
+        assert_eq!("inverse_perp".parse(), Ok(MarketType::InversePerpetual));
+
+        let err = "margin".parse::<MarketType>().unwrap_err();
+        assert_eq!(err, UnknownMarketType("margin".to_string()));
+        assert!(err.to_string().starts_with("unknown market type 'margin'"));
+
+        // Display matches the serialized name, so it parses back
+        for market_type in [
+            MarketType::Spot,
+            MarketType::Perpetual,
+            MarketType::InversePerpetual,
+            MarketType::Future,
+        ] {
+            assert_eq!(
+                serde_json::to_value(market_type).unwrap(),
+                serde_json::json!(market_type.to_string())
+            );
+            assert_eq!(market_type.to_string().parse(), Ok(market_type));
+        }
+    }
+
+    #[test]
+    fn test_dated_future_round_trip() {
+        let expiry = NaiveDate::from_ymd_opt(2024, 6, 28).unwrap();
+        let instrument = InstrumentId::new(
+            Symbol::new("BTC", "USD").with_expiry(expiry),
+            MarketType::Future,
+        );
+
+        let json = serde_json::to_value(&instrument).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "symbol": {"base": "BTC", "quote": "USD", "expiry": "2024-06-28"},
+                "market_type": "future"
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<InstrumentId>(json).unwrap(),
+            instrument
+        );
+        assert_eq!(instrument.canonical(), "BTC-USD-20240628:future");
+
+        // Undated symbols keep their existing wire format
+        let spot = serde_json::to_value(Symbol::new("BTC", "USDT")).unwrap();
+        assert_eq!(spot, serde_json::json!({"base": "BTC", "quote": "USDT"}));
+    }
+
+    #[test]
+    fn test_instrument_id_distinguishes_markets() {
+        let symbol = Symbol::new("BTC", "USDT");
+        let spot = InstrumentId::new(symbol.clone(), MarketType::Spot);
+        let perp = InstrumentId::new(symbol, MarketType::Perpetual);
+
+        assert_eq!(spot.canonical(), "BTC-USDT:spot");
+        assert_eq!(perp.canonical(), "BTC-USDT:perp");
+        assert_ne!(spot, perp);
+        assert_eq!(perp.to_string(), perp.canonical());
+    }
+
+    #[test]
+    fn test_side_from_binance_buyer_maker() {
+        // isBuyerMaker=true means the seller hit the bid
+        assert_eq!(Side::from_buyer_maker(true), Side::Sell);
+        assert_eq!(Side::from_buyer_maker(false), Side::Buy);
+        assert_eq!(
+            Side::from_exchange(ExchangeId::BINANCE, "true"),
+            Some(Side::Sell)
+        );
+        assert_eq!(
+            Side::from_exchange(ExchangeId::BINANCE, "false"),
+            Some(Side::Buy)
+        );
+        assert_eq!(
+            Side::from_exchange(ExchangeId::BINANCE, "SELL"),
+            Some(Side::Sell)
+        );
+    }
+
+    #[test]
+    fn test_side_from_bybit() {
+        assert_eq!(
+            Side::from_exchange(ExchangeId::BYBIT, "Buy"),
+            Some(Side::Buy)
+        );
+        assert_eq!(
+            Side::from_exchange(ExchangeId::BYBIT, "Sell"),
+            Some(Side::Sell)
+        );
+        assert_eq!(Side::from_exchange(ExchangeId::BYBIT, "true"), None);
+        assert_eq!(Side::from_exchange(ExchangeId::BYBIT, "b"), Some(Side::Buy));
+    }
+
+    #[test]
+    fn test_exchange_id_registry() {
+        assert!(ExchangeId::from(ExchangeId::BINANCE).is_known());
+        assert!(ExchangeId::from(ExchangeId::BYBIT).is_known());
+        assert!(!ExchangeId::from("binanace").is_known());
+    }
+
     #[test]
     fn test_price_level_creation() {
         let level = PriceLevel::new(Decimal::new(50000, 0), Decimal::new(1, 1));
@@ -223,6 +780,78 @@ mod tests {
         assert_eq!(level.quantity, Decimal::new(1, 1));
     }
 
+    fn quote(bid: Decimal, ask: Decimal) -> Ticker {
+        Ticker {
+            timestamp: Utc::now(),
+            exchange: ExchangeId::from("binance"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            bid,
+            ask,
+            last: bid,
+            bid_size: Decimal::ONE,
+            ask_size: Decimal::ONE,
+            quote_volume_24h: None,
+            change_pct_24h: None,
+            tick_direction: None,
+            high_24h: None,
+            low_24h: None,
+            volume_24h: None,
+        }
+    }
+
+    #[test]
+    fn test_ticker_mid_and_spread() {
+        let ticker = quote(Decimal::new(9990, 2), Decimal::new(10010, 2));
+        assert_eq!(ticker.mid(), Some(Decimal::new(100, 0)));
+        assert_eq!(ticker.spread(), Some(Decimal::new(20, 2)));
+        assert_eq!(ticker.spread_bps(), Some(Decimal::new(20, 0)));
+    }
+
+    #[test]
+    fn test_ticker_spread_helpers_never_panic() {
+        // Summing near-MAX sides overflows the mid
+        let huge = quote(Decimal::MAX, Decimal::MAX - Decimal::ONE);
+        assert_eq!(huge.mid(), None);
+        assert_eq!(huge.spread_bps(), None);
+
+        // Spanning MIN..MAX overflows the spread itself
+        let wide = quote(Decimal::MIN, Decimal::MAX);
+        assert_eq!(wide.spread(), None);
+        assert_eq!(wide.spread_bps(), None);
+
+        // A zero mid cannot be divided by
+        let empty = quote(Decimal::ZERO, Decimal::ZERO);
+        assert_eq!(empty.mid(), Some(Decimal::ZERO));
+        assert_eq!(empty.spread_bps(), None);
+
+        let crossed = quote(Decimal::ONE, -Decimal::ONE);
+        assert_eq!(crossed.mid(), Some(Decimal::ZERO));
+        assert_eq!(crossed.spread_bps(), None);
+    }
+
+    #[test]
+    fn test_tick_direction_between() {
+        let price = Decimal::new(50000, 0);
+        assert_eq!(TickDirection::between(None, price), TickDirection::Neutral);
+        assert_eq!(
+            TickDirection::between(Some(Decimal::new(49999, 0)), price),
+            TickDirection::Up
+        );
+        assert_eq!(
+            TickDirection::between(Some(Decimal::new(50001, 0)), price),
+            TickDirection::Down
+        );
+        assert_eq!(
+            TickDirection::between(Some(price), price),
+            TickDirection::Neutral
+        );
+        assert_eq!(
+            serde_json::to_value(TickDirection::Up).unwrap(),
+            serde_json::json!("up")
+        );
+    }
+
     #[test]
     fn ticker_defaults_to_spot_market() {
         let ticker = Ticker {
@@ -235,6 +864,12 @@ mod tests {
             last: Decimal::new(50005, 0),
             bid_size: Decimal::new(1, 0),
             ask_size: Decimal::new(1, 0),
+            quote_volume_24h: None,
+            change_pct_24h: None,
+            tick_direction: None,
+            high_24h: None,
+            low_24h: None,
+            volume_24h: None,
         };
 
         assert_eq!(ticker.market_type, MarketType::Spot);
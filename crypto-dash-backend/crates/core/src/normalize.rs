@@ -1,6 +1,7 @@
 use crate::model::{ExchangeId, Symbol};
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use std::collections::HashMap;
+use std::path::Path;
 
 /// Symbol normalization utilities
 #[derive(Clone)]
@@ -48,10 +49,53 @@ impl SymbolMapper {
             .cloned()
     }
 
+    /// Merge operator overrides from a JSON file over the current mappings, returning how
+    /// many were applied. The file maps exchange to exchange symbol to `BASE-QUOTE`:
+    /// `{"binance": {"1000PEPEUSDT": "1000PEPE-USDT"}}`
+    pub fn load_overrides(&mut self, path: impl AsRef<Path>) -> Result<usize> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let overrides: HashMap<String, HashMap<String, String>> =
+            serde_json::from_str(&contents)
+                .with_context(|| format!("failed to parse {}", path.display()))?;
+
+        // Validate everything first so a bad entry leaves the mapper untouched
+        let mut mappings = Vec::new();
+        for (exchange, symbols) in overrides {
+            let exchange = ExchangeId::from(exchange.trim().to_lowercase().as_str());
+            for (exchange_symbol, canonical) in symbols {
+                let (base, quote) = canonical
+                    .trim()
+                    .split_once('-')
+                    .filter(|(base, quote)| !base.is_empty() && !quote.is_empty())
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "{} {}: expected BASE-QUOTE, got {:?}",
+                            exchange.as_str(),
+                            exchange_symbol,
+                            canonical
+                        )
+                    })?;
+                mappings.push((
+                    exchange.clone(),
+                    exchange_symbol,
+                    Symbol::new(base.to_uppercase(), quote.to_uppercase()),
+                ));
+            }
+        }
+
+        let applied = mappings.len();
+        for (exchange, exchange_symbol, canonical) in mappings {
+            self.add_mapping(exchange, exchange_symbol, canonical);
+        }
+        Ok(applied)
+    }
+
     /// Load default mappings for common exchanges
     pub fn load_defaults(&mut self) {
         // Binance mappings
-        let binance = ExchangeId::from("binance");
+        let binance = ExchangeId::from(ExchangeId::BINANCE);
         self.add_mapping(
             binance.clone(),
             "BTCUSDT".to_string(),
@@ -74,7 +118,7 @@ impl SymbolMapper {
         );
 
         // Bybit mappings
-        let bybit = ExchangeId::from("bybit");
+        let bybit = ExchangeId::from(ExchangeId::BYBIT);
         self.add_mapping(
             bybit.clone(),
             "BTCUSDT".to_string(),
@@ -138,6 +182,65 @@ mod tests {
             Some(Symbol::new("BTC", "USDT"))
         );
     }
+
+    #[test]
+    fn test_override_file_merges_with_defaults() {
+        let path =
+            std::env::temp_dir().join(format!("symbol-overrides-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            r#"{"Binance": {"1000SATSUSDT": "1000sats-usdt", "BTCUSDT": "XBT-USDT"}}"#,
+        )
+        .unwrap();
+
+        let mut mapper = SymbolMapper::default();
+        let applied = mapper.load_overrides(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(applied.unwrap(), 2);
+
+        let binance = ExchangeId::from("binance");
+        assert_eq!(
+            mapper.to_canonical(&binance, "1000SATSUSDT"),
+            Some(Symbol::new("1000SATS", "USDT"))
+        );
+        assert_eq!(
+            mapper.to_exchange(&binance, &Symbol::new("1000SATS", "USDT")),
+            Some("1000SATSUSDT".to_string())
+        );
+        // Overrides win over built-ins, which otherwise stay in place
+        assert_eq!(
+            mapper.to_canonical(&binance, "BTCUSDT"),
+            Some(Symbol::new("XBT", "USDT"))
+        );
+        assert_eq!(
+            mapper.to_canonical(&binance, "ETHUSDT"),
+            Some(Symbol::new("ETH", "USDT"))
+        );
+    }
+
+    #[test]
+    fn test_invalid_override_file_leaves_mappings_untouched() {
+        let path =
+            std::env::temp_dir().join(format!("symbol-overrides-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            r#"{"bybit": {"BTCUSDT": "XBT-USDT", "PEPEUSDT": "PEPEUSDT"}}"#,
+        )
+        .unwrap();
+
+        let mut mapper = SymbolMapper::default();
+        let err = mapper.load_overrides(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert!(err.to_string().contains("PEPEUSDT"));
+
+        assert_eq!(
+            mapper.to_canonical(&ExchangeId::from("bybit"), "BTCUSDT"),
+            Some(Symbol::new("BTC", "USDT"))
+        );
+        assert!(SymbolMapper::new()
+            .load_overrides("/nonexistent/overrides.json")
+            .is_err());
+    }
 }
 
 /// Utility functions for symbol metadata normalization
@@ -176,7 +279,7 @@ pub fn precision_from_tick_size(tick_size: &str) -> Result<u32> {
 /// Normalize exchange symbol to canonical format
 pub fn normalize_symbol(exchange_symbol: &str, exchange: &ExchangeId) -> Symbol {
     match exchange.as_str() {
-        "binance" => {
+        ExchangeId::BINANCE => {
             // Binance uses concatenated format like "BTCUSDT"
             // This is a simple heuristic - in practice you'd use exchange API data
             if exchange_symbol.ends_with("USDT") {
@@ -193,7 +296,7 @@ pub fn normalize_symbol(exchange_symbol: &str, exchange: &ExchangeId) -> Symbol
                 Symbol::new(exchange_symbol, "USDT")
             }
         }
-        "bybit" => {
+        ExchangeId::BYBIT => {
             // Bybit also uses concatenated format
             if exchange_symbol.ends_with("USDT") {
                 let base = &exchange_symbol[..exchange_symbol.len() - 4];
@@ -15,6 +15,14 @@ pub fn to_millis(dt: DateTime<Utc>) -> i64 {
     dt.timestamp_millis()
 }
 
+/// Time remaining until the next funding event, or None if it has already passed
+pub fn funding_countdown(
+    next_funding_time: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Option<std::time::Duration> {
+    next_funding_time.signed_duration_since(now).to_std().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -28,4 +36,23 @@ mod tests {
         // Allow for small precision differences
         assert!((now.timestamp_millis() - converted.timestamp_millis()).abs() < 2);
     }
+
+    #[test]
+    fn test_funding_countdown_future() {
+        let now = from_millis(1_700_000_000_000).unwrap();
+        let next_funding = from_millis(1_700_000_090_000).unwrap();
+
+        assert_eq!(
+            funding_countdown(next_funding, now),
+            Some(std::time::Duration::from_secs(90))
+        );
+    }
+
+    #[test]
+    fn test_funding_countdown_past() {
+        let now = from_millis(1_700_000_000_000).unwrap();
+        let next_funding = from_millis(1_699_999_999_000).unwrap();
+
+        assert_eq!(funding_countdown(next_funding, now), None);
+    }
 }
@@ -1,4 +1,10 @@
-﻿use crate::types::{BinanceOrderBook, BinanceStreamMessage, BinanceTicker};
+use crate::depth::{
+    DepthBook, DepthSync, LocalBook, DEPTH_SNAPSHOT_LIMIT, MAX_BUFFERED_DEPTH_UPDATES,
+};
+use crate::types::{
+    BinanceAggTrade, BinanceDepthUpdate, BinanceMarkPrice, BinanceOrderBook, BinanceStreamMessage,
+    BinanceTicker,
+};
 
 use anyhow::{anyhow, Result};
 
@@ -8,25 +14,36 @@ use crypto_dash_cache::CacheHandle;
 
 use crypto_dash_core::{
     model::{
-        Channel, ChannelType, ExchangeId, MarketType, OrderBookSnapshot, PriceLevel, StreamMessage,
-        Symbol, Ticker,
+        AdapterMetrics, Channel, ChannelType, ConnectionStats, ExchangeId, FundingRate,
+        LifecycleEvent, MarketType, OrderBookDelta, OrderBookSnapshot, PriceLevel, Side,
+        StreamMessage, Symbol, TickDirection, Ticker, Trade,
     },
     normalize::SymbolMapper,
     time::{from_millis, now, to_millis},
 };
 
-use crypto_dash_exchanges_common::{ExchangeAdapter, WsClient};
+use crypto_dash_exchanges_common::{
+    check_ticker, optional_decimal, out_of_order_ms, parse_levels, publish_orderbook_resets,
+    retry_with_backoff, CircuitBreaker, ExchangeAdapter, MessageCounters, PendingSubscribes,
+    ReconnectBudget, RetryConfig, WsClient, DEFAULT_CONNECTION_TIMEOUT, DEFAULT_SUBSCRIBE_TIMEOUT,
+    THROTTLE_BACKOFF,
+};
 
 use crypto_dash_stream_hub::{HubHandle, Topic};
 
 use rust_decimal::Decimal;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::str::FromStr;
 
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 
 use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
 
 use tokio_tungstenite::tungstenite::Message;
 
@@ -34,7 +51,17 @@ use tracing::{debug, error, info, warn};
 
 const BINANCE_SPOT_WS_URL: &str = "wss://stream.binance.com:9443/ws";
 const BINANCE_PERP_WS_URL: &str = "wss://fstream.binance.com/ws";
+const BINANCE_SPOT_REST_URL: &str = "https://api.binance.com";
+const BINANCE_PERP_REST_URL: &str = "https://fapi.binance.com";
 const SUPPORTED_MARKETS: [MarketType; 2] = [MarketType::Spot, MarketType::Perpetual];
+const SUPPORTED_DEPTHS: [u16; 3] = [5, 10, 20];
+/// Order book depth subscribed when a channel leaves `depth` unset
+const DEFAULT_ORDERBOOK_DEPTH: u16 = 20;
+/// Binance accepts at most 5 incoming messages per second on a connection
+const BINANCE_SUBSCRIBE_PACING: Duration = Duration::from_millis(200);
+
+/// Market and symbol identifying one local order book
+type BookKey = (MarketType, Symbol);
 
 #[derive(Clone)]
 pub struct BinanceAdapter {
@@ -42,6 +69,26 @@ pub struct BinanceAdapter {
     cache: Arc<Mutex<Option<CacheHandle>>>,
     ws_clients: Arc<Mutex<HashMap<MarketType, Option<Arc<WsClient>>>>>,
     symbol_mapper: SymbolMapper,
+    ws_urls: HashMap<MarketType, String>,
+    connection_stats: Arc<Mutex<HashMap<MarketType, ConnectionStats>>>,
+    subscribed: Arc<Mutex<HashMap<MarketType, HashSet<Channel>>>>,
+    connection_timeout: Duration,
+    subscribe_pacing: Duration,
+    combined_streams: bool,
+    reconnect_retry: RetryConfig,
+    reconnect_budget: ReconnectBudget,
+    breaker: CircuitBreaker,
+    watchdogs: Arc<Mutex<Vec<JoinHandle<()>>>>,
+    throttled_until: Arc<Mutex<HashMap<MarketType, Instant>>>,
+    metrics: Arc<MessageCounters>,
+    pending_subscribes: PendingSubscribes,
+    orderbook_deltas: bool,
+    rest_urls: HashMap<MarketType, String>,
+    http_client: reqwest::Client,
+    /// Local books rebuilt from the diff depth stream, keyed by market and symbol
+    depth_books: Arc<Mutex<HashMap<BookKey, DepthBook>>>,
+    /// In-flight REST snapshot fetches per book, aborted on stop
+    book_syncs: Arc<Mutex<HashMap<BookKey, JoinHandle<()>>>>,
     // no mock generators or mock flags - production behavior only
 }
 
@@ -58,14 +105,111 @@ impl BinanceAdapter {
             cache: Arc::new(Mutex::new(None)),
             ws_clients: Arc::new(Mutex::new(ws_clients)),
             symbol_mapper: SymbolMapper::default(),
+            ws_urls: HashMap::from([
+                (MarketType::Spot, BINANCE_SPOT_WS_URL.to_string()),
+                (MarketType::Perpetual, BINANCE_PERP_WS_URL.to_string()),
+            ]),
+            connection_stats: Arc::new(Mutex::new(HashMap::new())),
+            subscribed: Arc::new(Mutex::new(HashMap::new())),
+            connection_timeout: DEFAULT_CONNECTION_TIMEOUT,
+            subscribe_pacing: BINANCE_SUBSCRIBE_PACING,
+            combined_streams: false,
+            reconnect_retry: RetryConfig::default(),
+            reconnect_budget: ReconnectBudget::default(),
+            breaker: CircuitBreaker::default(),
+            watchdogs: Arc::new(Mutex::new(Vec::new())),
+            throttled_until: Arc::new(Mutex::new(HashMap::new())),
+            metrics: Arc::new(MessageCounters::default()),
+            pending_subscribes: PendingSubscribes::new(DEFAULT_SUBSCRIBE_TIMEOUT),
+            orderbook_deltas: false,
+            rest_urls: HashMap::from([
+                (MarketType::Spot, BINANCE_SPOT_REST_URL.to_string()),
+                (MarketType::Perpetual, BINANCE_PERP_REST_URL.to_string()),
+            ]),
+            http_client: reqwest::Client::new(),
+            depth_books: Arc::new(Mutex::new(HashMap::new())),
+            book_syncs: Arc::new(Mutex::new(HashMap::new())),
             // no mock state
         }
     }
 
+    /// Override the WebSocket endpoint used for a market
+    pub fn with_ws_url(mut self, market_type: MarketType, url: impl Into<String>) -> Self {
+        self.ws_urls.insert(market_type, url.into());
+        self
+    }
+
+    /// Override how long a market connection may stay silent before it is reconnected
+    pub fn with_connection_timeout(mut self, connection_timeout: Duration) -> Self {
+        self.connection_timeout = connection_timeout;
+        self
+    }
+
+    /// Override the minimum gap between subscribe frames sent on one connection
+    pub fn with_subscribe_pacing(mut self, subscribe_pacing: Duration) -> Self {
+        self.subscribe_pacing = subscribe_pacing;
+        self
+    }
+
+    /// Connect markets with known subscriptions to the combined-stream endpoint with their
+    /// streams in the URL, e.g. `/stream?streams=btcusdt@ticker/ethusdt@ticker`, rather than
+    /// sending SUBSCRIBE requests once connected. Markets with nothing subscribed yet still
+    /// connect to the plain endpoint.
+    pub fn with_combined_streams(mut self, enabled: bool) -> Self {
+        self.combined_streams = enabled;
+        self
+    }
+
+    /// Stream order books from the `@depth@100ms` diff stream instead of partial snapshots:
+    /// each book is seeded from a REST snapshot, kept in sync by update id and published as
+    /// `OrderBookDelta` messages, with a fresh snapshot whenever a gap is detected
+    pub fn with_orderbook_deltas(mut self, enabled: bool) -> Self {
+        self.orderbook_deltas = enabled;
+        self
+    }
+
+    /// Override the REST root order book snapshots are fetched from for a market
+    pub fn with_rest_url(mut self, market_type: MarketType, url: impl Into<String>) -> Self {
+        self.rest_urls.insert(market_type, url.into());
+        self
+    }
+
+    /// Override the backoff used to restore a market connection that dropped
+    pub fn with_reconnect_retry(mut self, reconnect_retry: RetryConfig) -> Self {
+        self.reconnect_retry = reconnect_retry;
+        self
+    }
+
+    /// Override how many reconnects a market may make within a window
+    pub fn with_reconnect_budget(mut self, reconnect_budget: ReconnectBudget) -> Self {
+        self.reconnect_budget = reconnect_budget;
+        self
+    }
+
+    /// Override how many failed connects open a market's circuit and how long it stays open
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    /// Override how long a subscribe may go without an ack or data; zero disables the check
+    pub fn with_subscribe_timeout(mut self, subscribe_timeout: Duration) -> Self {
+        self.pending_subscribes = PendingSubscribes::new(subscribe_timeout);
+        self
+    }
+
+    /// Replace the symbol mappings, e.g. with built-ins merged with operator overrides
+    pub fn with_symbol_mapper(mut self, symbol_mapper: SymbolMapper) -> Self {
+        self.symbol_mapper = symbol_mapper;
+        self
+    }
+
     fn market_label(market_type: MarketType) -> &'static str {
         match market_type {
             MarketType::Spot => "spot",
             MarketType::Perpetual => "perpetual",
+            MarketType::InversePerpetual => "inverse_perpetual",
+            MarketType::Future => "future",
         }
     }
 
@@ -88,6 +232,58 @@ impl BinanceAdapter {
         }
     }
 
+    async fn record_connect_success(&self, market_type: MarketType) {
+        let mut guard = self.connection_stats.lock().await;
+        let stats = guard.entry(market_type).or_default();
+        stats.reconnect_attempts = 0;
+        stats.last_connected_at = Some(now());
+        drop(guard);
+        self.breaker.record_success(market_type);
+
+        self.emit_event(LifecycleEvent::UpstreamConnected {
+            exchange: self.id(),
+            market_type,
+        })
+        .await;
+    }
+
+    async fn emit_event(&self, event: LifecycleEvent) {
+        if let Some(hub) = &*self.hub.lock().await {
+            hub.emit_event(event);
+        }
+    }
+
+    async fn record_connect_failure(&self, market_type: MarketType, error: &anyhow::Error) {
+        let mut guard = self.connection_stats.lock().await;
+        let stats = guard.entry(market_type).or_default();
+        stats.reconnect_attempts += 1;
+        stats.last_connect_error = Some(error.to_string());
+        drop(guard);
+        self.breaker.record_failure(market_type);
+    }
+
+    /// Hold off new requests on a market after Binance reports rate limiting
+    async fn record_throttle(&self, market_type: MarketType) {
+        warn!(
+            market = Self::market_label(market_type),
+            "Binance rate limit notice, backing off for {:?}", THROTTLE_BACKOFF
+        );
+        self.throttled_until
+            .lock()
+            .await
+            .insert(market_type, Instant::now() + THROTTLE_BACKOFF);
+    }
+
+    /// End of the current back-off window for a market, if one is active
+    async fn throttled_until(&self, market_type: MarketType) -> Option<Instant> {
+        self.throttled_until
+            .lock()
+            .await
+            .get(&market_type)
+            .copied()
+            .filter(|until| *until > Instant::now())
+    }
+
     async fn get_mock_generator(&self, _market_type: MarketType) -> Option<()> {
         None
     }
@@ -110,12 +306,52 @@ impl BinanceAdapter {
                 self.handle_ticker(market_type, data).await?;
             }
 
+            BinanceStreamMessage::StreamMarkPrice { stream: _, data }
+            | BinanceStreamMessage::DirectMarkPrice(data) => {
+                self.handle_mark_price(market_type, data).await?;
+            }
+
+            BinanceStreamMessage::StreamAggTrade { stream: _, data }
+            | BinanceStreamMessage::DirectAggTrade(data) => {
+                self.handle_agg_trade(market_type, data).await?;
+            }
+
             BinanceStreamMessage::OrderBook { stream, data } => {
                 self.handle_orderbook(market_type, &stream, data).await?;
             }
 
-            BinanceStreamMessage::Error { error, .. } => {
-                error!("Binance error: {} - {}", error.code, error.msg);
+            BinanceStreamMessage::StreamDepthUpdate { stream: _, data }
+            | BinanceStreamMessage::DirectDepthUpdate(data) => {
+                self.handle_depth_update(market_type, data).await?;
+            }
+
+            BinanceStreamMessage::Response { id, .. } => {
+                self.pending_subscribes.resolve(id);
+            }
+
+            BinanceStreamMessage::Error { id, error } => {
+                if error.is_rate_limit() {
+                    self.record_throttle(market_type).await;
+                } else {
+                    error!("Binance error: {} - {}", error.code, error.msg);
+                }
+
+                let rejected = id
+                    .and_then(|id| u64::try_from(id).ok())
+                    .and_then(|id| self.pending_subscribes.resolve(id));
+                if let (Some(channels), Some(hub)) = (rejected, &*self.hub.lock().await) {
+                    for channel in channels {
+                        let message = StreamMessage::Error {
+                            message: format!(
+                                "Binance rejected subscription to {}: {}",
+                                channel.instrument(),
+                                error.msg
+                            ),
+                            code: None,
+                        };
+                        hub.publish(&Topic::from_channel(&channel), message).await;
+                    }
+                }
             }
         }
 
@@ -169,21 +405,19 @@ impl BinanceAdapter {
 
         let last_price = Decimal::from_str(ticker.c.as_deref().unwrap_or("0"))?;
 
-        let bid_price = ticker
-            .b
-            .as_deref()
-            .filter(|v| !v.is_empty())
-            .map(Decimal::from_str)
-            .transpose()?
-            .unwrap_or_else(|| last_price.clone());
-
-        let ask_price = ticker
-            .a
-            .as_deref()
-            .filter(|v| !v.is_empty())
-            .map(Decimal::from_str)
-            .transpose()?
-            .unwrap_or_else(|| last_price.clone());
+        let bid_price = optional_decimal(&ticker.b)?.unwrap_or_else(|| last_price.clone());
+
+        let ask_price = optional_decimal(&ticker.a)?.unwrap_or_else(|| last_price.clone());
+
+        // 24h statistics are absent from book ticker frames
+        let quote_volume_24h = optional_decimal(&ticker.q)?;
+        let change_pct_24h = optional_decimal(&ticker.price_change_percent)?;
+
+        let previous = match &*self.cache.lock().await {
+            Some(cache) => cache.get_ticker(&self.id(), market_type, &symbol).await,
+            None => None,
+        };
+        let previous_last = previous.as_ref().map(|previous| previous.last);
 
         let normalized_ticker = Ticker {
             timestamp,
@@ -202,17 +436,116 @@ impl BinanceAdapter {
             bid_size: Decimal::from_str(bid_size)?,
 
             ask_size: Decimal::from_str(ask_size)?,
+            quote_volume_24h,
+            change_pct_24h,
+            tick_direction: Some(TickDirection::between(previous_last, last_price)),
+            high_24h: optional_decimal(&ticker.h)?,
+            low_24h: optional_decimal(&ticker.l)?,
+            volume_24h: optional_decimal(&ticker.v)?,
         };
+        if let Err(e) = check_ticker(&normalized_ticker) {
+            warn!(
+                market = Self::market_label(market_type),
+                "Dropping Binance {} ticker: {}", ticker.s, e
+            );
+            return Ok(());
+        }
+        if let Some(behind_ms) = out_of_order_ms(previous.as_ref(), &normalized_ticker) {
+            warn!(
+                market = Self::market_label(market_type),
+                "Dropping Binance {} ticker {}ms older than the cached one", ticker.s, behind_ms
+            );
+            return Ok(());
+        }
 
         if let Some(cache) = &*self.cache.lock().await {
             cache.set_ticker(normalized_ticker.clone()).await;
         }
 
         let topic = Topic::ticker(self.id(), market_type, symbol);
+        self.pending_subscribes.settle(&topic);
 
         if let Some(hub) = &*self.hub.lock().await {
             hub.publish(&topic, StreamMessage::Ticker(normalized_ticker))
                 .await;
+            self.metrics.record_published();
+        }
+
+        self.disconnect_if_no_subscribers(&topic).await?;
+
+        Ok(())
+    }
+
+    async fn handle_mark_price(
+        &self,
+        market_type: MarketType,
+        mark: BinanceMarkPrice,
+    ) -> Result<()> {
+        let symbol = self.parse_symbol(&mark.s)?;
+
+        let timestamp = from_millis(mark.event_time)
+            .ok_or_else(|| anyhow!("Invalid timestamp: {}", mark.event_time))?;
+        let next_funding_time = from_millis(mark.next_funding_time)
+            .ok_or_else(|| anyhow!("Invalid funding time: {}", mark.next_funding_time))?;
+
+        let index_price = optional_decimal(&mark.i)?;
+
+        let funding = FundingRate {
+            timestamp,
+            exchange: self.id(),
+            market_type,
+            symbol: symbol.clone(),
+            rate: Decimal::from_str(&mark.r)?,
+            next_funding_time,
+            mark_price: Some(Decimal::from_str(&mark.p)?),
+            index_price,
+        };
+
+        if let Some(cache) = &*self.cache.lock().await {
+            cache.set_funding_rate(funding.clone()).await;
+        }
+
+        let topic = Topic::funding(self.id(), market_type, symbol);
+        self.pending_subscribes.settle(&topic);
+
+        if let Some(hub) = &*self.hub.lock().await {
+            hub.publish(&topic, StreamMessage::FundingRate(funding))
+                .await;
+            self.metrics.record_published();
+        }
+
+        self.disconnect_if_no_subscribers(&topic).await?;
+
+        Ok(())
+    }
+
+    async fn handle_agg_trade(
+        &self,
+        market_type: MarketType,
+        trade: BinanceAggTrade,
+    ) -> Result<()> {
+        let symbol = self.parse_symbol(&trade.s)?;
+
+        let timestamp = from_millis(trade.trade_time)
+            .ok_or_else(|| anyhow!("Invalid timestamp: {}", trade.trade_time))?;
+
+        let trade = Trade {
+            timestamp,
+            exchange: self.id(),
+            market_type,
+            symbol: symbol.clone(),
+            price: Decimal::from_str(&trade.p)?,
+            quantity: Decimal::from_str(&trade.q)?,
+            side: Side::from_buyer_maker(trade.buyer_is_maker),
+            trade_id: Some(trade.agg_trade_id.to_string()),
+        };
+
+        let topic = Topic::trades(self.id(), market_type, symbol);
+        self.pending_subscribes.settle(&topic);
+
+        if let Some(hub) = &*self.hub.lock().await {
+            hub.publish(&topic, StreamMessage::Trade(trade)).await;
+            self.metrics.record_published();
         }
 
         self.disconnect_if_no_subscribers(&topic).await?;
@@ -277,6 +610,7 @@ impl BinanceAdapter {
         }
 
         let topic = Topic::orderbook(self.id(), market_type, symbol);
+        self.pending_subscribes.settle(&topic);
 
         if let Some(hub) = &*self.hub.lock().await {
             hub.publish(
@@ -284,6 +618,115 @@ impl BinanceAdapter {
                 StreamMessage::OrderBookSnapshot(normalized_orderbook),
             )
             .await;
+            self.metrics.record_published();
+        }
+
+        self.disconnect_if_no_subscribers(&topic).await?;
+
+        Ok(())
+    }
+
+    async fn handle_depth_update(
+        &self,
+        market_type: MarketType,
+        update: BinanceDepthUpdate,
+    ) -> Result<()> {
+        // Futures partial depth streams carry the diff payload with the whole top-N book
+        if !self.orderbook_deltas {
+            let orderbook = BinanceOrderBook {
+                last_update_id: update.final_update_id,
+                bids: update.b,
+                asks: update.a,
+            };
+            return self
+                .handle_orderbook(market_type, &update.s, orderbook)
+                .await;
+        }
+
+        let symbol = self.parse_symbol(&update.s)?;
+        let timestamp = from_millis(update.event_time)
+            .ok_or_else(|| anyhow!("Invalid timestamp: {}", update.event_time))?;
+        let topic = Topic::orderbook(self.id(), market_type, symbol.clone());
+        self.pending_subscribes.settle(&topic);
+
+        let key = (market_type, symbol.clone());
+        let (bids, asks) = {
+            let mut books = self.depth_books.lock().await;
+            match books.get_mut(&key) {
+                Some(DepthBook::Syncing(buffered)) => {
+                    // Keep the newest diffs so the snapshot being fetched can still bridge them
+                    if buffered.len() >= MAX_BUFFERED_DEPTH_UPDATES {
+                        buffered.pop_front();
+                    }
+                    buffered.push_back(update);
+                    return Ok(());
+                }
+
+                Some(DepthBook::Live(book)) => match book.apply(&update)? {
+                    DepthSync::Applied => (book.bids.clone(), book.asks.clone()),
+                    DepthSync::Stale => return Ok(()),
+                    DepthSync::Gap => {
+                        warn!(
+                            market = Self::market_label(market_type),
+                            "Binance depth diff {}-{} for {} skipped updates, resyncing",
+                            update.first_update_id,
+                            update.final_update_id,
+                            symbol.canonical()
+                        );
+                        books.insert(key, DepthBook::Syncing(VecDeque::from([update])));
+                        self.spawn_book_sync(market_type, symbol).await;
+                        return Ok(());
+                    }
+                },
+
+                None => {
+                    books.insert(key, DepthBook::Syncing(VecDeque::from([update])));
+                    self.spawn_book_sync(market_type, symbol).await;
+                    return Ok(());
+                }
+            }
+        };
+
+        if let Some(cache) = &*self.cache.lock().await {
+            cache
+                .set_orderbook(OrderBookSnapshot {
+                    timestamp,
+                    exchange: self.id(),
+                    market_type,
+                    symbol: symbol.clone(),
+                    bids,
+                    asks,
+                    checksum: None,
+                })
+                .await;
+        }
+
+        let (bid_deletes, bids_upserts): (Vec<_>, Vec<_>) = parse_levels(&update.b)?
+            .into_iter()
+            .partition(|l| l.quantity.is_zero());
+        let (ask_deletes, asks_upserts): (Vec<_>, Vec<_>) = parse_levels(&update.a)?
+            .into_iter()
+            .partition(|l| l.quantity.is_zero());
+        let deletes: Vec<Decimal> = bid_deletes
+            .into_iter()
+            .chain(ask_deletes)
+            .map(|l| l.price)
+            .collect();
+
+        let delta = OrderBookDelta {
+            timestamp,
+            exchange: self.id(),
+            market_type,
+            symbol,
+            bids_upserts,
+            asks_upserts,
+            deletes: (!deletes.is_empty()).then_some(deletes),
+        };
+
+        if let Some(hub) = &*self.hub.lock().await {
+            hub.publish(&topic, StreamMessage::OrderBookDelta(delta))
+                .await;
+            self.metrics.record_published();
         }
 
         self.disconnect_if_no_subscribers(&topic).await?;
@@ -291,6 +734,116 @@ impl BinanceAdapter {
         Ok(())
     }
 
+    async fn spawn_book_sync(&self, market_type: MarketType, symbol: Symbol) {
+        let adapter = self.clone();
+        let key = (market_type, symbol.clone());
+        let sync = tokio::spawn(async move { adapter.sync_book(market_type, symbol).await });
+        // A book only resyncs once live, so any previous fetch for it has finished
+        self.book_syncs.lock().await.insert(key, sync);
+    }
+
+    /// Seed a syncing book from a REST snapshot plus the diffs buffered meanwhile and publish
+    /// it, refetching while the snapshot is too old to bridge the buffer
+    async fn sync_book(&self, market_type: MarketType, symbol: Symbol) {
+        let key = &(market_type, symbol.clone());
+        let synced: Result<()> = retry_with_backoff(
+            || async move {
+                let snapshot = self.fetch_depth_snapshot(market_type, &key.1).await?;
+                let mut books = self.depth_books.lock().await;
+                // Unsubscribed or reset while the snapshot was in flight
+                let Some(DepthBook::Syncing(buffered)) = books.get(key) else {
+                    return Ok(());
+                };
+                let book = LocalBook::from_snapshot(&snapshot, buffered)?;
+                // Published before the lock is released so it precedes the first delta
+                self.publish_book_snapshot(market_type, &key.1, &book).await;
+                books.insert(key.clone(), DepthBook::Live(book));
+                Ok(())
+            },
+            RetryConfig::default(),
+        )
+        .await;
+
+        if let Err(e) = synced {
+            error!(
+                market = Self::market_label(market_type),
+                "Binance order book snapshot for {} failed: {}",
+                symbol.canonical(),
+                e
+            );
+            // The next diff starts another sync
+            let mut books = self.depth_books.lock().await;
+            if matches!(books.get(key), Some(DepthBook::Syncing(_))) {
+                books.remove(key);
+            }
+        }
+    }
+
+    async fn publish_book_snapshot(
+        &self,
+        market_type: MarketType,
+        symbol: &Symbol,
+        book: &LocalBook,
+    ) {
+        let snapshot = OrderBookSnapshot {
+            timestamp: now(),
+            exchange: self.id(),
+            market_type,
+            symbol: symbol.clone(),
+            bids: book.bids.clone(),
+            asks: book.asks.clone(),
+            checksum: None,
+        };
+        if let Some(cache) = &*self.cache.lock().await {
+            cache.set_orderbook(snapshot.clone()).await;
+        }
+        if let Some(hub) = &*self.hub.lock().await {
+            let topic = Topic::orderbook(self.id(), market_type, symbol.clone());
+            hub.publish(&topic, StreamMessage::OrderBookSnapshot(snapshot))
+                .await;
+            self.metrics.record_published();
+        }
+    }
+
+    async fn fetch_depth_snapshot(
+        &self,
+        market_type: MarketType,
+        symbol: &Symbol,
+    ) -> Result<BinanceOrderBook> {
+        let base_url = self
+            .rest_urls
+            .get(&market_type)
+            .ok_or_else(|| anyhow!("No REST URL for {} market", Self::market_label(market_type)))?;
+        let path = match market_type {
+            MarketType::Spot => "/api/v3/depth",
+            _ => "/fapi/v1/depth",
+        };
+
+        let snapshot = self
+            .http_client
+            .get(format!("{}{}", base_url.trim_end_matches('/'), path))
+            .query(&[
+                ("symbol", self.to_exchange_symbol(symbol, market_type)),
+                ("limit", DEPTH_SNAPSHOT_LIMIT.to_string()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(snapshot)
+    }
+
+    /// Convert a canonical symbol to Binance's instrument name for a market
+    pub fn to_exchange_symbol(&self, symbol: &Symbol, _market_type: MarketType) -> String {
+        let base = symbol.base.to_uppercase();
+        let quote = symbol.quote.to_uppercase();
+
+        self.symbol_mapper
+            .to_exchange(&self.id(), &Symbol::new(base.clone(), quote.clone()))
+            .unwrap_or_else(|| format!("{}{}", base, quote))
+    }
+
     fn parse_symbol(&self, binance_symbol: &str) -> Result<Symbol> {
         // Use the symbol mapper for production-ready symbol normalization
         if let Some(symbol) = self.symbol_mapper.to_canonical(&self.id(), binance_symbol) {
@@ -322,29 +875,51 @@ impl BinanceAdapter {
         let mut streams = Vec::new();
 
         for channel in channels {
-            let symbol_str = format!(
-                "{}{}",
-                channel.symbol.base.to_lowercase(),
-                channel.symbol.quote.to_lowercase()
-            );
+            let symbol_str = self
+                .to_exchange_symbol(&channel.symbol, channel.market_type)
+                .to_lowercase();
 
             match channel.channel_type {
                 ChannelType::Ticker => {
                     streams.push(format!("{}@ticker", symbol_str));
                 }
 
+                ChannelType::OrderBook if self.orderbook_deltas => {
+                    streams.push(format!("{}@depth@100ms", symbol_str));
+                }
+
                 ChannelType::OrderBook => {
-                    let depth = channel.depth.unwrap_or(20);
+                    let depth = channel.depth.unwrap_or(DEFAULT_ORDERBOOK_DEPTH);
 
                     streams.push(format!("{}@depth{}", symbol_str, depth));
                 }
+
+                // Only USD-M futures publish mark price and funding
+                ChannelType::Funding if channel.market_type == MarketType::Perpetual => {
+                    streams.push(format!("{}@markPrice@1s", symbol_str));
+                }
+
+                ChannelType::Funding => {
+                    debug!(
+                        market = Self::market_label(channel.market_type),
+                        "Binance has no funding stream for {}",
+                        channel.instrument()
+                    );
+                }
+
+                ChannelType::Trades => {
+                    streams.push(format!("{}@aggTrade", symbol_str));
+                }
             }
         }
 
+        // Order book channels of any depth share one diff stream
+        let mut seen = HashSet::new();
+        streams.retain(|stream| seen.insert(stream.clone()));
         streams
     }
 
-    fn format_subscription(&self, channels: &[Channel]) -> Result<String> {
+    fn format_subscription(&self, channels: &[Channel], id: u64) -> Result<String> {
         let streams = self.streams_from_channels(channels);
 
         let subscription = serde_json::json!({
@@ -353,14 +928,14 @@ impl BinanceAdapter {
 
             "params": streams,
 
-            "id": 1
+            "id": id
 
         });
 
         Ok(subscription.to_string())
     }
 
-    fn format_unsubscription(&self, channels: &[Channel]) -> Result<String> {
+    fn format_unsubscription(&self, channels: &[Channel], id: u64) -> Result<String> {
         let streams = self.streams_from_channels(channels);
 
         let unsubscription = serde_json::json!({
@@ -369,7 +944,7 @@ impl BinanceAdapter {
 
             "params": streams,
 
-            "id": 1
+            "id": id
 
         });
 
@@ -381,65 +956,224 @@ impl BinanceAdapter {
         market_type: MarketType,
         ws_client: Arc<WsClient>,
     ) -> Result<()> {
-        loop {
-            let message = match ws_client.next_message().await? {
-                Some(Message::Text(text)) => text,
+        let reason = loop {
+            let message = match ws_client.next_message().await {
+                Ok(Some(Message::Text(text))) => text,
 
-                Some(Message::Close(_)) => {
+                Ok(Some(Message::Close(_))) => {
                     warn!("Binance WebSocket connection closed");
 
-                    break;
+                    break "connection closed".to_string();
                 }
 
-                Some(_) => continue,
+                Ok(Some(_)) => continue,
 
-                None => {
+                Ok(None) => {
                     warn!("Binance WebSocket stream ended");
 
-                    break;
+                    break "stream ended".to_string();
+                }
+
+                Err(e) => {
+                    warn!("Binance WebSocket read failed: {}", e);
+
+                    break e.to_string();
                 }
             };
 
+            self.metrics.record_received();
             match serde_json::from_str::<BinanceStreamMessage>(&message) {
                 Ok(stream_message) => {
                     if let Err(e) = self.handle_message(market_type, stream_message).await {
+                        self.metrics.record_parse_error();
                         error!("Failed to handle Binance message: {}", e);
                     }
                 }
 
                 Err(e) => {
+                    self.metrics.record_parse_error();
                     debug!("Failed to parse Binance message: {} - Raw: {}", e, message);
                 }
             }
-        }
+        };
 
-        let mut ws_guard = self.ws_clients.lock().await;
+        self.emit_event(LifecycleEvent::UpstreamDisconnected {
+            exchange: self.id(),
+            market_type,
+            reason,
+        })
+        .await;
 
-        if let Some(entry) = ws_guard.get_mut(&market_type) {
-            if let Some(current) = entry.as_ref() {
-                if Arc::ptr_eq(current, &ws_client) {
+        let dropped = {
+            let mut ws_guard = self.ws_clients.lock().await;
+            match ws_guard.get_mut(&market_type) {
+                Some(entry)
+                    if entry
+                        .as_ref()
+                        .is_some_and(|current| Arc::ptr_eq(current, &ws_client)) =>
+                {
                     *entry = None;
+                    true
                 }
+                _ => false,
             }
+        };
+
+        // Deliberate closes (stop, stale replacement, no subscribers) clear the entry first
+        if dropped {
+            self.restore_connection(market_type).await;
         }
 
         Ok(())
     }
 
+    /// Tell order book subscribers to discard their books before resubscribing them
+    async fn reset_orderbooks(&self, channels: &[Channel]) {
+        self.drop_depth_books(channels).await;
+        let hub = self.hub.lock().await.clone();
+        let cache = self.cache.lock().await.clone();
+        if let (Some(hub), Some(cache)) = (hub, cache) {
+            publish_orderbook_resets(&hub, &cache, channels).await;
+        }
+    }
+
+    /// Forget the local books of order book channels so their next diff starts a new sync
+    async fn drop_depth_books(&self, channels: &[Channel]) {
+        let mut books = self.depth_books.lock().await;
+        for channel in channels {
+            if channel.channel_type == ChannelType::OrderBook {
+                books.remove(&(channel.market_type, channel.symbol.clone()));
+            }
+        }
+    }
+
+    /// Reconnect a dropped market and resend its subscriptions while sessions are listening
+    // Boxed because the listener task it is awaited from is spawned by `try_real_connection`
+    fn restore_connection(
+        &self,
+        market_type: MarketType,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(async move {
+            let listening = match &*self.hub.lock().await {
+                Some(hub) => hub.global_subscriber_count() > 0,
+                None => false,
+            };
+            let channels: Vec<Channel> = self
+                .subscribed
+                .lock()
+                .await
+                .get(&market_type)
+                .map(|channels| channels.iter().cloned().collect())
+                .unwrap_or_default();
+            if !listening || channels.is_empty() {
+                return;
+            }
+
+            // A server that accepts and immediately closes would otherwise be redialled in
+            // a tight loop, so restores are budgeted per window and start after a pause
+            if let Err(e) = self.reconnect_budget.take(market_type) {
+                error!(
+                    market = Self::market_label(market_type),
+                    "Binance not reconnecting: {}", e
+                );
+                return;
+            }
+            warn!(
+                market = Self::market_label(market_type),
+                "Binance WebSocket dropped, reconnecting {} channels",
+                channels.len()
+            );
+            tokio::time::sleep(self.reconnect_retry.base_delay).await;
+            self.reset_orderbooks(&channels).await;
+            let channels = &channels;
+            let restored = retry_with_backoff(
+                || async move {
+                    let ws_client = self.try_real_connection(market_type).await?;
+                    self.send_subscription(market_type, &ws_client, channels.clone())
+                        .await
+                },
+                self.reconnect_retry.clone(),
+            )
+            .await;
+
+            match restored {
+                Ok(()) => {
+                    info!(
+                        market = Self::market_label(market_type),
+                        "Binance reconnected and resubscribed {} channels",
+                        channels.len()
+                    );
+                }
+                Err(e) => error!(
+                    market = Self::market_label(market_type),
+                    "Binance reconnect gave up after {} attempts: {}",
+                    self.reconnect_retry.max_attempts,
+                    e
+                ),
+            }
+        })
+    }
+
+    /// Combined-stream URL naming the market's subscribed streams, or the plain endpoint
+    /// when combined streams are off or nothing is subscribed yet
+    async fn connection_url(&self, market_type: MarketType, base_url: &str) -> String {
+        if !self.combined_streams {
+            return base_url.to_string();
+        }
+
+        let channels: Vec<Channel> = self
+            .subscribed
+            .lock()
+            .await
+            .get(&market_type)
+            .map(|channels| channels.iter().cloned().collect())
+            .unwrap_or_default();
+        let mut streams = self.streams_from_channels(&channels);
+        if streams.is_empty() {
+            return base_url.to_string();
+        }
+        streams.sort();
+        streams.dedup();
+
+        let root = base_url.trim_end_matches('/');
+        let root = root.strip_suffix("/ws").unwrap_or(root);
+        format!("{}/stream?streams={}", root, streams.join("/"))
+    }
+
     async fn try_real_connection(&self, market_type: MarketType) -> Result<Arc<WsClient>> {
-        let ws_url = match market_type {
-            MarketType::Spot => BINANCE_SPOT_WS_URL,
-            MarketType::Perpetual => BINANCE_PERP_WS_URL,
-        };
+        let base_url = self.ws_urls.get(&market_type).ok_or_else(|| {
+            anyhow!(
+                "No WebSocket URL for {} market",
+                Self::market_label(market_type)
+            )
+        })?;
+        let ws_url = self.connection_url(market_type, base_url).await;
 
         debug!(
             market = Self::market_label(market_type),
             "Attempting to connect to Binance WebSocket: {}", ws_url
         );
 
-        let ws_client = Arc::new(WsClient::new(ws_url));
+        // Fail fast while the market's circuit is open instead of hammering a dead endpoint
+        self.breaker.check(market_type).map_err(|e| {
+            anyhow!(
+                "Not connecting to Binance {} market: {}",
+                Self::market_label(market_type),
+                e
+            )
+        })?;
 
-        ws_client.connect().await?;
+        let ws_client = Arc::new(
+            WsClient::new(ws_url)
+                .with_timeout(self.connection_timeout)
+                .with_send_interval(self.subscribe_pacing),
+        );
+
+        if let Err(e) = ws_client.connect().await {
+            self.record_connect_failure(market_type, &e).await;
+            return Err(e);
+        }
+        self.record_connect_success(market_type).await;
 
         debug!(
             market = Self::market_label(market_type),
@@ -468,6 +1202,101 @@ impl BinanceAdapter {
         Ok(ws_client)
     }
 
+    /// Periodically reconnect the market if its connection has gone silent
+    async fn run_watchdog(&self, market_type: MarketType) {
+        let period = (self.connection_timeout / 2).max(Duration::from_millis(10));
+        let mut ticker = tokio::time::interval(period);
+
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.reconnect_if_stale(market_type).await {
+                warn!(
+                    market = Self::market_label(market_type),
+                    "Binance stale connection reconnect failed: {}", e
+                );
+            }
+        }
+    }
+
+    /// Reconnect and resubscribe a stale market connection that still has subscriptions
+    async fn reconnect_if_stale(&self, market_type: MarketType) -> Result<bool> {
+        let Some(client) = self.get_ws_client(market_type).await else {
+            return Ok(false);
+        };
+        if !client.is_stale() {
+            return Ok(false);
+        }
+
+        let channels: Vec<Channel> = self
+            .subscribed
+            .lock()
+            .await
+            .get(&market_type)
+            .map(|channels| channels.iter().cloned().collect())
+            .unwrap_or_default();
+        if channels.is_empty() {
+            return Ok(false);
+        }
+
+        warn!(
+            market = Self::market_label(market_type),
+            "Binance WebSocket connection is stale, reconnecting"
+        );
+        self.set_ws_client(market_type, None).await;
+        if let Err(e) = client.close().await {
+            debug!("Failed to close stale Binance connection: {}", e);
+        }
+        self.reset_orderbooks(&channels).await;
+
+        let ws_client = self.try_real_connection(market_type).await?;
+        self.send_subscription(market_type, &ws_client, channels.clone())
+            .await?;
+        info!(
+            market = Self::market_label(market_type),
+            "Binance resubscribed {} channels after stale connection",
+            channels.len()
+        );
+
+        Ok(true)
+    }
+
+    /// Send a SUBSCRIBE request and track it until Binance acks it or data arrives
+    async fn send_subscription(
+        &self,
+        market_type: MarketType,
+        ws_client: &WsClient,
+        channels: Vec<Channel>,
+    ) -> Result<()> {
+        let request_id = self.pending_subscribes.next_request_id();
+
+        // Streams named in a combined-stream URL are live as soon as the connection opens
+        let carried = combined_url_streams(ws_client.url());
+        let unsent: Vec<Channel> = channels
+            .iter()
+            .filter(|channel| {
+                carried.is_empty()
+                    || self
+                        .streams_from_channels(std::slice::from_ref(channel))
+                        .iter()
+                        .any(|stream| !carried.contains(stream.as_str()))
+            })
+            .cloned()
+            .collect();
+
+        if !unsent.is_empty() {
+            let subscription = self.format_subscription(&unsent, request_id)?;
+            ws_client.send_text(&subscription).await?;
+            debug!(
+                market = Self::market_label(market_type),
+                "Sent Binance subscription: {}", subscription
+            );
+        }
+
+        let hub = self.hub.lock().await.clone();
+        self.pending_subscribes.track(request_id, channels, hub);
+        Ok(())
+    }
+
     async fn start_mock_data(&self, _market_type: MarketType, _hub: HubHandle) -> Result<()> {
         // Mocks removed; nothing to do
         Ok(())
@@ -511,6 +1340,15 @@ impl BinanceAdapter {
                 continue;
             }
 
+            if let Some(until) = self.throttled_until(market_type).await {
+                debug!(
+                    market = Self::market_label(market_type),
+                    "Delaying Binance subscription until rate limit back-off ends"
+                );
+                tokio::time::sleep_until(until).await;
+            }
+
+            let was_connected = self.is_market_connected(market_type).await;
             let maybe_client = self.ensure_connection(market_type).await?;
 
             if maybe_client.is_none() {
@@ -521,14 +1359,34 @@ impl BinanceAdapter {
                 continue;
             }
 
-            let subscription = self.format_subscription(&market_channels)?;
             if let Some(ws_client) = maybe_client {
-                ws_client.send_text(&subscription).await?;
-                debug!(
-                    market = Self::market_label(market_type),
-                    "Sent Binance subscription: {}", subscription
-                );
+                let mut resubscribe = market_channels.clone();
+                if !was_connected {
+                    // A new connection carries no subscriptions, so the market's full set is resent
+                    let remembered: Vec<Channel> = self
+                        .subscribed
+                        .lock()
+                        .await
+                        .get(&market_type)
+                        .map(|channels| channels.iter().cloned().collect())
+                        .unwrap_or_default();
+                    self.reset_orderbooks(&remembered).await;
+                    for channel in remembered {
+                        if !resubscribe.contains(&channel) {
+                            resubscribe.push(channel);
+                        }
+                    }
+                }
+                self.send_subscription(market_type, &ws_client, resubscribe)
+                    .await?;
             }
+
+            self.subscribed
+                .lock()
+                .await
+                .entry(market_type)
+                .or_default()
+                .extend(market_channels);
         }
 
         Ok(())
@@ -555,6 +1413,29 @@ impl BinanceAdapter {
                 continue;
             }
 
+            let still_needed: HashSet<String> = {
+                let mut subscribed = self.subscribed.lock().await;
+                let subscribed = subscribed.entry(market_type).or_default();
+                for channel in &market_channels {
+                    subscribed.remove(channel);
+                }
+                let remaining: Vec<Channel> = subscribed.iter().cloned().collect();
+                self.streams_from_channels(&remaining).into_iter().collect()
+            };
+            // Keep streams that channels still subscribed share, e.g. one book at two depths
+            let market_channels: Vec<Channel> = market_channels
+                .into_iter()
+                .filter(|channel| {
+                    self.streams_from_channels(std::slice::from_ref(channel))
+                        .iter()
+                        .any(|stream| !still_needed.contains(stream))
+                })
+                .collect();
+            if market_channels.is_empty() {
+                continue;
+            }
+            self.drop_depth_books(&market_channels).await;
+
             if self.mock_enabled(market_type).await {
                 info!(
                     market = Self::market_label(market_type),
@@ -563,7 +1444,8 @@ impl BinanceAdapter {
                 continue;
             }
 
-            let unsubscription = self.format_unsubscription(&market_channels)?;
+            let request_id = self.pending_subscribes.next_request_id();
+            let unsubscription = self.format_unsubscription(&market_channels, request_id)?;
             if let Some(ws_client) = self.get_ws_client(market_type).await {
                 ws_client.send_text(&unsubscription).await?;
                 debug!(
@@ -586,7 +1468,27 @@ impl BinanceAdapter {
 
 impl ExchangeAdapter for BinanceAdapter {
     fn id(&self) -> ExchangeId {
-        ExchangeId::from("binance")
+        ExchangeId::from(ExchangeId::BINANCE)
+    }
+
+    fn supported_markets(&self) -> Vec<MarketType> {
+        SUPPORTED_MARKETS.to_vec()
+    }
+
+    fn supported_depths(&self) -> &'static [u16] {
+        &SUPPORTED_DEPTHS
+    }
+
+    fn orderbook_stream(&self, market_type: MarketType, depth: Option<u16>) -> (u16, u32) {
+        if self.orderbook_deltas {
+            return (DEPTH_SNAPSHOT_LIMIT, 100);
+        }
+        // Partial depth streams without a speed suffix push every 1000ms on spot, 250ms on futures
+        let update_speed = match market_type {
+            MarketType::Spot => 1000,
+            _ => 250,
+        };
+        (depth.unwrap_or(DEFAULT_ORDERBOOK_DEPTH), update_speed)
     }
 
     async fn start(&self, hub: HubHandle, cache: CacheHandle) -> Result<()> {
@@ -595,11 +1497,33 @@ impl ExchangeAdapter for BinanceAdapter {
         *self.hub.lock().await = Some(hub.clone());
         *self.cache.lock().await = Some(cache.clone());
 
+        let mut watchdogs = self.watchdogs.lock().await;
+        if watchdogs.is_empty() {
+            for market_type in SUPPORTED_MARKETS {
+                let adapter = self.clone();
+                watchdogs.push(tokio::spawn(async move {
+                    adapter.run_watchdog(market_type).await
+                }));
+            }
+        }
+
         debug!("Binance adapter initialized with hub and cache handles");
 
         Ok(())
     }
 
+    async fn connect_markets(&self, markets: &[MarketType]) -> Result<()> {
+        for market_type in markets {
+            self.ensure_connection(*market_type).await?;
+            info!(
+                market = Self::market_label(*market_type),
+                "Binance market connection opened"
+            );
+        }
+
+        Ok(())
+    }
+
     async fn subscribe(&self, channels: &[Channel]) -> Result<()> {
         self.subscribe_internal(channels).await
     }
@@ -622,9 +1546,39 @@ impl ExchangeAdapter for BinanceAdapter {
         false
     }
 
+    async fn is_market_connected(&self, market_type: MarketType) -> bool {
+        self.get_ws_client(market_type)
+            .await
+            .map(|client| client.is_connected())
+            .unwrap_or(false)
+    }
+
+    async fn connection_stats(&self, market_type: MarketType) -> ConnectionStats {
+        let mut stats = self
+            .connection_stats
+            .lock()
+            .await
+            .get(&market_type)
+            .cloned()
+            .unwrap_or_default();
+        stats.circuit = self.breaker.state(market_type);
+        stats
+    }
+
+    async fn metrics(&self) -> AdapterMetrics {
+        self.metrics.snapshot()
+    }
+
     async fn stop(&self) -> Result<()> {
         info!("Stopping Binance adapter");
 
+        for watchdog in self.watchdogs.lock().await.drain(..) {
+            watchdog.abort();
+        }
+        for (_, sync) in self.book_syncs.lock().await.drain() {
+            sync.abort();
+        }
+
         let mut ws_guard = self.ws_clients.lock().await;
 
         for (market_type, client_opt) in ws_guard.iter_mut() {
@@ -646,3 +1600,1053 @@ impl Default for BinanceAdapter {
         Self::new()
     }
 }
+
+/// Streams a combined-stream URL such as `.../stream?streams=a@ticker/b@ticker` opens with
+fn combined_url_streams(url: &str) -> HashSet<&str> {
+    url.split_once("/stream?streams=")
+        .map(|(_, streams)| streams.split('/').collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_dash_core::model::CircuitState;
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+
+    /// Spawn a local WebSocket server that accepts connections and keeps them open
+    async fn spawn_ws_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let _ws = tokio_tungstenite::accept_async(stream).await;
+                    std::future::pending::<()>().await;
+                });
+            }
+        });
+
+        format!("ws://{}", addr)
+    }
+
+    /// Spawn a local upstream that numbers its connections from 1 and reports every
+    /// text frame as `(connection, text)`. Each request is acknowledged and followed by
+    /// `frames`; the first `dropped` connections then close.
+    async fn spawn_ack_upstream(
+        frames: &'static [&'static str],
+        dropped: usize,
+    ) -> (String, mpsc::UnboundedReceiver<(usize, String)>) {
+        use futures::{SinkExt, StreamExt};
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (frames_tx, frames_rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut connection = 0;
+            while let Ok((stream, _)) = listener.accept().await {
+                connection += 1;
+                let frames_tx = frames_tx.clone();
+                tokio::spawn(async move {
+                    let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                    while let Some(Ok(Message::Text(text))) = ws.next().await {
+                        let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                        let _ = frames_tx.send((connection, text));
+                        let ack = format!(r#"{{"result":null,"id":{}}}"#, request["id"]);
+                        let replies =
+                            std::iter::once(ack).chain(frames.iter().map(|f| f.to_string()));
+                        for reply in replies {
+                            if ws.send(Message::Text(reply)).await.is_err() {
+                                return;
+                            }
+                        }
+                        if connection <= dropped {
+                            let _ = ws.close(None).await;
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+        (url, frames_rx)
+    }
+
+    #[tokio::test]
+    async fn open_circuit_fails_connects_fast() {
+        // Nothing listens on a port whose listener was dropped
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let adapter = BinanceAdapter::new()
+            .with_ws_url(MarketType::Spot, url)
+            .with_circuit_breaker(CircuitBreaker::new(2, Duration::from_secs(60)));
+
+        for _ in 0..2 {
+            assert!(adapter.try_real_connection(MarketType::Spot).await.is_err());
+        }
+        let Err(err) = adapter.try_real_connection(MarketType::Spot).await else {
+            panic!("connect went through an open circuit");
+        };
+        assert!(err.to_string().contains("circuit open"), "{}", err);
+
+        let stats = adapter.connection_stats(MarketType::Spot).await;
+        assert_eq!(stats.reconnect_attempts, 2);
+        assert_eq!(stats.circuit, CircuitState::Open);
+        assert_eq!(
+            adapter
+                .connection_stats(MarketType::Perpetual)
+                .await
+                .circuit,
+            CircuitState::Closed
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_connection_state_per_market() {
+        let adapter = BinanceAdapter::new();
+        let url = spawn_ws_server().await;
+
+        let spot_client = Arc::new(WsClient::new(url));
+        spot_client.connect().await.unwrap();
+        adapter
+            .set_ws_client(MarketType::Spot, Some(spot_client))
+            .await;
+
+        assert!(adapter.is_market_connected(MarketType::Spot).await);
+        assert!(!adapter.is_market_connected(MarketType::Perpetual).await);
+        assert!(adapter.is_connected().await);
+    }
+
+    #[tokio::test]
+    async fn records_failed_connection_attempts() {
+        let adapter = BinanceAdapter::new().with_ws_url(MarketType::Spot, "ws://127.0.0.1:1");
+
+        assert!(adapter.ensure_connection(MarketType::Spot).await.is_err());
+        assert!(adapter.ensure_connection(MarketType::Spot).await.is_err());
+
+        let stats = adapter.connection_stats(MarketType::Spot).await;
+        assert!(stats.last_connect_error.is_some());
+        assert_eq!(stats.reconnect_attempts, 2);
+        assert!(stats.last_connected_at.is_none());
+
+        let perp_stats = adapter.connection_stats(MarketType::Perpetual).await;
+        assert_eq!(perp_stats.reconnect_attempts, 0);
+    }
+
+    #[test]
+    fn exchange_symbols_round_trip() {
+        let adapter = BinanceAdapter::new();
+        let symbols = [
+            Symbol::new("BTC", "USDT"),
+            Symbol::new("ETH", "BTC"),
+            Symbol::new("SOL", "USDC"),
+            Symbol::new("USDC", "USDT"),
+            Symbol::new("BTC", "TUSD"),
+            Symbol::new("1000PEPE", "USDT"),
+            Symbol::new("1INCH", "ETH"),
+        ];
+
+        for market_type in SUPPORTED_MARKETS {
+            for symbol in &symbols {
+                let exchange_symbol = adapter.to_exchange_symbol(symbol, market_type);
+                assert_eq!(
+                    &adapter.parse_symbol(&exchange_symbol).unwrap(),
+                    symbol,
+                    "{} did not round-trip on {:?}",
+                    exchange_symbol,
+                    market_type
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn streams_use_exchange_symbol() {
+        let adapter = BinanceAdapter::new();
+        let channel = Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: adapter.id(),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("1000pepe", "usdt"),
+            depth: None,
+        };
+
+        assert_eq!(
+            adapter.streams_from_channels(&[channel]),
+            vec!["1000pepeusdt@ticker".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn connect_markets_opens_connection_before_subscribe() {
+        let url = spawn_ws_server().await;
+        let adapter = BinanceAdapter::new().with_ws_url(MarketType::Spot, url);
+
+        adapter.connect_markets(&[MarketType::Spot]).await.unwrap();
+
+        assert!(adapter.is_market_connected(MarketType::Spot).await);
+        assert!(!adapter.is_market_connected(MarketType::Perpetual).await);
+    }
+
+    #[tokio::test]
+    async fn watchdog_reconnects_stale_connection() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        // The test server never sends anything, so the connection goes stale
+        let url = spawn_ws_server().await;
+        let adapter = BinanceAdapter::new()
+            .with_ws_url(MarketType::Spot, url)
+            .with_connection_timeout(Duration::from_millis(200));
+        adapter
+            .start(StreamHub::new().handle(), MemoryCache::new().handle())
+            .await
+            .unwrap();
+
+        let channel = Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: adapter.id(),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        adapter.subscribe(&[channel]).await.unwrap();
+        let original = adapter.get_ws_client(MarketType::Spot).await.unwrap();
+
+        let mut reconnected = false;
+        for _ in 0..100 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            if let Some(current) = adapter.get_ws_client(MarketType::Spot).await {
+                if !Arc::ptr_eq(&original, &current) {
+                    reconnected = true;
+                    break;
+                }
+            }
+        }
+
+        assert!(reconnected, "watchdog did not replace the stale connection");
+        assert!(!original.is_connected());
+        assert!(adapter
+            .connection_stats(MarketType::Spot)
+            .await
+            .last_connect_error
+            .is_none());
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn dropped_connection_reconnects_and_resubscribes() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        // The first connection closes after the SUBSCRIBE; later ones report what they receive
+        let (url, mut frames) = spawn_ack_upstream(&[], 1).await;
+
+        let hub = StreamHub::new().handle();
+        let _session = hub.subscribe_all().await;
+        let adapter = BinanceAdapter::new()
+            .with_ws_url(MarketType::Spot, url)
+            .with_subscribe_timeout(Duration::ZERO)
+            .with_reconnect_retry(RetryConfig {
+                max_attempts: 3,
+                base_delay: Duration::from_millis(10),
+                ..RetryConfig::default()
+            });
+        adapter
+            .start(hub, MemoryCache::new().handle())
+            .await
+            .unwrap();
+
+        let channel = Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: adapter.id(),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        adapter.subscribe(&[channel]).await.unwrap();
+
+        let (first, _) = frames.recv().await.unwrap();
+        assert_eq!(first, 1);
+        let (second, resent) = tokio::time::timeout(Duration::from_secs(5), frames.recv())
+            .await
+            .expect("adapter did not reconnect")
+            .unwrap();
+        assert_eq!(second, 2);
+        let resent: serde_json::Value = serde_json::from_str(&resent).unwrap();
+        assert_eq!(resent["method"], "SUBSCRIBE");
+        assert_eq!(resent["params"], serde_json::json!(["btcusdt@ticker"]));
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn flapping_connection_restores_are_budgeted() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        // Every connection is closed as soon as its SUBSCRIBE arrives
+        let (url, mut frames) = spawn_ack_upstream(&[], usize::MAX).await;
+
+        let hub = StreamHub::new().handle();
+        let _session = hub.subscribe_all().await;
+        let adapter = BinanceAdapter::new()
+            .with_ws_url(MarketType::Spot, url)
+            .with_subscribe_timeout(Duration::ZERO)
+            .with_reconnect_retry(RetryConfig {
+                max_attempts: 1,
+                base_delay: Duration::from_millis(10),
+                ..RetryConfig::default()
+            })
+            .with_reconnect_budget(ReconnectBudget::new(2, Duration::from_secs(60)));
+        adapter
+            .start(hub, MemoryCache::new().handle())
+            .await
+            .unwrap();
+
+        let channel = Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: adapter.id(),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        adapter.subscribe(&[channel]).await.unwrap();
+
+        // The first connection plus one per restore the budget allows, then it gives up
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let mut connections = 0;
+        while let Ok((connection, _)) = frames.try_recv() {
+            connections = connection;
+        }
+        assert_eq!(connections, 3);
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn combined_streams_reconnect_names_streams_in_url() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+        use futures::{SinkExt, StreamExt};
+        use tokio::sync::mpsc;
+        use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+
+        // Reports each connection's request path and text frames. The first connection
+        // closes after its SUBSCRIBE; the second streams a ticker in the combined wrapper.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/ws", listener.local_addr().unwrap());
+        let (events_tx, mut events) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut connection = 0;
+            while let Ok((stream, _)) = listener.accept().await {
+                connection += 1;
+                let events_tx = events_tx.clone();
+                tokio::spawn(async move {
+                    let path_tx = events_tx.clone();
+                    // The handshake callback's error type is tungstenite's own
+                    #[allow(clippy::result_large_err)]
+                    let callback = move |request: &Request, response: Response| {
+                        let _ = path_tx.send((connection, request.uri().to_string()));
+                        Ok(response)
+                    };
+                    let mut ws = tokio_tungstenite::accept_hdr_async(stream, callback)
+                        .await
+                        .unwrap();
+                    if connection == 1 {
+                        if let Some(Ok(Message::Text(text))) = ws.next().await {
+                            let _ = events_tx.send((connection, text));
+                        }
+                        let _ = ws.close(None).await;
+                        return;
+                    }
+                    let frame = r#"{"stream":"btcusdt@ticker","data":{"s":"BTCUSDT","c":"50000.00","b":"49999.00","a":"50002.00","E":1234567890}}"#;
+                    ws.send(Message::Text(frame.to_string())).await.unwrap();
+                    while let Some(Ok(Message::Text(text))) = ws.next().await {
+                        let _ = events_tx.send((connection, text));
+                    }
+                });
+            }
+        });
+
+        let hub = StreamHub::new().handle();
+        let mut session = hub.subscribe_all().await;
+        let adapter = BinanceAdapter::new()
+            .with_ws_url(MarketType::Spot, url)
+            .with_combined_streams(true)
+            .with_subscribe_timeout(Duration::ZERO)
+            .with_reconnect_retry(RetryConfig {
+                max_attempts: 3,
+                base_delay: Duration::from_millis(10),
+                ..RetryConfig::default()
+            });
+        adapter
+            .start(hub, MemoryCache::new().handle())
+            .await
+            .unwrap();
+
+        let channel = Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: adapter.id(),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        adapter.subscribe(&[channel]).await.unwrap();
+
+        // Nothing was subscribed yet, so the first connection uses the plain endpoint
+        assert_eq!(events.recv().await.unwrap(), (1, "/ws".to_string()));
+        let (connection, subscribe) = events.recv().await.unwrap();
+        assert_eq!(connection, 1);
+        assert!(subscribe.contains("btcusdt@ticker"), "{}", subscribe);
+
+        let reconnect = tokio::time::timeout(Duration::from_secs(5), events.recv())
+            .await
+            .expect("adapter did not reconnect")
+            .unwrap();
+        assert_eq!(reconnect, (2, "/stream?streams=btcusdt@ticker".to_string()));
+
+        let (topic, message) = tokio::time::timeout(Duration::from_secs(5), session.recv())
+            .await
+            .expect("combined-stream ticker never arrived")
+            .unwrap();
+        assert_eq!(topic.channel_type, ChannelType::Ticker);
+        assert!(matches!(message, StreamMessage::Ticker(_)));
+
+        // The URL already carries the stream, so no SUBSCRIBE follows the reconnect
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(events.try_recv().is_err());
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn subscribe_on_new_connection_resends_market_channels() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        // The first connection closes after its SUBSCRIBE; nobody listens, so it stays down
+        let (url, mut frames) = spawn_ack_upstream(&[], 1).await;
+
+        let adapter = BinanceAdapter::new()
+            .with_ws_url(MarketType::Spot, url)
+            .with_subscribe_timeout(Duration::ZERO);
+        adapter
+            .start(StreamHub::new().handle(), MemoryCache::new().handle())
+            .await
+            .unwrap();
+
+        let btc = Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: adapter.id(),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        let eth = Channel {
+            symbol: Symbol::new("ETH", "USDT"),
+            ..btc.clone()
+        };
+        adapter.subscribe(&[btc]).await.unwrap();
+        let (first, _) = frames.recv().await.unwrap();
+        assert_eq!(first, 1);
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while adapter.is_market_connected(MarketType::Spot).await {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("connection was not dropped");
+
+        adapter.subscribe(&[eth]).await.unwrap();
+        let (second, sent) = tokio::time::timeout(Duration::from_secs(5), frames.recv())
+            .await
+            .expect("no subscription on the new connection")
+            .unwrap();
+        assert_eq!(second, 2);
+        let sent: serde_json::Value = serde_json::from_str(&sent).unwrap();
+        let mut params: Vec<&str> = sent["params"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|param| param.as_str().unwrap())
+            .collect();
+        params.sort();
+        assert_eq!(params, ["btcusdt@ticker", "ethusdt@ticker"]);
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn unacknowledged_subscribe_times_out() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_core::model::ErrorCode;
+        use crypto_dash_stream_hub::StreamHub;
+
+        // The test server never answers the SUBSCRIBE request
+        let url = spawn_ws_server().await;
+        let hub = StreamHub::new().handle();
+        let adapter = BinanceAdapter::new()
+            .with_ws_url(MarketType::Spot, url)
+            .with_subscribe_timeout(Duration::from_millis(100));
+        adapter
+            .start(hub.clone(), MemoryCache::new().handle())
+            .await
+            .unwrap();
+        let mut receiver = hub.subscribe_all().await;
+
+        let channel = Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: adapter.id(),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        adapter.subscribe(&[channel]).await.unwrap();
+
+        let (topic, message) = tokio::time::timeout(Duration::from_secs(2), receiver.recv())
+            .await
+            .expect("no subscribe timeout published")
+            .unwrap();
+        assert_eq!(topic.symbol, Symbol::new("BTC", "USDT"));
+        match message {
+            StreamMessage::Error { code, .. } => {
+                assert_eq!(code, Some(ErrorCode::SubscribeTimeout))
+            }
+            other => panic!("Expected Error, got {:?}", other),
+        }
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn acknowledged_subscribe_does_not_time_out() {
+        let adapter = BinanceAdapter::new().with_subscribe_timeout(Duration::from_millis(50));
+        let hub = crypto_dash_stream_hub::StreamHub::new().handle();
+        let mut receiver = hub.subscribe_all().await;
+
+        let channel = Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: adapter.id(),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        let request_id = adapter.pending_subscribes.next_request_id();
+        adapter
+            .pending_subscribes
+            .track(request_id, vec![channel], Some(hub));
+
+        let frame = format!(r#"{{"result":null,"id":{}}}"#, request_id);
+        let message: BinanceStreamMessage = serde_json::from_str(&frame).unwrap();
+        assert!(matches!(message, BinanceStreamMessage::Response { .. }));
+        adapter
+            .handle_message(MarketType::Spot, message)
+            .await
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn ticker_direction_follows_cached_last_price() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        let cache = MemoryCache::new().handle();
+        let adapter = BinanceAdapter::new();
+        adapter
+            .start(StreamHub::new().handle(), cache.clone())
+            .await
+            .unwrap();
+
+        let mut directions = Vec::new();
+        for last in ["50000.00", "50010.00", "50005.00", "50005.00"] {
+            let frame = format!(
+                r#"{{"stream":"btcusdt@ticker","data":{{"s":"BTCUSDT","c":"{}","E":1234567890}}}}"#,
+                last
+            );
+            adapter
+                .handle_message(MarketType::Spot, serde_json::from_str(&frame).unwrap())
+                .await
+                .unwrap();
+            let ticker = cache
+                .get_ticker(&adapter.id(), MarketType::Spot, &Symbol::new("BTC", "USDT"))
+                .await
+                .unwrap();
+            directions.push(ticker.tick_direction);
+        }
+
+        assert_eq!(
+            directions,
+            [
+                Some(TickDirection::Neutral),
+                Some(TickDirection::Up),
+                Some(TickDirection::Down),
+                Some(TickDirection::Neutral),
+            ]
+        );
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn out_of_order_ticker_does_not_replace_newer_one() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        let hub = StreamHub::new().handle();
+        let mut rx = hub.subscribe_all().await;
+        let cache = MemoryCache::new().handle();
+        let adapter = BinanceAdapter::new();
+        adapter.start(hub.clone(), cache.clone()).await.unwrap();
+
+        // The second frame was stamped a second before the first
+        for (last, event_time) in [("50010.00", 1700000001000_i64), ("50000.00", 1700000000000)] {
+            let frame = format!(
+                r#"{{"stream":"btcusdt@ticker","data":{{"s":"BTCUSDT","c":"{}","E":{}}}}}"#,
+                last, event_time
+            );
+            adapter
+                .handle_message(MarketType::Spot, serde_json::from_str(&frame).unwrap())
+                .await
+                .unwrap();
+        }
+
+        let cached = cache
+            .get_ticker(&adapter.id(), MarketType::Spot, &Symbol::new("BTC", "USDT"))
+            .await
+            .unwrap();
+        assert_eq!(cached.last, Decimal::from_str("50010.00").unwrap());
+        assert_eq!(cached.timestamp.timestamp_millis(), 1700000001000);
+        assert!(matches!(rx.try_recv(), Ok((_, StreamMessage::Ticker(_)))));
+        assert!(rx.try_recv().is_err());
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn ticker_frame_carries_24h_statistics() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        let cache = MemoryCache::new().handle();
+        let adapter = BinanceAdapter::new();
+        adapter
+            .start(StreamHub::new().handle(), cache.clone())
+            .await
+            .unwrap();
+
+        let frame = r#"{"stream":"btcusdt@ticker","data":{"e":"24hrTicker","E":1234567890,"s":"BTCUSDT","P":"-1.25","c":"50000.00","h":"51200.50","l":"49100.00","v":"1520.125","q":"76006250.00"}}"#;
+        adapter
+            .handle_message(MarketType::Spot, serde_json::from_str(frame).unwrap())
+            .await
+            .unwrap();
+
+        let ticker = cache
+            .get_ticker(&adapter.id(), MarketType::Spot, &Symbol::new("BTC", "USDT"))
+            .await
+            .unwrap();
+        assert_eq!(
+            ticker.high_24h,
+            Some(Decimal::from_str("51200.50").unwrap())
+        );
+        assert_eq!(ticker.low_24h, Some(Decimal::from_str("49100.00").unwrap()));
+        assert_eq!(
+            ticker.volume_24h,
+            Some(Decimal::from_str("1520.125").unwrap())
+        );
+        assert_eq!(
+            ticker.quote_volume_24h,
+            Some(Decimal::from_str("76006250.00").unwrap())
+        );
+        assert_eq!(
+            ticker.change_pct_24h,
+            Some(Decimal::from_str("-1.25").unwrap())
+        );
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn crossed_book_tickers_are_not_published() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        let cache = MemoryCache::new().handle();
+        let hub = StreamHub::new().handle();
+        let mut receiver = hub.subscribe_all().await;
+        let adapter = BinanceAdapter::new();
+        adapter.start(hub, cache.clone()).await.unwrap();
+
+        for frame in [
+            r#"{"stream":"btcusdt@ticker","data":{"s":"BTCUSDT","c":"50000.00","b":"50010.00","a":"50001.00","E":1234567890}}"#,
+            r#"{"stream":"btcusdt@ticker","data":{"s":"BTCUSDT","c":"0","b":"49999.00","a":"50001.00","E":1234567890}}"#,
+        ] {
+            adapter
+                .handle_message(MarketType::Spot, serde_json::from_str(frame).unwrap())
+                .await
+                .unwrap();
+        }
+
+        assert!(receiver.try_recv().is_err());
+        assert!(cache
+            .get_ticker(&adapter.id(), MarketType::Spot, &Symbol::new("BTC", "USDT"))
+            .await
+            .is_none());
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn mark_price_frame_publishes_funding_rate() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        let hub = StreamHub::new().handle();
+        let mut rx = hub.subscribe_all().await;
+        let adapter = BinanceAdapter::new();
+        adapter
+            .start(hub.clone(), MemoryCache::new().handle())
+            .await
+            .unwrap();
+
+        let frame = r#"{"e":"markPriceUpdate","E":1562305380000,"s":"BTCUSDT","p":"11794.15000000","i":"11784.62659091","P":"11784.25641265","r":"0.00038167","T":1562306400000}"#;
+        adapter
+            .handle_message(MarketType::Perpetual, serde_json::from_str(frame).unwrap())
+            .await
+            .unwrap();
+
+        let (topic, message) = rx.try_recv().unwrap();
+        assert_eq!(topic.channel_type, ChannelType::Funding);
+        let StreamMessage::FundingRate(funding) = message else {
+            panic!("expected funding rate, got {:?}", message);
+        };
+        assert_eq!(funding.market_type, MarketType::Perpetual);
+        assert_eq!(funding.symbol, Symbol::new("BTC", "USDT"));
+        assert_eq!(
+            funding.mark_price,
+            Some(Decimal::from_str("11794.15").unwrap())
+        );
+        assert_eq!(
+            funding.index_price,
+            Some(Decimal::from_str("11784.62659091").unwrap())
+        );
+        assert_eq!(funding.rate, Decimal::from_str("0.00038167").unwrap());
+        assert_eq!(funding.next_funding_time.timestamp_millis(), 1562306400000);
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn agg_trade_frame_publishes_trade() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        let hub = StreamHub::new().handle();
+        let mut rx = hub.subscribe_all().await;
+        let adapter = BinanceAdapter::new();
+        adapter
+            .start(hub.clone(), MemoryCache::new().handle())
+            .await
+            .unwrap();
+
+        let channel = Channel {
+            channel_type: ChannelType::Trades,
+            exchange: adapter.id(),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        assert_eq!(
+            adapter.streams_from_channels(&[channel]),
+            vec!["btcusdt@aggTrade".to_string()]
+        );
+
+        let frame = r#"{"e":"aggTrade","E":1672515782136,"s":"BTCUSDT","a":26129,"p":"16500.10","q":"0.25","f":100,"l":105,"T":1672515782130,"m":true,"M":true}"#;
+        adapter
+            .handle_message(MarketType::Spot, serde_json::from_str(frame).unwrap())
+            .await
+            .unwrap();
+
+        let (topic, message) = rx.try_recv().unwrap();
+        assert_eq!(topic.key(), "trades:binance:spot:BTC-USDT");
+        let StreamMessage::Trade(trade) = message else {
+            panic!("expected trade, got {:?}", message);
+        };
+        assert_eq!(trade.price, Decimal::from_str("16500.10").unwrap());
+        assert_eq!(trade.quantity, Decimal::from_str("0.25").unwrap());
+        // The buyer was the maker, so the aggressor sold
+        assert_eq!(trade.side, Side::Sell);
+        assert_eq!(trade.trade_id.as_deref(), Some("26129"));
+        assert_eq!(trade.timestamp.timestamp_millis(), 1672515782130);
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[test]
+    fn funding_channel_maps_to_mark_price_stream_for_perpetuals_only() {
+        let perp = Channel {
+            channel_type: ChannelType::Funding,
+            exchange: ExchangeId::from("binance"),
+            market_type: MarketType::Perpetual,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        let spot = Channel {
+            market_type: MarketType::Spot,
+            ..perp.clone()
+        };
+
+        assert_eq!(
+            BinanceAdapter::new().streams_from_channels(&[perp]),
+            vec!["btcusdt@markPrice@1s".to_string()]
+        );
+        assert!(BinanceAdapter::new()
+            .streams_from_channels(&[spot])
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn rate_limit_error_starts_back_off() {
+        let adapter = BinanceAdapter::new();
+        let frame = r#"{"id":1,"error":{"code":-1003,"msg":"Too many requests"}}"#;
+
+        let message: BinanceStreamMessage = serde_json::from_str(frame).unwrap();
+        adapter
+            .handle_message(MarketType::Perpetual, message)
+            .await
+            .unwrap();
+
+        assert!(adapter
+            .throttled_until(MarketType::Perpetual)
+            .await
+            .is_some());
+        assert!(adapter.throttled_until(MarketType::Spot).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn depth_diffs_publish_deltas_and_resnapshot_on_gap() {
+        use axum::{extract::Query, routing::get, Json};
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // The first snapshot is taken at update 100, the resync after the gap at 106
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let fetch_count = fetches.clone();
+        let router = axum::Router::new().route(
+            "/api/v3/depth",
+            get(
+                move |Query(query): Query<HashMap<String, String>>| async move {
+                    assert_eq!(query["symbol"], "BTCUSDT");
+                    let last_update_id = match fetch_count.fetch_add(1, Ordering::SeqCst) {
+                        0 => 100,
+                        _ => 106,
+                    };
+                    Json(serde_json::json!({
+                        "lastUpdateId": last_update_id,
+                        "bids": [["100.0", "1"]],
+                        "asks": [["101.0", "1"]],
+                    }))
+                },
+            ),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let rest_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+
+        let hub = StreamHub::new().handle();
+        let mut rx = hub.subscribe_all().await;
+        let cache = MemoryCache::new().handle();
+        let adapter = BinanceAdapter::new()
+            .with_orderbook_deltas(true)
+            .with_rest_url(MarketType::Spot, rest_url);
+        adapter.start(hub.clone(), cache.clone()).await.unwrap();
+
+        let diff = |first: i64, last: i64, bids: &str| {
+            let frame = format!(
+                r#"{{"stream":"btcusdt@depth@100ms","data":{{"e":"depthUpdate","E":1700000000000,"s":"BTCUSDT","U":{},"u":{},"b":{},"a":[]}}}}"#,
+                first, last, bids
+            );
+            serde_json::from_str::<BinanceStreamMessage>(&frame).unwrap()
+        };
+        let levels = |levels: &[PriceLevel]| -> Vec<(Decimal, Decimal)> {
+            levels.iter().map(|l| (l.price, l.quantity)).collect()
+        };
+        let timeout = Duration::from_secs(5);
+
+        // The diff buffered before the snapshot is older than it and is dropped
+        adapter
+            .handle_message(MarketType::Spot, diff(95, 99, r#"[["90.0","5"]]"#))
+            .await
+            .unwrap();
+        let StreamMessage::OrderBookSnapshot(snapshot) = tokio::time::timeout(timeout, rx.recv())
+            .await
+            .unwrap()
+            .unwrap()
+            .1
+        else {
+            panic!("expected the initial snapshot");
+        };
+        assert_eq!(snapshot.bids.len(), 1);
+
+        adapter
+            .handle_message(
+                MarketType::Spot,
+                diff(101, 102, r#"[["100.0","0"],["99.5","3"]]"#),
+            )
+            .await
+            .unwrap();
+        let StreamMessage::OrderBookDelta(delta) = tokio::time::timeout(timeout, rx.recv())
+            .await
+            .unwrap()
+            .unwrap()
+            .1
+        else {
+            panic!("expected a delta");
+        };
+        assert_eq!(
+            levels(&delta.bids_upserts),
+            [(Decimal::new(995, 1), Decimal::from(3))]
+        );
+        assert_eq!(delta.deletes, Some(vec![Decimal::new(1000, 1)]));
+        let cached = cache
+            .get_orderbook(&adapter.id(), MarketType::Spot, &Symbol::new("BTC", "USDT"))
+            .await
+            .unwrap();
+        assert_eq!(
+            levels(&cached.bids),
+            [(Decimal::new(995, 1), Decimal::from(3))]
+        );
+
+        // Updates 103 and 104 never arrived
+        adapter
+            .handle_message(MarketType::Spot, diff(105, 106, "[]"))
+            .await
+            .unwrap();
+        let StreamMessage::OrderBookSnapshot(resync) = tokio::time::timeout(timeout, rx.recv())
+            .await
+            .unwrap()
+            .unwrap()
+            .1
+        else {
+            panic!("expected a resync snapshot");
+        };
+        assert_eq!(
+            levels(&resync.bids),
+            [(Decimal::new(1000, 1), Decimal::from(1))]
+        );
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            adapter.streams_from_channels(&[Channel {
+                channel_type: ChannelType::OrderBook,
+                exchange: adapter.id(),
+                market_type: MarketType::Spot,
+                symbol: Symbol::new("BTC", "USDT"),
+                depth: Some(10),
+            }]),
+            ["btcusdt@depth@100ms"]
+        );
+    }
+
+    /// Serve `/api/v3/depth` snapshots at update 100 after `delay`
+    async fn spawn_depth_server(delay: Duration) -> String {
+        use axum::{routing::get, Json};
+
+        let router = axum::Router::new().route(
+            "/api/v3/depth",
+            get(move || async move {
+                tokio::time::sleep(delay).await;
+                Json(serde_json::json!({
+                    "lastUpdateId": 100,
+                    "bids": [["100.0", "1"]],
+                    "asks": [["101.0", "1"]],
+                }))
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let rest_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+        rest_url
+    }
+
+    fn depth_diff(id: i64) -> BinanceStreamMessage {
+        let frame = format!(
+            r#"{{"stream":"btcusdt@depth@100ms","data":{{"e":"depthUpdate","E":1700000000000,"s":"BTCUSDT","U":{},"u":{},"b":[["99.0","1"]],"a":[]}}}}"#,
+            id, id
+        );
+        serde_json::from_str(&frame).unwrap()
+    }
+
+    #[tokio::test]
+    async fn depth_snapshot_precedes_first_delta() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        let hub = StreamHub::new().handle();
+        let mut rx = hub.subscribe_all().await;
+        let adapter = BinanceAdapter::new()
+            .with_orderbook_deltas(true)
+            .with_rest_url(
+                MarketType::Spot,
+                spawn_depth_server(Duration::from_millis(50)).await,
+            );
+        adapter
+            .start(hub, MemoryCache::new().handle())
+            .await
+            .unwrap();
+
+        // Diffs keep arriving while the snapshot is fetched and published
+        let feeder = adapter.clone();
+        let feed = tokio::spawn(async move {
+            for id in 101..400 {
+                feeder
+                    .handle_message(MarketType::Spot, depth_diff(id))
+                    .await
+                    .unwrap();
+                tokio::time::sleep(Duration::from_micros(500)).await;
+            }
+        });
+
+        let (_, first) = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("order book never published")
+            .unwrap();
+        assert!(
+            matches!(first, StreamMessage::OrderBookSnapshot(_)),
+            "first book message was {:?}",
+            first
+        );
+        feed.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn stop_aborts_in_flight_book_syncs() {
+        let adapter = BinanceAdapter::new()
+            .with_orderbook_deltas(true)
+            .with_rest_url(
+                MarketType::Spot,
+                spawn_depth_server(Duration::from_secs(60)).await,
+            );
+        adapter
+            .handle_message(MarketType::Spot, depth_diff(101))
+            .await
+            .unwrap();
+        // The sync task holds a clone of the adapter while its snapshot is in flight
+        assert_eq!(Arc::strong_count(&adapter.depth_books), 2);
+
+        adapter.stop().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(Arc::strong_count(&adapter.depth_books), 1);
+    }
+}
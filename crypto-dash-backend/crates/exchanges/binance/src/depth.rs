@@ -0,0 +1,173 @@
+use crate::types::{BinanceDepthUpdate, BinanceOrderBook};
+use anyhow::{anyhow, Result};
+use crypto_dash_core::model::PriceLevel;
+use crypto_dash_exchanges_common::{apply_levels, parse_levels};
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+
+/// Levels per side requested in REST depth snapshots and kept in local books
+pub(crate) const DEPTH_SNAPSHOT_LIMIT: u16 = 1000;
+/// Diffs buffered per book while its REST snapshot is in flight; the oldest are dropped
+/// beyond this, and a snapshot that no longer bridges what remains is refetched
+pub(crate) const MAX_BUFFERED_DEPTH_UPDATES: usize = 1000;
+
+/// Outcome of applying one diff to a local book
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DepthSync {
+    Applied,
+    /// Already covered by the book, e.g. diffs buffered before the snapshot was taken
+    Stale,
+    /// Diffs were missed; the book must be rebuilt from a new snapshot
+    Gap,
+}
+
+/// One instrument's order book, rebuilt from a REST snapshot and the diffs after it
+#[derive(Debug, Clone)]
+pub(crate) struct LocalBook {
+    last_update_id: i64,
+    /// Whether a diff has been applied since the snapshot; the first only has to straddle it
+    bridged: bool,
+    pub bids: Vec<PriceLevel>,
+    pub asks: Vec<PriceLevel>,
+}
+
+impl LocalBook {
+    /// Seed a book from `snapshot` and replay the diffs buffered while it was fetched
+    pub fn from_snapshot<'a>(
+        snapshot: &BinanceOrderBook,
+        buffered: impl IntoIterator<Item = &'a BinanceDepthUpdate>,
+    ) -> Result<Self> {
+        let parse = |levels: &[[String; 2]]| -> Result<Vec<PriceLevel>> {
+            Ok(parse_levels(levels)?
+                .into_iter()
+                .filter(|level| !level.quantity.is_zero())
+                .collect())
+        };
+        let mut bids = parse(&snapshot.bids)?;
+        let mut asks = parse(&snapshot.asks)?;
+        bids.sort_by_key(|level| Reverse(level.price));
+        asks.sort_by_key(|level| level.price);
+
+        let mut book = Self {
+            last_update_id: snapshot.last_update_id,
+            bridged: false,
+            bids,
+            asks,
+        };
+        for update in buffered {
+            if book.apply(update)? == DepthSync::Gap {
+                return Err(anyhow!(
+                    "snapshot {} predates buffered diff {}-{}",
+                    snapshot.last_update_id,
+                    update.first_update_id,
+                    update.final_update_id
+                ));
+            }
+        }
+        Ok(book)
+    }
+
+    /// Apply `update` if it continues the book's update ids
+    pub fn apply(&mut self, update: &BinanceDepthUpdate) -> Result<DepthSync> {
+        if update.final_update_id <= self.last_update_id {
+            return Ok(DepthSync::Stale);
+        }
+        let continues = if !self.bridged {
+            update.first_update_id <= self.last_update_id + 1
+        } else {
+            // Futures diffs name their predecessor; spot ids are consecutive
+            match update.prev_final_update_id {
+                Some(prev) => prev == self.last_update_id,
+                None => update.first_update_id == self.last_update_id + 1,
+            }
+        };
+        if !continues {
+            return Ok(DepthSync::Gap);
+        }
+
+        let bids = parse_levels(&update.b)?;
+        let asks = parse_levels(&update.a)?;
+        apply_levels(&mut self.bids, &bids, |a, b| b.cmp(a));
+        apply_levels(&mut self.asks, &asks, |a, b| a.cmp(b));
+        self.bids.truncate(DEPTH_SNAPSHOT_LIMIT as usize);
+        self.asks.truncate(DEPTH_SNAPSHOT_LIMIT as usize);
+        self.last_update_id = update.final_update_id;
+        self.bridged = true;
+        Ok(DepthSync::Applied)
+    }
+}
+
+/// Sync state of one instrument's local book
+#[derive(Debug)]
+pub(crate) enum DepthBook {
+    /// Waiting for a REST snapshot, holding the diffs received meanwhile
+    Syncing(VecDeque<BinanceDepthUpdate>),
+    Live(LocalBook),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn level(price: &str, quantity: &str) -> [String; 2] {
+        [price.to_string(), quantity.to_string()]
+    }
+
+    fn update(
+        first: i64,
+        last: i64,
+        prev: Option<i64>,
+        bids: Vec<[String; 2]>,
+    ) -> BinanceDepthUpdate {
+        BinanceDepthUpdate {
+            event_time: 0,
+            s: "BTCUSDT".to_string(),
+            first_update_id: first,
+            final_update_id: last,
+            prev_final_update_id: prev,
+            b: bids,
+            a: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn diffs_apply_in_sequence_and_gaps_are_detected() {
+        let snapshot = BinanceOrderBook {
+            last_update_id: 100,
+            bids: vec![level("100.0", "1"), level("101.0", "2")],
+            asks: vec![level("102.0", "1")],
+        };
+        // The first buffered diff is older than the snapshot, the second straddles it
+        let buffered = [
+            update(95, 99, None, vec![level("90.0", "9")]),
+            update(98, 102, None, vec![level("101.0", "0"), level("99.5", "3")]),
+        ];
+
+        let mut book = LocalBook::from_snapshot(&snapshot, &buffered).unwrap();
+        let prices: Vec<Decimal> = book.bids.iter().map(|level| level.price).collect();
+        assert_eq!(prices, [Decimal::new(1000, 1), Decimal::new(995, 1)]);
+
+        assert_eq!(
+            book.apply(&update(103, 104, None, Vec::new())).unwrap(),
+            DepthSync::Applied
+        );
+        assert_eq!(
+            book.apply(&update(100, 104, None, Vec::new())).unwrap(),
+            DepthSync::Stale
+        );
+        assert_eq!(
+            book.apply(&update(106, 107, None, Vec::new())).unwrap(),
+            DepthSync::Gap
+        );
+        // Futures continuity follows `pu` rather than consecutive ids
+        assert_eq!(
+            book.apply(&update(110, 112, Some(104), Vec::new()))
+                .unwrap(),
+            DepthSync::Applied
+        );
+
+        let late = [update(150, 151, None, Vec::new())];
+        assert!(LocalBook::from_snapshot(&snapshot, &late).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod adapter;
+mod depth;
 pub mod types;
 
 pub use adapter::BinanceAdapter;
@@ -1,3 +1,4 @@
+use crypto_dash_exchanges_common::string_or_number;
 use serde::{Deserialize, Serialize};
 
 /// Binance ticker response (24hr statistics stream)
@@ -12,35 +13,35 @@ pub struct BinanceTicker {
     pub event_time: Option<i64>,
     #[serde(default)]
     pub s: String, // symbol
-    #[serde(default)]
+    #[serde(default, deserialize_with = "string_or_number::optional")]
     pub p: Option<String>, // price change
-    #[serde(rename = "P", default)]
+    #[serde(rename = "P", default, deserialize_with = "string_or_number::optional")]
     pub price_change_percent: Option<String>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "string_or_number::optional")]
     pub w: Option<String>, // weighted average price
-    #[serde(default)]
+    #[serde(default, deserialize_with = "string_or_number::optional")]
     pub x: Option<String>, // first trade price
-    #[serde(default)]
+    #[serde(default, deserialize_with = "string_or_number::optional")]
     pub c: Option<String>, // last price
-    #[serde(rename = "Q", default)]
+    #[serde(rename = "Q", default, deserialize_with = "string_or_number::optional")]
     pub last_qty: Option<String>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "string_or_number::optional")]
     pub b: Option<String>, // Best bid price
-    #[serde(rename = "B", default)]
+    #[serde(rename = "B", default, deserialize_with = "string_or_number::optional")]
     pub best_bid_qty: Option<String>, // Best bid qty
-    #[serde(default)]
+    #[serde(default, deserialize_with = "string_or_number::optional")]
     pub a: Option<String>, // Best ask price
-    #[serde(rename = "A", default)]
+    #[serde(rename = "A", default, deserialize_with = "string_or_number::optional")]
     pub best_ask_qty: Option<String>, // Best ask qty
-    #[serde(default)]
+    #[serde(default, deserialize_with = "string_or_number::optional")]
     pub o: Option<String>, // open price
-    #[serde(default)]
+    #[serde(default, deserialize_with = "string_or_number::optional")]
     pub h: Option<String>, // high price
-    #[serde(default)]
+    #[serde(default, deserialize_with = "string_or_number::optional")]
     pub l: Option<String>, // low price
-    #[serde(default)]
+    #[serde(default, deserialize_with = "string_or_number::optional")]
     pub v: Option<String>, // base asset volume
-    #[serde(default)]
+    #[serde(default, deserialize_with = "string_or_number::optional")]
     pub q: Option<String>, // quote asset volume
     #[serde(rename = "O", default)]
     pub statistics_open_time: Option<i64>,
@@ -67,23 +68,96 @@ pub struct BinanceOrderBook {
     pub asks: Vec<[String; 2]>, // [price, quantity]
 }
 
+/// `<symbol>@depth@100ms` diff: the levels that changed between update ids `U` and `u`,
+/// where a zero quantity removes the level
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceDepthUpdate {
+    #[serde(rename = "E")]
+    pub event_time: i64,
+    pub s: String, // symbol
+    #[serde(rename = "U")]
+    pub first_update_id: i64,
+    #[serde(rename = "u")]
+    pub final_update_id: i64,
+    /// Final update id of the previous diff; futures only
+    #[serde(rename = "pu", default)]
+    pub prev_final_update_id: Option<i64>,
+    pub b: Vec<[String; 2]>, // bids [price, quantity]
+    pub a: Vec<[String; 2]>, // asks [price, quantity]
+}
+
+/// Futures `<symbol>@markPrice@1s` payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceMarkPrice {
+    #[serde(rename = "E")]
+    pub event_time: i64,
+    pub s: String, // symbol
+    #[serde(deserialize_with = "string_or_number::required")]
+    pub p: String, // mark price
+    #[serde(default, deserialize_with = "string_or_number::optional")]
+    pub i: Option<String>, // index price
+    #[serde(deserialize_with = "string_or_number::required")]
+    pub r: String, // funding rate
+    #[serde(rename = "T")]
+    pub next_funding_time: i64,
+}
+
+/// `<symbol>@aggTrade` payload, trades filled by one taker order at one price
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceAggTrade {
+    #[serde(rename = "a")]
+    pub agg_trade_id: u64,
+    pub s: String, // symbol
+    #[serde(deserialize_with = "string_or_number::required")]
+    pub p: String, // price
+    #[serde(deserialize_with = "string_or_number::required")]
+    pub q: String, // quantity
+    #[serde(rename = "T")]
+    pub trade_time: i64,
+    #[serde(rename = "m")]
+    pub buyer_is_maker: bool,
+}
+
 /// Binance WebSocket stream message
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum BinanceStreamMessage {
+    // Depth diff, mark price and trade variants precede the tickers, whose all-default fields
+    // would match them
+    StreamDepthUpdate {
+        stream: String,
+        data: BinanceDepthUpdate,
+    },
+    StreamMarkPrice {
+        stream: String,
+        data: BinanceMarkPrice,
+    },
+    StreamAggTrade {
+        stream: String,
+        data: BinanceAggTrade,
+    },
     StreamTicker {
         stream: String,
         data: BinanceTicker,
     },
+    // Must precede DirectTicker, whose all-default fields would otherwise match error frames
+    Error {
+        id: Option<i64>,
+        error: BinanceError,
+    },
+    /// Reply to a SUBSCRIBE/UNSUBSCRIBE request, e.g. `{"result":null,"id":3}`
+    Response {
+        result: serde_json::Value,
+        id: u64,
+    },
+    DirectDepthUpdate(BinanceDepthUpdate),
+    DirectMarkPrice(BinanceMarkPrice),
+    DirectAggTrade(BinanceAggTrade),
     DirectTicker(BinanceTicker),
     OrderBook {
         stream: String,
         data: BinanceOrderBook,
     },
-    Error {
-        id: Option<i64>,
-        error: BinanceError,
-    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -94,6 +168,17 @@ pub struct BinanceError {
     pub msg: String,
 }
 
+/// Binance's TOO_MANY_REQUESTS error code
+const RATE_LIMIT_CODE: i32 = -1003;
+
+impl BinanceError {
+    /// Whether Binance is asking us to slow down
+    pub fn is_rate_limit(&self) -> bool {
+        self.code == RATE_LIMIT_CODE
+            || crypto_dash_exchanges_common::is_rate_limit_notice(&self.msg)
+    }
+}
+
 #[cfg(test)]
 
 mod tests {
@@ -204,4 +289,89 @@ mod tests {
             _ => panic!("Expected DirectTicker variant for ETH"),
         }
     }
+
+    #[test]
+
+    fn test_parse_agg_trade_message() {
+        let raw_message = r#"{"e":"aggTrade","E":1672515782136,"s":"BNBBTC","a":12345,"p":"0.001","q":"100","f":100,"l":105,"T":1672515782136,"m":true,"M":true}"#;
+
+        let parsed: BinanceStreamMessage =
+            serde_json::from_str(raw_message).expect("Failed to parse aggTrade message");
+
+        match parsed {
+            BinanceStreamMessage::DirectAggTrade(trade) => {
+                assert_eq!(trade.agg_trade_id, 12345);
+                assert_eq!(trade.s, "BNBBTC");
+                assert_eq!(trade.p, "0.001");
+                assert_eq!(trade.q, "100");
+                assert_eq!(trade.trade_time, 1672515782136);
+                assert!(trade.buyer_is_maker);
+            }
+            _ => panic!("Expected DirectAggTrade variant"),
+        }
+
+        let combined = format!(r#"{{"stream":"bnbbtc@aggTrade","data":{}}}"#, raw_message);
+        match serde_json::from_str(&combined).expect("Failed to parse combined aggTrade") {
+            BinanceStreamMessage::StreamAggTrade { stream, data } => {
+                assert_eq!(stream, "bnbbtc@aggTrade");
+                assert_eq!(data.q, "100");
+            }
+            _ => panic!("Expected StreamAggTrade variant"),
+        }
+    }
+
+    #[test]
+
+    fn test_parse_mark_price_message() {
+        let raw_message = r#"{"e":"markPriceUpdate","E":1562305380000,"s":"BTCUSDT","p":"11794.15000000","i":"11784.62659091","P":"11784.25641265","r":"0.00038167","T":1562306400000}"#;
+
+        let parsed: BinanceStreamMessage =
+            serde_json::from_str(raw_message).expect("Failed to parse mark price message");
+
+        match parsed {
+            BinanceStreamMessage::DirectMarkPrice(mark) => {
+                assert_eq!(mark.s, "BTCUSDT");
+                assert_eq!(mark.p, "11794.15000000");
+                assert_eq!(mark.i.as_deref(), Some("11784.62659091"));
+                assert_eq!(mark.r, "0.00038167");
+                assert_eq!(mark.next_funding_time, 1562306400000);
+            }
+            _ => panic!("Expected DirectMarkPrice variant"),
+        }
+
+        let combined = format!(
+            r#"{{"stream":"btcusdt@markPrice@1s","data":{}}}"#,
+            raw_message
+        );
+        match serde_json::from_str(&combined).expect("Failed to parse combined mark price") {
+            BinanceStreamMessage::StreamMarkPrice { stream, data } => {
+                assert_eq!(stream, "btcusdt@markPrice@1s");
+                assert_eq!(data.r, "0.00038167");
+            }
+            _ => panic!("Expected StreamMarkPrice variant"),
+        }
+    }
+
+    #[test]
+
+    fn test_parse_rate_limit_error() {
+        let raw_message = r#"{"id":3,"error":{"code":-1003,"msg":"Too many requests; current limit is 5 requests per second."}}"#;
+
+        let parsed: BinanceStreamMessage =
+            serde_json::from_str(raw_message).expect("Failed to parse error frame");
+
+        match parsed {
+            BinanceStreamMessage::Error { id, error } => {
+                assert_eq!(id, Some(3));
+                assert!(error.is_rate_limit());
+            }
+            _ => panic!("Expected Error variant"),
+        }
+
+        let invalid = BinanceError {
+            code: 2,
+            msg: "Invalid request".to_string(),
+        };
+        assert!(!invalid.is_rate_limit());
+    }
 }
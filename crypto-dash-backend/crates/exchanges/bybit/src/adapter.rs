@@ -1,4 +1,4 @@
-use crate::types::{BybitMessage, BybitTicker};
+use crate::types::{BybitMessage, BybitOrderBook, BybitTicker};
 
 use anyhow::{anyhow, Result};
 
@@ -7,22 +7,38 @@ use async_trait::async_trait;
 use crypto_dash_cache::CacheHandle;
 
 use crypto_dash_core::{
-    model::{Channel, ChannelType, ExchangeId, MarketType, StreamMessage, Symbol, Ticker},
+    model::{
+        AdapterMetrics, Channel, ChannelType, ConnectionStats, ExchangeId, FundingRate,
+        LifecycleEvent, MarketType, OrderBookDelta, OrderBookSnapshot, StreamMessage, Symbol,
+        TickDirection, Ticker,
+    },
     normalize::SymbolMapper,
+    time::now,
 };
 
-use crypto_dash_exchanges_common::{ExchangeAdapter, WsClient};
+use crypto_dash_exchanges_common::{
+    apply_levels, check_ticker, is_rate_limit_notice, optional_decimal, out_of_order_ms,
+    parse_levels, publish_orderbook_resets, retry_with_backoff, CircuitBreaker, ExchangeAdapter,
+    MessageCounters, PendingSubscribes, ReconnectBudget, RetryConfig, WsClient,
+    DEFAULT_CONNECTION_TIMEOUT, DEFAULT_SUBSCRIBE_PACING, DEFAULT_SUBSCRIBE_TIMEOUT,
+    THROTTLE_BACKOFF,
+};
 
 use crypto_dash_stream_hub::{HubHandle, Topic};
 
 use rust_decimal::Decimal;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 
 use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
 
 use tokio_tungstenite::tungstenite::Message;
 
@@ -30,7 +46,20 @@ use tracing::{debug, error, info, warn};
 
 const BYBIT_SPOT_WS_URL: &str = "wss://stream.bybit.com/v5/public/spot";
 const BYBIT_LINEAR_WS_URL: &str = "wss://stream.bybit.com/v5/public/linear";
-const SUPPORTED_MARKETS: [MarketType; 2] = [MarketType::Spot, MarketType::Perpetual];
+const BYBIT_INVERSE_WS_URL: &str = "wss://stream.bybit.com/v5/public/inverse";
+const SUPPORTED_MARKETS: [MarketType; 3] = [
+    MarketType::Spot,
+    MarketType::Perpetual,
+    MarketType::InversePerpetual,
+];
+/// Topics Bybit accepts in one subscribe request (the spot limit; linear allows more)
+const MAX_TOPICS_PER_REQUEST: usize = 10;
+const SUPPORTED_DEPTHS: [u16; 4] = [1, 50, 200, 500];
+/// Order book depth subscribed when a channel leaves `depth` unset
+const DEFAULT_ORDERBOOK_DEPTH: u16 = 50;
+
+/// Market and symbol identifying one local order book
+type BookKey = (MarketType, Symbol);
 
 #[derive(Clone)]
 pub struct BybitAdapter {
@@ -40,6 +69,33 @@ pub struct BybitAdapter {
 
     cache: Arc<Mutex<Option<CacheHandle>>>,
     symbol_mapper: SymbolMapper,
+    ws_urls: HashMap<MarketType, String>,
+    connection_stats: Arc<Mutex<HashMap<MarketType, ConnectionStats>>>,
+    subscribed: Arc<Mutex<HashMap<MarketType, HashSet<Channel>>>>,
+    connection_timeout: Duration,
+    subscribe_pacing: Duration,
+    reconnect_retry: RetryConfig,
+    breaker: CircuitBreaker,
+    watchdogs: Arc<Mutex<Vec<JoinHandle<()>>>>,
+    throttled_until: Arc<Mutex<HashMap<MarketType, Instant>>>,
+    reconnect_budget: ReconnectBudget,
+    pending_subscribes: PendingSubscribes,
+    /// Last ticker cross sequence per market and topic
+    ticker_sequences: Arc<Mutex<HashMap<(MarketType, String), u64>>>,
+    /// Full books deltas are applied to; the cache may only keep a truncated copy
+    books: Arc<Mutex<HashMap<BookKey, OrderBookSnapshot>>>,
+    metrics: Arc<MessageCounters>,
+}
+
+/// Outcome of comparing a ticker frame's `cs` with the last one seen on its topic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SequenceCheck {
+    /// A snapshot, the first frame on the topic, or a later sequence
+    InOrder,
+    /// A delta at or below the last sequence seen, i.e. replayed or out of order
+    Regression { previous: u64, received: u64 },
+    /// The frame carries no sequence
+    Untracked,
 }
 
 impl BybitAdapter {
@@ -60,14 +116,92 @@ impl BybitAdapter {
             cache: Arc::new(Mutex::new(None)),
 
             symbol_mapper: SymbolMapper::default(),
+            ws_urls: HashMap::from([
+                (MarketType::Spot, BYBIT_SPOT_WS_URL.to_string()),
+                (MarketType::Perpetual, BYBIT_LINEAR_WS_URL.to_string()),
+                (
+                    MarketType::InversePerpetual,
+                    BYBIT_INVERSE_WS_URL.to_string(),
+                ),
+            ]),
+            connection_stats: Arc::new(Mutex::new(HashMap::new())),
+            subscribed: Arc::new(Mutex::new(HashMap::new())),
+            connection_timeout: DEFAULT_CONNECTION_TIMEOUT,
+            subscribe_pacing: DEFAULT_SUBSCRIBE_PACING,
+            reconnect_retry: RetryConfig::default(),
+            breaker: CircuitBreaker::default(),
+            watchdogs: Arc::new(Mutex::new(Vec::new())),
+            throttled_until: Arc::new(Mutex::new(HashMap::new())),
+            reconnect_budget: ReconnectBudget::default(),
+            pending_subscribes: PendingSubscribes::new(DEFAULT_SUBSCRIBE_TIMEOUT),
+            ticker_sequences: Arc::new(Mutex::new(HashMap::new())),
+            books: Arc::new(Mutex::new(HashMap::new())),
+            metrics: Arc::new(MessageCounters::default()),
             // no mock state
         }
     }
 
+    /// Override the WebSocket endpoint used for a market
+    pub fn with_ws_url(mut self, market_type: MarketType, url: impl Into<String>) -> Self {
+        self.ws_urls.insert(market_type, url.into());
+        self
+    }
+
+    /// Override how long a market connection may stay silent before it is reconnected
+    pub fn with_connection_timeout(mut self, connection_timeout: Duration) -> Self {
+        self.connection_timeout = connection_timeout;
+        self
+    }
+
+    /// Override the minimum gap between subscribe frames sent on one connection
+    pub fn with_subscribe_pacing(mut self, subscribe_pacing: Duration) -> Self {
+        self.subscribe_pacing = subscribe_pacing;
+        self
+    }
+
+    /// Override the backoff used to restore a market connection that dropped
+    pub fn with_reconnect_retry(mut self, reconnect_retry: RetryConfig) -> Self {
+        self.reconnect_retry = reconnect_retry;
+        self
+    }
+
+    /// Override how many reconnects a market may make within a window
+    pub fn with_reconnect_budget(mut self, reconnect_budget: ReconnectBudget) -> Self {
+        self.reconnect_budget = reconnect_budget;
+        self
+    }
+
+    /// Override how many failed connects open a market's circuit and how long it stays open
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    /// Override how long a subscribe may go without data; zero disables the check
+    pub fn with_subscribe_timeout(mut self, subscribe_timeout: Duration) -> Self {
+        self.pending_subscribes = PendingSubscribes::new(subscribe_timeout);
+        self
+    }
+
+    /// Replace the symbol mappings, e.g. with built-ins merged with operator overrides
+    pub fn with_symbol_mapper(mut self, symbol_mapper: SymbolMapper) -> Self {
+        self.symbol_mapper = symbol_mapper;
+        self
+    }
+
+    /// Expect data for channels just subscribed upstream, matched by topic
+    async fn track_subscription(&self, channels: Vec<Channel>) {
+        let hub = self.hub.lock().await.clone();
+        let request_id = self.pending_subscribes.next_request_id();
+        self.pending_subscribes.track(request_id, channels, hub);
+    }
+
     fn market_label(market_type: MarketType) -> &'static str {
         match market_type {
             MarketType::Spot => "spot",
             MarketType::Perpetual => "perpetual",
+            MarketType::InversePerpetual => "inverse_perpetual",
+            MarketType::Future => "future",
         }
     }
 
@@ -90,6 +224,124 @@ impl BybitAdapter {
         }
     }
 
+    async fn record_connect_success(&self, market_type: MarketType) {
+        let mut guard = self.connection_stats.lock().await;
+        let stats = guard.entry(market_type).or_default();
+        stats.reconnect_attempts = 0;
+        stats.last_connected_at = Some(now());
+        drop(guard);
+        self.breaker.record_success(market_type);
+
+        self.emit_event(LifecycleEvent::UpstreamConnected {
+            exchange: self.id(),
+            market_type,
+        })
+        .await;
+    }
+
+    async fn emit_event(&self, event: LifecycleEvent) {
+        if let Some(hub) = &*self.hub.lock().await {
+            hub.emit_event(event);
+        }
+    }
+
+    async fn record_connect_failure(&self, market_type: MarketType, error: &anyhow::Error) {
+        let mut guard = self.connection_stats.lock().await;
+        let stats = guard.entry(market_type).or_default();
+        stats.reconnect_attempts += 1;
+        stats.last_connect_error = Some(error.to_string());
+        drop(guard);
+        self.breaker.record_failure(market_type);
+    }
+
+    /// Hold off new requests on a market after Bybit reports rate limiting
+    async fn record_throttle(&self, market_type: MarketType) {
+        warn!(
+            market = Self::market_label(market_type),
+            "Bybit rate limit notice, backing off for {:?}", THROTTLE_BACKOFF
+        );
+        self.throttled_until
+            .lock()
+            .await
+            .insert(market_type, Instant::now() + THROTTLE_BACKOFF);
+    }
+
+    /// Claim a reconnect slot for a market, failing once the window's budget is spent
+    fn take_reconnect_slot(&self, market_type: MarketType) -> Result<()> {
+        self.reconnect_budget.take(market_type).map_err(|e| {
+            anyhow!(
+                "Bybit {} market temporarily unavailable: {}, try again later",
+                Self::market_label(market_type),
+                e
+            )
+        })
+    }
+
+    /// End of the current back-off window for a market, if one is active
+    pub(crate) async fn throttled_until(&self, market_type: MarketType) -> Option<Instant> {
+        self.throttled_until
+            .lock()
+            .await
+            .get(&market_type)
+            .copied()
+            .filter(|until| *until > Instant::now())
+    }
+
+    /// Channels currently subscribed upstream on a market
+    pub(crate) async fn subscribed_channels(&self, market_type: MarketType) -> Vec<Channel> {
+        self.subscribed
+            .lock()
+            .await
+            .get(&market_type)
+            .map(|channels| channels.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Drop channels whose topics Bybit closed and tell their subscribers
+    async fn handle_forced_unsubscribe(&self, market_type: MarketType, topics: &[String]) {
+        let closed: Vec<Channel> = {
+            let mut subscribed = self.subscribed.lock().await;
+            let Some(channels) = subscribed.get_mut(&market_type) else {
+                return;
+            };
+            let closed: Vec<Channel> = channels
+                .iter()
+                .filter(|channel| {
+                    self.topics_from_channels(std::slice::from_ref(*channel))
+                        .iter()
+                        .any(|topic| topics.contains(topic))
+                })
+                .cloned()
+                .collect();
+            for channel in &closed {
+                channels.remove(channel);
+            }
+            closed
+        };
+
+        let hub_guard = self.hub.lock().await;
+        for channel in closed {
+            warn!(
+                market = Self::market_label(market_type),
+                "Bybit closed subscription for {}",
+                channel.instrument()
+            );
+            let topic = Topic::from_channel(&channel);
+            self.pending_subscribes.settle(&topic);
+            if let Some(hub) = hub_guard.as_ref() {
+                let message = StreamMessage::Error {
+                    message: format!(
+                        "{} {} subscription was closed by the exchange",
+                        self.id().as_str(),
+                        channel.instrument()
+                    ),
+                    code: None,
+                };
+                hub.publish(&topic, message).await;
+            }
+        }
+    }
+
     async fn get_mock_generator(&self, _market_type: MarketType) -> Option<()> {
         None
     }
@@ -98,16 +350,48 @@ impl BybitAdapter {
         // no-op: mocks removed
     }
 
-    async fn handle_message(&self, market_type: MarketType, message: BybitMessage) -> Result<()> {
+    pub(crate) async fn handle_message(
+        &self,
+        market_type: MarketType,
+        message: BybitMessage,
+    ) -> Result<()> {
         match message {
-            BybitMessage::Ticker { ts, data, .. } => {
+            BybitMessage::Ticker {
+                topic,
+                ts,
+                message_type,
+                data,
+                cs,
+            } => {
+                self.check_ticker_sequence(market_type, &topic, &message_type, cs)
+                    .await;
                 for ticker in data.into_vec() {
                     self.handle_ticker(market_type, ticker, ts).await?;
                 }
             }
 
+            BybitMessage::OrderBook {
+                ts,
+                message_type,
+                data,
+                ..
+            } => {
+                self.handle_orderbook(market_type, &message_type, data, ts)
+                    .await?;
+            }
+
+            BybitMessage::Control { op, args } => {
+                if op == "unsubscribe" {
+                    self.handle_forced_unsubscribe(market_type, &args).await;
+                } else {
+                    debug!("Ignoring Bybit control frame: {} {:?}", op, args);
+                }
+            }
+
             BybitMessage::Subscription { success, ret_msg } => {
-                if success {
+                if !success && is_rate_limit_notice(&ret_msg) {
+                    self.record_throttle(market_type).await;
+                } else if success {
                     info!("Bybit subscription successful: {}", ret_msg);
 
                     debug!(
@@ -128,6 +412,43 @@ impl BybitAdapter {
         Ok(())
     }
 
+    /// Track the ticker cross sequence per topic. Bybit's `cs` only increases but is not
+    /// consecutive, so a delta at or below the last sequence is a replay or regression;
+    /// snapshots set a new baseline.
+    pub(crate) async fn check_ticker_sequence(
+        &self,
+        market_type: MarketType,
+        topic: &str,
+        message_type: &str,
+        cs: Option<u64>,
+    ) -> SequenceCheck {
+        let Some(cs) = cs else {
+            return SequenceCheck::Untracked;
+        };
+
+        let mut sequences = self.ticker_sequences.lock().await;
+        let key = (market_type, topic.to_string());
+        match sequences.get(&key).copied() {
+            Some(previous) if message_type == "delta" && cs <= previous => {
+                warn!(
+                    market = Self::market_label(market_type),
+                    topic,
+                    previous,
+                    received = cs,
+                    "Bybit ticker sequence went backwards; frame is a replay or out of order"
+                );
+                SequenceCheck::Regression {
+                    previous,
+                    received: cs,
+                }
+            }
+            _ => {
+                sequences.insert(key, cs);
+                SequenceCheck::InOrder
+            }
+        }
+    }
+
     async fn handle_ticker(
         &self,
         market_type: MarketType,
@@ -167,6 +488,29 @@ impl BybitAdapter {
             .or_else(|| ticker.ask_size.as_deref().filter(|v| !v.is_empty()))
             .unwrap_or("0");
 
+        // Inverse turnover is in the base coin; their volume is already in USD contracts
+        let (base_volume, quote_volume) = match market_type {
+            MarketType::InversePerpetual => (&ticker.turnover_24h, &ticker.volume_24h),
+            _ => (&ticker.volume_24h, &ticker.turnover_24h),
+        };
+        let quote_volume_24h = optional_decimal(quote_volume)?;
+
+        // Bybit reports the change as a fraction, e.g. 0.025 for +2.5%
+        let change_pct_24h = optional_decimal(&ticker.price24h_pcnt)?
+            .map(|fraction| fraction * Decimal::ONE_HUNDRED);
+
+        let funding = self.funding_from_ticker(market_type, &symbol, &ticker, timestamp_ms)?;
+
+        let last = Decimal::from_str(&ticker.last_price)?;
+        let previous = match &*self.cache.lock().await {
+            Some(cache) => cache.get_ticker(&self.id(), market_type, &symbol).await,
+            None => None,
+        };
+        let tick_direction = match ticker.tick_direction.as_deref().and_then(tick_direction) {
+            Some(direction) => direction,
+            None => TickDirection::between(previous.as_ref().map(|previous| previous.last), last),
+        };
+
         let normalized_ticker = Ticker {
             timestamp,
             exchange: self.id(),
@@ -174,23 +518,201 @@ impl BybitAdapter {
             symbol: symbol.clone(),
             bid: Decimal::from_str(bid_price)?,
             ask: Decimal::from_str(ask_price)?,
-            last: Decimal::from_str(&ticker.last_price)?,
+            last,
             bid_size: Decimal::from_str(bid_size)?,
             ask_size: Decimal::from_str(ask_size)?,
+            quote_volume_24h,
+            change_pct_24h,
+            tick_direction: Some(tick_direction),
+            high_24h: optional_decimal(&ticker.high_price_24h)?,
+            low_24h: optional_decimal(&ticker.low_price_24h)?,
+            volume_24h: optional_decimal(base_volume)?,
         };
+        if let Err(e) = check_ticker(&normalized_ticker) {
+            warn!(
+                market = Self::market_label(market_type),
+                "Dropping Bybit {} ticker: {}", ticker.symbol, e
+            );
+            return Ok(());
+        }
+        if let Some(behind_ms) = out_of_order_ms(previous.as_ref(), &normalized_ticker) {
+            warn!(
+                market = Self::market_label(market_type),
+                "Dropping Bybit {} ticker {}ms older than the cached one", ticker.symbol, behind_ms
+            );
+            return Ok(());
+        }
 
         if let Some(cache) = &*self.cache.lock().await {
             cache.set_ticker(normalized_ticker.clone()).await;
         }
 
         let topic = Topic::ticker(self.id(), market_type, symbol);
+        self.pending_subscribes.settle(&topic);
 
         if let Some(hub) = &*self.hub.lock().await {
             hub.publish(&topic, StreamMessage::Ticker(normalized_ticker))
                 .await;
+            self.metrics.record_published();
+        }
+
+        let funding_topic = Topic::funding(self.id(), market_type, topic.symbol.clone());
+        if let Some(funding) = funding {
+            if let Some(cache) = &*self.cache.lock().await {
+                cache.set_funding_rate(funding.clone()).await;
+            }
+            self.pending_subscribes.settle(&funding_topic);
+            if let Some(hub) = &*self.hub.lock().await {
+                hub.publish(&funding_topic, StreamMessage::FundingRate(funding))
+                    .await;
+                self.metrics.record_published();
+            }
+        }
+
+        // Funding-only subscribers keep the ticker stream alive too
+        self.disconnect_if_no_subscribers(&[topic, funding_topic])
+            .await?;
+
+        Ok(())
+    }
+
+    /// Funding carried by a perpetual ticker; None for other markets and for deltas
+    /// that leave the funding fields out
+    fn funding_from_ticker(
+        &self,
+        market_type: MarketType,
+        symbol: &Symbol,
+        ticker: &BybitTicker,
+        timestamp_ms: u64,
+    ) -> Result<Option<FundingRate>> {
+        if !matches!(
+            market_type,
+            MarketType::Perpetual | MarketType::InversePerpetual
+        ) {
+            return Ok(None);
+        }
+        let field = |value: &Option<String>| value.clone().filter(|v| !v.is_empty());
+        let (Some(rate), Some(next_funding_time)) = (
+            field(&ticker.funding_rate),
+            field(&ticker.next_funding_time),
+        ) else {
+            return Ok(None);
+        };
+
+        let timestamp = crypto_dash_core::time::from_millis(timestamp_ms as i64)
+            .ok_or_else(|| anyhow!("Invalid timestamp: {}", timestamp_ms))?;
+        let next_funding_ms: i64 = next_funding_time.parse()?;
+        let next_funding_time = crypto_dash_core::time::from_millis(next_funding_ms)
+            .ok_or_else(|| anyhow!("Invalid funding time: {}", next_funding_ms))?;
+
+        Ok(Some(FundingRate {
+            timestamp,
+            exchange: self.id(),
+            market_type,
+            symbol: symbol.clone(),
+            rate: Decimal::from_str(&rate)?,
+            next_funding_time,
+            mark_price: optional_decimal(&ticker.mark_price)?,
+            index_price: optional_decimal(&ticker.index_price)?,
+        }))
+    }
+
+    async fn handle_orderbook(
+        &self,
+        market_type: MarketType,
+        message_type: &str,
+        orderbook: BybitOrderBook,
+        timestamp_ms: u64,
+    ) -> Result<()> {
+        let symbol = self.parse_symbol(&orderbook.s)?;
+
+        let timestamp = crypto_dash_core::time::from_millis(timestamp_ms as i64)
+            .ok_or_else(|| anyhow!("Invalid timestamp: {}", timestamp_ms))?;
+
+        let bids = parse_levels(&orderbook.b)?;
+        let asks = parse_levels(&orderbook.a)?;
+
+        let topic = Topic::orderbook(self.id(), market_type, symbol.clone());
+        self.pending_subscribes.settle(&topic);
+
+        let message = match message_type {
+            "snapshot" => {
+                let snapshot = OrderBookSnapshot {
+                    timestamp,
+                    exchange: self.id(),
+                    market_type,
+                    symbol,
+                    bids: bids.into_iter().filter(|l| !l.quantity.is_zero()).collect(),
+                    asks: asks.into_iter().filter(|l| !l.quantity.is_zero()).collect(),
+                    checksum: None,
+                };
+
+                self.books
+                    .lock()
+                    .await
+                    .insert((market_type, snapshot.symbol.clone()), snapshot.clone());
+                if let Some(cache) = &*self.cache.lock().await {
+                    cache.set_orderbook(snapshot.clone()).await;
+                }
+
+                StreamMessage::OrderBookSnapshot(snapshot)
+            }
+
+            "delta" => {
+                let book = {
+                    let mut books = self.books.lock().await;
+                    // A delta is meaningless without the snapshot it applies to
+                    let Some(book) = books.get_mut(&(market_type, symbol.clone())) else {
+                        debug!(
+                            market = Self::market_label(market_type),
+                            "Dropping Bybit order book delta for {} without a snapshot",
+                            symbol.canonical()
+                        );
+                        return Ok(());
+                    };
+                    apply_levels(&mut book.bids, &bids, |a, b| b.cmp(a));
+                    apply_levels(&mut book.asks, &asks, |a, b| a.cmp(b));
+                    book.timestamp = timestamp;
+                    book.clone()
+                };
+
+                if let Some(cache) = &*self.cache.lock().await {
+                    cache.set_orderbook(book).await;
+                }
+
+                let (bid_deletes, bids_upserts): (Vec<_>, Vec<_>) =
+                    bids.into_iter().partition(|l| l.quantity.is_zero());
+                let (ask_deletes, asks_upserts): (Vec<_>, Vec<_>) =
+                    asks.into_iter().partition(|l| l.quantity.is_zero());
+                let deletes: Vec<Decimal> = bid_deletes
+                    .into_iter()
+                    .chain(ask_deletes)
+                    .map(|l| l.price)
+                    .collect();
+
+                StreamMessage::OrderBookDelta(OrderBookDelta {
+                    timestamp,
+                    exchange: self.id(),
+                    market_type,
+                    symbol,
+                    bids_upserts,
+                    asks_upserts,
+                    deletes: (!deletes.is_empty()).then_some(deletes),
+                })
+            }
+
+            other => {
+                debug!("Ignoring Bybit order book frame of type {}", other);
+                return Ok(());
+            }
+        };
+
+        if let Some(hub) = &*self.hub.lock().await {
+            hub.publish(&topic, message).await;
+            self.metrics.record_published();
         }
 
-        self.disconnect_if_no_subscribers(&topic).await?;
+        self.disconnect_if_no_subscribers(&[topic]).await?;
 
         Ok(())
     }
@@ -212,7 +734,28 @@ impl BybitAdapter {
         false
     }
 
-    async fn reconnect_and_send(&self, market_type: MarketType, message: &str) -> Result<()> {
+    /// Reconnect a market and subscribe the channels along with every channel already on it
+    async fn reconnect_and_send(
+        &self,
+        market_type: MarketType,
+        channels: &[Channel],
+    ) -> Result<()> {
+        // Under a flapping connection every subscribe lands here; cap how often we dial
+        if let Err(e) = self.take_reconnect_slot(market_type) {
+            warn!(market = Self::market_label(market_type), "{}", e);
+            return Err(e);
+        }
+
+        // A new connection carries no subscriptions, so the market's full set is resent
+        let mut resubscribe = self.subscribed_channels(market_type).await;
+        self.reset_orderbooks(&resubscribe).await;
+        for channel in channels {
+            if !resubscribe.contains(channel) {
+                resubscribe.push(channel.clone());
+            }
+        }
+        let frames = self.subscription_frames(&resubscribe)?;
+
         match self.try_real_connection(market_type).await {
             Ok(_) => {
                 info!(
@@ -220,7 +763,9 @@ impl BybitAdapter {
                     "Bybit: Reconnected WebSocket, resending subscription"
                 );
                 if let Some(client) = self.get_ws_client(market_type).await {
-                    client.send_text(message).await?;
+                    for frame in frames {
+                        client.send_text(frame.as_str()).await?;
+                    }
                     info!(
                         market = Self::market_label(market_type),
                         "Bybit: Subscription sent after reconnect"
@@ -244,18 +789,67 @@ impl BybitAdapter {
         }
     }
 
-    async fn disconnect_if_no_subscribers(&self, topic: &Topic) -> Result<()> {
+    /// Periodically reconnect the market if its connection has gone silent
+    async fn run_watchdog(&self, market_type: MarketType) {
+        let period = (self.connection_timeout / 2).max(Duration::from_millis(10));
+        let mut ticker = tokio::time::interval(period);
+
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.reconnect_if_stale(market_type).await {
+                warn!(
+                    market = Self::market_label(market_type),
+                    "Bybit stale connection reconnect failed: {}", e
+                );
+            }
+        }
+    }
+
+    /// Reconnect and resubscribe a stale market connection that still has subscriptions
+    async fn reconnect_if_stale(&self, market_type: MarketType) -> Result<bool> {
+        let Some(client) = self.get_ws_client(market_type).await else {
+            return Ok(false);
+        };
+        if !client.is_stale() {
+            return Ok(false);
+        }
+
+        let channels = self.subscribed_channels(market_type).await;
+        if channels.is_empty() {
+            return Ok(false);
+        }
+
+        warn!(
+            market = Self::market_label(market_type),
+            "Bybit WebSocket connection is stale, reconnecting"
+        );
+        self.clear_ws_if_current(market_type, &client).await;
+        if let Err(e) = client.close().await {
+            debug!("Failed to close stale Bybit connection: {}", e);
+        }
+
+        self.reconnect_and_send(market_type, &channels).await?;
+        self.track_subscription(channels).await;
+
+        Ok(true)
+    }
+
+    /// Close the market's connection when nobody listens to any of the topics a frame fed
+    async fn disconnect_if_no_subscribers(&self, topics: &[Topic]) -> Result<()> {
+        let Some(market_type) = topics.first().map(|topic| topic.market_type) else {
+            return Ok(());
+        };
         let should_disconnect = {
             let hub_guard = self.hub.lock().await;
             if let Some(hub) = hub_guard.as_ref() {
-                hub.global_subscriber_count() == 0 && hub.subscriber_count(topic) == 0
+                hub.global_subscriber_count() == 0
+                    && topics.iter().all(|topic| hub.subscriber_count(topic) == 0)
             } else {
                 false
             }
         };
 
         if should_disconnect {
-            let market_type = topic.market_type;
             let mut ws_guard = self.ws_clients.lock().await;
             if let Some(entry) = ws_guard.get_mut(&market_type) {
                 if let Some(client) = entry.take() {
@@ -271,7 +865,22 @@ impl BybitAdapter {
         Ok(())
     }
 
-    fn parse_symbol(&self, bybit_symbol: &str) -> Result<Symbol> {
+    /// Convert a canonical symbol to Bybit's instrument name for a market
+    pub fn to_exchange_symbol(&self, symbol: &Symbol, market_type: MarketType) -> String {
+        let base = symbol.base.to_uppercase();
+        let quote = symbol.quote.to_uppercase();
+
+        // USDC-margined perpetuals are listed as e.g. BTCPERP rather than BTCUSDC
+        if market_type == MarketType::Perpetual && quote == "USDC" {
+            return format!("{}PERP", base);
+        }
+
+        self.symbol_mapper
+            .to_exchange(&self.id(), &Symbol::new(base.clone(), quote.clone()))
+            .unwrap_or_else(|| format!("{}{}", base, quote))
+    }
+
+    pub(crate) fn parse_symbol(&self, bybit_symbol: &str) -> Result<Symbol> {
         // Use the symbol mapper for production-ready symbol normalization
         if let Some(symbol) = self.symbol_mapper.to_canonical(&self.id(), bybit_symbol) {
             return Ok(symbol);
@@ -299,34 +908,52 @@ impl BybitAdapter {
             Ok(Symbol::new(base, "ETH"))
         } else if let Some(base) = upper.strip_suffix("USD") {
             Ok(Symbol::new(base, "USD"))
+        } else if let Some(base) = upper.strip_suffix("PERP") {
+            Ok(Symbol::new(base, "USDC"))
         } else {
             Err(anyhow!("Unknown Bybit symbol format: {}", bybit_symbol))
         }
     }
 
+    /// Whether a channel maps to a Bybit topic: funding only rides on derivative tickers
+    /// and trades are not streamed
+    fn streams_channel(channel: &Channel) -> bool {
+        match channel.channel_type {
+            ChannelType::Ticker | ChannelType::OrderBook => true,
+            ChannelType::Funding => matches!(
+                channel.market_type,
+                MarketType::Perpetual | MarketType::InversePerpetual
+            ),
+            ChannelType::Trades => false,
+        }
+    }
+
     fn topics_from_channels(&self, channels: &[Channel]) -> Vec<String> {
         let mut topics = Vec::new();
 
-        for channel in channels {
-            match channel.channel_type {
-                ChannelType::Ticker => {
-                    let symbol = format!("{}{}", channel.symbol.base, channel.symbol.quote);
-
-                    topics.push(format!("tickers.{}", symbol));
-                }
-
+        // Unsupported channels are rejected on subscribe and have no topic to unsubscribe
+        for channel in channels
+            .iter()
+            .filter(|channel| Self::streams_channel(channel))
+        {
+            let symbol = self.to_exchange_symbol(&channel.symbol, channel.market_type);
+            let topic = match channel.channel_type {
                 ChannelType::OrderBook => {
-                    let symbol = format!("{}{}", channel.symbol.base, channel.symbol.quote);
-
-                    topics.push(format!("orderbook.1.{}", symbol));
+                    format!("orderbook.{}.{}", orderbook_depth(channel.depth), symbol)
                 }
+                // Funding has no topic of its own; it rides on derivative tickers
+                _ => format!("tickers.{}", symbol),
+            };
+            // A ticker and funding channel for one instrument share the topic
+            if !topics.contains(&topic) {
+                topics.push(topic);
             }
         }
 
         topics
     }
 
-    fn format_subscription(&self, channels: &[Channel]) -> Result<String> {
+    pub(crate) fn format_subscription(&self, channels: &[Channel]) -> Result<String> {
         let topics = self.topics_from_channels(channels);
 
         let subscription = serde_json::json!({
@@ -358,9 +985,15 @@ impl BybitAdapter {
         Ok(subscription.to_string())
     }
 
-    fn format_unsubscription(&self, channels: &[Channel]) -> Result<String> {
-        let topics = self.topics_from_channels(channels);
+    /// Subscribe requests for the channels, split to respect Bybit's per-request topic limit
+    fn subscription_frames(&self, channels: &[Channel]) -> Result<Vec<String>> {
+        channels
+            .chunks(MAX_TOPICS_PER_REQUEST)
+            .map(|chunk| self.format_subscription(chunk))
+            .collect()
+    }
 
+    fn format_unsubscription(&self, topics: &[String]) -> Result<String> {
         let unsubscription = serde_json::json!({
 
 
@@ -395,67 +1028,197 @@ impl BybitAdapter {
         market_type: MarketType,
         ws_client: Arc<WsClient>,
     ) -> Result<()> {
-        loop {
-            let message = match ws_client.next_message().await? {
-                Some(Message::Text(text)) => text,
+        let reason = loop {
+            let message = match ws_client.next_message().await {
+                Ok(Some(Message::Text(text))) => text,
 
-                Some(Message::Close(_)) => {
+                Ok(Some(Message::Close(_))) => {
                     warn!("Bybit WebSocket connection closed");
 
-                    break;
+                    break "connection closed".to_string();
                 }
 
-                Some(_) => continue,
+                Ok(Some(_)) => continue,
 
-                None => {
+                Ok(None) => {
                     warn!("Bybit WebSocket stream ended");
 
-                    break;
+                    break "stream ended".to_string();
+                }
+
+                Err(e) => {
+                    warn!("Bybit WebSocket read failed: {}", e);
+
+                    break e.to_string();
                 }
             };
 
+            self.metrics.record_received();
             match serde_json::from_str::<BybitMessage>(&message) {
                 Ok(stream_message) => {
                     debug!("Received Bybit message: {:?}", stream_message);
 
                     if let Err(e) = self.handle_message(market_type, stream_message).await {
+                        self.metrics.record_parse_error();
                         error!("Failed to handle Bybit message: {}", e);
                     }
                 }
 
                 Err(e) => {
+                    self.metrics.record_parse_error();
                     warn!("Failed to parse Bybit message: {} - Raw: {}", e, message);
                 }
             }
-        }
+        };
 
-        let mut ws_guard = self.ws_clients.lock().await;
+        self.emit_event(LifecycleEvent::UpstreamDisconnected {
+            exchange: self.id(),
+            market_type,
+            reason,
+        })
+        .await;
 
-        if let Some(entry) = ws_guard.get_mut(&market_type) {
-            if let Some(current) = entry.as_ref() {
-                if Arc::ptr_eq(current, &ws_client) {
+        let dropped = {
+            let mut ws_guard = self.ws_clients.lock().await;
+            match ws_guard.get_mut(&market_type) {
+                Some(entry)
+                    if entry
+                        .as_ref()
+                        .is_some_and(|current| Arc::ptr_eq(current, &ws_client)) =>
+                {
                     *entry = None;
+                    true
                 }
+                _ => false,
             }
+        };
+
+        // Deliberate closes (stop, stale replacement, no subscribers) clear the entry first
+        if dropped {
+            self.restore_connection(market_type).await;
         }
 
         Ok(())
     }
 
+    /// Tell order book subscribers to discard their books before resubscribing them
+    async fn reset_orderbooks(&self, channels: &[Channel]) {
+        self.drop_books(channels).await;
+        let hub = self.hub.lock().await.clone();
+        let cache = self.cache.lock().await.clone();
+        if let (Some(hub), Some(cache)) = (hub, cache) {
+            publish_orderbook_resets(&hub, &cache, channels).await;
+        }
+    }
+
+    /// Forget the local books of order book channels so deltas wait for a fresh snapshot
+    async fn drop_books(&self, channels: &[Channel]) {
+        let mut books = self.books.lock().await;
+        for channel in channels {
+            if channel.channel_type == ChannelType::OrderBook {
+                books.remove(&(channel.market_type, channel.symbol.clone()));
+            }
+        }
+    }
+
+    /// Reconnect a dropped market and resend its subscriptions while sessions are listening
+    // Boxed because the listener task it is awaited from is spawned by `try_real_connection`
+    fn restore_connection(
+        &self,
+        market_type: MarketType,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(async move {
+            let listening = match &*self.hub.lock().await {
+                Some(hub) => hub.global_subscriber_count() > 0,
+                None => false,
+            };
+            let channels: Vec<Channel> = self.subscribed_channels(market_type).await;
+            if !listening || channels.is_empty() {
+                return;
+            }
+
+            // A server that accepts and immediately closes would otherwise be redialled in
+            // a tight loop, so restores share the subscribe path's budget and start after a pause
+            if let Err(e) = self.take_reconnect_slot(market_type) {
+                error!(market = Self::market_label(market_type), "{}", e);
+                return;
+            }
+            warn!(
+                market = Self::market_label(market_type),
+                "Bybit WebSocket dropped, reconnecting {} channels",
+                channels.len()
+            );
+            tokio::time::sleep(self.reconnect_retry.base_delay).await;
+            self.reset_orderbooks(&channels).await;
+            let channels = &channels;
+            let restored = retry_with_backoff(
+                || async move {
+                    let ws_client = self.try_real_connection(market_type).await?;
+                    for frame in self.subscription_frames(channels)? {
+                        ws_client.send_text(frame).await?;
+                    }
+                    Ok::<(), anyhow::Error>(())
+                },
+                self.reconnect_retry.clone(),
+            )
+            .await;
+
+            match restored {
+                Ok(()) => {
+                    self.track_subscription(channels.clone()).await;
+                    info!(
+                        market = Self::market_label(market_type),
+                        "Bybit reconnected and resubscribed {} channels",
+                        channels.len()
+                    );
+                }
+                Err(e) => error!(
+                    market = Self::market_label(market_type),
+                    "Bybit reconnect gave up after {} attempts: {}",
+                    self.reconnect_retry.max_attempts,
+                    e
+                ),
+            }
+        })
+    }
+
     async fn try_real_connection(&self, market_type: MarketType) -> Result<Arc<WsClient>> {
-        let ws_url = match market_type {
-            MarketType::Spot => BYBIT_SPOT_WS_URL,
-            MarketType::Perpetual => BYBIT_LINEAR_WS_URL,
-        };
+        let ws_url = self
+            .ws_urls
+            .get(&market_type)
+            .ok_or_else(|| {
+                anyhow!(
+                    "No WebSocket URL for {} market",
+                    Self::market_label(market_type)
+                )
+            })?
+            .clone();
 
         debug!(
             market = Self::market_label(market_type),
             "Attempting to connect to Bybit WebSocket: {}", ws_url
         );
 
-        let ws_client = Arc::new(WsClient::new(ws_url));
+        // Fail fast while the market's circuit is open instead of hammering a dead endpoint
+        self.breaker.check(market_type).map_err(|e| {
+            anyhow!(
+                "Not connecting to Bybit {} market: {}",
+                Self::market_label(market_type),
+                e
+            )
+        })?;
+
+        let ws_client = Arc::new(
+            WsClient::new(ws_url)
+                .with_timeout(self.connection_timeout)
+                .with_send_interval(self.subscribe_pacing),
+        );
 
-        ws_client.connect().await?;
+        if let Err(e) = ws_client.connect().await {
+            self.record_connect_failure(market_type, &e).await;
+            return Err(e);
+        }
+        self.record_connect_success(market_type).await;
 
         debug!(
             market = Self::market_label(market_type),
@@ -496,6 +1259,18 @@ impl BybitAdapter {
             debug!("No Bybit channels to subscribe");
             return Ok(());
         }
+        // Rejected before anything is sent or tracked, so clients get this error rather
+        // than a subscribe timeout for a topic that was never requested
+        if let Some(channel) = channels
+            .iter()
+            .find(|channel| !Self::streams_channel(channel))
+        {
+            return Err(anyhow!(
+                "Bybit does not stream {:?} channels for {}",
+                channel.channel_type,
+                channel.instrument()
+            ));
+        }
 
         let mut by_market: HashMap<MarketType, Vec<Channel>> = HashMap::new();
         for channel in channels {
@@ -510,42 +1285,62 @@ impl BybitAdapter {
                 continue;
             }
 
+            if let Some(until) = self.throttled_until(market_type).await {
+                debug!(
+                    market = Self::market_label(market_type),
+                    "Delaying Bybit subscription until rate limit back-off ends"
+                );
+                tokio::time::sleep_until(until).await;
+            }
+
             // No mock behavior: attempt to send subscription or reconnect and return error to caller
 
-            let subscription = self.format_subscription(&market_channels)?;
-            info!(
-                market = Self::market_label(market_type),
-                "Bybit subscription message: {}", subscription
-            );
+            let frames = self.subscription_frames(&market_channels)?;
 
             match self.get_ws_client(market_type).await {
-                Some(ws_client) => match ws_client.send_text(&subscription).await {
-                    Ok(()) => {
+                Some(ws_client) => {
+                    for subscription in &frames {
                         info!(
                             market = Self::market_label(market_type),
-                            "Successfully sent Bybit subscription: {}", subscription
+                            "Bybit subscription message: {}", subscription
                         );
-                    }
-                    Err(e) => {
-                        error!(
+                        if let Err(e) = ws_client.send_text(subscription.as_str()).await {
+                            error!(
+                                market = Self::market_label(market_type),
+                                "Failed to send Bybit subscription, connection may be broken: {}",
+                                e
+                            );
+
+                            let _cleared = self.clear_ws_if_current(market_type, &ws_client).await;
+
+                            // Attempt a reconnect/send once and propagate any error to caller
+                            self.reconnect_and_send(market_type, &market_channels)
+                                .await?;
+                            break;
+                        }
+                        info!(
                             market = Self::market_label(market_type),
-                            "Failed to send Bybit subscription, connection may be broken: {}", e
+                            "Successfully sent Bybit subscription: {}", subscription
                         );
-
-                        let _cleared = self.clear_ws_if_current(market_type, &ws_client).await;
-
-                        // Attempt a reconnect/send once and propagate any error to caller
-                        self.reconnect_and_send(market_type, &subscription).await?;
                     }
-                },
+                }
                 None => {
                     warn!(
                         market = Self::market_label(market_type),
                         "Bybit WebSocket client not connected, attempting to reconnect"
                     );
-                    self.reconnect_and_send(market_type, &subscription).await?;
+                    self.reconnect_and_send(market_type, &market_channels)
+                        .await?;
                 }
             }
+            self.track_subscription(market_channels.clone()).await;
+
+            self.subscribed
+                .lock()
+                .await
+                .entry(market_type)
+                .or_default()
+                .extend(market_channels);
         }
 
         Ok(())
@@ -572,9 +1367,45 @@ impl BybitAdapter {
                 continue;
             }
 
+            let remaining: Vec<Channel> = {
+                let mut subscribed = self.subscribed.lock().await;
+                match subscribed.get_mut(&market_type) {
+                    Some(subscribed) => {
+                        for channel in &market_channels {
+                            subscribed.remove(channel);
+                        }
+                        subscribed.iter().cloned().collect()
+                    }
+                    None => Vec::new(),
+                }
+            };
+            let still_needed: HashSet<String> =
+                self.topics_from_channels(&remaining).into_iter().collect();
+            // A book streamed at another depth keeps its local copy
+            let unused_books: Vec<Channel> = market_channels
+                .iter()
+                .filter(|channel| {
+                    !remaining.iter().any(|other| {
+                        other.channel_type == ChannelType::OrderBook
+                            && other.symbol == channel.symbol
+                    })
+                })
+                .cloned()
+                .collect();
+            self.drop_books(&unused_books).await;
+            // Keep topics another channel still reads, e.g. a ticker whose funding was dropped
+            let topics: Vec<String> = self
+                .topics_from_channels(&market_channels)
+                .into_iter()
+                .filter(|topic| !still_needed.contains(topic))
+                .collect();
+            if topics.is_empty() {
+                continue;
+            }
+
             // No mock behavior for unsubscribes
 
-            let unsubscription = self.format_unsubscription(&market_channels)?;
+            let unsubscription = self.format_unsubscription(&topics)?;
             info!(
                 market = Self::market_label(market_type),
                 "Bybit unsubscription message: {}", unsubscription
@@ -609,11 +1440,65 @@ impl BybitAdapter {
     }
 }
 
+/// Map Bybit's `tickDirection`; zero ticks traded at an unchanged price
+pub(crate) fn tick_direction(bybit: &str) -> Option<TickDirection> {
+    match bybit {
+        "PlusTick" => Some(TickDirection::Up),
+        "MinusTick" => Some(TickDirection::Down),
+        "ZeroPlusTick" | "ZeroMinusTick" => Some(TickDirection::Neutral),
+        _ => None,
+    }
+}
+
+/// Bybit depth level for a requested depth: the nearest supported level at or above it,
+/// clamped to the deepest level Bybit offers
+pub(crate) fn orderbook_depth(requested: Option<u16>) -> u16 {
+    let Some(requested) = requested else {
+        return DEFAULT_ORDERBOOK_DEPTH;
+    };
+
+    let depth = SUPPORTED_DEPTHS
+        .iter()
+        .copied()
+        .find(|level| *level >= requested)
+        .unwrap_or(SUPPORTED_DEPTHS[SUPPORTED_DEPTHS.len() - 1]);
+    if depth != requested {
+        warn!(
+            "Bybit has no order book depth {}, subscribing to {} instead",
+            requested, depth
+        );
+    }
+    depth
+}
+
+/// Push interval in milliseconds of a Bybit order book topic at `depth`
+fn orderbook_update_speed(market_type: MarketType, depth: u16) -> u32 {
+    match depth {
+        1 => 10,
+        50 => 20,
+        _ if market_type == MarketType::Spot => 200,
+        _ => 100,
+    }
+}
+
 #[async_trait]
 
 impl ExchangeAdapter for BybitAdapter {
     fn id(&self) -> ExchangeId {
-        ExchangeId::from("bybit")
+        ExchangeId::from(ExchangeId::BYBIT)
+    }
+
+    fn supported_markets(&self) -> Vec<MarketType> {
+        SUPPORTED_MARKETS.to_vec()
+    }
+
+    fn supported_depths(&self) -> &'static [u16] {
+        &SUPPORTED_DEPTHS
+    }
+
+    fn orderbook_stream(&self, market_type: MarketType, depth: Option<u16>) -> (u16, u32) {
+        let depth = orderbook_depth(depth);
+        (depth, orderbook_update_speed(market_type, depth))
     }
 
     async fn start(&self, hub: HubHandle, cache: CacheHandle) -> Result<()> {
@@ -622,11 +1507,37 @@ impl ExchangeAdapter for BybitAdapter {
         *self.hub.lock().await = Some(hub.clone());
         *self.cache.lock().await = Some(cache.clone());
 
+        let mut watchdogs = self.watchdogs.lock().await;
+        if watchdogs.is_empty() {
+            for market_type in SUPPORTED_MARKETS {
+                let adapter = self.clone();
+                watchdogs.push(tokio::spawn(async move {
+                    adapter.run_watchdog(market_type).await
+                }));
+            }
+        }
+
         debug!("Bybit adapter initialized with hub and cache handles");
 
         Ok(())
     }
 
+    async fn connect_markets(&self, markets: &[MarketType]) -> Result<()> {
+        for market_type in markets {
+            if self.is_market_connected(*market_type).await {
+                continue;
+            }
+
+            self.try_real_connection(*market_type).await?;
+            info!(
+                market = Self::market_label(*market_type),
+                "Bybit market connection opened"
+            );
+        }
+
+        Ok(())
+    }
+
     async fn subscribe(&self, channels: &[Channel]) -> Result<()> {
         self.subscribe_internal(channels).await
     }
@@ -648,9 +1559,36 @@ impl ExchangeAdapter for BybitAdapter {
         false
     }
 
+    async fn is_market_connected(&self, market_type: MarketType) -> bool {
+        self.get_ws_client(market_type)
+            .await
+            .map(|client| client.is_connected())
+            .unwrap_or(false)
+    }
+
+    async fn connection_stats(&self, market_type: MarketType) -> ConnectionStats {
+        let mut stats = self
+            .connection_stats
+            .lock()
+            .await
+            .get(&market_type)
+            .cloned()
+            .unwrap_or_default();
+        stats.circuit = self.breaker.state(market_type);
+        stats
+    }
+
+    async fn metrics(&self) -> AdapterMetrics {
+        self.metrics.snapshot()
+    }
+
     async fn stop(&self) -> Result<()> {
         info!("Stopping Bybit adapter");
 
+        for watchdog in self.watchdogs.lock().await.drain(..) {
+            watchdog.abort();
+        }
+
         let mut ws_guard = self.ws_clients.lock().await;
 
         for (market_type, client_opt) in ws_guard.iter_mut() {
@@ -662,6 +1600,7 @@ impl ExchangeAdapter for BybitAdapter {
                 client.close().await?;
             }
         }
+        self.books.lock().await.clear();
 
         Ok(())
     }
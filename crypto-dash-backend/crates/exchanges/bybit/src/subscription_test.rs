@@ -4,6 +4,63 @@ mod bybit_subscription_tests {
     use crypto_dash_core::model::{Channel, ChannelType, ExchangeId, MarketType, Symbol};
     use crypto_dash_exchanges_common::ExchangeAdapter;
 
+    /// Local WebSocket server that accepts connections and never sends anything
+    async fn spawn_silent_ws_server() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let _ws = tokio_tungstenite::accept_async(stream).await;
+                    std::future::pending::<()>().await;
+                });
+            }
+        });
+        url
+    }
+
+    /// Local upstream that numbers its connections from 1 and reports every text frame
+    /// as `(connection, text)`. Each frame is acknowledged and followed by `frames`;
+    /// the first `dropped` connections then close.
+    async fn spawn_ack_upstream(
+        frames: &'static [&'static str],
+        dropped: usize,
+    ) -> (
+        String,
+        tokio::sync::mpsc::UnboundedReceiver<(usize, String)>,
+    ) {
+        use futures::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::Message;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (frames_tx, frames_rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut connection = 0;
+            while let Ok((stream, _)) = listener.accept().await {
+                connection += 1;
+                let frames_tx = frames_tx.clone();
+                tokio::spawn(async move {
+                    let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                    while let Some(Ok(Message::Text(text))) = ws.next().await {
+                        let _ = frames_tx.send((connection, text));
+                        let ack = r#"{"success":true,"ret_msg":"","op":"subscribe"}"#;
+                        for frame in std::iter::once(ack).chain(frames.iter().copied()) {
+                            if ws.send(Message::Text(frame.to_string())).await.is_err() {
+                                return;
+                            }
+                        }
+                        if connection <= dropped {
+                            let _ = ws.close(None).await;
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+        (url, frames_rx)
+    }
+
     #[tokio::test]
     async fn test_bybit_subscription_with_broken_connection() {
         // Create a Bybit adapter with no WebSocket connection (simulating broken connection)
@@ -28,6 +85,1119 @@ mod bybit_subscription_tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_bybit_reconnect_failure_is_recorded() {
+        let adapter = BybitAdapter::new().with_ws_url(MarketType::Spot, "ws://127.0.0.1:1");
+
+        let channels = vec![Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: ExchangeId::from("bybit"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        }];
+
+        assert!(adapter.subscribe(&channels).await.is_err());
+        assert!(adapter.subscribe(&channels).await.is_err());
+
+        let stats = adapter.connection_stats(MarketType::Spot).await;
+        assert!(stats.last_connect_error.is_some());
+        assert_eq!(stats.reconnect_attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn test_bybit_reconnect_budget_rejects_without_dialing() {
+        let adapter = BybitAdapter::new().with_ws_url(MarketType::Spot, "ws://127.0.0.1:1");
+
+        let channels = vec![Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: ExchangeId::from("bybit"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        }];
+
+        for _ in 0..3 {
+            assert!(adapter.subscribe(&channels).await.is_err());
+        }
+        assert_eq!(
+            adapter
+                .connection_stats(MarketType::Spot)
+                .await
+                .reconnect_attempts,
+            3
+        );
+
+        let err = adapter.subscribe(&channels).await.unwrap_err();
+        assert!(
+            err.to_string().contains("temporarily unavailable"),
+            "unexpected error: {}",
+            err
+        );
+        // The budget check happens before dialing, so no new failure is recorded
+        assert_eq!(
+            adapter
+                .connection_stats(MarketType::Spot)
+                .await
+                .reconnect_attempts,
+            3
+        );
+
+        // Other markets keep their own budget
+        let mut perp = channels.clone();
+        perp[0].market_type = MarketType::Perpetual;
+        let perp_adapter = adapter.with_ws_url(MarketType::Perpetual, "ws://127.0.0.1:1");
+        let err = perp_adapter.subscribe(&perp).await.unwrap_err();
+        assert!(!err.to_string().contains("temporarily unavailable"));
+    }
+
+    #[tokio::test]
+    async fn test_bybit_watchdog_reconnects_stale_connection() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+        use std::time::Duration;
+
+        // The server never sends anything, so connections go stale
+        let url = spawn_silent_ws_server().await;
+
+        let adapter = BybitAdapter::new()
+            .with_ws_url(MarketType::Spot, url)
+            .with_connection_timeout(Duration::from_millis(200));
+        adapter
+            .start(StreamHub::new().handle(), MemoryCache::new().handle())
+            .await
+            .unwrap();
+
+        let channels = vec![Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: ExchangeId::from("bybit"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        }];
+        adapter.subscribe(&channels).await.unwrap();
+        let first_connect = adapter
+            .connection_stats(MarketType::Spot)
+            .await
+            .last_connected_at;
+
+        let mut reconnected = false;
+        for _ in 0..100 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            let stats = adapter.connection_stats(MarketType::Spot).await;
+            if stats.last_connected_at != first_connect {
+                reconnected = true;
+                break;
+            }
+        }
+
+        assert!(
+            reconnected,
+            "watchdog did not reconnect the stale connection"
+        );
+        adapter.stop().await.unwrap();
+    }
+
+    #[test]
+    fn test_bybit_control_frames_parse() {
+        let forced = r#"{"op":"unsubscribe","args":["tickers.BTCUSDT"]}"#;
+        match serde_json::from_str::<BybitMessage>(forced).unwrap() {
+            BybitMessage::Control { op, args } => {
+                assert_eq!(op, "unsubscribe");
+                assert_eq!(args, vec!["tickers.BTCUSDT".to_string()]);
+            }
+            other => panic!("Expected Control, got {:?}", other),
+        }
+
+        // Replies to our own requests carry no args and stay subscription responses
+        let throttled =
+            r#"{"success":false,"ret_msg":"Too many requests","conn_id":"abc","op":"subscribe"}"#;
+        assert!(matches!(
+            serde_json::from_str::<BybitMessage>(throttled).unwrap(),
+            BybitMessage::Subscription { success: false, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_bybit_forced_unsubscribe_notifies_clients() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_core::model::StreamMessage;
+        use crypto_dash_stream_hub::StreamHub;
+
+        let hub = StreamHub::new().handle();
+        let adapter =
+            BybitAdapter::new().with_ws_url(MarketType::Spot, spawn_silent_ws_server().await);
+        adapter
+            .start(hub.clone(), MemoryCache::new().handle())
+            .await
+            .unwrap();
+
+        let btc = Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: ExchangeId::from("bybit"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        let eth = Channel {
+            symbol: Symbol::new("ETH", "USDT"),
+            ..btc.clone()
+        };
+        adapter.subscribe(&[btc, eth.clone()]).await.unwrap();
+        let mut receiver = hub.subscribe_all().await;
+
+        let frame = r#"{"op":"unsubscribe","args":["tickers.BTCUSDT"]}"#;
+        adapter
+            .handle_message(MarketType::Spot, serde_json::from_str(frame).unwrap())
+            .await
+            .unwrap();
+
+        let (topic, message) = receiver.try_recv().expect("no notification published");
+        assert_eq!(topic.symbol, Symbol::new("BTC", "USDT"));
+        match message {
+            StreamMessage::Error { message, .. } => assert!(message.contains("BTC-USDT")),
+            other => panic!("Expected Error, got {:?}", other),
+        }
+        assert_eq!(
+            adapter.subscribed_channels(MarketType::Spot).await,
+            vec![eth]
+        );
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_bybit_unconfirmed_subscribe_times_out() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_core::model::{ErrorCode, StreamMessage};
+        use crypto_dash_stream_hub::StreamHub;
+        use std::time::Duration;
+
+        // The server never acks nor sends data
+        let hub = StreamHub::new().handle();
+        let adapter = BybitAdapter::new()
+            .with_ws_url(MarketType::Spot, spawn_silent_ws_server().await)
+            .with_subscribe_timeout(Duration::from_millis(100));
+        adapter
+            .start(hub.clone(), MemoryCache::new().handle())
+            .await
+            .unwrap();
+        let mut receiver = hub.subscribe_all().await;
+
+        let btc = Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: ExchangeId::from("bybit"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        let eth = Channel {
+            symbol: Symbol::new("ETH", "USDT"),
+            ..btc.clone()
+        };
+        adapter.subscribe(&[btc, eth]).await.unwrap();
+
+        // ETH data confirms its subscription; BTC stays unconfirmed
+        let frame = r#"{
+            "topic": "tickers.ETHUSDT",
+            "type": "snapshot",
+            "ts": 1744168585009,
+            "data": {"symbol": "ETHUSDT", "lastPrice": "3000"}
+        }"#;
+        adapter
+            .handle_message(MarketType::Spot, serde_json::from_str(frame).unwrap())
+            .await
+            .unwrap();
+        let (_, message) = receiver.recv().await.unwrap();
+        assert!(matches!(message, StreamMessage::Ticker(_)));
+
+        let (topic, message) = tokio::time::timeout(Duration::from_secs(2), receiver.recv())
+            .await
+            .expect("no subscribe timeout published")
+            .unwrap();
+        assert_eq!(topic.symbol, Symbol::new("BTC", "USDT"));
+        match message {
+            StreamMessage::Error { message, code } => {
+                assert_eq!(code, Some(ErrorCode::SubscribeTimeout));
+                assert!(message.contains("BTC-USDT"));
+            }
+            other => panic!("Expected Error, got {:?}", other),
+        }
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(receiver.try_recv().is_err());
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_bybit_subscribe_frames_are_paced() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+        use futures::StreamExt;
+        use std::time::{Duration, Instant};
+        use tokio_tungstenite::tungstenite::Message;
+
+        // Records when each text frame arrives
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (frames_tx, mut frames_rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while let Some(Ok(message)) = ws.next().await {
+                if let Message::Text(text) = message {
+                    let _ = frames_tx.send((Instant::now(), text));
+                }
+            }
+        });
+
+        let pacing = Duration::from_millis(100);
+        let adapter = BybitAdapter::new()
+            .with_ws_url(MarketType::Spot, url)
+            .with_subscribe_pacing(pacing);
+        adapter
+            .start(StreamHub::new().handle(), MemoryCache::new().handle())
+            .await
+            .unwrap();
+
+        let channels: Vec<Channel> = (0..25)
+            .map(|i| Channel {
+                channel_type: ChannelType::Ticker,
+                exchange: ExchangeId::from("bybit"),
+                market_type: MarketType::Spot,
+                symbol: Symbol::new(format!("COIN{}", i), "USDT"),
+                depth: None,
+            })
+            .collect();
+        adapter.subscribe(&channels).await.unwrap();
+
+        let mut frames = Vec::new();
+        for _ in 0..3 {
+            let frame = tokio::time::timeout(Duration::from_secs(2), frames_rx.recv())
+                .await
+                .expect("subscribe frame not received")
+                .unwrap();
+            frames.push(frame);
+        }
+
+        let topic_counts: Vec<usize> = frames
+            .iter()
+            .map(|(_, text)| {
+                let request: serde_json::Value = serde_json::from_str(text).unwrap();
+                request["args"].as_array().unwrap().len()
+            })
+            .collect();
+        assert_eq!(topic_counts, [10, 10, 5]);
+
+        // Frames leave at least `pacing` apart; allow a little jitter on the receiving side
+        for pair in frames.windows(2) {
+            let gap = pair[1].0 - pair[0].0;
+            assert!(
+                gap + Duration::from_millis(5) >= pacing,
+                "frames only {:?} apart",
+                gap
+            );
+        }
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_bybit_flapping_connection_restores_are_budgeted() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_exchanges_common::{ReconnectBudget, RetryConfig};
+        use crypto_dash_stream_hub::StreamHub;
+        use std::time::Duration;
+
+        // Every connection is closed as soon as its subscribe frame arrives
+        let (url, mut frames) = spawn_ack_upstream(&[], usize::MAX).await;
+
+        let hub = StreamHub::new().handle();
+        let _session = hub.subscribe_all().await;
+        let adapter = BybitAdapter::new()
+            .with_ws_url(MarketType::Spot, url)
+            .with_subscribe_timeout(Duration::ZERO)
+            .with_reconnect_retry(RetryConfig {
+                max_attempts: 1,
+                base_delay: Duration::from_millis(10),
+                ..RetryConfig::default()
+            })
+            .with_reconnect_budget(ReconnectBudget::new(3, Duration::from_secs(60)));
+        adapter
+            .start(hub, MemoryCache::new().handle())
+            .await
+            .unwrap();
+
+        let channel = Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: ExchangeId::from("bybit"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        adapter
+            .subscribe(std::slice::from_ref(&channel))
+            .await
+            .unwrap();
+
+        // Subscribe and restores draw on one budget, so redialling stops once it is spent
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let mut connections = 0;
+        while let Ok((connection, _)) = frames.try_recv() {
+            connections = connection;
+        }
+        assert_eq!(connections, 3);
+        let err = adapter.subscribe(&[channel]).await.unwrap_err();
+        assert!(
+            err.to_string().contains("temporarily unavailable"),
+            "{}",
+            err
+        );
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_bybit_reconnect_resets_orderbook_before_snapshot() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_core::model::StreamMessage;
+        use crypto_dash_exchanges_common::RetryConfig;
+        use crypto_dash_stream_hub::StreamHub;
+        use std::time::Duration;
+
+        // Every subscribe is answered with a snapshot; the first connection then drops
+        const SNAPSHOT: &str = r#"{"topic":"orderbook.50.BTCUSDT","type":"snapshot","ts":1672304484978,"data":{"s":"BTCUSDT","b":[["16493.50","0.006"]],"a":[["16611.00","0.029"]],"u":1,"seq":1}}"#;
+        let (url, _frames) = spawn_ack_upstream(&[SNAPSHOT], 1).await;
+
+        let hub = StreamHub::new().handle();
+        let mut receiver = hub.subscribe_all().await;
+        let adapter = BybitAdapter::new()
+            .with_ws_url(MarketType::Spot, url)
+            .with_subscribe_timeout(Duration::ZERO)
+            .with_reconnect_retry(RetryConfig {
+                max_attempts: 3,
+                base_delay: Duration::from_millis(10),
+                ..RetryConfig::default()
+            });
+        adapter
+            .start(hub, MemoryCache::new().handle())
+            .await
+            .unwrap();
+
+        let channel = Channel {
+            channel_type: ChannelType::OrderBook,
+            exchange: ExchangeId::from("bybit"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        adapter.subscribe(&[channel]).await.unwrap();
+
+        let mut book_messages = Vec::new();
+        while book_messages.len() < 3 {
+            let (topic, message) = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+                .await
+                .expect("order book stream stalled")
+                .unwrap();
+            match message {
+                StreamMessage::OrderBookSnapshot(_) => book_messages.push("snapshot"),
+                StreamMessage::OrderBookReset { symbol, .. } => {
+                    assert_eq!(topic.channel_type, ChannelType::OrderBook);
+                    assert_eq!(symbol, Symbol::new("BTC", "USDT"));
+                    book_messages.push("reset");
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(book_messages, ["snapshot", "reset", "snapshot"]);
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_bybit_reconnect_resends_all_market_channels() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+        use std::time::Duration;
+
+        // The first connection closes after its subscribe; nobody listens, so it stays down
+        let (url, mut frames) = spawn_ack_upstream(&[], 1).await;
+
+        let adapter = BybitAdapter::new()
+            .with_ws_url(MarketType::Spot, url)
+            .with_subscribe_timeout(Duration::ZERO);
+        adapter
+            .start(StreamHub::new().handle(), MemoryCache::new().handle())
+            .await
+            .unwrap();
+
+        let btc = Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: ExchangeId::from("bybit"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        let eth = Channel {
+            symbol: Symbol::new("ETH", "USDT"),
+            ..btc.clone()
+        };
+        adapter.subscribe(&[btc]).await.unwrap();
+        let (first, _) = frames.recv().await.unwrap();
+        assert_eq!(first, 1);
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while adapter.is_market_connected(MarketType::Spot).await {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("connection was not dropped");
+
+        adapter.subscribe(&[eth]).await.unwrap();
+        let (second, sent) = tokio::time::timeout(Duration::from_secs(5), frames.recv())
+            .await
+            .expect("no subscription on the new connection")
+            .unwrap();
+        assert_eq!(second, 2);
+        let sent: serde_json::Value = serde_json::from_str(&sent).unwrap();
+        let mut args: Vec<&str> = sent["args"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|arg| arg.as_str().unwrap())
+            .collect();
+        args.sort();
+        assert_eq!(args, ["tickers.BTCUSDT", "tickers.ETHUSDT"]);
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_bybit_throttle_notice_backs_off() {
+        let adapter = BybitAdapter::new();
+        let frame = r#"{"success":false,"ret_msg":"Too many requests","op":"subscribe"}"#;
+
+        adapter
+            .handle_message(MarketType::Spot, serde_json::from_str(frame).unwrap())
+            .await
+            .unwrap();
+
+        assert!(adapter.throttled_until(MarketType::Spot).await.is_some());
+        assert!(adapter
+            .throttled_until(MarketType::Perpetual)
+            .await
+            .is_none());
+    }
+
+    #[test]
+    fn test_bybit_orderbook_depth_mapping() {
+        use crate::adapter::orderbook_depth;
+
+        assert_eq!(orderbook_depth(None), 50);
+        for depth in [1, 50, 200, 500] {
+            assert_eq!(orderbook_depth(Some(depth)), depth);
+        }
+        assert_eq!(orderbook_depth(Some(0)), 1);
+        assert_eq!(orderbook_depth(Some(20)), 50);
+        assert_eq!(orderbook_depth(Some(100)), 200);
+        assert_eq!(orderbook_depth(Some(201)), 500);
+        assert_eq!(orderbook_depth(Some(1000)), 500);
+
+        let adapter = BybitAdapter::new();
+        let channel = |depth| Channel {
+            channel_type: ChannelType::OrderBook,
+            exchange: ExchangeId::from("bybit"),
+            market_type: MarketType::Perpetual,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth,
+        };
+        let subscription: serde_json::Value = serde_json::from_str(
+            &adapter
+                .format_subscription(&[channel(None), channel(Some(200))])
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            subscription["args"],
+            serde_json::json!(["orderbook.50.BTCUSDT", "orderbook.200.BTCUSDT"])
+        );
+
+        assert_eq!(
+            adapter.orderbook_stream(MarketType::Perpetual, None),
+            (50, 20)
+        );
+        assert_eq!(
+            adapter.orderbook_stream(MarketType::Perpetual, Some(1)),
+            (1, 10)
+        );
+        assert_eq!(
+            adapter.orderbook_stream(MarketType::Perpetual, Some(200)),
+            (200, 100)
+        );
+        assert_eq!(
+            adapter.orderbook_stream(MarketType::Spot, Some(200)),
+            (200, 200)
+        );
+    }
+
+    #[test]
+    fn test_bybit_tick_direction_mapping() {
+        use crate::adapter::tick_direction;
+        use crypto_dash_core::model::TickDirection;
+
+        assert_eq!(tick_direction("PlusTick"), Some(TickDirection::Up));
+        assert_eq!(tick_direction("MinusTick"), Some(TickDirection::Down));
+        assert_eq!(tick_direction("ZeroPlusTick"), Some(TickDirection::Neutral));
+        assert_eq!(
+            tick_direction("ZeroMinusTick"),
+            Some(TickDirection::Neutral)
+        );
+        assert_eq!(tick_direction(""), None);
+    }
+
+    #[test]
+    fn test_bybit_symbols_round_trip() {
+        let adapter = BybitAdapter::new();
+        let symbols = [
+            Symbol::new("BTC", "USDT"),
+            Symbol::new("ETH", "BTC"),
+            Symbol::new("SOL", "USDC"),
+            Symbol::new("USDC", "USDT"),
+            Symbol::new("1000PEPE", "USDT"),
+            Symbol::new("1INCH", "USDT"),
+        ];
+
+        for market_type in [MarketType::Spot, MarketType::Perpetual] {
+            for symbol in &symbols {
+                let exchange_symbol = adapter.to_exchange_symbol(symbol, market_type);
+                assert_eq!(
+                    &adapter.parse_symbol(&exchange_symbol).unwrap(),
+                    symbol,
+                    "{} did not round-trip on {:?}",
+                    exchange_symbol,
+                    market_type
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_bybit_usdc_perpetual_symbol() {
+        let adapter = BybitAdapter::new();
+        let symbol = Symbol::new("ETH", "USDC");
+
+        assert_eq!(
+            adapter.to_exchange_symbol(&symbol, MarketType::Perpetual),
+            "ETHPERP"
+        );
+        assert_eq!(
+            adapter.to_exchange_symbol(&symbol, MarketType::Spot),
+            "ETHUSDC"
+        );
+    }
+
+    #[test]
+    fn test_bybit_inverse_perpetual_symbol() {
+        let adapter = BybitAdapter::new();
+        let symbol = Symbol::new("BTC", "USD");
+
+        assert_eq!(
+            adapter.to_exchange_symbol(&symbol, MarketType::InversePerpetual),
+            "BTCUSD"
+        );
+        assert_eq!(adapter.parse_symbol("BTCUSD").unwrap(), symbol);
+        assert_eq!(
+            adapter.parse_symbol("ETHUSD").unwrap(),
+            Symbol::new("ETH", "USD")
+        );
+        assert!(adapter
+            .supported_markets()
+            .contains(&MarketType::InversePerpetual));
+    }
+
+    #[tokio::test]
+    async fn test_bybit_perpetual_ticker_publishes_funding() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_core::model::StreamMessage;
+        use crypto_dash_stream_hub::StreamHub;
+        use rust_decimal::Decimal;
+
+        let cache = MemoryCache::new().handle();
+        let hub = StreamHub::new().handle();
+        let mut receiver = hub.subscribe_all().await;
+        let adapter = BybitAdapter::new();
+        adapter.start(hub, cache.clone()).await.unwrap();
+        let symbol = Symbol::new("BTC", "USDT");
+
+        let frame = r#"{
+            "topic": "tickers.BTCUSDT",
+            "type": "snapshot",
+            "ts": 1673853746003,
+            "data": {"symbol": "BTCUSDT", "lastPrice": "21109.77", "markPrice": "21109.70",
+                     "indexPrice": "21111.46", "fundingRate": "0.0001",
+                     "nextFundingTime": "1673856000000"}
+        }"#;
+        adapter
+            .handle_message(MarketType::Perpetual, serde_json::from_str(frame).unwrap())
+            .await
+            .unwrap();
+
+        let (_, message) = receiver.try_recv().unwrap();
+        assert!(matches!(message, StreamMessage::Ticker(_)));
+        let (topic, message) = receiver.try_recv().unwrap();
+        assert_eq!(topic.channel_type, ChannelType::Funding);
+        let StreamMessage::FundingRate(funding) = message else {
+            panic!("expected funding rate, got {:?}", message);
+        };
+        assert_eq!(funding.rate, Decimal::new(1, 4));
+        assert_eq!(funding.next_funding_time.timestamp_millis(), 1673856000000);
+        assert_eq!(funding.mark_price, Some(Decimal::new(2110970, 2)));
+        assert_eq!(funding.index_price, Some(Decimal::new(2111146, 2)));
+        let cached = cache
+            .get_funding_rate(&adapter.id(), MarketType::Perpetual, &symbol)
+            .await
+            .expect("funding rate not cached");
+        assert_eq!(cached.rate, funding.rate);
+
+        // Deltas without funding fields and spot tickers publish only the ticker
+        let delta = r#"{"topic":"tickers.BTCUSDT","type":"delta","ts":1673853746103,"data":{"symbol":"BTCUSDT","lastPrice":"21110.00"}}"#;
+        adapter
+            .handle_message(MarketType::Perpetual, serde_json::from_str(delta).unwrap())
+            .await
+            .unwrap();
+        adapter
+            .handle_message(MarketType::Spot, serde_json::from_str(frame).unwrap())
+            .await
+            .unwrap();
+        for _ in 0..2 {
+            let (_, message) = receiver.try_recv().unwrap();
+            assert!(matches!(message, StreamMessage::Ticker(_)));
+        }
+        assert!(receiver.try_recv().is_err());
+        assert!(cache
+            .get_funding_rate(&adapter.id(), MarketType::Spot, &symbol)
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_bybit_unsupported_channels_are_rejected_up_front() {
+        let adapter = BybitAdapter::new().with_ws_url(MarketType::Spot, "ws://127.0.0.1:1");
+        let ticker = Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: ExchangeId::from("bybit"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+
+        for channel_type in [ChannelType::Trades, ChannelType::Funding] {
+            let unsupported = Channel {
+                channel_type,
+                ..ticker.clone()
+            };
+            let err = adapter
+                .subscribe(&[ticker.clone(), unsupported])
+                .await
+                .unwrap_err();
+            assert!(
+                err.to_string().contains("does not stream"),
+                "unexpected error: {}",
+                err
+            );
+        }
+        // Nothing was dialed or recorded as subscribed
+        let stats = adapter.connection_stats(MarketType::Spot).await;
+        assert_eq!(stats.reconnect_attempts, 0);
+        assert!(adapter
+            .subscribed_channels(MarketType::Spot)
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bybit_funding_only_subscription_streams_funding() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_core::model::StreamMessage;
+        use crypto_dash_stream_hub::{StreamHub, Topic};
+        use futures::{SinkExt, StreamExt};
+        use std::time::Duration;
+        use tokio::sync::mpsc;
+        use tokio_tungstenite::tungstenite::Message;
+
+        // Reports each subscribe frame and answers it with a perpetual ticker snapshot
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (frames_tx, mut frames) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let frames_tx = frames_tx.clone();
+                tokio::spawn(async move {
+                    let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                    while let Some(Ok(Message::Text(text))) = ws.next().await {
+                        let _ = frames_tx.send(text);
+                        let ticker = r#"{"topic":"tickers.BTCUSDT","type":"snapshot","ts":1673853746003,"data":{"symbol":"BTCUSDT","lastPrice":"21109.77","fundingRate":"0.0001","nextFundingTime":"1673856000000"}}"#;
+                        ws.send(Message::Text(ticker.to_string())).await.unwrap();
+                    }
+                });
+            }
+        });
+
+        let funding = Channel {
+            channel_type: ChannelType::Funding,
+            exchange: ExchangeId::from("bybit"),
+            market_type: MarketType::Perpetual,
+            symbol: Symbol::new("BTC", "USDT"),
+            depth: None,
+        };
+        // Only the funding topic has a subscriber, so the ticker topic alone would look idle
+        let hub = StreamHub::new().handle();
+        let mut subscriber = hub.subscribe(&Topic::from_channel(&funding)).await.unwrap();
+        let adapter = BybitAdapter::new()
+            .with_ws_url(MarketType::Perpetual, url)
+            .with_subscribe_timeout(Duration::from_millis(500));
+        adapter
+            .start(hub, MemoryCache::new().handle())
+            .await
+            .unwrap();
+
+        adapter
+            .subscribe(std::slice::from_ref(&funding))
+            .await
+            .unwrap();
+
+        let frame: serde_json::Value = serde_json::from_str(&frames.recv().await.unwrap()).unwrap();
+        assert_eq!(frame["args"], serde_json::json!(["tickers.BTCUSDT"]));
+        let message = tokio::time::timeout(Duration::from_secs(2), subscriber.recv())
+            .await
+            .expect("funding never arrived")
+            .unwrap();
+        assert!(
+            matches!(message, StreamMessage::FundingRate(_)),
+            "{:?}",
+            message
+        );
+
+        // The subscribe is settled and the connection kept for the funding subscriber
+        tokio::time::sleep(Duration::from_millis(700)).await;
+        assert!(subscriber.try_recv().is_err());
+        assert!(adapter.is_market_connected(MarketType::Perpetual).await);
+
+        // A ticker and funding channel for one instrument share a topic; spot has no funding
+        let ticker = Channel {
+            channel_type: ChannelType::Ticker,
+            ..funding.clone()
+        };
+        let spot_funding = Channel {
+            market_type: MarketType::Spot,
+            ..funding.clone()
+        };
+        let frame: serde_json::Value = serde_json::from_str(
+            &adapter
+                .format_subscription(&[ticker, funding, spot_funding])
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(frame["args"], serde_json::json!(["tickers.BTCUSDT"]));
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_bybit_crossed_book_ticker_is_not_published() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+
+        let cache = MemoryCache::new().handle();
+        let hub = StreamHub::new().handle();
+        let mut receiver = hub.subscribe_all().await;
+        let adapter = BybitAdapter::new();
+        adapter.start(hub, cache.clone()).await.unwrap();
+
+        let crossed = r#"{"topic":"tickers.BTCUSDT","type":"snapshot","ts":1673853746003,
+            "data":{"symbol":"BTCUSDT","lastPrice":"21109.77","bid1Price":"21120.00","ask1Price":"21110.00",
+                    "fundingRate":"0.0001","nextFundingTime":"1673856000000"}}"#;
+        adapter
+            .handle_message(
+                MarketType::Perpetual,
+                serde_json::from_str(crossed).unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Neither the ticker nor the funding it carried reaches subscribers or the cache
+        assert!(receiver.try_recv().is_err());
+        let symbol = Symbol::new("BTC", "USDT");
+        assert!(cache
+            .get_ticker(&adapter.id(), MarketType::Perpetual, &symbol)
+            .await
+            .is_none());
+        assert!(cache
+            .get_funding_rate(&adapter.id(), MarketType::Perpetual, &symbol)
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_bybit_orderbook_snapshot_and_delta() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_core::model::StreamMessage;
+        use crypto_dash_stream_hub::StreamHub;
+        use rust_decimal::Decimal;
+
+        let cache = MemoryCache::new().handle();
+        let hub = StreamHub::new().handle();
+        let mut receiver = hub.subscribe_all().await;
+        let adapter = BybitAdapter::new();
+        adapter.start(hub, cache.clone()).await.unwrap();
+        let symbol = Symbol::new("BTC", "USDT");
+        let levels = |side: &[crypto_dash_core::model::PriceLevel]| -> Vec<(String, String)> {
+            side.iter()
+                .map(|l| (l.price.to_string(), l.quantity.to_string()))
+                .collect()
+        };
+
+        // A delta before any snapshot has nothing to apply to
+        let early = r#"{"topic":"orderbook.50.BTCUSDT","type":"delta","ts":1672304484977,"data":{"s":"BTCUSDT","b":[["16493.00","1"]],"a":[],"u":1,"seq":1}}"#;
+        adapter
+            .handle_message(MarketType::Perpetual, serde_json::from_str(early).unwrap())
+            .await
+            .unwrap();
+        assert!(receiver.try_recv().is_err());
+
+        let snapshot = r#"{"topic":"orderbook.50.BTCUSDT","type":"snapshot","ts":1672304484978,"data":{"s":"BTCUSDT","b":[["16493.50","0.006"],["16493.00","0.100"]],"a":[["16611.00","0.029"],["16612.00","0.213"]],"u":18521288,"seq":7961638724},"cts":1672304484976}"#;
+        let parsed: BybitMessage = serde_json::from_str(snapshot).unwrap();
+        assert!(matches!(parsed, BybitMessage::OrderBook { .. }));
+        adapter
+            .handle_message(MarketType::Perpetual, parsed)
+            .await
+            .unwrap();
+
+        let (topic, message) = receiver.try_recv().unwrap();
+        assert_eq!(topic.channel_type, ChannelType::OrderBook);
+        assert!(matches!(message, StreamMessage::OrderBookSnapshot(_)));
+
+        let delta = r#"{"topic":"orderbook.50.BTCUSDT","type":"delta","ts":1672304484990,"data":{"s":"BTCUSDT","b":[["16493.50","0"],["16494.00","0.500"]],"a":[["16611.00","0.010"]],"u":18521289,"seq":7961638725},"cts":1672304484988}"#;
+        adapter
+            .handle_message(MarketType::Perpetual, serde_json::from_str(delta).unwrap())
+            .await
+            .unwrap();
+
+        let (_, message) = receiver.try_recv().unwrap();
+        let StreamMessage::OrderBookDelta(delta) = message else {
+            panic!("expected order book delta, got {:?}", message);
+        };
+        assert_eq!(
+            levels(&delta.bids_upserts),
+            [("16494.00".into(), "0.500".into())]
+        );
+        assert_eq!(delta.deletes, Some(vec![Decimal::new(1649350, 2)]));
+
+        let book = cache
+            .get_orderbook(&adapter.id(), MarketType::Perpetual, &symbol)
+            .await
+            .unwrap();
+        assert_eq!(
+            levels(&book.bids),
+            [
+                ("16494.00".into(), "0.500".into()),
+                ("16493.00".into(), "0.100".into())
+            ]
+        );
+        assert_eq!(
+            levels(&book.asks),
+            [
+                ("16611.00".into(), "0.010".into()),
+                ("16612.00".into(), "0.213".into())
+            ]
+        );
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_bybit_orderbook_deltas_apply_past_the_cached_depth() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+        use rust_decimal::Decimal;
+
+        // The cache keeps one level per side, the local book keeps them all
+        let cache = MemoryCache::new().with_orderbook_depth_cap(1).handle();
+        let adapter = BybitAdapter::new();
+        adapter
+            .start(StreamHub::new().handle(), cache.clone())
+            .await
+            .unwrap();
+        let symbol = Symbol::new("BTC", "USDT");
+
+        let snapshot = r#"{"topic":"orderbook.50.BTCUSDT","type":"snapshot","ts":1672304484978,"data":{"s":"BTCUSDT","b":[["16493.50","0.006"],["16493.00","0.100"]],"a":[["16611.00","0.029"],["16612.00","0.213"]],"u":18521288,"seq":7961638724},"cts":1672304484976}"#;
+        let delta = r#"{"topic":"orderbook.50.BTCUSDT","type":"delta","ts":1672304484990,"data":{"s":"BTCUSDT","b":[["16493.50","0"]],"a":[["16611.00","0"]],"u":18521289,"seq":7961638725},"cts":1672304484988}"#;
+        for frame in [snapshot, delta] {
+            adapter
+                .handle_message(MarketType::Perpetual, serde_json::from_str(frame).unwrap())
+                .await
+                .unwrap();
+        }
+
+        // Removing the top levels exposes the ones the cache had truncated away
+        let book = cache
+            .get_orderbook(&adapter.id(), MarketType::Perpetual, &symbol)
+            .await
+            .unwrap();
+        assert_eq!(book.bids[0].price, Decimal::new(1649300, 2));
+        assert_eq!(book.asks[0].price, Decimal::new(1661200, 2));
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_bybit_ticker_sequence_regression_is_detected() {
+        use crate::adapter::SequenceCheck;
+
+        let adapter = BybitAdapter::new();
+        let frames = [
+            r#"{"topic":"tickers.BTCUSDT","type":"snapshot","ts":1673853746003,"cs":100,"data":{"symbol":"BTCUSDT","lastPrice":"21109.77"}}"#,
+            r#"{"topic":"tickers.BTCUSDT","type":"delta","ts":1673853746103,"cs":137,"data":{"symbol":"BTCUSDT","lastPrice":"21110.00"}}"#,
+            r#"{"topic":"tickers.BTCUSDT","type":"delta","ts":1673853746203,"cs":412,"data":{"symbol":"BTCUSDT","lastPrice":"21111.00"}}"#,
+            r#"{"topic":"tickers.BTCUSDT","type":"delta","ts":1673853746303,"cs":412,"data":{"symbol":"BTCUSDT","lastPrice":"21111.00"}}"#,
+            r#"{"topic":"tickers.BTCUSDT","type":"delta","ts":1673853746403,"cs":300,"data":{"symbol":"BTCUSDT","lastPrice":"21109.00"}}"#,
+        ];
+
+        let mut checks = Vec::new();
+        for frame in frames {
+            let message: BybitMessage = serde_json::from_str(frame).unwrap();
+            let BybitMessage::Ticker {
+                topic,
+                message_type,
+                cs,
+                ..
+            } = message
+            else {
+                panic!("expected a ticker frame");
+            };
+            checks.push(
+                adapter
+                    .check_ticker_sequence(MarketType::Perpetual, &topic, &message_type, cs)
+                    .await,
+            );
+        }
+
+        assert_eq!(
+            checks,
+            vec![
+                SequenceCheck::InOrder,
+                SequenceCheck::InOrder,
+                SequenceCheck::InOrder,
+                SequenceCheck::Regression {
+                    previous: 412,
+                    received: 412
+                },
+                SequenceCheck::Regression {
+                    previous: 412,
+                    received: 300
+                },
+            ]
+        );
+
+        // A snapshot sets a new baseline even when its sequence is lower
+        let check = adapter
+            .check_ticker_sequence(
+                MarketType::Perpetual,
+                "tickers.BTCUSDT",
+                "snapshot",
+                Some(200),
+            )
+            .await;
+        assert_eq!(check, SequenceCheck::InOrder);
+        let check = adapter
+            .check_ticker_sequence(MarketType::Perpetual, "tickers.BTCUSDT", "delta", Some(250))
+            .await;
+        assert_eq!(check, SequenceCheck::InOrder);
+        // Sequences are tracked per topic and frames without `cs` are not tracked
+        let check = adapter
+            .check_ticker_sequence(MarketType::Perpetual, "tickers.ETHUSDT", "delta", Some(7))
+            .await;
+        assert_eq!(check, SequenceCheck::InOrder);
+        let check = adapter
+            .check_ticker_sequence(MarketType::Spot, "tickers.BTCUSDT", "delta", None)
+            .await;
+        assert_eq!(check, SequenceCheck::Untracked);
+    }
+
+    #[tokio::test]
+    async fn test_bybit_inverse_ticker_parsing() {
+        use crypto_dash_cache::MemoryCache;
+        use crypto_dash_stream_hub::StreamHub;
+        use rust_decimal::Decimal;
+
+        let cache = MemoryCache::new().handle();
+        let adapter = BybitAdapter::new();
+        adapter
+            .start(StreamHub::new().handle(), cache.clone())
+            .await
+            .unwrap();
+
+        let frame = r#"{
+            "topic": "tickers.BTCUSD",
+            "type": "snapshot",
+            "ts": 1744168585009,
+            "data": {
+                "symbol": "BTCUSD",
+                "tickDirection": "PlusTick",
+                "price24hPcnt": "0.0123",
+                "lastPrice": "64250.5",
+                "highPrice24h": "65100",
+                "lowPrice24h": "63020.5",
+                "markPrice": "64251.1",
+                "indexPrice": "64260.2",
+                "openInterest": "512345678",
+                "turnover24h": "4321.987",
+                "volume24h": "277654321",
+                "fundingRate": "0.0001",
+                "bid1Price": "64250",
+                "bid1Size": "12000",
+                "ask1Price": "64250.5",
+                "ask1Size": "3400"
+            }
+        }"#;
+        adapter
+            .handle_message(
+                MarketType::InversePerpetual,
+                serde_json::from_str(frame).unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let ticker = cache
+            .get_ticker(
+                &ExchangeId::from("bybit"),
+                MarketType::InversePerpetual,
+                &Symbol::new("BTC", "USD"),
+            )
+            .await
+            .expect("inverse ticker not cached");
+        assert_eq!(ticker.market_type, MarketType::InversePerpetual);
+        assert_eq!(ticker.bid, "64250".parse::<Decimal>().unwrap());
+        assert_eq!(ticker.ask, "64250.5".parse::<Decimal>().unwrap());
+        // Volume is in USD contracts, unlike the coin-denominated turnover
+        assert_eq!(
+            ticker.quote_volume_24h,
+            Some("277654321".parse::<Decimal>().unwrap())
+        );
+        assert_eq!(ticker.change_pct_24h, Some("1.23".parse().unwrap()));
+        assert_eq!(
+            ticker.tick_direction,
+            Some(crypto_dash_core::model::TickDirection::Up)
+        );
+        assert_eq!(ticker.high_24h, Some("65100".parse().unwrap()));
+        assert_eq!(ticker.low_24h, Some("63020.5".parse().unwrap()));
+        // ...and the base volume is the turnover
+        assert_eq!(ticker.volume_24h, Some("4321.987".parse().unwrap()));
+
+        adapter.stop().await.unwrap();
+    }
+
     #[test]
     fn test_bybit_ticker_parsing() {
         // This is the example message from the user
@@ -84,4 +1254,28 @@ mod bybit_subscription_tests {
 
         println!("✅ BybitTicker type successfully parses the API message format!");
     }
+
+    #[test]
+    fn test_bybit_ticker_numeric_fields_accept_numbers_or_strings() {
+        let ticker = |last_price: &str, bid_size: &str| {
+            let frame = format!(
+                r#"{{"topic":"tickers.BTCUSDT","type":"snapshot","ts":1673272861686,
+                    "data":{{"symbol":"BTCUSDT","lastPrice":{},"bid1Size":{},"fundingRate":null}}}}"#,
+                last_price, bid_size
+            );
+            match serde_json::from_str::<BybitMessage>(&frame).unwrap() {
+                BybitMessage::Ticker { data, .. } => data.into_vec().remove(0),
+                other => panic!("Expected Ticker message, got {:?}", other),
+            }
+        };
+
+        let quoted = ticker(r#""17216.5""#, r#""84.489""#);
+        assert_eq!(quoted.last_price, "17216.5");
+        assert_eq!(quoted.bid1_size.as_deref(), Some("84.489"));
+
+        let bare = ticker("17216.5", "84.489");
+        assert_eq!(bare.last_price, "17216.5");
+        assert_eq!(bare.bid1_size.as_deref(), Some("84.489"));
+        assert_eq!(bare.funding_rate, None);
+    }
 }
@@ -1,3 +1,4 @@
+use crypto_dash_exchanges_common::string_or_number;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -6,59 +7,98 @@ pub struct BybitTicker {
     pub symbol: String,
     #[serde(rename = "tickDirection")]
     pub tick_direction: Option<String>,
-    #[serde(rename = "price24hPcnt")]
+    #[serde(
+        rename = "price24hPcnt",
+        deserialize_with = "string_or_number::optional"
+    )]
     pub price24h_pcnt: Option<String>,
-    #[serde(rename = "lastPrice")]
+    #[serde(rename = "lastPrice", deserialize_with = "string_or_number::required")]
     pub last_price: String,
-    #[serde(rename = "prevPrice24h")]
+    #[serde(
+        rename = "prevPrice24h",
+        deserialize_with = "string_or_number::optional"
+    )]
     pub prev_price_24h: Option<String>,
-    #[serde(rename = "highPrice24h")]
+    #[serde(
+        rename = "highPrice24h",
+        deserialize_with = "string_or_number::optional"
+    )]
     pub high_price_24h: Option<String>,
-    #[serde(rename = "lowPrice24h")]
+    #[serde(
+        rename = "lowPrice24h",
+        deserialize_with = "string_or_number::optional"
+    )]
     pub low_price_24h: Option<String>,
-    #[serde(rename = "prevPrice1h")]
+    #[serde(
+        rename = "prevPrice1h",
+        deserialize_with = "string_or_number::optional"
+    )]
     pub prev_price_1h: Option<String>,
-    #[serde(rename = "markPrice")]
+    #[serde(rename = "markPrice", deserialize_with = "string_or_number::optional")]
     pub mark_price: Option<String>,
-    #[serde(rename = "indexPrice")]
+    #[serde(rename = "indexPrice", deserialize_with = "string_or_number::optional")]
     pub index_price: Option<String>,
-    #[serde(rename = "openInterest")]
+    #[serde(
+        rename = "openInterest",
+        deserialize_with = "string_or_number::optional"
+    )]
     pub open_interest: Option<String>,
-    #[serde(rename = "openInterestValue")]
+    #[serde(
+        rename = "openInterestValue",
+        deserialize_with = "string_or_number::optional"
+    )]
     pub open_interest_value: Option<String>,
-    #[serde(rename = "turnover24h")]
+    #[serde(
+        rename = "turnover24h",
+        deserialize_with = "string_or_number::optional"
+    )]
     pub turnover_24h: Option<String>,
-    #[serde(rename = "volume24h")]
+    #[serde(rename = "volume24h", deserialize_with = "string_or_number::optional")]
     pub volume_24h: Option<String>,
-    #[serde(rename = "nextFundingTime")]
+    #[serde(
+        rename = "nextFundingTime",
+        deserialize_with = "string_or_number::optional"
+    )]
     pub next_funding_time: Option<String>,
-    #[serde(rename = "fundingRate")]
+    #[serde(
+        rename = "fundingRate",
+        deserialize_with = "string_or_number::optional"
+    )]
     pub funding_rate: Option<String>,
-    #[serde(rename = "bid1Price")]
+    #[serde(rename = "bid1Price", deserialize_with = "string_or_number::optional")]
     pub bid1_price: Option<String>,
-    #[serde(rename = "bid1Size")]
+    #[serde(rename = "bid1Size", deserialize_with = "string_or_number::optional")]
     pub bid1_size: Option<String>,
-    #[serde(rename = "ask1Price")]
+    #[serde(rename = "ask1Price", deserialize_with = "string_or_number::optional")]
     pub ask1_price: Option<String>,
-    #[serde(rename = "ask1Size")]
+    #[serde(rename = "ask1Size", deserialize_with = "string_or_number::optional")]
     pub ask1_size: Option<String>,
-    #[serde(rename = "bidPrice")]
+    #[serde(rename = "bidPrice", deserialize_with = "string_or_number::optional")]
     pub bid_price: Option<String>,
-    #[serde(rename = "bidSize")]
+    #[serde(rename = "bidSize", deserialize_with = "string_or_number::optional")]
     pub bid_size: Option<String>,
-    #[serde(rename = "askPrice")]
+    #[serde(rename = "askPrice", deserialize_with = "string_or_number::optional")]
     pub ask_price: Option<String>,
-    #[serde(rename = "askSize")]
+    #[serde(rename = "askSize", deserialize_with = "string_or_number::optional")]
     pub ask_size: Option<String>,
-    #[serde(rename = "basisRate")]
+    #[serde(rename = "basisRate", deserialize_with = "string_or_number::optional")]
     pub basis_rate: Option<String>,
-    #[serde(rename = "deliveryFeeRate")]
+    #[serde(
+        rename = "deliveryFeeRate",
+        deserialize_with = "string_or_number::optional"
+    )]
     pub delivery_fee_rate: Option<String>,
-    #[serde(rename = "predictedDeliveryPrice")]
+    #[serde(
+        rename = "predictedDeliveryPrice",
+        deserialize_with = "string_or_number::optional"
+    )]
     pub predicted_delivery_price: Option<String>,
-    #[serde(rename = "preOpenPrice")]
+    #[serde(
+        rename = "preOpenPrice",
+        deserialize_with = "string_or_number::optional"
+    )]
     pub pre_open_price: Option<String>,
-    #[serde(rename = "preQty")]
+    #[serde(rename = "preQty", deserialize_with = "string_or_number::optional")]
     pub pre_qty: Option<String>,
     #[serde(rename = "curPreListingPhase")]
     pub cur_pre_listing_phase: Option<String>,
@@ -71,18 +111,43 @@ pub enum BybitTickerPayload {
     Multiple(Vec<BybitTicker>),
 }
 
+/// `orderbook.{depth}.{symbol}` payload; levels are `[price, size]` and a zero size deletes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BybitOrderBook {
+    pub s: String,
+    pub b: Vec<[String; 2]>,
+    pub a: Vec<[String; 2]>,
+    /// Update id; Bybit resets it to 1 when it resends a full snapshot
+    #[serde(default)]
+    pub u: Option<u64>,
+    #[serde(default)]
+    pub seq: Option<u64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum BybitMessage {
+    // Must precede Ticker, whose all-default payload would otherwise match book frames
+    OrderBook {
+        topic: String,
+        ts: u64,
+        /// `snapshot` or `delta`
+        #[serde(rename = "type")]
+        message_type: String,
+        data: BybitOrderBook,
+    },
     Ticker {
         topic: String,
         ts: u64,
         #[serde(rename = "type")]
         message_type: String,
         data: BybitTickerPayload,
+        /// Matching engine cross sequence, not a checksum; Bybit v5 publishes no book checksum
         #[serde(default)]
         cs: Option<u64>,
     },
+    /// Exchange-initiated control frame naming the topics it applies to, e.g. a forced unsubscribe
+    Control { op: String, args: Vec<String> },
     Subscription {
         success: bool,
         #[serde(rename = "ret_msg")]
@@ -1,8 +1,10 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use crypto_dash_cache::CacheHandle;
-use crypto_dash_core::model::{Channel, ExchangeId};
-use crypto_dash_stream_hub::HubHandle;
+use crypto_dash_core::model::{
+    AdapterMetrics, Channel, ChannelType, ConnectionStats, ExchangeId, MarketType, StreamMessage,
+};
+use crypto_dash_stream_hub::{HubHandle, Topic};
 
 /// Common interface for exchange adapters
 #[async_trait]
@@ -10,9 +12,22 @@ pub trait ExchangeAdapter: Send + Sync {
     /// Get the exchange identifier
     fn id(&self) -> ExchangeId;
 
+    /// Market types this adapter can stream
+    fn supported_markets(&self) -> Vec<MarketType>;
+
+    /// Order book depths accepted by the exchange, in ascending order
+    fn supported_depths(&self) -> &'static [u16];
+
+    /// Depth and update interval in milliseconds the exchange streams for an order book,
+    /// given a depth already passed through [`resolve_depth`]
+    fn orderbook_stream(&self, market_type: MarketType, depth: Option<u16>) -> (u16, u32);
+
     /// Start the adapter with the given hub and cache handles
     async fn start(&self, hub: HubHandle, cache: CacheHandle) -> Result<()>;
 
+    /// Eagerly open connections for the given markets without subscribing
+    async fn connect_markets(&self, markets: &[MarketType]) -> Result<()>;
+
     /// Subscribe to channels
     async fn subscribe(&self, channels: &[Channel]) -> Result<()>;
 
@@ -22,6 +37,87 @@ pub trait ExchangeAdapter: Send + Sync {
     /// Check if the adapter is connected
     async fn is_connected(&self) -> bool;
 
+    /// Check if the connection for a specific market is live
+    async fn is_market_connected(&self, market_type: MarketType) -> bool;
+
+    /// Connection attempt history for a specific market
+    async fn connection_stats(&self, market_type: MarketType) -> ConnectionStats;
+
+    /// Upstream messages received, published and failed to parse since the adapter was created
+    async fn metrics(&self) -> AdapterMetrics;
+
     /// Stop the adapter
     async fn stop(&self) -> Result<()>;
 }
+
+/// Resolve a requested order book depth against the exchange's supported levels.
+///
+/// Exact matches pass through, depths between supported levels are clamped up to the
+/// next level, and depths beyond the deepest level are clamped down to it. Only an
+/// exchange without order book depths rejects a requested depth.
+pub fn resolve_depth(requested: Option<u16>, supported: &[u16]) -> Result<Option<u16>> {
+    let Some(depth) = requested else {
+        return Ok(None);
+    };
+
+    supported
+        .iter()
+        .copied()
+        .find(|level| *level >= depth)
+        .or_else(|| supported.last().copied())
+        .map(Some)
+        .ok_or_else(|| anyhow!("Unsupported order book depth {} (no depths offered)", depth))
+}
+
+/// Invalidate the order books of channels about to be resubscribed on a new connection.
+///
+/// Cached books are dropped and subscribers get an `OrderBookReset` ahead of the snapshot
+/// the resubscribe produces, since updates missed while disconnected cannot be replayed.
+pub async fn publish_orderbook_resets(hub: &HubHandle, cache: &CacheHandle, channels: &[Channel]) {
+    for channel in channels
+        .iter()
+        .filter(|channel| channel.channel_type == ChannelType::OrderBook)
+    {
+        cache
+            .remove_orderbook(&channel.exchange, channel.market_type, &channel.symbol)
+            .await;
+        let topic = Topic::orderbook(
+            channel.exchange.clone(),
+            channel.market_type,
+            channel.symbol.clone(),
+        );
+        let reset = StreamMessage::OrderBookReset {
+            exchange: channel.exchange.clone(),
+            market_type: channel.market_type,
+            symbol: channel.symbol.clone(),
+        };
+        hub.publish(&topic, reset).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEPTHS: &[u16] = &[5, 10, 20];
+
+    #[test]
+    fn test_resolve_depth_accepts_supported_level() {
+        assert_eq!(resolve_depth(Some(10), DEPTHS).unwrap(), Some(10));
+        assert_eq!(resolve_depth(None, DEPTHS).unwrap(), None);
+    }
+
+    #[test]
+    fn test_resolve_depth_clamps_beyond_deepest_level() {
+        assert_eq!(resolve_depth(Some(5000), DEPTHS).unwrap(), Some(20));
+
+        let err = resolve_depth(Some(5000), &[]).unwrap_err();
+        assert!(err.to_string().contains("5000"));
+    }
+
+    #[test]
+    fn test_resolve_depth_clamps_to_next_level() {
+        assert_eq!(resolve_depth(Some(1), DEPTHS).unwrap(), Some(5));
+        assert_eq!(resolve_depth(Some(15), DEPTHS).unwrap(), Some(20));
+    }
+}
@@ -0,0 +1,44 @@
+use anyhow::Result;
+use crypto_dash_core::model::PriceLevel;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// Parse `[price, size]` string pairs as sent by exchange depth payloads
+pub fn parse_levels(levels: &[[String; 2]]) -> Result<Vec<PriceLevel>> {
+    levels
+        .iter()
+        .map(|[price, size]| {
+            Ok(PriceLevel::new(
+                Decimal::from_str(price)?,
+                Decimal::from_str(size)?,
+            ))
+        })
+        .collect()
+}
+
+/// Parse an optional decimal field, treating an empty string as absent
+pub fn optional_decimal(value: &Option<String>) -> Result<Option<Decimal>> {
+    Ok(value
+        .as_deref()
+        .filter(|v| !v.is_empty())
+        .map(Decimal::from_str)
+        .transpose()?)
+}
+
+/// Merge delta levels into one side of a book kept sorted by `order`; zero sizes remove the level
+pub fn apply_levels(
+    side: &mut Vec<PriceLevel>,
+    updates: &[PriceLevel],
+    order: impl Fn(&Decimal, &Decimal) -> std::cmp::Ordering,
+) {
+    for update in updates {
+        match side.binary_search_by(|level| order(&level.price, &update.price)) {
+            Ok(index) if update.quantity.is_zero() => {
+                side.remove(index);
+            }
+            Ok(index) => side[index].quantity = update.quantity,
+            Err(_) if update.quantity.is_zero() => {}
+            Err(index) => side.insert(index, update.clone()),
+        }
+    }
+}
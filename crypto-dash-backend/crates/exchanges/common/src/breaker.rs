@@ -0,0 +1,190 @@
+use anyhow::{anyhow, Result};
+use crypto_dash_core::model::{CircuitState, MarketType};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Consecutive connect failures that open a market's circuit
+pub const DEFAULT_BREAKER_THRESHOLD: u32 = 5;
+
+/// How long an open circuit fails connects fast before allowing a trial
+pub const DEFAULT_BREAKER_COOLDOWN: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Default)]
+struct MarketCircuit {
+    state: CircuitState,
+    failures: u32,
+    opened_at: Option<Instant>,
+    trial_started_at: Option<Instant>,
+}
+
+/// Per-market circuit breaker for exchange connection attempts.
+///
+/// After `threshold` consecutive failures the circuit opens and [`CircuitBreaker::check`]
+/// fails fast until `cooldown` elapses. The next check then lets a single half-open trial
+/// through; its success closes the circuit and its failure reopens it for another cooldown.
+/// A trial that reports neither within one cooldown is treated as lost and replaced.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    circuits: Arc<Mutex<HashMap<MarketType, MarketCircuit>>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold: threshold.max(1),
+            cooldown,
+            circuits: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Whether a connect may be attempted now; an `Ok` on an open circuit starts the trial
+    pub fn check(&self, market_type: MarketType) -> Result<()> {
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.entry(market_type).or_default();
+
+        match circuit.state {
+            CircuitState::Closed => Ok(()),
+            CircuitState::HalfOpen => {
+                let running = circuit
+                    .trial_started_at
+                    .map_or(self.cooldown, |at| at.elapsed());
+                if running >= self.cooldown {
+                    circuit.trial_started_at = Some(Instant::now());
+                    Ok(())
+                } else {
+                    Err(anyhow!("circuit half-open, trial connect in progress"))
+                }
+            }
+            CircuitState::Open => {
+                let elapsed = circuit.opened_at.map_or(self.cooldown, |at| at.elapsed());
+                if elapsed >= self.cooldown {
+                    circuit.state = CircuitState::HalfOpen;
+                    circuit.trial_started_at = Some(Instant::now());
+                    Ok(())
+                } else {
+                    Err(anyhow!(
+                        "circuit open after {} failed connects, retrying in {:?}",
+                        circuit.failures,
+                        self.cooldown - elapsed
+                    ))
+                }
+            }
+        }
+    }
+
+    pub fn record_success(&self, market_type: MarketType) {
+        self.circuits
+            .lock()
+            .unwrap()
+            .insert(market_type, MarketCircuit::default());
+    }
+
+    pub fn record_failure(&self, market_type: MarketType) {
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.entry(market_type).or_default();
+        circuit.failures += 1;
+
+        if circuit.state == CircuitState::HalfOpen || circuit.failures >= self.threshold {
+            circuit.state = CircuitState::Open;
+            circuit.opened_at = Some(Instant::now());
+            circuit.trial_started_at = None;
+        }
+    }
+
+    pub fn state(&self, market_type: MarketType) -> CircuitState {
+        self.circuits
+            .lock()
+            .unwrap()
+            .get(&market_type)
+            .map(|circuit| circuit.state)
+            .unwrap_or_default()
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(DEFAULT_BREAKER_THRESHOLD, DEFAULT_BREAKER_COOLDOWN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opens_after_threshold() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+
+        for _ in 0..2 {
+            assert!(breaker.check(MarketType::Spot).is_ok());
+            breaker.record_failure(MarketType::Spot);
+        }
+        assert_eq!(breaker.state(MarketType::Spot), CircuitState::Closed);
+
+        breaker.record_failure(MarketType::Spot);
+        assert_eq!(breaker.state(MarketType::Spot), CircuitState::Open);
+        let err = breaker.check(MarketType::Spot).unwrap_err();
+        assert!(err.to_string().contains("circuit open"), "{}", err);
+
+        // Markets trip independently
+        assert!(breaker.check(MarketType::Perpetual).is_ok());
+        assert_eq!(breaker.state(MarketType::Perpetual), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_success_resets_failure_count() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+
+        breaker.record_failure(MarketType::Spot);
+        breaker.record_success(MarketType::Spot);
+        breaker.record_failure(MarketType::Spot);
+
+        assert_eq!(breaker.state(MarketType::Spot), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_trial_closes_or_reopens() {
+        let breaker = CircuitBreaker::new(1, Duration::from_millis(20));
+
+        breaker.record_failure(MarketType::Spot);
+        assert!(breaker.check(MarketType::Spot).is_err());
+        std::thread::sleep(Duration::from_millis(30));
+
+        // One trial is let through; others fail fast while it runs
+        assert!(breaker.check(MarketType::Spot).is_ok());
+        assert_eq!(breaker.state(MarketType::Spot), CircuitState::HalfOpen);
+        assert!(breaker.check(MarketType::Spot).is_err());
+
+        breaker.record_failure(MarketType::Spot);
+        assert_eq!(breaker.state(MarketType::Spot), CircuitState::Open);
+        assert!(breaker.check(MarketType::Spot).is_err());
+        std::thread::sleep(Duration::from_millis(30));
+
+        assert!(breaker.check(MarketType::Spot).is_ok());
+        breaker.record_success(MarketType::Spot);
+        assert_eq!(breaker.state(MarketType::Spot), CircuitState::Closed);
+        assert!(breaker.check(MarketType::Spot).is_ok());
+    }
+
+    #[test]
+    fn test_lost_half_open_trial_is_replaced() {
+        let breaker = CircuitBreaker::new(1, Duration::from_millis(20));
+
+        breaker.record_failure(MarketType::Spot);
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(breaker.check(MarketType::Spot).is_ok());
+
+        // The trial never reports back; after a cooldown another one is allowed
+        assert!(breaker.check(MarketType::Spot).is_err());
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(breaker.check(MarketType::Spot).is_ok());
+        assert_eq!(breaker.state(MarketType::Spot), CircuitState::HalfOpen);
+        assert!(breaker.check(MarketType::Spot).is_err());
+
+        breaker.record_success(MarketType::Spot);
+        assert_eq!(breaker.state(MarketType::Spot), CircuitState::Closed);
+    }
+}
@@ -3,14 +3,24 @@ use futures::stream::{SplitSink, SplitStream};
 use futures::{SinkExt, StreamExt};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::net::TcpStream;
-use tokio::sync::Mutex;
-use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+use tokio::sync::{Mutex, Notify};
+use tokio_tungstenite::{
+    connect_async, tungstenite::Error as WsError, tungstenite::Message, MaybeTlsStream,
+    WebSocketStream,
+};
 use tracing::{debug, error, warn};
 use url::Url;
 
 pub type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
 
+/// How long a connection may go without receiving anything before it is considered stale
+pub const DEFAULT_CONNECTION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Minimum gap between text frames sent on one connection unless configured otherwise
+pub const DEFAULT_SUBSCRIBE_PACING: Duration = Duration::from_millis(100);
+
 /// WebSocket client helper that supports concurrent send/receive operations
 #[derive(Clone)]
 pub struct WsClient {
@@ -18,6 +28,11 @@ pub struct WsClient {
     writer: Arc<Mutex<Option<SplitSink<WsStream, Message>>>>,
     reader: Arc<Mutex<Option<SplitStream<WsStream>>>>,
     connected: Arc<AtomicBool>,
+    last_activity: Arc<std::sync::Mutex<Instant>>,
+    connection_timeout: Duration,
+    shutdown: Arc<Notify>,
+    send_interval: Duration,
+    last_text_sent: Arc<Mutex<Option<Instant>>>,
 }
 
 impl WsClient {
@@ -27,9 +42,35 @@ impl WsClient {
             writer: Arc::new(Mutex::new(None)),
             reader: Arc::new(Mutex::new(None)),
             connected: Arc::new(AtomicBool::new(false)),
+            last_activity: Arc::new(std::sync::Mutex::new(Instant::now())),
+            connection_timeout: DEFAULT_CONNECTION_TIMEOUT,
+            shutdown: Arc::new(Notify::new()),
+            send_interval: Duration::ZERO,
+            last_text_sent: Arc::new(Mutex::new(None)),
         }
     }
 
+    /// Override how long the connection may stay silent before `is_stale` reports it
+    pub fn with_timeout(mut self, connection_timeout: Duration) -> Self {
+        self.connection_timeout = connection_timeout;
+        self
+    }
+
+    /// Space text frames at least `interval` apart to stay under per-connection request limits
+    pub fn with_send_interval(mut self, interval: Duration) -> Self {
+        self.send_interval = interval;
+        self
+    }
+
+    /// Endpoint this client connects to
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn touch(&self) {
+        *self.last_activity.lock().unwrap() = Instant::now();
+    }
+
     /// Connect to the WebSocket
     pub async fn connect(&self) -> Result<()> {
         let url = Url::parse(self.url.as_str())?;
@@ -47,6 +88,7 @@ impl WsClient {
             let mut reader_guard = self.reader.lock().await;
             *reader_guard = Some(reader);
         }
+        self.touch();
         self.connected.store(true, Ordering::SeqCst);
         Ok(())
     }
@@ -62,9 +104,16 @@ impl WsClient {
         }
     }
 
-    /// Send a text message
+    /// Send a text message, waiting out the send interval since the previous one
     pub async fn send_text(&self, text: impl Into<String>) -> Result<()> {
-        self.send(Message::Text(text.into())).await
+        // Held across the wait so concurrent senders go out in turn
+        let mut last_sent = self.last_text_sent.lock().await;
+        if let Some(last) = *last_sent {
+            tokio::time::sleep_until((last + self.send_interval).into()).await;
+        }
+        let result = self.send(Message::Text(text.into())).await;
+        *last_sent = Some(Instant::now());
+        result
     }
 
     /// Send a JSON message
@@ -77,8 +126,27 @@ impl WsClient {
     pub async fn next_message(&self) -> Result<Option<Message>> {
         let mut reader_guard = self.reader.lock().await;
         if let Some(reader) = reader_guard.as_mut() {
-            match reader.next().await {
-                Some(Ok(message)) => Ok(Some(message)),
+            let next = tokio::select! {
+                next = reader.next() => next,
+                // close() was called while waiting, possibly on a silent peer
+                _ = self.shutdown.notified() => return Ok(None),
+            };
+            match next {
+                Some(Ok(Message::Binary(data))) => {
+                    self.touch();
+                    Ok(Some(Message::Text(lossy_text(&data))))
+                }
+                Some(Ok(message)) => {
+                    self.touch();
+                    Ok(Some(message))
+                }
+                Some(Err(WsError::Utf8)) => {
+                    // tungstenite ends the stream on a text frame it cannot decode, so the
+                    // caller has to reconnect
+                    self.connected.store(false, Ordering::SeqCst);
+                    warn!("WebSocket text frame was not valid UTF-8, connection ended");
+                    Err(WsError::Utf8.into())
+                }
                 Some(Err(e)) => {
                     self.connected.store(false, Ordering::SeqCst);
                     error!("WebSocket error: {}", e);
@@ -100,9 +168,17 @@ impl WsClient {
         self.connected.load(Ordering::SeqCst)
     }
 
+    /// Check if the connection has received nothing for longer than its timeout
+    pub fn is_stale(&self) -> bool {
+        self.is_connected()
+            && self.last_activity.lock().unwrap().elapsed() > self.connection_timeout
+    }
+
     /// Close the connection
     pub async fn close(&self) -> Result<()> {
         self.connected.store(false, Ordering::SeqCst);
+        // Release a reader blocked in next_message so the reader lock can be taken below
+        self.shutdown.notify_one();
         {
             let mut writer_guard = self.writer.lock().await;
             if let Some(mut writer) = writer_guard.take() {
@@ -118,6 +194,18 @@ impl WsClient {
     }
 }
 
+/// Decode a binary frame as text, replacing invalid UTF-8 with U+FFFD so one bad
+/// payload is logged and skipped over rather than failing the connection
+fn lossy_text(data: &[u8]) -> String {
+    match String::from_utf8_lossy(data) {
+        std::borrow::Cow::Borrowed(text) => text.to_string(),
+        std::borrow::Cow::Owned(repaired) => {
+            warn!("Replaced invalid UTF-8 in a {} byte frame", data.len());
+            repaired
+        }
+    }
+}
+
 impl Drop for WsClient {
     fn drop(&mut self) {
         if self.connected.load(Ordering::SeqCst) {
@@ -125,3 +213,51 @@ impl Drop for WsClient {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lossy_text_replaces_only_invalid_bytes() {
+        assert_eq!(lossy_text(br#"{"price":"2"}"#), r#"{"price":"2"}"#);
+        assert_eq!(
+            lossy_text(b"{\"price\":\"1\xff\xfe\"}"),
+            "{\"price\":\"1\u{fffd}\u{fffd}\"}"
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_utf8_payload_does_not_end_the_stream() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            ws.send(Message::Binary(b"{\"price\":\"1\xff\xfe\"}".to_vec()))
+                .await
+                .unwrap();
+            ws.send(Message::Text(r#"{"price":"2"}"#.to_string()))
+                .await
+                .unwrap();
+            // Hold the connection open until the client is done
+            while ws.next().await.is_some() {}
+        });
+
+        let client = WsClient::new(url);
+        client.connect().await.unwrap();
+        assert_eq!(
+            client.next_message().await.unwrap(),
+            Some(Message::Text(
+                "{\"price\":\"1\u{fffd}\u{fffd}\"}".to_string()
+            ))
+        );
+        let Some(Message::Text(text)) = client.next_message().await.unwrap() else {
+            panic!("expected a text frame");
+        };
+        let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(parsed["price"], "2");
+        assert!(client.is_connected());
+        client.close().await.unwrap();
+    }
+}
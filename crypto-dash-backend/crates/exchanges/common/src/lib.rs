@@ -1,9 +1,23 @@
 pub mod adapter;
+pub mod book;
+pub mod breaker;
 pub mod client;
+pub mod metrics;
 pub mod mock;
+pub mod pending;
 pub mod retry;
+pub mod sanity;
+pub mod string_or_number;
 
-pub use adapter::ExchangeAdapter;
-pub use client::WsClient;
+pub use adapter::{publish_orderbook_resets, resolve_depth, ExchangeAdapter};
+pub use book::{apply_levels, optional_decimal, parse_levels};
+pub use breaker::{CircuitBreaker, DEFAULT_BREAKER_COOLDOWN, DEFAULT_BREAKER_THRESHOLD};
+pub use client::{WsClient, DEFAULT_CONNECTION_TIMEOUT, DEFAULT_SUBSCRIBE_PACING};
+pub use metrics::MessageCounters;
 pub use mock::MockDataGenerator;
-pub use retry::{exponential_backoff, RetryConfig};
+pub use pending::{PendingSubscribes, DEFAULT_SUBSCRIBE_TIMEOUT};
+pub use retry::{
+    exponential_backoff, is_rate_limit_notice, retry_with_backoff, ReconnectBudget, RetryConfig,
+    DEFAULT_MAX_RECONNECTS, DEFAULT_RECONNECT_WINDOW, THROTTLE_BACKOFF,
+};
+pub use sanity::{check_ticker, out_of_order_ms};
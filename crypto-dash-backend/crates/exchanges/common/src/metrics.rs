@@ -0,0 +1,32 @@
+use crypto_dash_core::model::AdapterMetrics;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Lock-free message counters an adapter bumps from its listener tasks
+#[derive(Debug, Default)]
+pub struct MessageCounters {
+    received: AtomicU64,
+    published: AtomicU64,
+    parse_errors: AtomicU64,
+}
+
+impl MessageCounters {
+    pub fn record_received(&self) {
+        self.received.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_published(&self) {
+        self.published.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_parse_error(&self) {
+        self.parse_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> AdapterMetrics {
+        AdapterMetrics {
+            messages_received: self.received.load(Ordering::Relaxed),
+            messages_published: self.published.load(Ordering::Relaxed),
+            parse_errors: self.parse_errors.load(Ordering::Relaxed),
+        }
+    }
+}
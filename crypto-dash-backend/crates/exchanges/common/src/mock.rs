@@ -118,6 +118,12 @@ impl MockDataGenerator {
             last: current_price,
             bid_size,
             ask_size,
+            quote_volume_24h: None,
+            change_pct_24h: None,
+            tick_direction: None,
+            high_24h: None,
+            low_24h: None,
+            volume_24h: None,
         }
     }
 }
@@ -0,0 +1,95 @@
+use crypto_dash_core::model::{Channel, ErrorCode, StreamMessage};
+use crypto_dash_stream_hub::{HubHandle, Topic};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::warn;
+
+/// How long an upstream subscribe may go unconfirmed before clients are told
+pub const DEFAULT_SUBSCRIBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Upstream subscribe requests still waiting for an ack or their first data
+#[derive(Clone)]
+pub struct PendingSubscribes {
+    timeout: Duration,
+    next_id: Arc<AtomicU64>,
+    pending: Arc<Mutex<HashMap<u64, Vec<Channel>>>>,
+}
+
+impl PendingSubscribes {
+    /// Track subscribes for `timeout`; zero disables tracking
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            next_id: Arc::new(AtomicU64::new(1)),
+            pending: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Id for the next upstream request, unique per adapter
+    pub fn next_request_id(&self) -> u64 {
+        self.next_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Track channels sent under `request_id`; any still unconfirmed when the timeout
+    /// elapses get a `SubscribeTimeout` error published on their topic
+    pub fn track(&self, request_id: u64, channels: Vec<Channel>, hub: Option<HubHandle>) {
+        if self.timeout.is_zero() || channels.is_empty() {
+            return;
+        }
+        self.pending.lock().unwrap().insert(request_id, channels);
+
+        let tracker = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(tracker.timeout).await;
+            let Some(channels) = tracker.resolve(request_id) else {
+                return;
+            };
+            warn!(
+                "{} subscriptions unconfirmed after {:?} (request {})",
+                channels.len(),
+                tracker.timeout,
+                request_id
+            );
+            let Some(hub) = hub else {
+                return;
+            };
+            for channel in channels {
+                let message = StreamMessage::Error {
+                    message: format!(
+                        "{} {}: subscribe not confirmed within {}s",
+                        channel.exchange.as_str(),
+                        channel.instrument(),
+                        tracker.timeout.as_secs_f64()
+                    ),
+                    code: Some(ErrorCode::SubscribeTimeout),
+                };
+                hub.publish(&Topic::from_channel(&channel), message).await;
+            }
+        });
+    }
+
+    /// Stop tracking a request the exchange answered, returning its outstanding channels
+    pub fn resolve(&self, request_id: u64) -> Option<Vec<Channel>> {
+        self.pending.lock().unwrap().remove(&request_id)
+    }
+
+    /// Stop waiting on a topic, e.g. once its first data arrives
+    pub fn settle(&self, topic: &Topic) {
+        let mut pending = self.pending.lock().unwrap();
+        if pending.is_empty() {
+            return;
+        }
+        for channels in pending.values_mut() {
+            channels.retain(|channel| Topic::from_channel(channel) != *topic);
+        }
+        pending.retain(|_, channels| !channels.is_empty());
+    }
+}
+
+impl Default for PendingSubscribes {
+    fn default() -> Self {
+        Self::new(DEFAULT_SUBSCRIBE_TIMEOUT)
+    }
+}
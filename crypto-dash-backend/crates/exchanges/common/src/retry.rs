@@ -1,4 +1,8 @@
-use std::time::Duration;
+use anyhow::{anyhow, Result};
+use crypto_dash_core::model::MarketType;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::time::sleep;
 use tracing::debug;
 
@@ -22,6 +26,68 @@ impl Default for RetryConfig {
     }
 }
 
+/// How long to hold off new requests on a market after the exchange signals rate limiting
+pub const THROTTLE_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Reconnects allowed per market within `DEFAULT_RECONNECT_WINDOW`
+pub const DEFAULT_MAX_RECONNECTS: usize = 3;
+pub const DEFAULT_RECONNECT_WINDOW: Duration = Duration::from_secs(30);
+
+/// Per-market cap on reconnects within a sliding window, so a flapping connection
+/// cannot make an adapter redial in a tight loop
+#[derive(Debug, Clone)]
+pub struct ReconnectBudget {
+    max_reconnects: usize,
+    window: Duration,
+    attempts: Arc<Mutex<HashMap<MarketType, VecDeque<Instant>>>>,
+}
+
+impl ReconnectBudget {
+    pub fn new(max_reconnects: usize, window: Duration) -> Self {
+        Self {
+            max_reconnects: max_reconnects.max(1),
+            window,
+            attempts: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Claim a reconnect for a market, failing once the window's budget is spent
+    pub fn take(&self, market_type: MarketType) -> Result<()> {
+        let now = Instant::now();
+        let mut guard = self.attempts.lock().unwrap();
+        let attempts = guard.entry(market_type).or_default();
+        while attempts
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= self.window)
+        {
+            attempts.pop_front();
+        }
+
+        if attempts.len() >= self.max_reconnects {
+            return Err(anyhow!(
+                "{} reconnect attempts within {:?}",
+                attempts.len(),
+                self.window
+            ));
+        }
+
+        attempts.push_back(now);
+        Ok(())
+    }
+}
+
+impl Default for ReconnectBudget {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_RECONNECTS, DEFAULT_RECONNECT_WINDOW)
+    }
+}
+
+/// Whether an exchange error or control message is a rate-limit notice
+pub fn is_rate_limit_notice(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("too many") || message.contains("rate limit")
+}
+
 /// Exponential backoff with jitter
 pub async fn exponential_backoff(attempt: u32, config: &RetryConfig) {
     if attempt == 0 {
@@ -93,6 +159,20 @@ mod tests {
         assert!(delay2.as_millis() >= 150 && delay2.as_millis() <= 250);
     }
 
+    #[test]
+    fn test_reconnect_budget_is_per_market_and_window() {
+        let budget = ReconnectBudget::new(2, Duration::from_millis(20));
+
+        assert!(budget.take(MarketType::Spot).is_ok());
+        assert!(budget.take(MarketType::Spot).is_ok());
+        let err = budget.take(MarketType::Spot).unwrap_err();
+        assert_eq!(err.to_string(), "2 reconnect attempts within 20ms");
+        assert!(budget.take(MarketType::Perpetual).is_ok());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(budget.take(MarketType::Spot).is_ok());
+    }
+
     #[tokio::test]
     async fn test_retry_success() {
         let mut call_count = 0;
@@ -142,6 +222,15 @@ mod tests {
         assert_eq!(result, Err("always fails"));
         assert_eq!(call_count, 3);
     }
+
+    #[test]
+    fn test_rate_limit_notice() {
+        assert!(is_rate_limit_notice(
+            "Too many requests; current limit is 5"
+        ));
+        assert!(is_rate_limit_notice("Rate limit exceeded"));
+        assert!(!is_rate_limit_notice("Invalid request"));
+    }
 }
 
 // Simple random function for jitter when std::random is not available
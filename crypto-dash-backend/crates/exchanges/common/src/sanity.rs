@@ -0,0 +1,97 @@
+//! Checks applied to normalized market data before it is cached or published.
+
+use anyhow::{bail, Result};
+use crypto_dash_core::model::Ticker;
+use rust_decimal::Decimal;
+
+/// Reject tickers no market could produce: a last price that is not positive, negative
+/// prices or sizes, or a crossed book. A zero bid or ask means that side is empty.
+pub fn check_ticker(ticker: &Ticker) -> Result<()> {
+    if ticker.last <= Decimal::ZERO {
+        bail!("last price {} is not positive", ticker.last);
+    }
+    for (name, value) in [
+        ("bid", ticker.bid),
+        ("ask", ticker.ask),
+        ("bid size", ticker.bid_size),
+        ("ask size", ticker.ask_size),
+    ] {
+        if value.is_sign_negative() && !value.is_zero() {
+            bail!("{} {} is negative", name, value);
+        }
+    }
+    if !ticker.bid.is_zero() && !ticker.ask.is_zero() && ticker.bid > ticker.ask {
+        bail!("crossed book: bid {} above ask {}", ticker.bid, ticker.ask);
+    }
+    Ok(())
+}
+
+/// Milliseconds `ticker` is older than the `cached` ticker for the same instrument, or
+/// `None` when it is not older. An older update means the feed reordered messages or a
+/// stuck connection is replaying them. Equal timestamps pass, since exchanges stamp bursts
+/// with the same millisecond.
+pub fn out_of_order_ms(cached: Option<&Ticker>, ticker: &Ticker) -> Option<i64> {
+    let lag = cached?.timestamp - ticker.timestamp;
+    (lag > chrono::Duration::zero()).then(|| lag.num_milliseconds())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_dash_core::model::{ExchangeId, MarketType, Symbol};
+    use crypto_dash_core::time::now;
+
+    fn ticker(bid: i64, ask: i64, last: i64) -> Ticker {
+        Ticker {
+            timestamp: now(),
+            exchange: ExchangeId::from("binance"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            bid: Decimal::new(bid, 0),
+            ask: Decimal::new(ask, 0),
+            last: Decimal::new(last, 0),
+            bid_size: Decimal::ONE,
+            ask_size: Decimal::ONE,
+            quote_volume_24h: None,
+            change_pct_24h: None,
+            tick_direction: None,
+            high_24h: None,
+            low_24h: None,
+            volume_24h: None,
+        }
+    }
+
+    #[test]
+    fn sane_tickers_pass() {
+        assert!(check_ticker(&ticker(99, 101, 100)).is_ok());
+        // Locked and one-sided books are still valid
+        assert!(check_ticker(&ticker(100, 100, 100)).is_ok());
+        assert!(check_ticker(&ticker(0, 101, 100)).is_ok());
+        assert!(check_ticker(&ticker(99, 0, 100)).is_ok());
+    }
+
+    #[test]
+    fn impossible_tickers_are_rejected() {
+        let err = check_ticker(&ticker(102, 101, 100)).unwrap_err();
+        assert!(err.to_string().contains("crossed"), "{}", err);
+        assert!(check_ticker(&ticker(99, 101, 0)).is_err());
+        assert!(check_ticker(&ticker(99, 101, -5)).is_err());
+        assert!(check_ticker(&ticker(-1, 101, 100)).is_err());
+
+        let mut negative_size = ticker(99, 101, 100);
+        negative_size.ask_size = Decimal::NEGATIVE_ONE;
+        assert!(check_ticker(&negative_size).is_err());
+    }
+
+    #[test]
+    fn older_tickers_are_out_of_order() {
+        let newer = ticker(99, 101, 100);
+        let mut older = newer.clone();
+        older.timestamp = newer.timestamp - chrono::Duration::milliseconds(250);
+
+        assert_eq!(out_of_order_ms(Some(&newer), &older), Some(250));
+        assert_eq!(out_of_order_ms(Some(&older), &newer), None);
+        assert_eq!(out_of_order_ms(Some(&newer), &newer), None);
+        assert_eq!(out_of_order_ms(None, &older), None);
+    }
+}
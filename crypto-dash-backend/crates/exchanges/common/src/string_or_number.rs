@@ -0,0 +1,69 @@
+//! Deserializers for exchange fields that are documented as strings but sometimes arrive as
+//! bare JSON numbers, or the other way round, depending on the API version.
+//!
+//! Use with `#[serde(deserialize_with = "string_or_number::required")]` (or `optional` for
+//! `Option<String>` fields); the value is kept as the string the adapters already parse.
+
+use serde::{Deserialize, Deserializer};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumber {
+    String(String),
+    Number(serde_json::Number),
+}
+
+impl From<StringOrNumber> for String {
+    fn from(value: StringOrNumber) -> Self {
+        match value {
+            StringOrNumber::String(text) => text,
+            StringOrNumber::Number(number) => number.to_string(),
+        }
+    }
+}
+
+/// A string, or a number rendered as its JSON text
+pub fn required<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    StringOrNumber::deserialize(deserializer).map(String::from)
+}
+
+/// Like `required`, with `null` read as None
+pub fn optional<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<StringOrNumber>::deserialize(deserializer)?.map(String::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Frame {
+        #[serde(deserialize_with = "super::required")]
+        price: String,
+        #[serde(default, deserialize_with = "super::optional")]
+        size: Option<String>,
+    }
+
+    #[test]
+    fn accepts_strings_and_numbers() {
+        let frame: Frame = serde_json::from_str(r#"{"price":"64250.5","size":3}"#).unwrap();
+        assert_eq!(frame.price, "64250.5");
+        assert_eq!(frame.size.as_deref(), Some("3"));
+
+        let frame: Frame = serde_json::from_str(r#"{"price":64250.5,"size":null}"#).unwrap();
+        assert_eq!(frame.price, "64250.5");
+        assert_eq!(frame.size, None);
+
+        let frame: Frame = serde_json::from_str(r#"{"price":-2}"#).unwrap();
+        assert_eq!(frame.price, "-2");
+        assert_eq!(frame.size, None);
+
+        assert!(serde_json::from_str::<Frame>(r#"{"price":true}"#).is_err());
+    }
+}
@@ -0,0 +1,816 @@
+use crate::types::{OkxMessage, OkxTicker};
+
+use anyhow::{anyhow, bail, Result};
+
+use async_trait::async_trait;
+
+use crypto_dash_cache::CacheHandle;
+
+use crypto_dash_core::{
+    model::{
+        AdapterMetrics, Channel, ChannelType, ConnectionStats, ExchangeId, LifecycleEvent,
+        MarketType, StreamMessage, Symbol, TickDirection, Ticker,
+    },
+    time::{from_millis, now},
+};
+
+use crypto_dash_exchanges_common::{
+    check_ticker, optional_decimal, retry_with_backoff, CircuitBreaker, ExchangeAdapter,
+    MessageCounters, PendingSubscribes, ReconnectBudget, RetryConfig, WsClient,
+    DEFAULT_CONNECTION_TIMEOUT, DEFAULT_SUBSCRIBE_TIMEOUT,
+};
+
+use crypto_dash_stream_hub::{HubHandle, Topic};
+
+use rust_decimal::Decimal;
+
+use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+
+use tokio_tungstenite::tungstenite::Message;
+
+use tracing::{debug, error, info, warn};
+
+const OKX_PUBLIC_WS_URL: &str = "wss://ws.okx.com:8443/ws/v5/public";
+const SUPPORTED_MARKETS: [MarketType; 2] = [MarketType::Spot, MarketType::Perpetual];
+/// OKX accepts 3 subscribe requests per second on a connection
+const OKX_SUBSCRIBE_PACING: Duration = Duration::from_millis(350);
+/// Key for the per-market circuit and reconnect budget of the one shared connection
+const CONNECTION_MARKET: MarketType = MarketType::Spot;
+
+/// OKX adapter streaming `tickers` for spot and USDT-margined swaps.
+///
+/// One public connection carries every market, so connection state and stats are shared.
+#[derive(Clone)]
+pub struct OkxAdapter {
+    hub: Arc<Mutex<Option<HubHandle>>>,
+    cache: Arc<Mutex<Option<CacheHandle>>>,
+    ws_client: Arc<Mutex<Option<Arc<WsClient>>>>,
+    ws_url: String,
+    connection_stats: Arc<Mutex<ConnectionStats>>,
+    subscribed: Arc<Mutex<HashSet<Channel>>>,
+    connection_timeout: Duration,
+    reconnect_retry: RetryConfig,
+    reconnect_budget: ReconnectBudget,
+    breaker: CircuitBreaker,
+    pending_subscribes: PendingSubscribes,
+    metrics: Arc<MessageCounters>,
+}
+
+impl OkxAdapter {
+    pub fn new() -> Self {
+        Self {
+            hub: Arc::new(Mutex::new(None)),
+            cache: Arc::new(Mutex::new(None)),
+            ws_client: Arc::new(Mutex::new(None)),
+            ws_url: OKX_PUBLIC_WS_URL.to_string(),
+            connection_stats: Arc::new(Mutex::new(ConnectionStats::default())),
+            subscribed: Arc::new(Mutex::new(HashSet::new())),
+            connection_timeout: DEFAULT_CONNECTION_TIMEOUT,
+            reconnect_retry: RetryConfig::default(),
+            reconnect_budget: ReconnectBudget::default(),
+            breaker: CircuitBreaker::default(),
+            pending_subscribes: PendingSubscribes::new(DEFAULT_SUBSCRIBE_TIMEOUT),
+            metrics: Arc::new(MessageCounters::default()),
+        }
+    }
+
+    /// Override the public WebSocket endpoint
+    pub fn with_ws_url(mut self, url: impl Into<String>) -> Self {
+        self.ws_url = url.into();
+        self
+    }
+
+    /// Override the backoff used to restore a connection that dropped
+    pub fn with_reconnect_retry(mut self, reconnect_retry: RetryConfig) -> Self {
+        self.reconnect_retry = reconnect_retry;
+        self
+    }
+
+    /// Override how many reconnects the connection may make within a window
+    pub fn with_reconnect_budget(mut self, reconnect_budget: ReconnectBudget) -> Self {
+        self.reconnect_budget = reconnect_budget;
+        self
+    }
+
+    /// Override how many failed connects open the circuit and how long it stays open
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    /// Override how long a subscribe may go without data; zero disables the check
+    pub fn with_subscribe_timeout(mut self, subscribe_timeout: Duration) -> Self {
+        self.pending_subscribes = PendingSubscribes::new(subscribe_timeout);
+        self
+    }
+
+    /// Expect data for channels just subscribed upstream, matched by topic
+    async fn track_subscription(&self, channels: Vec<Channel>) {
+        let hub = self.hub.lock().await.clone();
+        let request_id = self.pending_subscribes.next_request_id();
+        self.pending_subscribes.track(request_id, channels, hub);
+    }
+
+    /// Instrument id OKX uses for a channel: `BTC-USDT` on spot, `BTC-USDT-SWAP` on perpetuals
+    fn inst_id(channel: &Channel) -> String {
+        match channel.market_type {
+            MarketType::Perpetual => {
+                format!("{}-{}-SWAP", channel.symbol.base, channel.symbol.quote)
+            }
+            _ => format!("{}-{}", channel.symbol.base, channel.symbol.quote),
+        }
+    }
+
+    /// Inverse of [`Self::inst_id`]; coin-margined swaps and dated futures are not streamed
+    fn parse_inst_id(inst_id: &str) -> Result<(Symbol, MarketType)> {
+        let parts: Vec<&str> = inst_id.split('-').collect();
+        match parts.as_slice() {
+            [base, quote] => Ok((Symbol::new(*base, *quote), MarketType::Spot)),
+            [base, quote, "SWAP"] if *quote != "USD" => {
+                Ok((Symbol::new(*base, *quote), MarketType::Perpetual))
+            }
+            _ => Err(anyhow!("Unsupported OKX instrument: {}", inst_id)),
+        }
+    }
+
+    fn format_request(op: &str, channels: &[Channel]) -> String {
+        let args: Vec<_> = channels
+            .iter()
+            .map(|channel| serde_json::json!({"channel": "tickers", "instId": Self::inst_id(channel)}))
+            .collect();
+        serde_json::json!({"op": op, "args": args}).to_string()
+    }
+
+    async fn get_ws_client(&self) -> Option<Arc<WsClient>> {
+        self.ws_client.lock().await.clone()
+    }
+
+    async fn emit_event(&self, event: LifecycleEvent) {
+        if let Some(hub) = &*self.hub.lock().await {
+            hub.emit_event(event);
+        }
+    }
+
+    async fn handle_message(&self, message: OkxMessage) -> Result<()> {
+        match message {
+            OkxMessage::Push { arg, data } if arg.channel == "tickers" => {
+                for ticker in data {
+                    self.handle_ticker(ticker).await?;
+                }
+            }
+
+            OkxMessage::Push { arg, .. } => {
+                debug!("Ignoring OKX push on channel {}", arg.channel);
+            }
+
+            OkxMessage::Event {
+                event, code, msg, ..
+            } if event == "error" => {
+                error!(
+                    "OKX error: {} - {}",
+                    code.unwrap_or_default(),
+                    msg.unwrap_or_default()
+                );
+            }
+
+            OkxMessage::Event { event, arg, .. } => {
+                debug!("OKX {} event: {:?}", event, arg);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether anyone listens to the shared connection: a global subscriber or a
+    /// subscriber of any channel it carries
+    async fn has_listeners(&self, hub: &HubHandle) -> bool {
+        hub.global_subscriber_count() > 0
+            || self
+                .subscribed
+                .lock()
+                .await
+                .iter()
+                .any(|channel| hub.subscriber_count(&Topic::from_channel(channel)) > 0)
+    }
+
+    async fn disconnect_if_no_subscribers(&self) -> Result<()> {
+        let listening = match &*self.hub.lock().await {
+            Some(hub) => self.has_listeners(hub).await,
+            None => true,
+        };
+        if listening {
+            return Ok(());
+        }
+
+        if let Some(client) = self.ws_client.lock().await.take() {
+            info!("OKX disconnected due to no subscribers");
+            client.close().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn handle_ticker(&self, ticker: OkxTicker) -> Result<()> {
+        let (symbol, market_type) = Self::parse_inst_id(&ticker.inst_id)?;
+
+        let millis = i64::from_str(&ticker.ts)?;
+        let timestamp =
+            from_millis(millis).ok_or_else(|| anyhow!("Invalid timestamp: {}", ticker.ts))?;
+
+        let last_price = Decimal::from_str(&ticker.last)?;
+        let open_24h = optional_decimal(&ticker.open24h)?.filter(|open| !open.is_zero());
+        // Swap volumes are in contracts and base currency, so no quote volume is reported
+        let (volume_24h, quote_volume_24h) = match market_type {
+            MarketType::Spot => (
+                optional_decimal(&ticker.vol_24h)?,
+                optional_decimal(&ticker.vol_ccy_24h)?,
+            ),
+            _ => (optional_decimal(&ticker.vol_ccy_24h)?, None),
+        };
+
+        let previous_last = match &*self.cache.lock().await {
+            Some(cache) => cache
+                .get_ticker(&self.id(), market_type, &symbol)
+                .await
+                .map(|previous| previous.last),
+            None => None,
+        };
+
+        let normalized_ticker = Ticker {
+            timestamp,
+            exchange: self.id(),
+            market_type,
+            symbol: symbol.clone(),
+            bid: optional_decimal(&ticker.bid_px)?.unwrap_or_default(),
+            ask: optional_decimal(&ticker.ask_px)?.unwrap_or_default(),
+            last: last_price,
+            bid_size: optional_decimal(&ticker.bid_sz)?.unwrap_or_default(),
+            ask_size: optional_decimal(&ticker.ask_sz)?.unwrap_or_default(),
+            quote_volume_24h,
+            change_pct_24h: open_24h.map(|open| (last_price - open) / open * Decimal::ONE_HUNDRED),
+            tick_direction: Some(TickDirection::between(previous_last, last_price)),
+            high_24h: optional_decimal(&ticker.high24h)?,
+            low_24h: optional_decimal(&ticker.low24h)?,
+            volume_24h,
+        };
+        if let Err(e) = check_ticker(&normalized_ticker) {
+            warn!("Dropping OKX {} ticker: {}", ticker.inst_id, e);
+            return Ok(());
+        }
+
+        if let Some(cache) = &*self.cache.lock().await {
+            cache.set_ticker(normalized_ticker.clone()).await;
+        }
+
+        let topic = Topic::ticker(self.id(), market_type, symbol);
+        self.pending_subscribes.settle(&topic);
+        if let Some(hub) = &*self.hub.lock().await {
+            hub.publish(&topic, StreamMessage::Ticker(normalized_ticker))
+                .await;
+            self.metrics.record_published();
+        }
+
+        self.disconnect_if_no_subscribers().await?;
+
+        Ok(())
+    }
+
+    async fn listen_for_messages(&self, ws_client: Arc<WsClient>) -> Result<()> {
+        let reason = loop {
+            let message = match ws_client.next_message().await {
+                Ok(Some(Message::Text(text))) => text,
+
+                Ok(Some(Message::Close(_))) => {
+                    warn!("OKX WebSocket connection closed");
+                    break "connection closed".to_string();
+                }
+
+                Ok(Some(_)) => continue,
+
+                Ok(None) => {
+                    warn!("OKX WebSocket stream ended");
+                    break "stream ended".to_string();
+                }
+
+                Err(e) => {
+                    warn!("OKX WebSocket read failed: {}", e);
+                    break e.to_string();
+                }
+            };
+
+            self.metrics.record_received();
+            match serde_json::from_str::<OkxMessage>(&message) {
+                Ok(okx_message) => {
+                    if let Err(e) = self.handle_message(okx_message).await {
+                        self.metrics.record_parse_error();
+                        error!("Failed to handle OKX message: {}", e);
+                    }
+                }
+
+                Err(e) => {
+                    self.metrics.record_parse_error();
+                    debug!("Failed to parse OKX message: {} - Raw: {}", e, message);
+                }
+            }
+        };
+
+        for market_type in SUPPORTED_MARKETS {
+            self.emit_event(LifecycleEvent::UpstreamDisconnected {
+                exchange: self.id(),
+                market_type,
+                reason: reason.clone(),
+            })
+            .await;
+        }
+
+        let dropped = {
+            let mut guard = self.ws_client.lock().await;
+            if guard
+                .as_ref()
+                .is_some_and(|current| Arc::ptr_eq(current, &ws_client))
+            {
+                *guard = None;
+                true
+            } else {
+                false
+            }
+        };
+
+        // Deliberate closes (stop, no subscribers) clear the client first
+        if dropped {
+            self.restore_connection().await;
+        }
+
+        Ok(())
+    }
+
+    /// Reconnect a dropped connection and resend its subscriptions while anyone listens
+    // Boxed because the listener task it is awaited from is spawned by `try_real_connection`
+    fn restore_connection(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(async move {
+            let channels: Vec<Channel> = self.subscribed.lock().await.iter().cloned().collect();
+            let listening = match &*self.hub.lock().await {
+                Some(hub) => self.has_listeners(hub).await,
+                None => false,
+            };
+            if channels.is_empty() || !listening {
+                return;
+            }
+
+            // A server that accepts and immediately closes would otherwise be redialled in
+            // a tight loop, so restores are budgeted per window and start after a pause
+            if let Err(e) = self.reconnect_budget.take(CONNECTION_MARKET) {
+                error!("OKX not reconnecting: {}", e);
+                return;
+            }
+            warn!(
+                "OKX WebSocket dropped, reconnecting {} channels",
+                channels.len()
+            );
+            tokio::time::sleep(self.reconnect_retry.base_delay).await;
+            let channels = &channels;
+            let restored = retry_with_backoff(
+                || async move {
+                    let ws_client = self.try_real_connection().await?;
+                    ws_client
+                        .send_text(Self::format_request("subscribe", channels))
+                        .await
+                },
+                self.reconnect_retry.clone(),
+            )
+            .await;
+
+            match restored {
+                Ok(()) => {
+                    self.track_subscription(channels.clone()).await;
+                    info!(
+                        "OKX reconnected and resubscribed {} channels",
+                        channels.len()
+                    );
+                }
+                Err(e) => error!(
+                    "OKX reconnect gave up after {} attempts: {}",
+                    self.reconnect_retry.max_attempts, e
+                ),
+            }
+        })
+    }
+
+    async fn try_real_connection(&self) -> Result<Arc<WsClient>> {
+        debug!("Attempting to connect to OKX WebSocket: {}", self.ws_url);
+
+        // Fail fast while the circuit is open instead of hammering a dead endpoint
+        self.breaker
+            .check(CONNECTION_MARKET)
+            .map_err(|e| anyhow!("Not connecting to OKX: {}", e))?;
+
+        let ws_client = Arc::new(
+            WsClient::new(self.ws_url.clone())
+                .with_timeout(self.connection_timeout)
+                .with_send_interval(OKX_SUBSCRIBE_PACING),
+        );
+
+        if let Err(e) = ws_client.connect().await {
+            let mut stats = self.connection_stats.lock().await;
+            stats.reconnect_attempts += 1;
+            stats.last_connect_error = Some(e.to_string());
+            drop(stats);
+            self.breaker.record_failure(CONNECTION_MARKET);
+            return Err(e);
+        }
+        {
+            let mut stats = self.connection_stats.lock().await;
+            stats.reconnect_attempts = 0;
+            stats.last_connected_at = Some(now());
+        }
+        self.breaker.record_success(CONNECTION_MARKET);
+        for market_type in SUPPORTED_MARKETS {
+            self.emit_event(LifecycleEvent::UpstreamConnected {
+                exchange: self.id(),
+                market_type,
+            })
+            .await;
+        }
+
+        *self.ws_client.lock().await = Some(ws_client.clone());
+
+        let adapter = self.clone();
+        let listener_client = ws_client.clone();
+        tokio::spawn(async move {
+            if let Err(e) = adapter.listen_for_messages(listener_client).await {
+                error!("OKX WebSocket listener error: {}", e);
+            }
+        });
+
+        Ok(ws_client)
+    }
+
+    async fn ensure_connection(&self) -> Result<Arc<WsClient>> {
+        if let Some(client) = self.get_ws_client().await {
+            if client.is_connected() {
+                return Ok(client);
+            }
+        }
+
+        self.try_real_connection().await
+    }
+}
+
+#[async_trait]
+impl ExchangeAdapter for OkxAdapter {
+    fn id(&self) -> ExchangeId {
+        ExchangeId::from(ExchangeId::OKX)
+    }
+
+    fn supported_markets(&self) -> Vec<MarketType> {
+        SUPPORTED_MARKETS.to_vec()
+    }
+
+    fn supported_depths(&self) -> &'static [u16] {
+        &[]
+    }
+
+    fn orderbook_stream(&self, _market_type: MarketType, depth: Option<u16>) -> (u16, u32) {
+        // Never reached: order book channels are rejected on subscribe
+        (depth.unwrap_or_default(), 0)
+    }
+
+    async fn start(&self, hub: HubHandle, cache: CacheHandle) -> Result<()> {
+        info!("Starting OKX adapter");
+
+        *self.hub.lock().await = Some(hub);
+        *self.cache.lock().await = Some(cache);
+
+        Ok(())
+    }
+
+    async fn connect_markets(&self, markets: &[MarketType]) -> Result<()> {
+        if markets
+            .iter()
+            .any(|market| SUPPORTED_MARKETS.contains(market))
+        {
+            self.ensure_connection().await?;
+            info!("OKX connection opened");
+        }
+
+        Ok(())
+    }
+
+    async fn subscribe(&self, channels: &[Channel]) -> Result<()> {
+        info!("Subscribing to {} OKX channels", channels.len());
+
+        if channels.is_empty() {
+            return Ok(());
+        }
+        if let Some(channel) = channels.iter().find(|channel| {
+            channel.channel_type != ChannelType::Ticker
+                || !SUPPORTED_MARKETS.contains(&channel.market_type)
+        }) {
+            bail!(
+                "OKX streams spot and perpetual tickers only, not {:?} on {:?}",
+                channel.channel_type,
+                channel.market_type
+            );
+        }
+
+        let was_connected = self.is_connected().await;
+        let ws_client = self.ensure_connection().await?;
+
+        let mut request = channels.to_vec();
+        if !was_connected {
+            // A new connection carries no subscriptions, so the full set is resent
+            for channel in self.subscribed.lock().await.iter() {
+                if !request.contains(channel) {
+                    request.push(channel.clone());
+                }
+            }
+        }
+        let subscription = Self::format_request("subscribe", &request);
+        ws_client.send_text(&subscription).await?;
+        debug!("Sent OKX subscription: {}", subscription);
+        self.track_subscription(request).await;
+
+        self.subscribed
+            .lock()
+            .await
+            .extend(channels.iter().cloned());
+
+        Ok(())
+    }
+
+    async fn unsubscribe(&self, channels: &[Channel]) -> Result<()> {
+        info!("Unsubscribing from {} OKX channels", channels.len());
+
+        if channels.is_empty() {
+            return Ok(());
+        }
+
+        {
+            let mut subscribed = self.subscribed.lock().await;
+            for channel in channels {
+                subscribed.remove(channel);
+            }
+        }
+
+        match self.get_ws_client().await {
+            Some(ws_client) => {
+                let unsubscription = Self::format_request("unsubscribe", channels);
+                ws_client.send_text(&unsubscription).await?;
+                debug!("Sent OKX unsubscription: {}", unsubscription);
+                Ok(())
+            }
+            None => Err(anyhow!("WebSocket client not connected for OKX")),
+        }
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.get_ws_client()
+            .await
+            .is_some_and(|client| client.is_connected())
+    }
+
+    async fn is_market_connected(&self, market_type: MarketType) -> bool {
+        SUPPORTED_MARKETS.contains(&market_type) && self.is_connected().await
+    }
+
+    async fn connection_stats(&self, _market_type: MarketType) -> ConnectionStats {
+        let mut stats = self.connection_stats.lock().await.clone();
+        stats.circuit = self.breaker.state(CONNECTION_MARKET);
+        stats
+    }
+
+    async fn metrics(&self) -> AdapterMetrics {
+        self.metrics.snapshot()
+    }
+
+    async fn stop(&self) -> Result<()> {
+        info!("Stopping OKX adapter");
+
+        if let Some(client) = self.ws_client.lock().await.take() {
+            info!("Closing OKX WebSocket connection");
+            client.close().await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for OkxAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_dash_cache::MemoryCache;
+    use crypto_dash_stream_hub::StreamHub;
+    use futures::{SinkExt, StreamExt};
+    use tokio::net::TcpListener;
+
+    const BTC_TICKER: &str = r#"{"arg":{"channel":"tickers","instId":"BTC-USDT"},"data":[{"instType":"SPOT","instId":"BTC-USDT","last":"67245.1","lastSz":"0.00012","askPx":"67245.2","askSz":"0.52","bidPx":"67245.1","bidSz":"1.03","open24h":"66012.3","high24h":"67600","low24h":"65810.5","sodUtc0":"66420.7","sodUtc8":"66150","volCcy24h":"612340871.25","vol24h":"9205.41","ts":"1718263592015"}]}"#;
+
+    fn ticker_channel(market_type: MarketType, base: &str) -> Channel {
+        Channel {
+            channel_type: ChannelType::Ticker,
+            exchange: ExchangeId::from(ExchangeId::OKX),
+            market_type,
+            symbol: Symbol::new(base, "USDT"),
+            depth: None,
+        }
+    }
+
+    #[test]
+    fn inst_ids_round_trip() {
+        for market_type in SUPPORTED_MARKETS {
+            let channel = ticker_channel(market_type, "BTC");
+            let (symbol, parsed_market) =
+                OkxAdapter::parse_inst_id(&OkxAdapter::inst_id(&channel)).unwrap();
+            assert_eq!(symbol, channel.symbol);
+            assert_eq!(parsed_market, market_type);
+        }
+        assert_eq!(
+            OkxAdapter::inst_id(&ticker_channel(MarketType::Perpetual, "ETH")),
+            "ETH-USDT-SWAP"
+        );
+
+        assert!(OkxAdapter::parse_inst_id("BTC-USD-SWAP").is_err());
+        assert!(OkxAdapter::parse_inst_id("BTC-USDT-240628").is_err());
+    }
+
+    #[tokio::test]
+    async fn ticker_push_publishes_normalized_ticker() {
+        let cache = MemoryCache::new().handle();
+        let hub = StreamHub::new().handle();
+        let mut receiver = hub.subscribe_all().await;
+        let adapter = OkxAdapter::new();
+        adapter.start(hub, cache.clone()).await.unwrap();
+
+        adapter
+            .handle_message(serde_json::from_str(BTC_TICKER).unwrap())
+            .await
+            .unwrap();
+
+        let (topic, message) = receiver.try_recv().unwrap();
+        assert_eq!(
+            topic,
+            Topic::from_channel(&ticker_channel(MarketType::Spot, "BTC"))
+        );
+        let StreamMessage::Ticker(ticker) = message else {
+            panic!("expected a ticker, got {:?}", message);
+        };
+        assert_eq!(ticker.exchange, ExchangeId::from("okx"));
+        assert_eq!(ticker.last, Decimal::new(672451, 1));
+        assert_eq!(ticker.bid, Decimal::new(672451, 1));
+        assert_eq!(ticker.ask, Decimal::new(672452, 1));
+        assert_eq!(ticker.ask_size, Decimal::new(52, 2));
+        assert_eq!(ticker.volume_24h, Some(Decimal::new(920541, 2)));
+        assert_eq!(ticker.quote_volume_24h, Some(Decimal::new(61234087125, 2)));
+        assert_eq!(ticker.high_24h, Some(Decimal::new(67600, 0)));
+        assert_eq!(ticker.timestamp.timestamp_millis(), 1718263592015);
+        assert!(ticker.change_pct_24h.unwrap() > Decimal::ONE);
+
+        assert!(cache
+            .get_ticker(&ticker.exchange, MarketType::Spot, &ticker.symbol)
+            .await
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn subscribe_sends_tickers_request_and_streams_pushes() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (request_tx, mut request_rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while let Some(Ok(Message::Text(text))) = ws.next().await {
+                request_tx.send(text).unwrap();
+                ws.send(Message::Text(BTC_TICKER.to_string()))
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let hub = StreamHub::new().handle();
+        let mut receiver = hub.subscribe_all().await;
+        let adapter = OkxAdapter::new().with_ws_url(url);
+        adapter
+            .start(hub, MemoryCache::new().handle())
+            .await
+            .unwrap();
+
+        let channel = ticker_channel(MarketType::Spot, "BTC");
+        adapter
+            .subscribe(std::slice::from_ref(&channel))
+            .await
+            .unwrap();
+        assert!(adapter.is_market_connected(MarketType::Perpetual).await);
+        assert!(!adapter.is_market_connected(MarketType::Future).await);
+
+        let request: serde_json::Value =
+            serde_json::from_str(&request_rx.recv().await.unwrap()).unwrap();
+        assert_eq!(
+            request,
+            serde_json::json!({"op": "subscribe", "args": [{"channel": "tickers", "instId": "BTC-USDT"}]})
+        );
+
+        let (topic, _) = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(topic, Topic::from_channel(&channel));
+
+        let book = Channel {
+            channel_type: ChannelType::OrderBook,
+            ..channel
+        };
+        assert!(adapter.subscribe(&[book]).await.is_err());
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn unconfirmed_subscribe_times_out() {
+        use crypto_dash_core::model::ErrorCode;
+
+        // The server accepts the connection but never sends data
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let _ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            std::future::pending::<()>().await;
+        });
+
+        let hub = StreamHub::new().handle();
+        let mut receiver = hub.subscribe_all().await;
+        let adapter = OkxAdapter::new()
+            .with_ws_url(url)
+            .with_subscribe_timeout(Duration::from_millis(100));
+        adapter
+            .start(hub, MemoryCache::new().handle())
+            .await
+            .unwrap();
+
+        let channel = ticker_channel(MarketType::Perpetual, "ETH");
+        adapter
+            .subscribe(std::slice::from_ref(&channel))
+            .await
+            .unwrap();
+
+        let (topic, message) = tokio::time::timeout(Duration::from_secs(2), receiver.recv())
+            .await
+            .expect("no subscribe timeout published")
+            .unwrap();
+        assert_eq!(topic, Topic::from_channel(&channel));
+        match message {
+            StreamMessage::Error { code, .. } => {
+                assert_eq!(code, Some(ErrorCode::SubscribeTimeout))
+            }
+            other => panic!("Expected Error, got {:?}", other),
+        }
+
+        adapter.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn failed_connects_open_the_circuit() {
+        use crypto_dash_core::model::CircuitState;
+
+        let adapter = OkxAdapter::new()
+            .with_ws_url("ws://127.0.0.1:1")
+            .with_circuit_breaker(CircuitBreaker::new(2, Duration::from_secs(60)));
+        let channel = ticker_channel(MarketType::Spot, "BTC");
+
+        for _ in 0..2 {
+            let err = adapter
+                .subscribe(std::slice::from_ref(&channel))
+                .await
+                .unwrap_err();
+            assert!(!err.to_string().contains("circuit open"), "{}", err);
+        }
+        let stats = adapter.connection_stats(MarketType::Perpetual).await;
+        assert_eq!(stats.circuit, CircuitState::Open);
+        assert_eq!(stats.reconnect_attempts, 2);
+
+        // Further connects fail fast without dialing
+        let err = adapter.subscribe(&[channel]).await.unwrap_err();
+        assert!(err.to_string().contains("circuit open"), "{}", err);
+        assert_eq!(
+            adapter
+                .connection_stats(MarketType::Spot)
+                .await
+                .reconnect_attempts,
+            2
+        );
+    }
+}
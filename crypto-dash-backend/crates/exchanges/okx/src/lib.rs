@@ -0,0 +1,4 @@
+pub mod adapter;
+pub mod types;
+
+pub use adapter::OkxAdapter;
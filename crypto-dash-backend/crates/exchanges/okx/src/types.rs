@@ -0,0 +1,158 @@
+use crypto_dash_exchanges_common::string_or_number;
+use serde::{Deserialize, Serialize};
+
+/// Channel and instrument a push or subscribe event refers to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OkxArg {
+    pub channel: String,
+    #[serde(rename = "instId", default)]
+    pub inst_id: Option<String>,
+}
+
+/// `tickers` channel entry. Empty strings mark a missing value, e.g. an empty book side.
+///
+/// Sizes are in base currency for spot and in contracts for swaps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OkxTicker {
+    #[serde(rename = "instType")]
+    pub inst_type: String,
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    #[serde(deserialize_with = "string_or_number::required")]
+    pub last: String,
+    #[serde(
+        rename = "lastSz",
+        default,
+        deserialize_with = "string_or_number::optional"
+    )]
+    pub last_sz: Option<String>,
+    #[serde(
+        rename = "askPx",
+        default,
+        deserialize_with = "string_or_number::optional"
+    )]
+    pub ask_px: Option<String>,
+    #[serde(
+        rename = "askSz",
+        default,
+        deserialize_with = "string_or_number::optional"
+    )]
+    pub ask_sz: Option<String>,
+    #[serde(
+        rename = "bidPx",
+        default,
+        deserialize_with = "string_or_number::optional"
+    )]
+    pub bid_px: Option<String>,
+    #[serde(
+        rename = "bidSz",
+        default,
+        deserialize_with = "string_or_number::optional"
+    )]
+    pub bid_sz: Option<String>,
+    #[serde(default, deserialize_with = "string_or_number::optional")]
+    pub open24h: Option<String>,
+    #[serde(default, deserialize_with = "string_or_number::optional")]
+    pub high24h: Option<String>,
+    #[serde(default, deserialize_with = "string_or_number::optional")]
+    pub low24h: Option<String>,
+    /// 24h volume in quote currency for spot, in base currency for swaps
+    #[serde(
+        rename = "volCcy24h",
+        default,
+        deserialize_with = "string_or_number::optional"
+    )]
+    pub vol_ccy_24h: Option<String>,
+    /// 24h volume in base currency for spot, in contracts for swaps
+    #[serde(
+        rename = "vol24h",
+        default,
+        deserialize_with = "string_or_number::optional"
+    )]
+    pub vol_24h: Option<String>,
+    /// Milliseconds since the epoch
+    #[serde(deserialize_with = "string_or_number::required")]
+    pub ts: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum OkxMessage {
+    Push {
+        arg: OkxArg,
+        data: Vec<OkxTicker>,
+    },
+    /// `subscribe`/`unsubscribe` acks and `error` events
+    Event {
+        event: String,
+        #[serde(default)]
+        arg: Option<OkxArg>,
+        #[serde(default)]
+        code: Option<String>,
+        #[serde(default)]
+        msg: Option<String>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_spot_ticker_push() {
+        let raw_message = r#"{"arg":{"channel":"tickers","instId":"BTC-USDT"},"data":[{"instType":"SPOT","instId":"BTC-USDT","last":"67245.1","lastSz":"0.00012","askPx":"67245.2","askSz":"0.52","bidPx":"67245.1","bidSz":"1.03","open24h":"66012.3","high24h":"67600","low24h":"65810.5","sodUtc0":"66420.7","sodUtc8":"66150","volCcy24h":"612340871.25","vol24h":"9205.41","ts":"1718263592015"}]}"#;
+
+        match serde_json::from_str::<OkxMessage>(raw_message).unwrap() {
+            OkxMessage::Push { arg, data } => {
+                assert_eq!(arg.channel, "tickers");
+                assert_eq!(arg.inst_id.as_deref(), Some("BTC-USDT"));
+                let ticker = &data[0];
+                assert_eq!(ticker.inst_type, "SPOT");
+                assert_eq!(ticker.last, "67245.1");
+                assert_eq!(ticker.bid_px.as_deref(), Some("67245.1"));
+                assert_eq!(ticker.ask_sz.as_deref(), Some("0.52"));
+                assert_eq!(ticker.vol_ccy_24h.as_deref(), Some("612340871.25"));
+                assert_eq!(ticker.ts, "1718263592015");
+            }
+            other => panic!("Expected Push variant, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_swap_ticker_push() {
+        let raw_message = r#"{"arg":{"channel":"tickers","instId":"ETH-USDT-SWAP"},"data":[{"instType":"SWAP","instId":"ETH-USDT-SWAP","last":"3512.45","lastSz":"3","askPx":"3512.46","askSz":"120","bidPx":"3512.45","bidSz":"87","open24h":"3490.01","high24h":"3550","low24h":"3470.2","volCcy24h":"412395.2","vol24h":"4123952","ts":"1718263592101","sodUtc0":"3501.3","sodUtc8":"3495.6"}]}"#;
+
+        match serde_json::from_str::<OkxMessage>(raw_message).unwrap() {
+            OkxMessage::Push { data, .. } => {
+                assert_eq!(data[0].inst_type, "SWAP");
+                assert_eq!(data[0].inst_id, "ETH-USDT-SWAP");
+                assert_eq!(data[0].vol_24h.as_deref(), Some("4123952"));
+            }
+            other => panic!("Expected Push variant, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_events() {
+        let ack = r#"{"event":"subscribe","arg":{"channel":"tickers","instId":"BTC-USDT"},"connId":"a4d3ae55"}"#;
+        match serde_json::from_str::<OkxMessage>(ack).unwrap() {
+            OkxMessage::Event { event, arg, .. } => {
+                assert_eq!(event, "subscribe");
+                assert_eq!(arg.unwrap().inst_id.as_deref(), Some("BTC-USDT"));
+            }
+            other => panic!("Expected Event variant, got {:?}", other),
+        }
+
+        let error = r#"{"event":"error","code":"60018","msg":"Wrong URL or channel:tickers,instId:FOO-BAR doesn't exist.","connId":"a4d3ae55"}"#;
+        match serde_json::from_str::<OkxMessage>(error).unwrap() {
+            OkxMessage::Event {
+                event, code, arg, ..
+            } => {
+                assert_eq!(event, "error");
+                assert_eq!(code.as_deref(), Some("60018"));
+                assert!(arg.is_none());
+            }
+            other => panic!("Expected Event variant, got {:?}", other),
+        }
+    }
+}
@@ -1,12 +1,33 @@
+use crate::skew::{ClockSkew, SkewTracker, DEFAULT_SKEW_WARN_THRESHOLD};
 use crate::topics::Topic;
-use crypto_dash_core::model::StreamMessage;
+use crypto_dash_core::model::{ChannelType, ExchangeId, LifecycleEvent, MarketType, StreamMessage};
+use crypto_dash_core::time::{now, to_millis};
 use dashmap::DashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::broadcast;
-use tracing::debug;
+use tracing::{debug, warn};
 use uuid::Uuid;
 
-const CHANNEL_CAPACITY: usize = 1000;
+/// Debug-log an event with the topic's routing fields ahead of any extra fields
+macro_rules! debug_topic {
+    ($topic:expr, $($rest:tt)+) => {
+        debug!(
+            topic = %$topic,
+            exchange = $topic.exchange.as_str(),
+            market_type = $topic.market_type.as_str(),
+            channel_type = ?$topic.channel_type,
+            symbol = %$topic.symbol.canonical(),
+            $($rest)+
+        )
+    };
+}
+
+/// Messages each broadcast channel buffers for its slowest receiver unless configured otherwise
+pub const DEFAULT_CHANNEL_CAPACITY: usize = 1000;
+/// Distinct topics a hub creates unless configured otherwise
+pub const DEFAULT_MAX_TOPICS: usize = 10_000;
 
 /// Handle to interact with the stream hub
 #[derive(Clone)]
@@ -20,8 +41,9 @@ impl HubHandle {
         self.inner.publish(topic, message).await;
     }
 
-    /// Subscribe to a topic and get a receiver
-    pub async fn subscribe(&self, topic: &Topic) -> SubscriberHandle {
+    /// Subscribe to a topic and get a receiver; fails if the topic is new and the hub
+    /// already holds its maximum number of topics
+    pub async fn subscribe(&self, topic: &Topic) -> anyhow::Result<SubscriberHandle> {
         self.inner.subscribe(topic).await
     }
 
@@ -30,17 +52,62 @@ impl HubHandle {
         self.inner.subscribe_all().await
     }
 
+    /// Send a message that is not tied to a topic, such as periodic stats
+    pub fn broadcast(&self, message: StreamMessage) {
+        if self.inner.broadcast_sender.send(message).is_err() {
+            debug!("No active broadcast subscribers");
+        }
+    }
+
+    /// Receive messages sent with `broadcast`
+    pub fn subscribe_broadcasts(&self) -> broadcast::Receiver<StreamMessage> {
+        self.inner.broadcast_sender.subscribe()
+    }
+
+    /// Emit a lifecycle event for observers such as metrics or the admin event stream
+    pub fn emit_event(&self, event: LifecycleEvent) {
+        // Nobody listening is the common case; events are best-effort
+        let _ = self.inner.events_sender.send(event);
+    }
+
+    /// Receive lifecycle events emitted after this call
+    pub fn subscribe_events(&self) -> broadcast::Receiver<LifecycleEvent> {
+        self.inner.events_sender.subscribe()
+    }
+
+    /// Total number of messages published to topics since the hub was created
+    pub fn published_count(&self) -> u64 {
+        self.inner.published.load(Ordering::Relaxed)
+    }
+
+    /// Rolling `server_now - exchange_ts` estimate per exchange, from published market data
+    pub fn clock_skew(&self) -> Vec<(ExchangeId, ClockSkew)> {
+        self.inner.skew.snapshot()
+    }
+
     /// Get the number of active topics
     pub fn topic_count(&self) -> usize {
         self.inner.topics.len()
     }
 
-    /// Get the number of subscribers for a topic
+    /// Get the number of active topics for an exchange market
+    pub fn topic_count_for(&self, exchange: &ExchangeId, market_type: MarketType) -> usize {
+        self.inner
+            .topics
+            .iter()
+            .filter(|entry| {
+                let topic = &entry.value().topic;
+                &topic.exchange == exchange && topic.market_type == market_type
+            })
+            .count()
+    }
+
     /// Get the number of global subscribers
     pub fn global_subscriber_count(&self) -> usize {
         self.inner.global_sender.receiver_count()
     }
 
+    /// Get the number of subscribers for a topic
     pub fn subscriber_count(&self, topic: &Topic) -> usize {
         self.inner
             .topics
@@ -48,9 +115,35 @@ impl HubHandle {
             .map(|entry| entry.value().sender.receiver_count())
             .unwrap_or(0)
     }
+
+    /// Every topic with its live receiver count, most watched first
+    pub fn active_topics(&self) -> Vec<(Topic, usize)> {
+        let mut topics: Vec<(Topic, usize)> = self
+            .inner
+            .topics
+            .iter()
+            .map(|entry| {
+                let entry = entry.value();
+                (entry.topic.clone(), entry.sender.receiver_count())
+            })
+            .collect();
+        topics.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.key().cmp(&b.0.key())));
+        topics
+    }
+
+    /// Total subscribers across all topics of a channel type
+    pub fn subscriber_count_by_channel_type(&self, channel_type: ChannelType) -> usize {
+        self.inner
+            .topics
+            .iter()
+            .filter(|entry| entry.value().topic.channel_type == channel_type)
+            .map(|entry| entry.value().sender.receiver_count())
+            .sum()
+    }
 }
 
 /// Handle for a subscription to receive messages
+#[derive(Debug)]
 pub struct SubscriberHandle {
     pub id: Uuid,
     pub topic: Topic,
@@ -88,38 +181,70 @@ impl GlobalSubscriberHandle {
 }
 
 struct TopicChannel {
+    topic: Topic,
     sender: broadcast::Sender<StreamMessage>,
 }
 
 struct StreamHubInner {
     topics: DashMap<String, TopicChannel>,
     global_sender: broadcast::Sender<(Topic, StreamMessage)>,
+    broadcast_sender: broadcast::Sender<StreamMessage>,
+    events_sender: broadcast::Sender<LifecycleEvent>,
+    published: AtomicU64,
+    skew: SkewTracker,
+    max_topics: usize,
+    capacity: usize,
 }
 
 impl StreamHubInner {
-    fn new() -> Self {
-        let (global_sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+    fn new(max_topics: usize, capacity: usize) -> Self {
+        // Tokio panics on zero-capacity broadcast channels
+        let capacity = capacity.max(1);
+        let (global_sender, _) = broadcast::channel(capacity);
+        let (broadcast_sender, _) = broadcast::channel(capacity);
+        let (events_sender, _) = broadcast::channel(capacity);
         Self {
             topics: DashMap::new(),
             global_sender,
+            broadcast_sender,
+            events_sender,
+            published: AtomicU64::new(0),
+            skew: SkewTracker::new(DEFAULT_SKEW_WARN_THRESHOLD),
+            max_topics,
+            capacity,
         }
     }
 
     async fn publish(&self, topic: &Topic, message: StreamMessage) {
+        // Each channel type has its own topic channel; market data never crosses into another
+        // type's subscribers. Status messages such as errors may go to any topic.
+        if let Some(channel_type) = message.channel_type() {
+            if channel_type != topic.channel_type {
+                warn!(
+                    topic = %topic,
+                    message_channel_type = ?channel_type,
+                    "Dropped market data published to a topic of another channel type"
+                );
+                return;
+            }
+        }
+
         let topic_key = topic.key();
+        self.published.fetch_add(1, Ordering::Relaxed);
+        self.skew.observe(&message, to_millis(now()));
 
         // Publish to specific topic subscribers
         if let Some(entry) = self.topics.get(&topic_key) {
             match entry.sender.send(message.clone()) {
                 Ok(subscriber_count) => {
-                    debug!(
-                        topic = %topic_key,
+                    debug_topic!(
+                        topic,
                         subscribers = subscriber_count,
                         "Published message to topic"
                     );
                 }
                 Err(_) => {
-                    debug!(topic = %topic_key, "No active subscribers for topic");
+                    debug!(topic = %topic, "No active subscribers for topic");
                 }
             }
         }
@@ -127,8 +252,8 @@ impl StreamHubInner {
         // Also publish to global subscribers (like WebSocket clients)
         match self.global_sender.send((topic.clone(), message)) {
             Ok(subscriber_count) => {
-                debug!(
-                    topic = %topic_key,
+                debug_topic!(
+                    topic,
                     global_subscribers = subscriber_count,
                     "Published message to global subscribers"
                 );
@@ -139,31 +264,51 @@ impl StreamHubInner {
         }
     }
 
-    async fn subscribe(&self, topic: &Topic) -> SubscriberHandle {
+    async fn subscribe(&self, topic: &Topic) -> anyhow::Result<SubscriberHandle> {
         let topic_key = topic.key();
 
+        if !self.topics.contains_key(&topic_key) && self.topics.len() >= self.max_topics {
+            // Topics whose subscribers have all gone make room before anything is refused
+            self.topics
+                .retain(|_, channel| channel.sender.receiver_count() > 0);
+            if self.topics.len() >= self.max_topics {
+                warn!(
+                    topic = %topic,
+                    max_topics = self.max_topics,
+                    "Rejected subscription: topic limit reached"
+                );
+                return Err(anyhow::anyhow!(
+                    "Topic limit reached ({} topics)",
+                    self.max_topics
+                ));
+            }
+        }
+
         let receiver = {
             let entry = self.topics.entry(topic_key.clone()).or_insert_with(|| {
-                let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
-                debug!(topic = %topic_key, "Created new topic channel");
-                TopicChannel { sender }
+                let (sender, _) = broadcast::channel(self.capacity);
+                debug_topic!(topic, "Created new topic channel");
+                TopicChannel {
+                    topic: topic.clone(),
+                    sender,
+                }
             });
 
             entry.sender.subscribe()
         };
 
         let id = Uuid::new_v4();
-        debug!(
-            topic = %topic_key,
+        debug_topic!(
+            topic,
             subscriber_id = %id,
             "New subscriber"
         );
 
-        SubscriberHandle {
+        Ok(SubscriberHandle {
             id,
             topic: topic.clone(),
             receiver,
-        }
+        })
     }
 
     async fn subscribe_all(&self) -> GlobalSubscriberHandle {
@@ -187,11 +332,28 @@ pub struct StreamHub {
 impl StreamHub {
     /// Create a new stream hub
     pub fn new() -> Self {
+        Self::with_max_topics(DEFAULT_MAX_TOPICS)
+    }
+
+    /// Create a hub that refuses subscriptions creating more than `max_topics` topics
+    pub fn with_max_topics(max_topics: usize) -> Self {
+        Self::with_limits(max_topics, DEFAULT_CHANNEL_CAPACITY)
+    }
+
+    /// Create a hub capped at `max_topics` topics that buffers `capacity` messages per
+    /// channel before slow receivers lag and drop messages
+    pub fn with_limits(max_topics: usize, capacity: usize) -> Self {
         Self {
-            inner: Arc::new(StreamHubInner::new()),
+            inner: Arc::new(StreamHubInner::new(max_topics, capacity)),
         }
     }
 
+    /// Log a warning when an exchange's clock skew exceeds `threshold`; zero disables it
+    pub fn with_skew_warn_threshold(self, threshold: Duration) -> Self {
+        self.inner.skew.set_warn_threshold(threshold);
+        self
+    }
+
     /// Get a handle to interact with the hub
     pub fn handle(&self) -> HubHandle {
         HubHandle {
@@ -230,7 +392,7 @@ mod tests {
             Symbol::new("BTC", "USDT"),
         );
 
-        let mut subscriber = handle.subscribe(&topic).await;
+        let mut subscriber = handle.subscribe(&topic).await.unwrap();
 
         let ticker = Ticker {
             timestamp: now(),
@@ -242,6 +404,12 @@ mod tests {
             last: Decimal::new(50000, 0),
             bid_size: Decimal::new(1, 0),
             ask_size: Decimal::new(1, 0),
+            quote_volume_24h: None,
+            change_pct_24h: None,
+            tick_direction: None,
+            high_24h: None,
+            low_24h: None,
+            volume_24h: None,
         };
 
         handle
@@ -258,6 +426,76 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_subscriber_count_by_channel_type() {
+        let handle = StreamHub::new().handle();
+        let exchange = ExchangeId::from("binance");
+
+        let _btc = handle
+            .subscribe(&Topic::ticker(
+                exchange.clone(),
+                MarketType::Spot,
+                Symbol::new("BTC", "USDT"),
+            ))
+            .await;
+        let _eth = handle
+            .subscribe(&Topic::ticker(
+                exchange.clone(),
+                MarketType::Perpetual,
+                Symbol::new("ETH", "USDT"),
+            ))
+            .await;
+        let book = handle
+            .subscribe(&Topic::orderbook(
+                exchange,
+                MarketType::Spot,
+                Symbol::new("BTC", "USDT"),
+            ))
+            .await;
+
+        assert_eq!(
+            handle.subscriber_count_by_channel_type(ChannelType::Ticker),
+            2
+        );
+        assert_eq!(
+            handle.subscriber_count_by_channel_type(ChannelType::OrderBook),
+            1
+        );
+
+        drop(book);
+        assert_eq!(
+            handle.subscriber_count_by_channel_type(ChannelType::OrderBook),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_active_topics_report_receiver_counts() {
+        let handle = StreamHub::new().handle();
+        let btc = Topic::ticker(
+            ExchangeId::from("binance"),
+            MarketType::Spot,
+            Symbol::new("BTC", "USDT"),
+        );
+        let eth = Topic::ticker(
+            ExchangeId::from("binance"),
+            MarketType::Spot,
+            Symbol::new("ETH", "USDT"),
+        );
+
+        let _eth = handle.subscribe(&eth).await.unwrap();
+        let _btc_a = handle.subscribe(&btc).await.unwrap();
+        let btc_b = handle.subscribe(&btc).await.unwrap();
+
+        assert_eq!(
+            handle.active_topics(),
+            vec![(btc.clone(), 2), (eth.clone(), 1)]
+        );
+
+        drop(btc_b);
+        assert_eq!(handle.active_topics(), vec![(btc, 1), (eth, 1)]);
+    }
+
     #[tokio::test]
     async fn test_multiple_subscribers() {
         let hub = StreamHub::new();
@@ -269,8 +507,8 @@ mod tests {
             Symbol::new("BTC", "USDT"),
         );
 
-        let mut sub1 = handle.subscribe(&topic).await;
-        let mut sub2 = handle.subscribe(&topic).await;
+        let mut sub1 = handle.subscribe(&topic).await.unwrap();
+        let mut sub2 = handle.subscribe(&topic).await.unwrap();
 
         assert_eq!(handle.subscriber_count(&topic), 2);
 
@@ -284,6 +522,12 @@ mod tests {
             last: Decimal::new(50000, 0),
             bid_size: Decimal::new(1, 0),
             ask_size: Decimal::new(1, 0),
+            quote_volume_24h: None,
+            change_pct_24h: None,
+            tick_direction: None,
+            high_24h: None,
+            low_24h: None,
+            volume_24h: None,
         };
 
         handle.publish(&topic, StreamMessage::Ticker(ticker)).await;
@@ -292,4 +536,202 @@ mod tests {
         let _ = sub1.recv().await.unwrap();
         let _ = sub2.recv().await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_topic_limit_rejects_new_topics() {
+        let handle = StreamHub::with_max_topics(2).handle();
+        let topic = |base: &str| {
+            Topic::ticker(
+                ExchangeId::from("binance"),
+                MarketType::Spot,
+                Symbol::new(base, "USDT"),
+            )
+        };
+
+        let _btc = handle.subscribe(&topic("BTC")).await.unwrap();
+        let eth = handle.subscribe(&topic("ETH")).await.unwrap();
+
+        let Err(err) = handle.subscribe(&topic("SOL")).await else {
+            panic!("subscribe beyond the topic limit succeeded");
+        };
+        assert!(err.to_string().contains("Topic limit"));
+        assert_eq!(handle.topic_count(), 2);
+
+        // Existing topics still take subscribers
+        let _btc2 = handle.subscribe(&topic("BTC")).await.unwrap();
+        assert_eq!(handle.subscriber_count(&topic("BTC")), 2);
+
+        // A topic left without subscribers is cleaned up to make room
+        drop(eth);
+        let _sol = handle.subscribe(&topic("SOL")).await.unwrap();
+        assert_eq!(handle.topic_count(), 2);
+        assert_eq!(handle.subscriber_count(&topic("ETH")), 0);
+    }
+
+    #[tokio::test]
+    async fn test_channel_capacity_bounds_lag() {
+        let topic = Topic::ticker(
+            ExchangeId::from("binance"),
+            MarketType::Spot,
+            Symbol::new("BTC", "USDT"),
+        );
+        let ticker = || Ticker {
+            timestamp: now(),
+            exchange: ExchangeId::from("binance"),
+            market_type: MarketType::Spot,
+            symbol: Symbol::new("BTC", "USDT"),
+            bid: Decimal::ONE,
+            ask: Decimal::ONE,
+            last: Decimal::ONE,
+            bid_size: Decimal::ONE,
+            ask_size: Decimal::ONE,
+            quote_volume_24h: None,
+            change_pct_24h: None,
+            tick_direction: None,
+            high_24h: None,
+            low_24h: None,
+            volume_24h: None,
+        };
+
+        let handle = StreamHub::with_limits(DEFAULT_MAX_TOPICS, 4)
+            .with_skew_warn_threshold(Duration::from_secs(3))
+            .handle();
+        assert_eq!(handle.inner.skew.warn_threshold(), Duration::from_secs(3));
+        let mut topic_sub = handle.subscribe(&topic).await.unwrap();
+        let mut global_sub = handle.subscribe_all().await;
+
+        for _ in 0..10 {
+            handle
+                .publish(&topic, StreamMessage::Ticker(ticker()))
+                .await;
+        }
+
+        // Only the last four messages are still buffered
+        assert!(matches!(
+            topic_sub.recv().await,
+            Err(broadcast::error::RecvError::Lagged(6))
+        ));
+        assert!(matches!(
+            global_sub.recv().await,
+            Err(broadcast::error::RecvError::Lagged(6))
+        ));
+
+        // The default capacity holds the same burst
+        let handle = StreamHub::new().handle();
+        let mut topic_sub = handle.subscribe(&topic).await.unwrap();
+        for _ in 0..10 {
+            handle
+                .publish(&topic, StreamMessage::Ticker(ticker()))
+                .await;
+        }
+        assert!(topic_sub.recv().await.is_ok());
+    }
+
+    /// Collects formatted log output in memory
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for CapturedLogs {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_channel_types_are_isolated() {
+        let handle = StreamHub::new().handle();
+        let exchange = ExchangeId::from("binance");
+        let symbol = Symbol::new("BTC", "USDT");
+        let ticker_topic = Topic::ticker(exchange.clone(), MarketType::Spot, symbol.clone());
+        let book_topic = Topic::orderbook(exchange.clone(), MarketType::Spot, symbol.clone());
+        let mut ticker_sub = handle.subscribe(&ticker_topic).await.unwrap();
+        let mut book_sub = handle.subscribe(&book_topic).await.unwrap();
+        let mut global_sub = handle.subscribe_all().await;
+        let reset = || StreamMessage::OrderBookReset {
+            exchange: exchange.clone(),
+            market_type: MarketType::Spot,
+            symbol: symbol.clone(),
+        };
+
+        handle.publish(&book_topic, reset()).await;
+        assert!(matches!(
+            book_sub.try_recv(),
+            Ok(StreamMessage::OrderBookReset { .. })
+        ));
+        assert!(global_sub.try_recv().is_ok());
+        assert!(matches!(
+            ticker_sub.try_recv(),
+            Err(broadcast::error::TryRecvError::Empty)
+        ));
+
+        // Book data published under the ticker topic reaches nobody
+        handle.publish(&ticker_topic, reset()).await;
+        assert!(matches!(
+            ticker_sub.try_recv(),
+            Err(broadcast::error::TryRecvError::Empty)
+        ));
+        assert!(matches!(
+            global_sub.try_recv(),
+            Err(broadcast::error::TryRecvError::Empty)
+        ));
+
+        // Status messages still go to whichever topic they concern
+        let error = StreamMessage::Error {
+            message: "subscription closed".to_string(),
+            code: None,
+        };
+        handle.publish(&ticker_topic, error).await;
+        assert!(matches!(
+            ticker_sub.try_recv(),
+            Ok(StreamMessage::Error { .. })
+        ));
+        assert!(matches!(
+            book_sub.try_recv(),
+            Err(broadcast::error::TryRecvError::Empty)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_logs_structured_topic() {
+        let logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .json()
+            .with_max_level(tracing::Level::DEBUG)
+            .with_writer(logs.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let handle = StreamHub::new().handle();
+        let topic = Topic::ticker(
+            ExchangeId::from("bybit"),
+            MarketType::Perpetual,
+            Symbol::new("ETH", "USDT"),
+        );
+        let _subscriber = handle.subscribe(&topic).await.unwrap();
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let event: serde_json::Value = output
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .find(|event| event["fields"]["message"] == "New subscriber")
+            .expect("no subscribe log event");
+
+        assert_eq!(event["fields"]["topic"], "ticker:bybit:perpetual:ETH-USDT");
+        assert_eq!(event["fields"]["exchange"], "bybit");
+        assert_eq!(event["fields"]["market_type"], "perpetual");
+        assert_eq!(event["fields"]["symbol"], "ETH-USDT");
+    }
 }
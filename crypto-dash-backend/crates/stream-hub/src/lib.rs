@@ -1,5 +1,9 @@
 pub mod hub;
+pub mod skew;
 pub mod topics;
 
-pub use hub::{HubHandle, StreamHub, SubscriberHandle};
+pub use hub::{
+    HubHandle, StreamHub, SubscriberHandle, DEFAULT_CHANNEL_CAPACITY, DEFAULT_MAX_TOPICS,
+};
+pub use skew::{ClockSkew, DEFAULT_SKEW_WARN_THRESHOLD};
 pub use topics::Topic;
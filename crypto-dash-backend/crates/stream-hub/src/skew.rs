@@ -0,0 +1,150 @@
+use crypto_dash_core::model::{ExchangeId, StreamMessage};
+use crypto_dash_core::time::to_millis;
+use dashmap::DashMap;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tracing::warn;
+
+/// Skew beyond which a possible clock issue is logged, unless configured otherwise
+pub const DEFAULT_SKEW_WARN_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// Weight of the newest sample in the rolling estimate
+const SMOOTHING: f64 = 0.1;
+
+/// Rolling estimate of `server_now - exchange_ts` for one exchange
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ClockSkew {
+    /// Exponentially weighted mean in milliseconds; positive when exchange times lag ours
+    pub skew_ms: f64,
+    /// Timestamps sampled so far
+    pub samples: u64,
+}
+
+struct SkewEntry {
+    skew: ClockSkew,
+    warned: bool,
+}
+
+/// Per-exchange skew between exchange event times and the server clock.
+///
+/// The estimate includes network latency, so a steady few tens of milliseconds is normal;
+/// seconds of skew point at a drifting clock on one side.
+pub struct SkewTracker {
+    entries: DashMap<ExchangeId, SkewEntry>,
+    warn_threshold_ms: AtomicU64,
+}
+
+impl SkewTracker {
+    pub fn new(warn_threshold: Duration) -> Self {
+        Self {
+            entries: DashMap::new(),
+            warn_threshold_ms: AtomicU64::new(warn_threshold.as_millis() as u64),
+        }
+    }
+
+    /// Change the skew that triggers a warning; zero disables warnings
+    pub fn set_warn_threshold(&self, warn_threshold: Duration) {
+        self.warn_threshold_ms
+            .store(warn_threshold.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    pub fn warn_threshold(&self) -> Duration {
+        Duration::from_millis(self.warn_threshold_ms.load(Ordering::Relaxed))
+    }
+
+    /// Sample the exchange time a message carries, if it has one
+    pub fn observe(&self, message: &StreamMessage, now_ms: i64) {
+        let (exchange, timestamp) = match message {
+            StreamMessage::Ticker(ticker) => (&ticker.exchange, ticker.timestamp),
+            StreamMessage::Trade(trade) => (&trade.exchange, trade.timestamp),
+            StreamMessage::FundingRate(funding) => (&funding.exchange, funding.timestamp),
+            // Order books are not sampled: some feeds stamp them with local receive time
+            _ => return,
+        };
+        self.record(exchange, to_millis(timestamp), now_ms);
+    }
+
+    /// Fold one `now_ms - exchange_ms` sample into the exchange's estimate
+    pub fn record(&self, exchange: &ExchangeId, exchange_ms: i64, now_ms: i64) {
+        let sample = (now_ms - exchange_ms) as f64;
+        let mut entry = self
+            .entries
+            .entry(exchange.clone())
+            .or_insert_with(|| SkewEntry {
+                skew: ClockSkew {
+                    skew_ms: sample,
+                    samples: 0,
+                },
+                warned: false,
+            });
+
+        let skew = &mut entry.skew;
+        if skew.samples > 0 {
+            skew.skew_ms += SMOOTHING * (sample - skew.skew_ms);
+        }
+        skew.samples += 1;
+        let skew_ms = skew.skew_ms;
+
+        // Warn once per excursion rather than on every message
+        let threshold = self.warn_threshold_ms.load(Ordering::Relaxed);
+        let exceeded = threshold > 0 && skew_ms.abs() > threshold as f64;
+        if exceeded && !entry.warned {
+            warn!(
+                exchange = exchange.as_str(),
+                skew_ms = skew_ms.round(),
+                threshold_ms = threshold,
+                "Exchange timestamps are skewed from the server clock; check clock sync"
+            );
+        }
+        entry.warned = exceeded;
+    }
+
+    /// Current estimate for every exchange sampled so far, ordered by exchange id
+    pub fn snapshot(&self) -> Vec<(ExchangeId, ClockSkew)> {
+        let mut skews: Vec<(ExchangeId, ClockSkew)> = self
+            .entries
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().skew))
+            .collect();
+        skews.sort_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
+        skews
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_tracks_a_steady_offset() {
+        let tracker = SkewTracker::new(DEFAULT_SKEW_WARN_THRESHOLD);
+        let binance = ExchangeId::from("binance");
+        for i in 0..50 {
+            let now_ms = 1_700_000_000_000 + i * 100;
+            tracker.record(&binance, now_ms - 250, now_ms);
+        }
+
+        let skews = tracker.snapshot();
+        assert_eq!(skews.len(), 1);
+        assert_eq!(skews[0].0, binance);
+        assert_eq!(skews[0].1.samples, 50);
+        assert!((skews[0].1.skew_ms - 250.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn estimate_smooths_outliers_and_follows_drift() {
+        let tracker = SkewTracker::new(Duration::ZERO);
+        let bybit = ExchangeId::from("bybit");
+        tracker.record(&bybit, 0, 100);
+        tracker.record(&bybit, 0, 1_100);
+        let skew = tracker.snapshot()[0].1.skew_ms;
+        assert!((skew - 200.0).abs() < 1e-9);
+
+        // An exchange clock running ahead of ours yields a negative skew
+        for _ in 0..200 {
+            tracker.record(&bybit, 500, 0);
+        }
+        assert!((tracker.snapshot()[0].1.skew_ms + 500.0).abs() < 1.0);
+    }
+}
@@ -1,5 +1,12 @@
-use crypto_dash_core::model::{Channel, ChannelType, ExchangeId, MarketType, Symbol};
+use crypto_dash_core::model::{
+    Channel, ChannelType, ExchangeId, MarketType, Symbol, TopicKeyScheme,
+};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether `Topic::key` renders v2 keys; set once at startup from `TOPIC_KEY_SCHEME`
+static KEY_SCHEME_V2: AtomicBool = AtomicBool::new(false);
 
 /// Topic key for routing messages in the stream hub
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -8,6 +15,9 @@ pub struct Topic {
     pub exchange: ExchangeId,
     pub market_type: MarketType,
     pub symbol: Symbol,
+    /// Book depth the topic streams at, part of the key under `TopicKeyScheme::V2`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<u16>,
 }
 
 impl Topic {
@@ -22,6 +32,7 @@ impl Topic {
             exchange,
             market_type,
             symbol,
+            depth: None,
         }
     }
 
@@ -32,6 +43,8 @@ impl Topic {
             exchange: channel.exchange.clone(),
             market_type: channel.market_type,
             symbol: channel.symbol.clone(),
+            // Books of every depth share a topic unless the publisher keys them apart
+            depth: None,
         }
     }
 
@@ -45,24 +58,75 @@ impl Topic {
         Self::new(ChannelType::OrderBook, exchange, market_type, symbol)
     }
 
-    /// Generate a string key for this topic
+    /// Create a funding rate / mark price topic
+    pub fn funding(exchange: ExchangeId, market_type: MarketType, symbol: Symbol) -> Self {
+        Self::new(ChannelType::Funding, exchange, market_type, symbol)
+    }
+
+    /// Create a public trades topic
+    pub fn trades(exchange: ExchangeId, market_type: MarketType, symbol: Symbol) -> Self {
+        Self::new(ChannelType::Trades, exchange, market_type, symbol)
+    }
+
+    /// Topic streaming books at `depth` levels
+    pub fn with_depth(mut self, depth: u16) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    /// Select the scheme `key()` renders for every topic; set before any topic is used
+    pub fn set_key_scheme(scheme: TopicKeyScheme) {
+        KEY_SCHEME_V2.store(scheme == TopicKeyScheme::V2, Ordering::Relaxed);
+    }
+
+    /// Scheme `key()` currently renders
+    pub fn key_scheme() -> TopicKeyScheme {
+        if KEY_SCHEME_V2.load(Ordering::Relaxed) {
+            TopicKeyScheme::V2
+        } else {
+            TopicKeyScheme::V1
+        }
+    }
+
+    /// Generate a string key for this topic in the configured scheme
     pub fn key(&self) -> String {
+        self.key_with_scheme(Self::key_scheme())
+    }
+
+    /// Key in the given layout; under v2 the book depth is appended,
+    /// e.g. `orderbook:binance:spot:BTC-USDT:50`
+    pub fn key_with_scheme(&self, scheme: TopicKeyScheme) -> String {
         let channel_segment = match self.channel_type {
             ChannelType::Ticker => "ticker",
             ChannelType::OrderBook => "orderbook",
+            ChannelType::Funding => "funding",
+            ChannelType::Trades => "trades",
         };
         let market_segment = match self.market_type {
             MarketType::Spot => "spot",
             MarketType::Perpetual => "perpetual",
+            MarketType::InversePerpetual => "inverse_perpetual",
+            MarketType::Future => "future",
         };
 
-        format!(
+        let key = format!(
             "{}:{}:{}:{}",
             channel_segment,
             self.exchange.as_str(),
             market_segment,
             self.symbol.canonical()
-        )
+        );
+        match (scheme, self.depth) {
+            (TopicKeyScheme::V2, Some(depth)) => format!("{}:{}", key, depth),
+            _ => key,
+        }
+    }
+}
+
+/// Compact form used in logs; structured fields are logged alongside it at the hub's debug sites
+impl fmt::Display for Topic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.key())
     }
 }
 
@@ -81,6 +145,59 @@ mod tests {
         assert_eq!(topic.key(), "ticker:binance:spot:BTC-USDT");
     }
 
+    #[test]
+    fn test_topic_key_schemes() {
+        let book = Topic::orderbook(
+            ExchangeId::from("bybit"),
+            MarketType::Perpetual,
+            Symbol::new("ETH", "USDT"),
+        );
+        let deep = book.clone().with_depth(50);
+
+        assert_eq!(
+            deep.key_with_scheme(TopicKeyScheme::V1),
+            "orderbook:bybit:perpetual:ETH-USDT"
+        );
+        assert_eq!(
+            deep.key_with_scheme(TopicKeyScheme::V2),
+            "orderbook:bybit:perpetual:ETH-USDT:50"
+        );
+        assert_eq!(
+            book.key_with_scheme(TopicKeyScheme::V2),
+            "orderbook:bybit:perpetual:ETH-USDT"
+        );
+
+        // key() follows the configured scheme; topics without a depth render the same
+        // under both, so other tests are unaffected while v2 is selected
+        assert_eq!(Topic::key_scheme(), TopicKeyScheme::V1);
+        assert_eq!(deep.key(), "orderbook:bybit:perpetual:ETH-USDT");
+        Topic::set_key_scheme(TopicKeyScheme::V2);
+        assert_eq!(deep.key(), "orderbook:bybit:perpetual:ETH-USDT:50");
+        assert_eq!(book.key(), "orderbook:bybit:perpetual:ETH-USDT");
+        Topic::set_key_scheme(TopicKeyScheme::V1);
+        assert_eq!(deep.key(), "orderbook:bybit:perpetual:ETH-USDT");
+
+        assert_eq!(TopicKeyScheme::parse(" V2 "), Some(TopicKeyScheme::V2));
+        assert_eq!(TopicKeyScheme::parse("v3"), None);
+    }
+
+    #[test]
+    fn test_dated_future_topic_keys() {
+        let june = chrono::NaiveDate::from_ymd_opt(2024, 6, 28).unwrap();
+        let september = chrono::NaiveDate::from_ymd_opt(2024, 9, 27).unwrap();
+        let topic = |expiry| {
+            Topic::ticker(
+                ExchangeId::from("binance"),
+                MarketType::Future,
+                Symbol::new("BTC", "USD").with_expiry(expiry),
+            )
+        };
+
+        assert_eq!(topic(june).key(), "ticker:binance:future:BTC-USD-20240628");
+        assert_ne!(topic(june).key(), topic(september).key());
+        assert_ne!(topic(june), topic(september));
+    }
+
     #[test]
     fn test_from_channel() {
         let channel = Channel {
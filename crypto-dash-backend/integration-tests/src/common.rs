@@ -45,10 +45,22 @@ pub async fn create_test_app() -> Result<(Router, Box<dyn FnOnce() + Send>)> {
             "/api/exchanges",
             axum::routing::get(crypto_dash_api::routes::list_exchanges),
         )
+        .route(
+            "/api/markets",
+            axum::routing::get(crypto_dash_api::routes::markets_overview),
+        )
         .route(
             "/api/symbols",
             axum::routing::get(crypto_dash_api::routes::list_symbols),
         )
+        .route(
+            "/api/ticker",
+            axum::routing::get(crypto_dash_api::routes::get_ticker),
+        )
+        .route(
+            "/api/tickers",
+            axum::routing::get(crypto_dash_api::routes::list_tickers),
+        )
         .route(
             "/ws",
             axum::routing::get(crypto_dash_api::ws::websocket_handler),
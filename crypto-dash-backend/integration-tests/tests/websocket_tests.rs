@@ -90,6 +90,8 @@ async fn test_subscription_workflow() -> Result<()> {
 
     let subscribe_msg = ClientMessage::Subscribe {
         channels: vec![channel.clone()],
+        stats: false,
+        envelope: false,
     };
     let subscribe_text = serde_json::to_string(&subscribe_msg)?;
     ws_sink
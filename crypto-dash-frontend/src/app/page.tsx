@@ -13,7 +13,7 @@ import { Channel, SelectedTicker, SymbolInfo, MarketType, SymbolsPayload, Allowe
 interface Exchange {
   id: string
   name: string
-  status: 'online' | 'offline' | 'maintenance'
+  status: 'online' | 'offline' | 'maintenance' | 'degraded'
 }
 
 const MOCK_EXCHANGES: Exchange[] = [
@@ -30,7 +30,7 @@ export default function HomePage() {
   const [selectedTickers, setSelectedTickers] = useState<SelectedTicker[]>([])
   const [selectedMarketType, setSelectedMarketType] = useState<MarketType>('spot')
   const [selectedQuoteSymbol, setSelectedQuoteSymbol] = useState<QuoteSymbol>('USDT')
-  const [allowedQuotes, setAllowedQuotes] = useState<AllowedQuotes>({ spot: [], perpetual: [] })
+  const [allowedQuotes, setAllowedQuotes] = useState<AllowedQuotes>({ spot: [], perpetual: [], inverse_perpetual: [], future: [] })
   const [hasInitializedDefaults, setHasInitializedDefaults] = useState(false)
   const [aiSummary, setAiSummary] = useState('Click summarise to generate insights about your selected markets.')
   const [chartInterval, setChartInterval] = useState<string>('1m')
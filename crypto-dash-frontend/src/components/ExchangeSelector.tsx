@@ -6,7 +6,7 @@ import { Check } from 'lucide-react'
 interface Exchange {
   id: string
   name: string
-  status: 'online' | 'offline' | 'maintenance'
+  status: 'online' | 'offline' | 'maintenance' | 'degraded'
 }
 
 interface ExchangeSelectorProps {
@@ -36,6 +36,8 @@ export function ExchangeSelector({
         return 'bg-red-500'
       case 'maintenance':
         return 'bg-yellow-500'
+      case 'degraded':
+        return 'bg-orange-500'
       default:
         return 'bg-gray-500'
     }
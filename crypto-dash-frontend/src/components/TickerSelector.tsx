@@ -8,6 +8,8 @@ import { SymbolResponse, SymbolInfo, SelectedTicker, MarketType, SymbolsPayload,
 const MARKET_TYPE_LABEL: Record<MarketType, string> = {
   spot: 'Spot',
   perpetual: 'Perpetual',
+  inverse_perpetual: 'Inverse Perpetual',
+  future: 'Future',
 }
 
 const formatExchange = (value: string) => (value ? value.charAt(0).toUpperCase() + value.slice(1) : value)
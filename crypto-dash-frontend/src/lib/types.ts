@@ -1,19 +1,30 @@
-export type MarketType = 'spot' | 'perpetual';
+export type MarketType = 'spot' | 'perpetual' | 'inverse_perpetual' | 'future';
 
 // Shared TypeScript types for the frontend
 
 export interface ExchangeInfo {
   id: string
   name: string
-  status: 'online' | 'offline' | 'maintenance'
+  status: 'online' | 'offline' | 'maintenance' | 'degraded'
   rate_limits?: Record<string, number>
   ws_url?: string
   rest_url?: string
+  markets?: MarketStatus[]
+}
+
+export interface MarketStatus {
+  market_type: MarketType
+  connected: boolean
+  last_connect_error?: string | null
+  reconnect_attempts?: number
+  last_connected_at?: string | null
+  circuit?: 'closed' | 'open' | 'half_open'
 }
 
 export interface Symbol {
   base: string
   quote: string
+  expiry?: string  // YYYY-MM-DD, dated futures only
 }
 
 export interface Ticker {
@@ -26,6 +37,35 @@ export interface Ticker {
   last: number
   bid_size: number
   ask_size: number
+  quote_volume_24h?: number | null
+  change_pct_24h?: number | null
+  tick_direction?: TickDirection | null  // last price move, e.g. for green/red coloring
+}
+
+export type TickDirection = 'up' | 'down' | 'neutral'
+
+export interface FundingRate {
+  timestamp: string
+  exchange: string
+  market_type: MarketType
+  symbol: Symbol
+  rate: number
+  next_funding_time: string
+  mark_price?: number | null
+  index_price?: number | null
+}
+
+export type Side = 'buy' | 'sell'
+
+export interface Trade {
+  timestamp: string
+  exchange: string
+  market_type: MarketType
+  symbol: Symbol
+  price: number
+  quantity: number
+  side: Side  // aggressor side
+  trade_id?: string | null
 }
 
 export interface PriceLevel {
@@ -43,18 +83,47 @@ export interface OrderBookSnapshot {
 }
 
 export interface StreamMessage {
-  type: 'ticker' | 'orderbook_snapshot' | 'orderbook_delta' | 'info' | 'error'
+  type: 'ticker' | 'orderbook_snapshot' | 'orderbook_delta' | 'order_book_reset' | 'funding_rate' | 'trade' | 'info' | 'error' | 'stats'
   payload?: any
   message?: string
 }
 
+// Sent when an exchange feed reconnects: drop the local book, a fresh snapshot follows
+export interface OrderBookResetPayload {
+  exchange: string
+  market_type: MarketType
+  symbol: Symbol
+}
+
+export type ErrorCode = 'subscribe_timeout'
+
+export interface ErrorPayload {
+  message: string
+  code?: ErrorCode  // set when the server can say why, e.g. an unconfirmed subscribe
+}
+
+export interface StreamStats {
+  timestamp: string
+  connected_exchanges: string[]
+  active_topics: number
+  messages_per_second: number
+}
+
 export interface ClientMessage {
   op: 'subscribe' | 'unsubscribe' | 'ping'
   channels?: Channel[]
+  stats?: boolean  // subscribe only: receive periodic stats messages
+  envelope?: boolean  // subscribe only: wrap stream messages in StreamEnvelope
+}
+
+export interface StreamEnvelope {
+  server_ts: number  // unix ms when the server forwarded the message
+  topic: string
+  data: StreamMessage
 }
 
 export interface Channel {
-  channel_type: 'ticker' | 'orderbook'
+  channel_type: 'ticker' | 'orderbook' | 'funding' | 'trades'
   exchange: string
   market_type: MarketType
   symbol: Symbol
@@ -71,6 +140,7 @@ export interface SymbolInfo {
   tick_size?: string       // Optional for backwards compatibility
   min_qty?: number         // Optional for backwards compatibility
   step_size?: number       // Optional for backwards compatibility
+  expiry?: string          // YYYY-MM-DD, dated futures only
 }
 
 export interface SymbolResponse {
@@ -81,6 +151,8 @@ export interface SymbolResponse {
 export interface AllowedQuotes {
   spot: string[]
   perpetual: string[]
+  inverse_perpetual: string[]
+  future: string[]
 }
 
 export interface SymbolsPayload {